@@ -0,0 +1,10 @@
+-- Create `notifications` table.
+CREATE TABLE notifications(
+    id SERIAL PRIMARY KEY,
+    user_id uuid NOT NULL REFERENCES users (user_id),
+    ticket_id INTEGER NOT NULL REFERENCES tickets (id),
+    kind TEXT NOT NULL,
+    message TEXT NOT NULL,
+    created_at timestamptz NOT NULL,
+    read_at timestamptz NULL
+);
\ No newline at end of file
@@ -0,0 +1,3 @@
+-- Update tickets table to add `version` column.
+ALTER TABLE tickets
+ADD COLUMN version INTEGER NULL;
\ No newline at end of file
@@ -0,0 +1,4 @@
+-- Update tickets table to backfill `version` column.
+UPDATE tickets
+SET version = 1
+WHERE version IS NULL;
\ No newline at end of file
@@ -0,0 +1,4 @@
+-- Update tickets table to make mandatory `version` column.
+ALTER TABLE tickets
+ALTER COLUMN version
+SET NOT NULL;
\ No newline at end of file
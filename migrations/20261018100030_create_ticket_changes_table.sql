@@ -0,0 +1,11 @@
+-- Create `ticket_changes` table.
+CREATE TABLE ticket_changes(
+    id SERIAL PRIMARY KEY,
+    ticket_id INTEGER NOT NULL REFERENCES tickets (id),
+    version INTEGER NOT NULL,
+    field TEXT NOT NULL,
+    old_value TEXT NOT NULL,
+    new_value TEXT NOT NULL,
+    changed_by TEXT NOT NULL REFERENCES users (username),
+    changed_at timestamptz NOT NULL
+);
\ No newline at end of file
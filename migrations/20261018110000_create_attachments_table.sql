@@ -0,0 +1,12 @@
+-- Create `attachments` table.
+CREATE TABLE attachments(
+    id uuid PRIMARY KEY,
+    ticket_id INTEGER NOT NULL REFERENCES tickets (id),
+    file_name TEXT NOT NULL,
+    content_type TEXT NOT NULL,
+    size_bytes BIGINT NOT NULL,
+    storage_key TEXT NOT NULL UNIQUE,
+    thumbnail_key TEXT NULL,
+    uploaded_by uuid NOT NULL REFERENCES users (user_id),
+    uploaded_at timestamptz NOT NULL
+);
@@ -0,0 +1,8 @@
+-- Create `projects` table.
+CREATE TABLE projects(
+    id SERIAL PRIMARY KEY,
+    key TEXT NOT NULL UNIQUE,
+    name TEXT NOT NULL,
+    next_ticket_number INTEGER NOT NULL,
+    created_at timestamptz NOT NULL
+);
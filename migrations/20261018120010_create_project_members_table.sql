@@ -0,0 +1,7 @@
+-- Create `project_members` table.
+CREATE TABLE project_members(
+    project_id INTEGER NOT NULL REFERENCES projects (id),
+    user_id uuid NOT NULL REFERENCES users (user_id),
+    joined_at timestamptz NOT NULL,
+    PRIMARY KEY (project_id, user_id)
+);
@@ -0,0 +1,4 @@
+-- Update tickets table to add `project_id` and `key` columns.
+ALTER TABLE tickets
+ADD COLUMN project_id INTEGER NULL REFERENCES projects (id),
+ADD COLUMN key TEXT NULL;
@@ -0,0 +1,22 @@
+-- Create the default project, continuing the sequence of the existing tickets.
+INSERT INTO projects (key, name, next_ticket_number, created_at)
+VALUES (
+        'MAIN',
+        'Main',
+        (SELECT COALESCE(MAX(id), 0) + 1 FROM tickets),
+        now()
+    );
+
+-- Make every existing user a member of the default project.
+INSERT INTO project_members (project_id, user_id, joined_at)
+SELECT projects.id, users.user_id, now()
+FROM projects, users
+WHERE projects.key = 'MAIN';
+
+-- Update tickets table to backfill `project_id` and `key` columns.
+UPDATE tickets
+SET project_id = projects.id,
+    key = projects.key || '-' || tickets.id
+FROM projects
+WHERE projects.key = 'MAIN'
+    AND tickets.project_id IS NULL;
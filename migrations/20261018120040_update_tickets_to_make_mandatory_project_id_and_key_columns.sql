@@ -0,0 +1,7 @@
+-- Update tickets table to make mandatory `project_id` and `key` columns.
+ALTER TABLE tickets
+ALTER COLUMN project_id
+SET NOT NULL,
+ALTER COLUMN key
+SET NOT NULL,
+ADD CONSTRAINT tickets_key_key UNIQUE (key);
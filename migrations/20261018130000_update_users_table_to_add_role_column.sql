@@ -0,0 +1,3 @@
+-- Update users table to add `role` column.
+ALTER TABLE users
+ADD COLUMN role TEXT NULL;
\ No newline at end of file
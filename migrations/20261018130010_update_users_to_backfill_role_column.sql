@@ -0,0 +1,7 @@
+-- Update users table to backfill `role` column.
+UPDATE users
+SET role = CASE
+        WHEN username = 'admin' THEN 'admin'
+        ELSE 'user'
+    END
+WHERE role IS NULL;
\ No newline at end of file
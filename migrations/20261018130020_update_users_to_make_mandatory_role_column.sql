@@ -0,0 +1,7 @@
+-- Update users table to make mandatory `role` column.
+ALTER TABLE users
+ALTER COLUMN role
+SET NOT NULL,
+ALTER COLUMN role
+SET DEFAULT 'user',
+ADD CONSTRAINT users_role_check CHECK (role IN ('admin', 'user'));
\ No newline at end of file
@@ -0,0 +1,4 @@
+-- Update tickets table to add `rank` column.
+-- Ranks are compared byte by byte, whatever the collation of the database.
+ALTER TABLE tickets
+ADD COLUMN rank TEXT COLLATE "C" NULL;
\ No newline at end of file
@@ -0,0 +1,5 @@
+-- Update tickets table to backfill `rank` column.
+-- The existing tickets keep the order of their ids.
+UPDATE tickets
+SET rank = lpad(id::TEXT, 10, '0') || 'i'
+WHERE rank IS NULL;
\ No newline at end of file
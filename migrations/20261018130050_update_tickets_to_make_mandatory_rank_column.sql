@@ -0,0 +1,4 @@
+-- Update tickets table to make mandatory `rank` column.
+ALTER TABLE tickets
+ALTER COLUMN rank
+SET NOT NULL;
\ No newline at end of file
@@ -0,0 +1,7 @@
+-- Create `board_wip_limits` table.
+CREATE TABLE board_wip_limits(
+    project_id INTEGER NOT NULL REFERENCES projects (id),
+    board_column TEXT NOT NULL,
+    wip_limit INTEGER NOT NULL CHECK (wip_limit > 0),
+    PRIMARY KEY (project_id, board_column)
+);
\ No newline at end of file
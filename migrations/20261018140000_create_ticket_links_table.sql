@@ -0,0 +1,17 @@
+-- Create `ticket_links` table.
+CREATE TABLE ticket_links(
+    id SERIAL PRIMARY KEY,
+    source_id INTEGER NOT NULL REFERENCES tickets (id),
+    target_id INTEGER NOT NULL REFERENCES tickets (id),
+    kind TEXT NOT NULL CHECK (kind IN ('blocks', 'duplicates', 'relates_to', 'parent_of')),
+    created_by TEXT NOT NULL REFERENCES users (username),
+    created_at timestamptz NOT NULL,
+    CONSTRAINT ticket_links_not_self CHECK (source_id <> target_id),
+    CONSTRAINT ticket_links_unique UNIQUE (source_id, target_id, kind)
+);
+-- A ticket has one parent at most.
+CREATE UNIQUE INDEX ticket_links_one_parent ON ticket_links (target_id)
+WHERE kind = 'parent_of';
+-- A ticket duplicates one canonical ticket at most.
+CREATE UNIQUE INDEX ticket_links_one_canonical ON ticket_links (source_id)
+WHERE kind = 'duplicates';
\ No newline at end of file
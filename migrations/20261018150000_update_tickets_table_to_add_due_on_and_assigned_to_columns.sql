@@ -0,0 +1,4 @@
+-- Update tickets table to add `due_on` and `assigned_to` columns.
+ALTER TABLE tickets
+ADD COLUMN due_on DATE NULL,
+ADD COLUMN assigned_to TEXT NULL REFERENCES users (username);
\ No newline at end of file
@@ -0,0 +1,4 @@
+-- Update tickets table to add `first_responded_at` and `closed_at` columns.
+ALTER TABLE tickets
+ADD COLUMN first_responded_at timestamptz NULL,
+ADD COLUMN closed_at timestamptz NULL;
\ No newline at end of file
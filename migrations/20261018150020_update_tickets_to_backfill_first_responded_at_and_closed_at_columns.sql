@@ -0,0 +1,24 @@
+-- Update tickets table to backfill `first_responded_at` and `closed_at` columns.
+-- The first response is the first change made by someone else than the creator.
+UPDATE tickets
+SET first_responded_at = (
+        SELECT MIN(changed_at)
+        FROM ticket_changes
+        WHERE ticket_changes.ticket_id = tickets.id
+            AND ticket_changes.changed_by <> tickets.created_by
+    )
+WHERE first_responded_at IS NULL;
+-- Tickets closed before the changes were tracked are considered closed when created.
+UPDATE tickets
+SET closed_at = COALESCE(
+        (
+            SELECT MAX(changed_at)
+            FROM ticket_changes
+            WHERE ticket_changes.ticket_id = tickets.id
+                AND ticket_changes.field = 'status'
+                AND ticket_changes.new_value = 'closed'
+        ),
+        created_at
+    )
+WHERE NOT is_open
+    AND closed_at IS NULL;
\ No newline at end of file
@@ -0,0 +1,8 @@
+-- Create `sla_breaches` table.
+CREATE TABLE sla_breaches(
+    ticket_id INTEGER NOT NULL REFERENCES tickets (id),
+    target TEXT NOT NULL CHECK (target IN ('first_response', 'resolution')),
+    breached_at timestamptz NOT NULL,
+    flagged_at timestamptz NOT NULL,
+    PRIMARY KEY (ticket_id, target)
+);
\ No newline at end of file
@@ -0,0 +1,3 @@
+-- Update tickets table to add `estimate_minutes` column.
+ALTER TABLE tickets
+ADD COLUMN estimate_minutes INTEGER NULL CHECK (estimate_minutes > 0);
\ No newline at end of file
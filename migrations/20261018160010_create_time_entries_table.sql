@@ -0,0 +1,13 @@
+-- Create `time_entries` table.
+CREATE TABLE time_entries(
+    id SERIAL PRIMARY KEY,
+    ticket_id INTEGER NOT NULL REFERENCES tickets (id),
+    logged_by TEXT NOT NULL REFERENCES users (username),
+    spent_on DATE NOT NULL,
+    duration_minutes INTEGER NOT NULL CHECK (duration_minutes > 0),
+    note TEXT NOT NULL,
+    logged_at timestamptz NOT NULL
+);
+
+CREATE INDEX time_entries_logged_by_spent_on_idx ON time_entries (logged_by, spent_on);
+CREATE INDEX time_entries_ticket_id_idx ON time_entries (ticket_id);
\ No newline at end of file
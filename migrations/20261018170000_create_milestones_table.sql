@@ -0,0 +1,10 @@
+-- Create `milestones` table.
+CREATE TABLE milestones(
+    id SERIAL PRIMARY KEY,
+    project_id INTEGER NOT NULL REFERENCES projects (id),
+    name TEXT NOT NULL,
+    target_on DATE NULL,
+    is_open BOOLEAN NOT NULL DEFAULT TRUE,
+    created_at timestamptz NOT NULL,
+    CONSTRAINT milestones_unique_name UNIQUE (project_id, name)
+);
\ No newline at end of file
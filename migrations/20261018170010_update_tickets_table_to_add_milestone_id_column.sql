@@ -0,0 +1,3 @@
+-- Update tickets table to add `milestone_id` column.
+ALTER TABLE tickets
+ADD COLUMN milestone_id INTEGER NULL REFERENCES milestones (id);
\ No newline at end of file
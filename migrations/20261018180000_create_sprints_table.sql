@@ -0,0 +1,11 @@
+-- Create `sprints` table.
+CREATE TABLE sprints(
+    id SERIAL PRIMARY KEY,
+    project_id INTEGER NOT NULL REFERENCES projects (id),
+    name TEXT NOT NULL,
+    starts_on DATE NOT NULL,
+    ends_on DATE NOT NULL,
+    created_at timestamptz NOT NULL,
+    CONSTRAINT sprints_unique_name UNIQUE (project_id, name),
+    CONSTRAINT sprints_valid_dates CHECK (ends_on >= starts_on)
+);
\ No newline at end of file
@@ -0,0 +1,4 @@
+-- Update tickets table to add `sprint_id` and `story_points` columns.
+ALTER TABLE tickets
+ADD COLUMN sprint_id INTEGER NULL REFERENCES sprints (id),
+ADD COLUMN story_points INTEGER NULL CHECK (story_points >= 0);
\ No newline at end of file
@@ -0,0 +1,8 @@
+-- Create `sprint_snapshots` table.
+CREATE TABLE sprint_snapshots(
+    sprint_id INTEGER NOT NULL REFERENCES sprints (id),
+    taken_on DATE NOT NULL,
+    remaining_points INTEGER NOT NULL,
+    total_points INTEGER NOT NULL,
+    PRIMARY KEY (sprint_id, taken_on)
+);
\ No newline at end of file
@@ -0,0 +1,5 @@
+-- Create the indexes of the dashboard metrics.
+CREATE INDEX tickets_project_id_created_at_idx ON tickets (project_id, created_at);
+CREATE INDEX tickets_project_id_closed_at_idx ON tickets (project_id, closed_at);
+CREATE INDEX tickets_open_assigned_to_idx ON tickets (assigned_to) WHERE is_open;
+CREATE INDEX ticket_changes_changed_at_idx ON ticket_changes (changed_at);
\ No newline at end of file
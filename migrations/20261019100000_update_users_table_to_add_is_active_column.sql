@@ -0,0 +1,3 @@
+-- Update users table to add `is_active` column.
+ALTER TABLE users
+ADD COLUMN is_active BOOLEAN NOT NULL DEFAULT TRUE;
\ No newline at end of file
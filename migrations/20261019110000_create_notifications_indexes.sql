@@ -0,0 +1,3 @@
+-- Create the indexes of the unread count and of the retention of the notifications.
+CREATE INDEX notifications_unread_user_id_idx ON notifications (user_id) WHERE read_at IS NULL;
+CREATE INDEX notifications_read_at_idx ON notifications (read_at);
\ No newline at end of file
@@ -0,0 +1,152 @@
+use crate::{
+    authentication::{compute_password_hash, update_password},
+    configuration::Settings,
+    domain::{UserPassword, UserRole, UserUsername},
+    routes::join_default_project,
+    telemetry::spawn_blocking_with_tracing,
+};
+use anyhow::Context;
+use secrecy::{ExposeSecret, Secret};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+/// Creates a user with the given role, member of the default project, and then return its id.
+#[tracing::instrument(name = "Creating a user", skip(pool, password))]
+pub async fn create_user(
+    pool: &PgPool,
+    username: String,
+    password: Secret<String>,
+    role: UserRole,
+) -> Result<Uuid, anyhow::Error> {
+    let username = UserUsername::parse(username).map_err(anyhow::Error::msg)?;
+    let password =
+        UserPassword::parse(password.expose_secret().to_string()).map_err(anyhow::Error::msg)?;
+    let password = Secret::new(password.as_ref().to_string());
+    let password_hash = spawn_blocking_with_tracing(move || compute_password_hash(password))
+        .await?
+        .context("Failed to hash password")?;
+
+    let user_id = Uuid::new_v4();
+    let mut transaction = pool
+        .begin()
+        .await
+        .context("Failed to acquire a Postgres connection from the pool")?;
+    let is_created = sqlx::query!(
+        r#"
+        INSERT INTO users (user_id, username, password_hash, role)
+        VALUES ($1, $2, $3, $4)
+        ON CONFLICT (username) DO NOTHING
+        "#,
+        user_id,
+        username.as_ref(),
+        password_hash.expose_secret(),
+        role.as_str(),
+    )
+    .execute(&mut transaction)
+    .await
+    .context("Failed to insert the user into the users table")?
+    .rows_affected()
+        == 1;
+    if !is_created {
+        anyhow::bail!("The username `{}` is already taken", username.as_ref());
+    }
+    join_default_project(&mut transaction, user_id)
+        .await
+        .context("Failed to add the user to the default project")?;
+    transaction
+        .commit()
+        .await
+        .context("Failed to commit SQL transaction to store a new user")?;
+
+    Ok(user_id)
+}
+
+/// Replaces the password of the user.
+#[tracing::instrument(name = "Resetting a password", skip(pool, password))]
+pub async fn reset_password(
+    pool: &PgPool,
+    username: &str,
+    password: Secret<String>,
+) -> Result<(), anyhow::Error> {
+    let password =
+        UserPassword::parse(password.expose_secret().to_string()).map_err(anyhow::Error::msg)?;
+    let user_id = get_user_id(pool, username).await?;
+
+    update_password(pool, user_id, Secret::new(password.as_ref().to_string())).await
+}
+
+/// Deactivates the user, who cannot log in anymore and whose sessions end.
+#[tracing::instrument(name = "Deactivating a user", skip(pool))]
+pub async fn deactivate_user(pool: &PgPool, username: &str) -> Result<(), anyhow::Error> {
+    let user_id = get_user_id(pool, username).await?;
+
+    sqlx::query!(
+        r#"
+        UPDATE users
+        SET is_active = FALSE
+        WHERE user_id = $1
+        "#,
+        user_id
+    )
+    .execute(pool)
+    .await
+    .context("Failed to deactivate the user")?;
+
+    Ok(())
+}
+
+/// Checks the settings which are only read when used
+/// and then try to connect to the database, to its read replica if any and to Redis.
+#[tracing::instrument(name = "Checking the configuration", skip(configuration))]
+pub async fn check_config(configuration: &Settings) -> Result<(), anyhow::Error> {
+    configuration
+        .sla
+        .get_policies()
+        .context("The SLA policies are invalid")?;
+    configuration
+        .attachments
+        .storage
+        .get_storage()
+        .context("The attachment storage is invalid")?;
+
+    sqlx::query("SELECT 1")
+        .execute(&configuration.database.get_connection_pool())
+        .await
+        .context("Failed to connect to the database")?;
+    if let Some(read_replica_pool) = configuration.database.get_read_replica_pool() {
+        sqlx::query("SELECT 1")
+            .execute(&read_replica_pool)
+            .await
+            .context("Failed to connect to the read replica")?;
+    }
+
+    let client = redis::Client::open(configuration.redis_uri.expose_secret().as_str())
+        .context("The Redis URI is invalid")?;
+    let mut connection = client
+        .get_async_connection()
+        .await
+        .context("Failed to connect to Redis")?;
+    redis::cmd("PING")
+        .query_async::<_, String>(&mut connection)
+        .await
+        .context("Failed to ping Redis")?;
+
+    Ok(())
+}
+
+/// Returns the id of the user with the given username.
+async fn get_user_id(pool: &PgPool, username: &str) -> Result<Uuid, anyhow::Error> {
+    sqlx::query!(
+        r#"
+        SELECT user_id
+        FROM users
+        WHERE username = $1
+        "#,
+        username
+    )
+    .fetch_optional(pool)
+    .await
+    .context("Failed to retrieve the user")?
+    .map(|row| row.user_id)
+    .with_context(|| format!("The user `{}` does not exist", username))
+}
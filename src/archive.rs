@@ -0,0 +1,374 @@
+use crate::{
+    authentication::compute_password_hash, storage::AttachmentStorage,
+    telemetry::spawn_blocking_with_tracing,
+};
+use anyhow::Context;
+use chrono::{DateTime, Utc};
+use flate2::{read::GzDecoder, write::GzEncoder, Compression};
+use secrecy::{ExposeSecret, Secret};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sqlx::{PgPool, Postgres, Transaction};
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs::File,
+    io::Read,
+    path::Path,
+};
+use uuid::Uuid;
+
+/// Version of the archive format, increased on every incompatible change.
+pub const ARCHIVE_VERSION: u32 = 1;
+
+/// Tables stored in the archive, in an order satisfying their foreign keys.
+pub const ARCHIVE_TABLES: [&str; 14] = [
+    "users",
+    "projects",
+    "project_members",
+    "milestones",
+    "sprints",
+    "tickets",
+    "ticket_changes",
+    "ticket_links",
+    "time_entries",
+    "attachments",
+    "notifications",
+    "sla_breaches",
+    "board_wip_limits",
+    "sprint_snapshots",
+];
+
+/// Rows created by the migrations, the only ones an empty database holds.
+const SEEDED_ROWS: [(&str, &str); 1] = [("projects", "key = 'MAIN'")];
+
+/// Name of the file describing the content of the archive.
+const MANIFEST_PATH: &str = "manifest.json";
+
+/// Representation of the description of an archive.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct ArchiveManifest {
+    pub version: u32,
+    pub exported_at: DateTime<Utc>,
+    pub includes_password_hashes: bool,
+    pub tables: BTreeMap<String, usize>,
+    pub attachments: usize,
+}
+
+/// Exports every table and every attachment into a gzipped tar archive
+/// and then return its manifest.
+///
+/// # Implementation Notes
+///
+/// Each table is stored as a JSON array of its rows, under `tables/`,
+/// and each stored file under `attachments/` with its storage key.
+/// The password hashes are left out unless explicitly requested.
+#[tracing::instrument(name = "Exporting archive", skip(pool, storage))]
+pub async fn export_archive(
+    pool: &PgPool,
+    storage: &dyn AttachmentStorage,
+    path: &Path,
+    include_password_hashes: bool,
+) -> Result<ArchiveManifest, anyhow::Error> {
+    let file = File::create(path).context("Failed to create the archive file")?;
+    let mut archive = tar::Builder::new(GzEncoder::new(file, Compression::default()));
+
+    // Read every table from the same snapshot of the database.
+    let mut transaction = pool
+        .begin()
+        .await
+        .context("Failed to acquire a Postgres connection from the pool")?;
+    sqlx::query("SET TRANSACTION ISOLATION LEVEL REPEATABLE READ READ ONLY")
+        .execute(&mut transaction)
+        .await
+        .context("Failed to set the isolation level of the transaction")?;
+    lift_statement_timeout(&mut transaction).await?;
+
+    let mut manifest = ArchiveManifest {
+        version: ARCHIVE_VERSION,
+        exported_at: Utc::now(),
+        includes_password_hashes: include_password_hashes,
+        tables: BTreeMap::new(),
+        attachments: 0,
+    };
+    let mut storage_keys = Vec::new();
+    for table in ARCHIVE_TABLES {
+        let mut rows = get_table_rows(&mut transaction, table).await?;
+        for row in &mut rows {
+            match table {
+                "users" if !include_password_hashes => {
+                    row.as_object_mut().map(|row| row.remove("password_hash"));
+                }
+                "attachments" => {
+                    for column in ["storage_key", "thumbnail_key"] {
+                        if let Some(key) = row[column].as_str() {
+                            storage_keys.push(key.to_string());
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        append_file(
+            &mut archive,
+            &format!("tables/{}.json", table),
+            &serde_json::to_vec(&rows)?,
+        )?;
+        manifest.tables.insert(table.to_string(), rows.len());
+    }
+    transaction
+        .commit()
+        .await
+        .context("Failed to commit SQL transaction to export the tables")?;
+
+    for key in storage_keys {
+        let content = storage
+            .get(&key)
+            .await
+            .with_context(|| format!("Failed to read the attachment `{}`", key))?;
+        append_file(&mut archive, &format!("attachments/{}", key), &content)?;
+        manifest.attachments += 1;
+    }
+
+    append_file(
+        &mut archive,
+        MANIFEST_PATH,
+        &serde_json::to_vec_pretty(&manifest)?,
+    )?;
+    archive
+        .into_inner()
+        .and_then(|encoder| encoder.finish())
+        .context("Failed to write the archive file")?;
+
+    Ok(manifest)
+}
+
+/// Restores an archive into the database and the storage and then return its manifest.
+///
+/// # Implementation Notes
+///
+/// The database must be migrated and must not hold any ticket, the rows created
+/// by the migrations are replaced by those of the archive. The users exported without
+/// their password hash get a random password, to be reset by an administrator.
+#[tracing::instrument(name = "Importing archive", skip(pool, storage))]
+pub async fn import_archive(
+    pool: &PgPool,
+    storage: &dyn AttachmentStorage,
+    path: &Path,
+) -> Result<ArchiveManifest, anyhow::Error> {
+    let mut files = read_archive(path)?;
+    let manifest: ArchiveManifest = serde_json::from_slice(
+        &files
+            .remove(MANIFEST_PATH)
+            .context("The archive does not have a manifest")?,
+    )
+    .context("Failed to read the manifest of the archive")?;
+    if manifest.version != ARCHIVE_VERSION {
+        anyhow::bail!(
+            "The archive version {} is not supported, expected version {}",
+            manifest.version,
+            ARCHIVE_VERSION
+        );
+    }
+
+    let mut transaction = pool
+        .begin()
+        .await
+        .context("Failed to acquire a Postgres connection from the pool")?;
+    lift_statement_timeout(&mut transaction).await?;
+    ensure_is_empty(&mut transaction).await?;
+    // Replace the rows created by the migrations, such as the default project.
+    for table in ARCHIVE_TABLES.iter().rev() {
+        sqlx::query(&format!("DELETE FROM {}", table))
+            .execute(&mut transaction)
+            .await
+            .with_context(|| format!("Failed to delete the rows from the {} table", table))?;
+    }
+
+    let mut storage_keys = Vec::new();
+    for table in ARCHIVE_TABLES {
+        let content = files
+            .remove(&format!("tables/{}.json", table))
+            .with_context(|| format!("The archive does not have the {} table", table))?;
+        let mut rows: Vec<Value> = serde_json::from_slice(&content)
+            .with_context(|| format!("Failed to read the {} table of the archive", table))?;
+        for row in &mut rows {
+            match table {
+                "users" if row.get("password_hash").is_none() => {
+                    row["password_hash"] = random_password_hash().await?.into();
+                }
+                "attachments" => {
+                    for (column, content_type) in [
+                        (
+                            "storage_key",
+                            row["content_type"].as_str().unwrap_or_default(),
+                        ),
+                        ("thumbnail_key", "image/png"),
+                    ] {
+                        if let Some(key) = row[column].as_str() {
+                            storage_keys.push((key.to_string(), content_type.to_string()));
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        insert_table_rows(&mut transaction, table, &rows).await?;
+    }
+
+    // Store the attachments before committing, so that a failure leaves the database untouched.
+    for (key, content_type) in storage_keys {
+        let content = files
+            .remove(&format!("attachments/{}", key))
+            .with_context(|| format!("The archive does not have the attachment `{}`", key))?;
+        storage
+            .put(&key, &content_type, content.into())
+            .await
+            .with_context(|| format!("Failed to store the attachment `{}`", key))?;
+    }
+
+    transaction
+        .commit()
+        .await
+        .context("Failed to commit SQL transaction to import the archive")?;
+
+    Ok(manifest)
+}
+
+/// Lifts the statement timeout of the requests until the end of the transaction,
+/// reading or writing whole tables may take much longer.
+async fn lift_statement_timeout(
+    transaction: &mut Transaction<'_, Postgres>,
+) -> Result<(), anyhow::Error> {
+    sqlx::query("SET LOCAL statement_timeout = 0")
+        .execute(&mut *transaction)
+        .await
+        .context("Failed to lift the statement timeout")?;
+
+    Ok(())
+}
+
+/// Fails unless the archived tables only hold the rows created by the migrations.
+async fn ensure_is_empty(transaction: &mut Transaction<'_, Postgres>) -> Result<(), anyhow::Error> {
+    for table in ARCHIVE_TABLES {
+        let seeded_rows = SEEDED_ROWS
+            .iter()
+            .find(|(seeded_table, _)| *seeded_table == table)
+            .map(|(_, condition)| format!("WHERE NOT ({})", condition))
+            .unwrap_or_default();
+        let has_rows: bool = sqlx::query_scalar(&format!(
+            "SELECT EXISTS (SELECT 1 FROM {} {})",
+            table, seeded_rows
+        ))
+        .fetch_one(&mut *transaction)
+        .await
+        .with_context(|| format!("Failed to check whether the {} table is empty", table))?;
+        if has_rows {
+            anyhow::bail!("The database is not empty, the {} table holds rows", table);
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns the rows of the table as JSON objects, ordered by their first column.
+async fn get_table_rows(
+    transaction: &mut Transaction<'_, Postgres>,
+    table: &str,
+) -> Result<Vec<Value>, anyhow::Error> {
+    let rows: String = sqlx::query_scalar(&format!(
+        "SELECT COALESCE(json_agg(rows), '[]')::TEXT FROM (SELECT * FROM {} ORDER BY 1) AS rows",
+        table
+    ))
+    .fetch_one(&mut *transaction)
+    .await
+    .with_context(|| format!("Failed to get the rows from the {} table", table))?;
+
+    Ok(serde_json::from_str(&rows)?)
+}
+
+/// Inserts the rows into the table and then move its sequences past the inserted ids.
+async fn insert_table_rows(
+    transaction: &mut Transaction<'_, Postgres>,
+    table: &str,
+    rows: &[Value],
+) -> Result<(), anyhow::Error> {
+    sqlx::query(&format!(
+        "INSERT INTO {table} SELECT * FROM json_populate_recordset(NULL::{table}, $1::JSON)"
+    ))
+    .bind(serde_json::to_string(rows)?)
+    .execute(&mut *transaction)
+    .await
+    .with_context(|| format!("Failed to insert the rows into the {} table", table))?;
+
+    let serial_columns: Vec<String> = sqlx::query_scalar(
+        r#"
+        SELECT column_name::TEXT
+        FROM information_schema.columns
+        WHERE table_schema = current_schema() AND table_name = $1
+            AND column_default LIKE 'nextval%'
+        "#,
+    )
+    .bind(table)
+    .fetch_all(&mut *transaction)
+    .await
+    .with_context(|| format!("Failed to get the sequences of the {} table", table))?;
+    for column in serial_columns {
+        sqlx::query(&format!(
+            "SELECT setval(pg_get_serial_sequence('{table}', '{column}'), \
+            COALESCE(MAX({column}), 0) + 1, false) FROM {table}"
+        ))
+        .execute(&mut *transaction)
+        .await
+        .with_context(|| format!("Failed to reset the sequence of the {} table", table))?;
+    }
+
+    Ok(())
+}
+
+/// Returns the hash of a random password that nobody knows.
+async fn random_password_hash() -> Result<String, anyhow::Error> {
+    let password = Secret::new(Uuid::new_v4().to_string());
+    let password_hash = spawn_blocking_with_tracing(move || compute_password_hash(password))
+        .await?
+        .context("Failed to hash password")?;
+
+    Ok(password_hash.expose_secret().clone())
+}
+
+/// Appends a file with the given content to the archive.
+fn append_file<W: std::io::Write>(
+    archive: &mut tar::Builder<W>,
+    path: &str,
+    content: &[u8],
+) -> Result<(), anyhow::Error> {
+    let mut header = tar::Header::new_gnu();
+    header.set_size(content.len() as u64);
+    header.set_mode(0o644);
+    header.set_mtime(Utc::now().timestamp() as u64);
+    archive
+        .append_data(&mut header, path, content)
+        .with_context(|| format!("Failed to append `{}` to the archive", path))?;
+
+    Ok(())
+}
+
+/// Reads every file of a gzipped tar archive, by path.
+fn read_archive(path: &Path) -> Result<HashMap<String, Vec<u8>>, anyhow::Error> {
+    let file = File::open(path).context("Failed to open the archive file")?;
+    let mut archive = tar::Archive::new(GzDecoder::new(file));
+
+    let mut files = HashMap::new();
+    for entry in archive.entries().context("Failed to read the archive")? {
+        let mut entry = entry.context("Failed to read the archive")?;
+        let path = entry.path()?.to_string_lossy().into_owned();
+        let mut content = Vec::new();
+        entry
+            .read_to_end(&mut content)
+            .with_context(|| format!("Failed to read `{}` from the archive", path))?;
+        files.insert(path, content);
+    }
+
+    Ok(files)
+}
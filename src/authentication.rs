@@ -1,4 +1,5 @@
 use crate::{
+    metrics::Metrics,
     session_state::TypedSession,
     telemetry::spawn_blocking_with_tracing,
     utils::{e500, see_other},
@@ -7,7 +8,7 @@ use actix_web::{
     body::MessageBody,
     dev::{ServiceRequest, ServiceResponse},
     error::InternalError,
-    FromRequest, HttpMessage,
+    web, FromRequest, HttpMessage,
 };
 use actix_web_lab::middleware::Next;
 use anyhow::Context;
@@ -77,7 +78,7 @@ async fn get_stored_credentials(
         r#"
         SELECT user_id, password_hash
         FROM users
-        WHERE username = $1
+        WHERE username = $1 AND is_active
         "#,
         username
     )
@@ -100,6 +101,9 @@ fn verify_password_hash(
 ) -> Result<(), AuthError> {
     let expected_password_hash = PasswordHash::new(expected_password_hash.expose_secret())
         .context("Failed to parse hash in PHC string format")?;
+    let _timer = Metrics::global()
+        .password_hash_timer("verify")
+        .start_timer();
 
     Argon2::default()
         .verify_password(
@@ -139,6 +143,9 @@ pub async fn update_password(
 
 /// Computes password hash.
 pub fn compute_password_hash(password: Secret<String>) -> Result<Secret<String>, anyhow::Error> {
+    let _timer = Metrics::global()
+        .password_hash_timer("compute")
+        .start_timer();
     let salt = SaltString::generate(&mut rand::thread_rng());
     let password_hash = Argon2::new(
         Algorithm::Argon2id,
@@ -171,7 +178,29 @@ impl Deref for UserId {
     }
 }
 
+/// Returns whether the user can still use the application.
+#[tracing::instrument(name = "Checking if the user is active", skip(pool))]
+async fn is_active_user(pool: &PgPool, user_id: Uuid) -> Result<bool, anyhow::Error> {
+    let row = sqlx::query!(
+        r#"
+        SELECT is_active
+        FROM users
+        WHERE user_id = $1
+        "#,
+        user_id
+    )
+    .fetch_optional(pool)
+    .await
+    .context("Failed to check if the user is active")?;
+
+    Ok(row.is_some_and(|row| row.is_active))
+}
+
 /// Middleware used to reject anonymous users.
+///
+/// # Implementation Notes
+///
+/// The session of a deactivated user is removed, which logs them out.
 pub async fn reject_anonymous_users(
     mut req: ServiceRequest,
     next: Next<impl MessageBody>,
@@ -181,8 +210,24 @@ pub async fn reject_anonymous_users(
         TypedSession::from_request(http_request, payload).await
     }?;
 
-    // Check if user has logged in.
-    match session.get_user_id().map_err(e500)? {
+    // Check if user has logged in and is still active.
+    let user_id = match session.get_user_id().map_err(e500)? {
+        Some(user_id) => {
+            let pool = req
+                .app_data::<web::Data<PgPool>>()
+                .context("The connection pool is not registered")
+                .map_err(e500)?;
+            if is_active_user(pool, user_id).await.map_err(e500)? {
+                Some(user_id)
+            } else {
+                session.log_out();
+                None
+            }
+        }
+        None => None,
+    };
+
+    match user_id {
         Some(user_id) => {
             req.extensions_mut().insert(UserId(user_id));
             next.call(req).await
@@ -0,0 +1,146 @@
+use crate::{admin::create_user, configuration::BootstrapSettings, domain::UserRole};
+use anyhow::Context;
+use rand::{distributions::Alphanumeric, Rng};
+use secrecy::{ExposeSecret, Secret};
+use sqlx::PgPool;
+use std::sync::Mutex;
+use uuid::Uuid;
+
+/// Hash of the password of the administrator created by the first versions of the application.
+const DEFAULT_ADMIN_PASSWORD_HASH: &str = "$argon2id$v=19$m=15000,t=2,p=1$\
+    c2XBZ2Hrm0G04m+67AoJwg$\
+    sF8yP0YrbY9P/rOd7dFz2oArRgkphfEr4y05IdgOsFA";
+
+/// Representation of the one-time token guarding the setup page.
+pub struct SetupToken(Mutex<Option<Secret<String>>>);
+
+impl SetupToken {
+    /// Returns a new random token.
+    fn generate() -> Self {
+        let token: String = rand::thread_rng()
+            .sample_iter(&Alphanumeric)
+            .map(char::from)
+            .take(32)
+            .collect();
+
+        Self(Mutex::new(Some(Secret::new(token))))
+    }
+
+    /// Returns a token that has already been used.
+    fn used() -> Self {
+        Self(Mutex::new(None))
+    }
+
+    /// Returns the token, unless it has already been used.
+    pub fn get(&self) -> Option<Secret<String>> {
+        self.0.lock().unwrap().clone()
+    }
+
+    /// Returns whether the candidate matches the token, which can then never be used again.
+    pub fn consume(&self, candidate: &str) -> bool {
+        let mut token = self.0.lock().unwrap();
+        let is_valid = token
+            .as_ref()
+            .is_some_and(|token| token.expose_secret() == candidate);
+        if is_valid {
+            *token = None;
+        }
+
+        is_valid
+    }
+
+    /// Makes a consumed token usable again.
+    pub fn restore(&self, token: String) {
+        *self.0.lock().unwrap() = Some(Secret::new(token));
+    }
+}
+
+/// Creates the first administrator and then return the token of the setup page,
+/// used instead when no password is configured.
+///
+/// # Implementation Notes
+///
+/// Nothing is done when an active administrator exists already,
+/// the users still having the default password of the first versions are only reported.
+#[tracing::instrument(name = "Bootstrapping the application", skip(pool, settings))]
+pub async fn bootstrap_admin(
+    pool: &PgPool,
+    settings: &BootstrapSettings,
+) -> Result<SetupToken, anyhow::Error> {
+    warn_about_default_password(pool).await?;
+
+    if has_admin(pool).await? {
+        return Ok(SetupToken::used());
+    }
+
+    match &settings.admin_password {
+        Some(password) => {
+            let outcome =
+                create_admin(pool, settings.admin_username.clone(), password.clone()).await;
+            match outcome {
+                Ok(_) => tracing::info!("Created the administrator {}", settings.admin_username),
+                // Another instance starting together may have created it first.
+                Err(e) if !has_admin(pool).await? => return Err(e),
+                Err(_) => {}
+            }
+
+            Ok(SetupToken::used())
+        }
+        None => {
+            let token = SetupToken::generate();
+            tracing::warn!(
+                "No administrator exists yet, create one at /setup?token={}",
+                token.get().unwrap().expose_secret()
+            );
+
+            Ok(token)
+        }
+    }
+}
+
+/// Creates an administrator, member of the default project.
+#[tracing::instrument(name = "Creating the administrator", skip(pool, password))]
+pub async fn create_admin(
+    pool: &PgPool,
+    username: String,
+    password: Secret<String>,
+) -> Result<Uuid, anyhow::Error> {
+    create_user(pool, username, password, UserRole::Admin).await
+}
+
+/// Returns whether an active administrator exists.
+pub async fn has_admin(pool: &PgPool) -> Result<bool, anyhow::Error> {
+    let row = sqlx::query!(
+        r#"SELECT EXISTS (SELECT 1 FROM users WHERE role = 'admin' AND is_active) AS "exists!""#
+    )
+    .fetch_one(pool)
+    .await
+    .context("Failed to check if an administrator exists")?;
+
+    Ok(row.exists)
+}
+
+/// Logs a warning for every user still having the default password of the first versions.
+async fn warn_about_default_password(pool: &PgPool) -> Result<(), anyhow::Error> {
+    let rows = sqlx::query!(
+        r#"
+        SELECT username
+        FROM users
+        WHERE password_hash = $1 AND is_active
+        "#,
+        DEFAULT_ADMIN_PASSWORD_HASH
+    )
+    .fetch_all(pool)
+    .await
+    .context("Failed to look for the default password")?;
+
+    for row in rows {
+        tracing::warn!(
+            "The user {} still has the default password, change it or run `tessera reset-password {}`",
+            row.username,
+            row.username
+        );
+    }
+
+    Ok(())
+}
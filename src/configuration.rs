@@ -1,11 +1,19 @@
+use crate::{
+    domain::{BusinessCalendar, SlaPolicies, SlaPolicy},
+    storage::{AttachmentStorage, LocalStorage, S3Storage},
+};
+use chrono::NaiveDate;
 use config::{Config, ConfigError, Environment, File};
 use secrecy::{ExposeSecret, Secret};
 use serde::Deserialize;
-use serde_aux::field_attributes::deserialize_number_from_string;
+use serde_aux::field_attributes::{
+    deserialize_number_from_string, deserialize_option_number_from_string,
+};
 use sqlx::{
     postgres::{PgConnectOptions, PgPoolOptions, PgSslMode},
     ConnectOptions, PgPool,
 };
+use std::{collections::HashMap, num::NonZeroU64, sync::Arc};
 
 /// Representation of the settings.
 #[derive(Clone, Deserialize)]
@@ -13,6 +21,14 @@ pub struct Settings {
     pub application: ApplicationSettings,
     pub database: DatabaseSettings,
     pub redis_uri: Secret<String>,
+    pub notifications: NotificationSettings,
+    pub attachments: AttachmentSettings,
+    pub sla: SlaSettings,
+    pub sprints: SprintSettings,
+    pub dashboard: DashboardSettings,
+    pub import: ImportSettings,
+    pub bootstrap: BootstrapSettings,
+    pub tracing: TracingSettings,
 }
 
 /// Representation of the application's settings.
@@ -22,6 +38,58 @@ pub struct ApplicationSettings {
     #[serde(deserialize_with = "deserialize_number_from_string")]
     pub port: u16,
     pub hmac_secret: Secret<String>,
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub readiness_timeout_milliseconds: u64,
+    #[serde(default, deserialize_with = "deserialize_option_number_from_string")]
+    pub metrics_port: Option<u16>,
+    #[serde(default)]
+    pub expose_metrics_on_main_port: bool,
+    #[serde(default, deserialize_with = "deserialize_option_number_from_string")]
+    pub workers: Option<usize>,
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub keep_alive_seconds: u64,
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub client_request_timeout_milliseconds: u64,
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub client_disconnect_timeout_milliseconds: u64,
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub backlog: u32,
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub shutdown_delay_seconds: u64,
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub shutdown_grace_period_seconds: u64,
+}
+
+impl ApplicationSettings {
+    /// Returns how long to wait for a dependency before considering it unhealthy.
+    pub fn readiness_timeout(&self) -> std::time::Duration {
+        std::time::Duration::from_millis(self.readiness_timeout_milliseconds)
+    }
+
+    /// Returns how long an idle connection is kept alive.
+    pub fn keep_alive(&self) -> std::time::Duration {
+        std::time::Duration::from_secs(self.keep_alive_seconds)
+    }
+
+    /// Returns how long a client has to send the headers of its request.
+    pub fn client_request_timeout(&self) -> std::time::Duration {
+        std::time::Duration::from_millis(self.client_request_timeout_milliseconds)
+    }
+
+    /// Returns how long a client has to acknowledge the closing of its connection.
+    pub fn client_disconnect_timeout(&self) -> std::time::Duration {
+        std::time::Duration::from_millis(self.client_disconnect_timeout_milliseconds)
+    }
+
+    /// Returns how long `/ready` reports the shutdown before new connections are refused.
+    pub fn shutdown_delay(&self) -> std::time::Duration {
+        std::time::Duration::from_secs(self.shutdown_delay_seconds)
+    }
+
+    /// Returns how long the in-flight requests and the workers have to finish once shutting down.
+    pub fn shutdown_grace_period(&self) -> std::time::Duration {
+        std::time::Duration::from_secs(self.shutdown_grace_period_seconds)
+    }
 }
 
 /// Representation of the database's settings.
@@ -34,6 +102,37 @@ pub struct DatabaseSettings {
     pub port: u16,
     pub database_name: String,
     pub require_ssl: bool,
+    pub migrate_on_startup: bool,
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub min_connections: u32,
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub max_connections: u32,
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub idle_timeout_seconds: u64,
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub acquire_timeout_milliseconds: u64,
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub statement_timeout_milliseconds: u64,
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub slow_statement_threshold_milliseconds: u64,
+    #[serde(default)]
+    pub read_replica: Option<ReadReplicaSettings>,
+}
+
+/// Application name of the connections to the primary database.
+const PRIMARY_APPLICATION_NAME: &str = "tessera";
+
+/// Application name of the connections to the read replica.
+pub const READ_REPLICA_APPLICATION_NAME: &str = "tessera-read-replica";
+
+/// Representation of the read replica's settings.
+///
+/// The replica is reached with the credentials and the database name of the primary database.
+#[derive(Clone, Deserialize)]
+pub struct ReadReplicaSettings {
+    pub host: String,
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub port: u16,
 }
 
 impl DatabaseSettings {
@@ -56,23 +155,262 @@ impl DatabaseSettings {
     }
 
     /// Returns the connection string for a Postgres database.
+    ///
+    /// # Implementation Notes
+    ///
+    /// Every statement is logged at the trace level, only exported with the traces,
+    /// whereas the statements slower than the threshold are logged as warnings.
+    /// A statement timeout of 0 disables it, the migrations and the archives lift it.
     pub fn with_db(&self) -> PgConnectOptions {
-        let mut options = self.without_db().database(&self.database_name);
+        let mut options = self
+            .without_db()
+            .database(&self.database_name)
+            .application_name(PRIMARY_APPLICATION_NAME)
+            .options([(
+                "statement_timeout",
+                self.statement_timeout_milliseconds.to_string(),
+            )]);
 
         // Remove noise.
         options.log_statements(tracing::log::LevelFilter::Trace);
+        options.log_slow_statements(
+            tracing::log::LevelFilter::Warn,
+            std::time::Duration::from_millis(self.slow_statement_threshold_milliseconds),
+        );
 
         options
     }
 
     /// Returns the connection pool for a Postgres database.
     pub fn get_connection_pool(&self) -> PgPool {
-        PgPoolOptions::new()
-            // Timeout after 2 seconds.
-            .acquire_timeout(std::time::Duration::from_secs(2))
+        self.pool_options()
             // Establish a connection when the pool is used for the first time.
             .connect_lazy_with(self.with_db())
     }
+
+    /// Returns the connection pool for the read replica, if any.
+    ///
+    /// # Implementation Notes
+    ///
+    /// The connections carry the application name of the replica,
+    /// so that `pg_stat_activity` tells them apart from the connections to the primary database.
+    pub fn get_read_replica_pool(&self) -> Option<PgPool> {
+        self.read_replica.as_ref().map(|replica| {
+            self.pool_options().connect_lazy_with(
+                self.with_db()
+                    .host(&replica.host)
+                    .port(replica.port)
+                    .application_name(READ_REPLICA_APPLICATION_NAME),
+            )
+        })
+    }
+
+    /// Returns the options shared by the connection pools.
+    fn pool_options(&self) -> PgPoolOptions {
+        PgPoolOptions::new()
+            .min_connections(self.min_connections)
+            .max_connections(self.max_connections)
+            .idle_timeout(std::time::Duration::from_secs(self.idle_timeout_seconds))
+            .acquire_timeout(std::time::Duration::from_millis(
+                self.acquire_timeout_milliseconds,
+            ))
+    }
+}
+
+/// Representation of the first administrator's settings.
+#[derive(Clone, Deserialize)]
+pub struct BootstrapSettings {
+    pub admin_username: String,
+    pub admin_password: Option<Secret<String>>,
+}
+
+/// Representation of the notifications' settings.
+#[derive(Clone, Deserialize)]
+pub struct NotificationSettings {
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub retention_days: i64,
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub cleanup_interval_seconds: u64,
+}
+
+impl NotificationSettings {
+    /// Returns how long a read notification is kept.
+    pub fn retention(&self) -> chrono::Duration {
+        chrono::Duration::days(self.retention_days)
+    }
+
+    /// Returns how long to wait between two clean ups.
+    pub fn cleanup_interval(&self) -> std::time::Duration {
+        std::time::Duration::from_secs(self.cleanup_interval_seconds)
+    }
+}
+
+/// Representation of the service level agreements' settings.
+#[derive(Clone, Deserialize)]
+pub struct SlaSettings {
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub check_interval_seconds: u64,
+    pub calendar: CalendarSettings,
+    pub policies: HashMap<String, SlaPolicySettings>,
+}
+
+/// Representation of the business hours' settings.
+#[derive(Clone, Deserialize)]
+pub struct CalendarSettings {
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub utc_offset_minutes: i32,
+    pub work_days: Vec<String>,
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub start_hour: u32,
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub end_hour: u32,
+    pub holidays: Vec<NaiveDate>,
+}
+
+/// Representation of the targets of a priority, in business hours.
+#[derive(Clone, Deserialize)]
+pub struct SlaPolicySettings {
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub first_response_hours: i64,
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub resolution_hours: i64,
+}
+
+impl SlaSettings {
+    /// Returns how long to wait between two checks of the breaches.
+    pub fn check_interval(&self) -> std::time::Duration {
+        std::time::Duration::from_secs(self.check_interval_seconds)
+    }
+
+    /// Returns the service level agreements of the support desk.
+    pub fn get_policies(&self) -> Result<SlaPolicies, anyhow::Error> {
+        let calendar = BusinessCalendar::parse(
+            self.calendar.utc_offset_minutes,
+            &self.calendar.work_days,
+            self.calendar.start_hour,
+            self.calendar.end_hour,
+            &self.calendar.holidays,
+        )
+        .map_err(anyhow::Error::msg)?;
+        let policies = self
+            .policies
+            .iter()
+            .map(|(priority, policy)| {
+                (
+                    priority.clone(),
+                    SlaPolicy {
+                        first_response: chrono::Duration::hours(policy.first_response_hours),
+                        resolution: chrono::Duration::hours(policy.resolution_hours),
+                    },
+                )
+            })
+            .collect();
+
+        Ok(SlaPolicies { calendar, policies })
+    }
+}
+
+/// Representation of the sprints' settings.
+#[derive(Clone, Deserialize)]
+pub struct SprintSettings {
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub snapshot_interval_seconds: NonZeroU64,
+}
+
+impl SprintSettings {
+    /// Returns how long to wait between two snapshots of the sprints.
+    ///
+    /// # Implementation Notes
+    ///
+    /// An interval of 0 seconds is rejected when the settings are read,
+    /// the worker would never wait between two snapshots.
+    pub fn snapshot_interval(&self) -> std::time::Duration {
+        std::time::Duration::from_secs(self.snapshot_interval_seconds.get())
+    }
+}
+
+/// Representation of the dashboard's settings.
+#[derive(Clone, Deserialize)]
+pub struct DashboardSettings {
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub cache_ttl_seconds: u64,
+}
+
+impl DashboardSettings {
+    /// Returns how long the metrics of a project are reused before being computed again.
+    pub fn cache_ttl(&self) -> std::time::Duration {
+        std::time::Duration::from_secs(self.cache_ttl_seconds)
+    }
+}
+
+/// Representation of the import's settings.
+#[derive(Clone, Deserialize)]
+pub struct ImportSettings {
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub max_size_bytes: usize,
+    pub placeholder_username: String,
+}
+
+/// Representation of the attachments' settings.
+#[derive(Clone, Deserialize)]
+pub struct AttachmentSettings {
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub max_size_bytes: usize,
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub max_parts: usize,
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub max_form_size_bytes: usize,
+    pub allowed_content_types: Vec<String>,
+    pub storage: StorageSettings,
+}
+
+/// Representation of the attachments' storage settings.
+#[derive(Clone, Deserialize)]
+#[serde(tag = "backend", rename_all = "snake_case")]
+pub enum StorageSettings {
+    Local {
+        path: String,
+    },
+    S3 {
+        endpoint: String,
+        bucket: String,
+        region: String,
+        access_key_id: String,
+        secret_access_key: Secret<String>,
+    },
+}
+
+impl StorageSettings {
+    /// Returns the storage backend of the attachments.
+    pub fn get_storage(&self) -> Result<Arc<dyn AttachmentStorage>, anyhow::Error> {
+        let storage: Arc<dyn AttachmentStorage> = match self {
+            StorageSettings::Local { path } => Arc::new(LocalStorage::new(path)),
+            StorageSettings::S3 {
+                endpoint,
+                bucket,
+                region,
+                access_key_id,
+                secret_access_key,
+            } => Arc::new(S3Storage::new(
+                endpoint.parse()?,
+                bucket.clone(),
+                region.clone(),
+                access_key_id.clone(),
+                secret_access_key.clone(),
+            )),
+        };
+
+        Ok(storage)
+    }
+}
+
+/// Representation of the traces' settings.
+#[derive(Clone, Deserialize)]
+pub struct TracingSettings {
+    pub service_name: String,
+    pub otlp_endpoint: Option<String>,
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub sampling_ratio: f64,
 }
 
 /// Representation of the runtime environments.
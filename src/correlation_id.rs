@@ -0,0 +1,147 @@
+use actix_web::{
+    body::MessageBody,
+    dev::{ServiceRequest, ServiceResponse},
+    error::InternalError,
+    http::header::{HeaderName, HeaderValue},
+    HttpMessage,
+};
+use actix_web_lab::middleware::Next;
+use std::fmt::Display;
+use tracing::Span;
+use tracing_actix_web::{root_span, DefaultRootSpanBuilder, RequestId, RootSpanBuilder};
+use uuid::Uuid;
+
+/// Header carrying the correlation id of a request, both in the request and in its response.
+pub const REQUEST_ID_HEADER: HeaderName = HeaderName::from_static("x-request-id");
+
+/// Maximum length of a correlation id sent by a caller.
+const MAX_LENGTH: usize = 128;
+
+tokio::task_local! {
+    // Correlation id of the request being handled by the current task.
+    static CURRENT_CORRELATION_ID: CorrelationId;
+}
+
+/// Representation of the id correlating a request with its logs, its response and its error pages.
+#[derive(Clone, Debug)]
+pub struct CorrelationId(String);
+
+impl CorrelationId {
+    /// Returns the id sent by the caller in the `X-Request-Id` header if valid,
+    /// otherwise the id generated for the request.
+    ///
+    /// # Implementation Notes
+    ///
+    /// Only short ids made of letters, digits, `-`, `_` and `.` are accepted,
+    /// so that a caller cannot forge log records or headers.
+    fn from_request(request: &ServiceRequest) -> Self {
+        let sent_id = request
+            .headers()
+            .get(REQUEST_ID_HEADER)
+            .and_then(|value| value.to_str().ok())
+            .filter(|id| {
+                !id.is_empty()
+                    && id.len() <= MAX_LENGTH
+                    && id
+                        .chars()
+                        .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+            });
+
+        match sent_id {
+            Some(id) => Self(id.to_string()),
+            // Reuse the id of the request, already part of its logs.
+            None => {
+                let request_id = request
+                    .extensions()
+                    .get::<RequestId>()
+                    .map(|request_id| **request_id)
+                    .unwrap_or_else(Uuid::new_v4);
+
+                Self(request_id.to_string())
+            }
+        }
+    }
+
+    /// Returns the correlation id of the request being handled, if any.
+    pub fn current() -> Option<Self> {
+        CURRENT_CORRELATION_ID.try_with(Clone::clone).ok()
+    }
+}
+
+impl AsRef<str> for CorrelationId {
+    /// Converts this type into a shared reference of the (usually inferred) input type.
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Display for CorrelationId {
+    /// Formats the value using the given formatter.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+/// Root span builder recording the correlation id of each request.
+pub struct CorrelationRootSpanBuilder;
+
+impl RootSpanBuilder for CorrelationRootSpanBuilder {
+    /// Creates the root span of the request.
+    fn on_request_start(request: &ServiceRequest) -> Span {
+        let correlation_id = CorrelationId::from_request(request);
+        let span = root_span!(request, correlation_id = %correlation_id);
+        request.extensions_mut().insert(correlation_id);
+
+        span
+    }
+
+    /// Records the outcome of the request in its root span.
+    fn on_request_end<B: MessageBody>(
+        span: Span,
+        outcome: &Result<ServiceResponse<B>, actix_web::Error>,
+    ) {
+        DefaultRootSpanBuilder::on_request_end(span, outcome);
+    }
+}
+
+/// Middleware making the correlation id available to the error responses
+/// and then returning it in the `X-Request-Id` header.
+///
+/// # Implementation Notes
+///
+/// The errors are turned into responses here rather than by the server,
+/// while the correlation id of the request is still available to them.
+pub async fn propagate_correlation_id(
+    req: ServiceRequest,
+    next: Next<impl MessageBody>,
+) -> Result<ServiceResponse<impl MessageBody>, actix_web::Error> {
+    let correlation_id = req
+        .extensions()
+        .get::<CorrelationId>()
+        .cloned()
+        .unwrap_or_else(|| CorrelationId::from_request(&req));
+    let header_value = HeaderValue::from_str(correlation_id.as_ref())
+        .expect("The correlation id is a valid header value");
+
+    CURRENT_CORRELATION_ID
+        .scope(correlation_id, async move {
+            match next.call(req).await {
+                Ok(mut response) => {
+                    response
+                        .headers_mut()
+                        .insert(REQUEST_ID_HEADER, header_value);
+
+                    Ok(response)
+                }
+                Err(e) => {
+                    let mut response = e.error_response();
+                    response
+                        .headers_mut()
+                        .insert(REQUEST_ID_HEADER, header_value);
+
+                    Err(InternalError::from_response(e, response).into())
+                }
+            }
+        })
+        .await
+}
@@ -0,0 +1,49 @@
+use crate::domain::DashboardMetrics;
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+/// Representation of the cache of the dashboard metrics of each project.
+///
+/// # Implementation Notes
+///
+/// The metrics are aggregated over every ticket of the project,
+/// they are reused for a short while so that the dashboard stays fast on large projects.
+pub struct DashboardCache {
+    ttl: Duration,
+    entries: Mutex<HashMap<i32, (Instant, Arc<DashboardMetrics>)>>,
+}
+
+impl DashboardCache {
+    /// Creates a cache keeping the metrics for the given duration.
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Returns the metrics of the project, none if they are missing or expired.
+    pub fn get(&self, project_id: i32) -> Option<Arc<DashboardMetrics>> {
+        let entries = self.entries.lock().unwrap();
+
+        entries
+            .get(&project_id)
+            .filter(|(stored_at, _)| stored_at.elapsed() < self.ttl)
+            .map(|(_, metrics)| Arc::clone(metrics))
+    }
+
+    /// Stores the metrics of the project and then returns them.
+    pub fn insert(&self, project_id: i32, metrics: DashboardMetrics) -> Arc<DashboardMetrics> {
+        let metrics = Arc::new(metrics);
+        let mut entries = self.entries.lock().unwrap();
+
+        // Drop the expired entries so that the cache does not grow with the projects.
+        entries.retain(|_, (stored_at, _)| stored_at.elapsed() < self.ttl);
+        entries.insert(project_id, (Instant::now(), Arc::clone(&metrics)));
+
+        metrics
+    }
+}
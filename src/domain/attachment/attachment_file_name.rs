@@ -0,0 +1,92 @@
+use unicode_segmentation::UnicodeSegmentation;
+
+/// Representation of an attachment's file name.
+#[derive(Debug)]
+pub struct AttachmentFileName(String);
+
+impl AttachmentFileName {
+    /// Returns a valid attachment file name.
+    ///
+    /// # Implementation Notes
+    ///
+    /// Only the last component of a path is kept, some browsers send the full path.
+    pub fn parse(s: String) -> Result<Self, String> {
+        let s = s
+            .rsplit(['/', '\\'])
+            .next()
+            .unwrap_or_default()
+            .trim()
+            .to_string();
+
+        // Check if the input is empty or with a trailing whitespace-like character.
+        let is_empty_or_whitespace = s.is_empty();
+        // Check if the input is too long.
+        let is_too_long = s.graphemes(true).count() > 256;
+        // Check if the input contains control characters.
+        let contains_control_characters = s.chars().any(char::is_control);
+
+        // Validate whether the input is a valid file name or not.
+        if is_empty_or_whitespace {
+            Err("attachment file name cannot be empty.".to_string())
+        } else if is_too_long {
+            Err("attachment file name cannot be longer than 256 characters.".to_string())
+        } else if contains_control_characters {
+            Err("attachment file name cannot contain control characters.".to_string())
+        } else {
+            Ok(Self(s))
+        }
+    }
+}
+
+impl AsRef<str> for AttachmentFileName {
+    /// Performs the conversion.
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::AttachmentFileName;
+    use claims::{assert_err, assert_ok};
+
+    // Must return `Err` if the input is empty.
+    #[test]
+    fn attachment_file_name_returns_err_when_empty() {
+        let name = "".to_string();
+        assert_err!(AttachmentFileName::parse(name));
+    }
+
+    // Must return `Err` if the input is bigger than 256 characters.
+    #[test]
+    fn attachment_file_name_returns_err_when_too_long() {
+        let name = "a".repeat(257);
+        assert_err!(AttachmentFileName::parse(name));
+    }
+
+    // Must return `Err` if the input contains control characters.
+    #[test]
+    fn attachment_file_name_returns_err_when_control_characters() {
+        let name = "screen\nshot.png".to_string();
+        assert_err!(AttachmentFileName::parse(name));
+    }
+
+    // Must keep only the last component of a path.
+    #[test]
+    fn attachment_file_name_keeps_only_the_last_path_component() {
+        let name = r"C:\Users\me\..\screenshot.png".to_string();
+        let file_name = AttachmentFileName::parse(name).unwrap();
+        assert_eq!(file_name.as_ref(), "screenshot.png");
+
+        let name = "../../etc/passwd".to_string();
+        let file_name = AttachmentFileName::parse(name).unwrap();
+        assert_eq!(file_name.as_ref(), "passwd");
+    }
+
+    // Must return `Ok` if the input is valid.
+    #[test]
+    fn attachment_file_name_returns_ok_when_valid() {
+        let name = "screenshot.png".to_string();
+        assert_ok!(AttachmentFileName::parse(name));
+    }
+}
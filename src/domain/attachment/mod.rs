@@ -0,0 +1,7 @@
+mod attachment_file_name;
+mod new_attachment;
+mod valid_attachment;
+
+pub use attachment_file_name::AttachmentFileName;
+pub use new_attachment::NewAttachment;
+pub use valid_attachment::ValidAttachment;
@@ -0,0 +1,122 @@
+use crate::{configuration::AttachmentSettings, domain::AttachmentFileName};
+use actix_web::web::Bytes;
+
+/// Representation of a new attachment.
+#[derive(Debug)]
+pub struct NewAttachment {
+    pub file_name: AttachmentFileName,
+    pub content_type: String,
+    pub content: Bytes,
+}
+
+impl NewAttachment {
+    /// Returns a valid attachment, within the limits of the settings.
+    pub fn parse(
+        file_name: String,
+        content_type: String,
+        content: Bytes,
+        settings: &AttachmentSettings,
+    ) -> Result<Self, String> {
+        let file_name = AttachmentFileName::parse(file_name)?;
+
+        if content.is_empty() {
+            return Err(format!("attachment `{}` is empty.", file_name.as_ref()));
+        }
+        if content.len() > settings.max_size_bytes {
+            return Err(format!(
+                "attachment `{}` cannot be larger than {} bytes.",
+                file_name.as_ref(),
+                settings.max_size_bytes
+            ));
+        }
+        if !settings.allowed_content_types.contains(&content_type) {
+            return Err(format!(
+                "attachment `{}` cannot be of type `{}`.",
+                file_name.as_ref(),
+                content_type
+            ));
+        }
+
+        Ok(Self {
+            file_name,
+            content_type,
+            content,
+        })
+    }
+
+    /// Returns `true` if the attachment is an image.
+    pub fn is_image(&self) -> bool {
+        self.content_type.starts_with("image/")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        configuration::{AttachmentSettings, StorageSettings},
+        domain::NewAttachment,
+    };
+    use actix_web::web::Bytes;
+    use claims::{assert_err, assert_ok};
+
+    /// Returns settings allowing small text files only.
+    fn settings() -> AttachmentSettings {
+        AttachmentSettings {
+            max_size_bytes: 10,
+            max_parts: 5,
+            max_form_size_bytes: 20,
+            allowed_content_types: vec!["text/plain".to_string()],
+            storage: StorageSettings::Local {
+                path: std::env::temp_dir().to_string_lossy().into(),
+            },
+        }
+    }
+
+    // Must return `Err` if the content is empty.
+    #[test]
+    fn new_attachment_returns_err_when_empty() {
+        let attachment = NewAttachment::parse(
+            "logs.txt".to_string(),
+            "text/plain".to_string(),
+            Bytes::new(),
+            &settings(),
+        );
+        assert_err!(attachment);
+    }
+
+    // Must return `Err` if the content is too large.
+    #[test]
+    fn new_attachment_returns_err_when_too_large() {
+        let attachment = NewAttachment::parse(
+            "logs.txt".to_string(),
+            "text/plain".to_string(),
+            Bytes::from("a".repeat(11)),
+            &settings(),
+        );
+        assert_err!(attachment);
+    }
+
+    // Must return `Err` if the content type is not allowed.
+    #[test]
+    fn new_attachment_returns_err_when_content_type_not_allowed() {
+        let attachment = NewAttachment::parse(
+            "page.html".to_string(),
+            "text/html".to_string(),
+            Bytes::from("<p></p>"),
+            &settings(),
+        );
+        assert_err!(attachment);
+    }
+
+    // Must return `Ok` if the input is valid.
+    #[test]
+    fn new_attachment_returns_ok_when_valid() {
+        let attachment = NewAttachment::parse(
+            "logs.txt".to_string(),
+            "text/plain".to_string(),
+            Bytes::from("a".repeat(10)),
+            &settings(),
+        );
+        assert_ok!(attachment);
+    }
+}
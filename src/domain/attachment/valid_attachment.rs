@@ -0,0 +1,23 @@
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+/// Representation of a valid attachment.
+#[derive(Debug, PartialEq)]
+pub struct ValidAttachment {
+    pub id: Uuid,
+    pub ticket_id: i32,
+    pub file_name: String,
+    pub content_type: String,
+    pub size_bytes: i64,
+    pub storage_key: String,
+    pub thumbnail_key: Option<String>,
+    pub uploaded_by: Uuid,
+    pub uploaded_at: DateTime<Utc>,
+}
+
+impl ValidAttachment {
+    /// Returns `true` if the attachment is an image.
+    pub fn is_image(&self) -> bool {
+        self.content_type.starts_with("image/")
+    }
+}
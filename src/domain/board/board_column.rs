@@ -0,0 +1,127 @@
+use crate::domain::ValidTicket;
+
+/// Representation of the ways of splitting a board into columns.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum BoardGrouping {
+    Status,
+    Priority,
+}
+
+impl BoardGrouping {
+    /// Extracts a string slice containing the entire `String`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BoardGrouping::Status => "status",
+            BoardGrouping::Priority => "priority",
+        }
+    }
+
+    /// Returns the columns of the board, from left to right.
+    pub fn columns(&self) -> &'static [BoardColumn] {
+        match self {
+            BoardGrouping::Status => &[BoardColumn::Open, BoardColumn::Closed],
+            BoardGrouping::Priority => &[BoardColumn::High, BoardColumn::Medium, BoardColumn::Low],
+        }
+    }
+}
+
+impl TryFrom<String> for BoardGrouping {
+    type Error = String;
+
+    /// Performs the conversion.
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        match s.to_lowercase().as_str() {
+            "status" => Ok(Self::Status),
+            "priority" => Ok(Self::Priority),
+            other => Err(format!("`{other}` is not a supported board grouping.")),
+        }
+    }
+}
+
+/// Representation of a column of a board.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum BoardColumn {
+    Open,
+    Closed,
+    High,
+    Medium,
+    Low,
+}
+
+impl BoardColumn {
+    /// Extracts a string slice containing the entire `String`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BoardColumn::Open => "open",
+            BoardColumn::Closed => "closed",
+            BoardColumn::High => "high",
+            BoardColumn::Medium => "medium",
+            BoardColumn::Low => "low",
+        }
+    }
+
+    /// Returns the grouping the column belongs to.
+    pub fn grouping(&self) -> BoardGrouping {
+        match self {
+            BoardColumn::Open | BoardColumn::Closed => BoardGrouping::Status,
+            BoardColumn::High | BoardColumn::Medium | BoardColumn::Low => BoardGrouping::Priority,
+        }
+    }
+
+    /// Returns whether the ticket belongs to the column.
+    ///
+    /// # Implementation Notes
+    ///
+    /// The columns by priority only hold the open tickets.
+    pub fn contains(&self, ticket: &ValidTicket) -> bool {
+        match self {
+            BoardColumn::Open => ticket.is_open,
+            BoardColumn::Closed => !ticket.is_open,
+            _ => ticket.is_open && ticket.priority == self.as_str(),
+        }
+    }
+}
+
+impl TryFrom<String> for BoardColumn {
+    type Error = String;
+
+    /// Performs the conversion.
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        match s.to_lowercase().as_str() {
+            "open" => Ok(Self::Open),
+            "closed" => Ok(Self::Closed),
+            "high" => Ok(Self::High),
+            "medium" => Ok(Self::Medium),
+            "low" => Ok(Self::Low),
+            other => Err(format!("`{other}` is not a supported board column.")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::{BoardColumn, BoardGrouping};
+    use claims::{assert_err, assert_ok_eq};
+
+    // Must return `Ok` if the input is a supported column of its grouping.
+    #[test]
+    fn board_column_returns_ok_when_valid() {
+        for grouping in [BoardGrouping::Status, BoardGrouping::Priority] {
+            assert_ok_eq!(
+                BoardGrouping::try_from(grouping.as_str().to_string()),
+                grouping
+            );
+            for column in grouping.columns() {
+                assert_ok_eq!(BoardColumn::try_from(column.as_str().to_string()), *column);
+                assert_eq!(column.grouping(), grouping);
+            }
+        }
+    }
+
+    // Must return `Err` if the input is not a supported column.
+    #[test]
+    fn board_column_returns_err_when_unknown() {
+        assert_err!(BoardColumn::try_from("in_progress".to_string()));
+        assert_err!(BoardGrouping::try_from("assignee".to_string()));
+    }
+}
@@ -0,0 +1,3 @@
+mod board_column;
+
+pub use board_column::{BoardColumn, BoardGrouping};
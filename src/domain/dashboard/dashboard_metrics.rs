@@ -0,0 +1,178 @@
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+
+/// Representation of the number of open and closed tickets of a priority.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PriorityCount {
+    pub priority: String,
+    pub open: i64,
+    pub closed: i64,
+}
+
+/// Representation of the number of tickets created and closed during a day.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DailyActivity {
+    pub day: NaiveDate,
+    pub created: i64,
+    pub closed: i64,
+}
+
+/// Representation of a ticket as listed among the recently updated ones.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RecentTicket {
+    pub key: String,
+    pub title: String,
+    pub is_open: bool,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Representation of the metrics of a project shown on the dashboard.
+#[derive(Debug)]
+pub struct DashboardMetrics {
+    pub priority_counts: Vec<PriorityCount>,
+    pub daily_activity: Vec<DailyActivity>,
+    pub median_time_to_close: Option<Duration>,
+    pub recently_updated: Vec<RecentTicket>,
+    pub computed_at: DateTime<Utc>,
+}
+
+impl DashboardMetrics {
+    /// Returns the counts of every priority, from the highest to the lowest,
+    /// given the counts by status and priority.
+    pub fn count_by_priority(counts: &[(bool, String, i64)]) -> Vec<PriorityCount> {
+        ["high", "medium", "low"]
+            .into_iter()
+            .map(|priority| {
+                let count = |is_open: bool| {
+                    counts
+                        .iter()
+                        .filter(|(open, p, _)| *open == is_open && p == priority)
+                        .map(|(_, _, count)| count)
+                        .sum()
+                };
+
+                PriorityCount {
+                    priority: priority.to_string(),
+                    open: count(true),
+                    closed: count(false),
+                }
+            })
+            .collect()
+    }
+
+    /// Returns the number of open tickets.
+    pub fn open_total(&self) -> i64 {
+        self.priority_counts.iter().map(|c| c.open).sum()
+    }
+
+    /// Returns the number of closed tickets.
+    pub fn closed_total(&self) -> i64 {
+        self.priority_counts.iter().map(|c| c.closed).sum()
+    }
+
+    /// Returns the number of tickets created during the period.
+    pub fn created_in_period(&self) -> i64 {
+        self.daily_activity.iter().map(|a| a.created).sum()
+    }
+
+    /// Returns the number of tickets closed during the period.
+    pub fn closed_in_period(&self) -> i64 {
+        self.daily_activity.iter().map(|a| a.closed).sum()
+    }
+
+    /// Returns the height of a bar of the activity chart, in percent of the busiest day.
+    pub fn bar_height(&self, count: &i64) -> i64 {
+        let max = self
+            .daily_activity
+            .iter()
+            .map(|a| a.created.max(a.closed))
+            .max()
+            .unwrap_or(0);
+
+        if max == 0 {
+            0
+        } else {
+            count * 100 / max
+        }
+    }
+
+    /// Returns the median time to close, such as `2d 04h`.
+    pub fn median_time_to_close_label(&self) -> String {
+        match self.median_time_to_close {
+            Some(median) if median.num_hours() >= 24 => {
+                format!("{}d {:02}h", median.num_days(), median.num_hours() % 24)
+            }
+            Some(median) => format!("{}h {:02}m", median.num_hours(), median.num_minutes() % 60),
+            None => "no ticket has been closed".to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::{DailyActivity, DashboardMetrics};
+    use chrono::{Duration, NaiveDate, Utc};
+
+    /// Returns the metrics with the given activity and median time to close.
+    fn metrics(activity: &[(i64, i64)], median: Option<Duration>) -> DashboardMetrics {
+        let counts = [
+            (true, "low".to_string(), 2),
+            (false, "low".to_string(), 1),
+            (true, "high".to_string(), 3),
+        ];
+
+        DashboardMetrics {
+            priority_counts: DashboardMetrics::count_by_priority(&counts),
+            daily_activity: activity
+                .iter()
+                .enumerate()
+                .map(|(offset, (created, closed))| DailyActivity {
+                    day: NaiveDate::from_ymd_opt(2026, 10, 1 + offset as u32).unwrap(),
+                    created: *created,
+                    closed: *closed,
+                })
+                .collect(),
+            median_time_to_close: median,
+            recently_updated: Vec::new(),
+            computed_at: Utc::now(),
+        }
+    }
+
+    // Must count every priority, the highest first, even without tickets.
+    #[test]
+    fn count_by_priority_lists_every_priority() {
+        let metrics = metrics(&[], None);
+
+        let priorities: Vec<&str> = metrics
+            .priority_counts
+            .iter()
+            .map(|c| c.priority.as_str())
+            .collect();
+        assert_eq!(priorities, ["high", "medium", "low"]);
+        assert_eq!(metrics.priority_counts[1].open, 0);
+        assert_eq!(metrics.open_total(), 5);
+        assert_eq!(metrics.closed_total(), 1);
+    }
+
+    // Must scale the bars of the activity chart to the busiest day.
+    #[test]
+    fn bar_height_is_relative_to_the_busiest_day() {
+        let busy = metrics(&[(4, 1), (0, 2)], None);
+        assert_eq!(busy.bar_height(&4), 100);
+        assert_eq!(busy.bar_height(&1), 25);
+        assert_eq!(busy.created_in_period(), 4);
+        assert_eq!(busy.closed_in_period(), 3);
+
+        let idle = metrics(&[(0, 0)], None);
+        assert_eq!(idle.bar_height(&0), 0);
+    }
+
+    // Must show the median time to close in days, or in hours when shorter than a day.
+    #[test]
+    fn median_time_to_close_label_is_readable() {
+        let label = |median| metrics(&[], median).median_time_to_close_label();
+
+        assert_eq!(label(Some(Duration::hours(52))), "2d 04h");
+        assert_eq!(label(Some(Duration::minutes(95))), "1h 35m");
+        assert_eq!(label(None), "no ticket has been closed");
+    }
+}
@@ -0,0 +1,3 @@
+mod dashboard_metrics;
+
+pub use dashboard_metrics::{DailyActivity, DashboardMetrics, PriorityCount, RecentTicket};
@@ -0,0 +1,89 @@
+/// Representation of the CSV columns holding each field of the imported tickets.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ColumnMapping {
+    pub title: String,
+    pub description: String,
+    pub priority: Option<String>,
+    pub author: Option<String>,
+    pub status: Option<String>,
+}
+
+impl ColumnMapping {
+    /// Returns a valid mapping, the title and the description being the only mandatory columns.
+    pub fn parse(
+        title: String,
+        description: String,
+        priority: String,
+        author: String,
+        status: String,
+    ) -> Result<Self, String> {
+        let optional = |column: String| Some(column.trim().to_string()).filter(|c| !c.is_empty());
+        let title = optional(title).ok_or("the title column must be mapped.")?;
+        let description = optional(description).ok_or("the description column must be mapped.")?;
+
+        Ok(Self {
+            title,
+            description,
+            priority: optional(priority),
+            author: optional(author),
+            status: optional(status),
+        })
+    }
+}
+
+impl Default for ColumnMapping {
+    /// Returns the mapping of the columns named after the fields.
+    fn default() -> Self {
+        Self {
+            title: "title".to_string(),
+            description: "description".to_string(),
+            priority: Some("priority".to_string()),
+            author: Some("author".to_string()),
+            status: Some("status".to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::ColumnMapping;
+    use claims::{assert_err, assert_ok_eq};
+
+    // Must ignore the optional columns left empty.
+    #[test]
+    fn column_mapping_ignores_empty_optional_columns() {
+        let mapping = ColumnMapping::parse(
+            " Summary ".to_string(),
+            "Details".to_string(),
+            "".to_string(),
+            "Reporter".to_string(),
+            " ".to_string(),
+        );
+        assert_ok_eq!(
+            mapping,
+            ColumnMapping {
+                title: "Summary".to_string(),
+                description: "Details".to_string(),
+                priority: None,
+                author: Some("Reporter".to_string()),
+                status: None,
+            }
+        );
+    }
+
+    // Must return `Err` if the title or the description is not mapped.
+    #[test]
+    fn column_mapping_returns_err_when_mandatory_column_is_missing() {
+        let mapping = |title: &str, description: &str| {
+            ColumnMapping::parse(
+                title.to_string(),
+                description.to_string(),
+                String::new(),
+                String::new(),
+                String::new(),
+            )
+        };
+        assert_err!(mapping("", "Details"));
+        assert_err!(mapping("Summary", ""));
+    }
+}
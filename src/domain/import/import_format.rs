@@ -0,0 +1,44 @@
+/// Representation of the formats of the files that tickets can be imported from.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ImportFormat {
+    Csv,
+    GithubJson,
+}
+
+impl ImportFormat {
+    /// Extracts a string slice containing the entire `String`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ImportFormat::Csv => "csv",
+            ImportFormat::GithubJson => "github-json",
+        }
+    }
+}
+
+impl TryFrom<String> for ImportFormat {
+    type Error = String;
+
+    /// Performs the conversion.
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        match s.to_lowercase().as_str() {
+            "csv" => Ok(Self::Csv),
+            "github-json" => Ok(Self::GithubJson),
+            other => Err(format!("`{other}` is not a supported import format.")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::ImportFormat;
+    use claims::{assert_err, assert_ok_eq};
+
+    // Must convert the supported formats back and forth and reject the others.
+    #[test]
+    fn import_format_parses_supported_formats() {
+        for format in [ImportFormat::Csv, ImportFormat::GithubJson] {
+            assert_ok_eq!(ImportFormat::try_from(format.as_str().to_string()), format);
+        }
+        assert_err!(ImportFormat::try_from("xlsx".to_string()));
+    }
+}
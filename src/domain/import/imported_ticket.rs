@@ -0,0 +1,107 @@
+use crate::domain::{TicketDescription, TicketTitle};
+use chrono::{DateTime, Utc};
+
+/// Representation of a ticket as read from an import file, before any validation.
+#[derive(Debug, Default)]
+pub struct RawTicket {
+    pub title: String,
+    pub description: String,
+    pub priority: String,
+    pub author: String,
+    pub status: String,
+    pub created_at: Option<DateTime<Utc>>,
+    pub closed_at: Option<DateTime<Utc>>,
+}
+
+/// Representation of a valid ticket ready to be imported.
+#[derive(Debug)]
+pub struct ImportedTicket {
+    pub title: TicketTitle,
+    pub description: TicketDescription,
+    pub priority: String,
+    pub author: Option<String>,
+    pub is_open: bool,
+    pub created_at: Option<DateTime<Utc>>,
+    pub closed_at: Option<DateTime<Utc>>,
+}
+
+impl ImportedTicket {
+    /// Returns a valid imported ticket, open and with a medium priority by default.
+    pub fn parse(raw: RawTicket) -> Result<Self, String> {
+        let title = TicketTitle::parse(raw.title)?;
+        let description = TicketDescription::parse(raw.description)?;
+        let priority = match raw.priority.trim().to_lowercase().as_str() {
+            "" => "medium".to_string(),
+            priority @ ("high" | "medium" | "low") => priority.to_string(),
+            _ => {
+                return Err(format!(
+                    "`{}` is not a priority such as high, medium or low.",
+                    raw.priority
+                ))
+            }
+        };
+        let is_open = match raw.status.trim().to_lowercase().as_str() {
+            "" | "open" => true,
+            "closed" => false,
+            _ => {
+                return Err(format!(
+                    "`{}` is not a status such as open or closed.",
+                    raw.status
+                ))
+            }
+        };
+        let author = Some(raw.author.trim().to_string()).filter(|author| !author.is_empty());
+
+        Ok(Self {
+            title,
+            description,
+            priority,
+            author,
+            is_open,
+            created_at: raw.created_at,
+            // Only the closed tickets keep their closing date.
+            closed_at: raw.closed_at.filter(|_| !is_open),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::{ImportedTicket, RawTicket};
+    use claims::{assert_err, assert_ok};
+
+    /// Returns a raw ticket with the given priority and status.
+    fn raw_ticket(priority: &str, status: &str) -> RawTicket {
+        RawTicket {
+            title: "Issue with x".to_string(),
+            description: "After doing x".to_string(),
+            priority: priority.to_string(),
+            status: status.to_string(),
+            ..Default::default()
+        }
+    }
+
+    // Must default to an open ticket with a medium priority.
+    #[test]
+    fn imported_ticket_defaults_to_open_and_medium_priority() {
+        let ticket = assert_ok!(ImportedTicket::parse(raw_ticket("", "")));
+        assert_eq!(ticket.priority, "medium");
+        assert!(ticket.is_open);
+        assert_eq!(ticket.author, None);
+
+        let ticket = assert_ok!(ImportedTicket::parse(raw_ticket(" High", "Closed")));
+        assert_eq!(ticket.priority, "high");
+        assert!(!ticket.is_open);
+    }
+
+    // Must return `Err` if a field is invalid.
+    #[test]
+    fn imported_ticket_returns_err_when_invalid() {
+        assert_err!(ImportedTicket::parse(raw_ticket("urgent", "open")));
+        assert_err!(ImportedTicket::parse(raw_ticket("low", "pending")));
+        assert_err!(ImportedTicket::parse(RawTicket {
+            title: " ".to_string(),
+            ..raw_ticket("low", "open")
+        }));
+    }
+}
@@ -0,0 +1,9 @@
+mod column_mapping;
+mod import_format;
+mod imported_ticket;
+mod ticket_import;
+
+pub use column_mapping::ColumnMapping;
+pub use import_format::ImportFormat;
+pub use imported_ticket::{ImportedTicket, RawTicket};
+pub use ticket_import::{ImportError, TicketImport};
@@ -0,0 +1,289 @@
+use crate::domain::{ColumnMapping, ImportFormat, ImportedTicket, RawTicket};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+
+/// Representation of a row of an import file that cannot be imported.
+#[derive(Debug, PartialEq)]
+pub struct ImportError {
+    pub row: usize,
+    pub message: String,
+}
+
+/// Representation of an import file, split into its valid tickets and its invalid rows.
+#[derive(Debug)]
+pub struct TicketImport {
+    pub tickets: Vec<ImportedTicket>,
+    pub errors: Vec<ImportError>,
+}
+
+/// Representation of a row of an import file with its number, starting at 1.
+type NumberedRow = (usize, Result<RawTicket, String>);
+
+/// Representation of an issue of a GitHub issues export.
+#[derive(Deserialize)]
+struct GithubIssue {
+    number: Option<i64>,
+    title: String,
+    body: Option<String>,
+    state: String,
+    user: Option<GithubUser>,
+    #[serde(default)]
+    labels: Vec<GithubLabel>,
+    created_at: Option<DateTime<Utc>>,
+    closed_at: Option<DateTime<Utc>>,
+}
+
+/// Representation of the author of a GitHub issue.
+#[derive(Deserialize)]
+struct GithubUser {
+    login: String,
+}
+
+/// Representation of a label of a GitHub issue.
+#[derive(Deserialize)]
+struct GithubLabel {
+    name: String,
+}
+
+impl GithubIssue {
+    /// Returns the priority of the issue, given by a label such as `high` or `priority: high`.
+    fn priority(&self) -> String {
+        self.labels
+            .iter()
+            .map(|label| {
+                let name = label.name.to_lowercase();
+                name.trim_start_matches("priority")
+                    .trim_start_matches([':', '/', '-', ' '])
+                    .to_string()
+            })
+            .find(|name| ["high", "medium", "low"].contains(&name.as_str()))
+            .unwrap_or_default()
+    }
+}
+
+impl TicketImport {
+    /// Returns the tickets of the file in the given format, every row being validated.
+    ///
+    /// # Implementation Notes
+    ///
+    /// An invalid row does not stop the import,
+    /// only a file that cannot be read as a whole is rejected.
+    pub fn parse(
+        format: ImportFormat,
+        content: &str,
+        mapping: &ColumnMapping,
+    ) -> Result<Self, String> {
+        let rows = match format {
+            ImportFormat::Csv => read_csv(content, mapping)?,
+            ImportFormat::GithubJson => read_github_json(content)?,
+        };
+
+        let mut import = Self {
+            tickets: Vec::new(),
+            errors: Vec::new(),
+        };
+        for (row, ticket) in rows {
+            match ticket.and_then(ImportedTicket::parse) {
+                Ok(ticket) => import.tickets.push(ticket),
+                Err(message) => import.errors.push(ImportError { row, message }),
+            }
+        }
+
+        Ok(import)
+    }
+
+    /// Returns the distinct authors of the tickets, sorted by name.
+    pub fn authors(&self) -> Vec<String> {
+        let mut authors: Vec<String> = self
+            .tickets
+            .iter()
+            .filter_map(|ticket| ticket.author.clone())
+            .collect();
+        authors.sort();
+        authors.dedup();
+
+        authors
+    }
+}
+
+/// Reads the rows of a CSV file with their number, the mapped columns being looked up in its header.
+fn read_csv(content: &str, mapping: &ColumnMapping) -> Result<Vec<NumberedRow>, String> {
+    let mut reader = csv::Reader::from_reader(content.as_bytes());
+    let headers = reader
+        .headers()
+        .map_err(|e| format!("the CSV header cannot be read: {}.", e))?
+        .clone();
+    let position = |column: &str| {
+        headers
+            .iter()
+            .position(|header| header.trim() == column)
+            .ok_or_else(|| format!("the column `{}` does not exist in the CSV file.", column))
+    };
+    let optional_position = |column: &Option<String>| column.as_deref().map(position).transpose();
+    let title = position(&mapping.title)?;
+    let description = position(&mapping.description)?;
+    let priority = optional_position(&mapping.priority)?;
+    let author = optional_position(&mapping.author)?;
+    let status = optional_position(&mapping.status)?;
+
+    Ok(reader
+        .records()
+        .enumerate()
+        .map(|(index, record)| {
+            let record = match record {
+                Ok(record) => record,
+                Err(e) => return (index + 1, Err(format!("the row cannot be read: {}.", e))),
+            };
+            let field = |position: Option<usize>| {
+                position
+                    .and_then(|position| record.get(position))
+                    .unwrap_or_default()
+                    .to_string()
+            };
+
+            let ticket = RawTicket {
+                title: field(Some(title)),
+                description: field(Some(description)),
+                priority: field(priority),
+                author: field(author),
+                status: field(status),
+                created_at: None,
+                closed_at: None,
+            };
+
+            (index + 1, Ok(ticket))
+        })
+        .collect())
+}
+
+/// Reads the issues of a GitHub issues export with their number in the file,
+/// the pull requests being left out.
+fn read_github_json(content: &str) -> Result<Vec<NumberedRow>, String> {
+    let issues: Vec<serde_json::Value> = serde_json::from_str(content)
+        .map_err(|_| "the file is not a JSON array of GitHub issues.".to_string())?;
+
+    Ok(issues
+        .into_iter()
+        .enumerate()
+        .filter(|(_, issue)| issue.get("pull_request").is_none())
+        .map(|(index, issue)| {
+            let issue: GithubIssue = match serde_json::from_value(issue) {
+                Ok(issue) => issue,
+                Err(e) => return (index + 1, Err(format!("the issue cannot be read: {}.", e))),
+            };
+            let priority = issue.priority();
+            // Issues without body are imported with a description pointing to the original issue.
+            let description = match issue.body.filter(|body| !body.trim().is_empty()) {
+                Some(body) => body,
+                None => match issue.number {
+                    Some(number) => format!("Imported from the GitHub issue #{}.", number),
+                    None => "Imported from GitHub.".to_string(),
+                },
+            };
+
+            let ticket = RawTicket {
+                title: issue.title,
+                description,
+                priority,
+                author: issue.user.map(|user| user.login).unwrap_or_default(),
+                status: issue.state,
+                created_at: issue.created_at,
+                closed_at: issue.closed_at,
+            };
+
+            (index + 1, Ok(ticket))
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::{ColumnMapping, ImportError, ImportFormat, TicketImport};
+    use claims::{assert_err, assert_ok};
+
+    /// Returns the mapping of a spreadsheet export.
+    fn mapping() -> ColumnMapping {
+        ColumnMapping {
+            title: "Summary".to_string(),
+            description: "Details".to_string(),
+            priority: Some("Priority".to_string()),
+            author: Some("Reporter".to_string()),
+            status: None,
+        }
+    }
+
+    // Must read the mapped columns and report the invalid rows.
+    #[test]
+    fn ticket_import_reads_csv_with_column_mapping() {
+        let content = "Reporter,Summary,Details,Priority\n\
+                       alice,Issue with x,After doing x,high\n\
+                       bob,,After doing y,low\n\
+                       ,Issue with z,After doing z,urgent\n";
+
+        let import = assert_ok!(TicketImport::parse(ImportFormat::Csv, content, &mapping()));
+        assert_eq!(import.tickets.len(), 1);
+        assert_eq!(import.tickets[0].title.as_ref(), "Issue with x");
+        assert_eq!(import.tickets[0].priority, "high");
+        assert_eq!(import.authors(), ["alice"]);
+        assert_eq!(
+            import.errors,
+            [
+                ImportError {
+                    row: 2,
+                    message: "ticket title cannot be empty.".to_string()
+                },
+                ImportError {
+                    row: 3,
+                    message: "`urgent` is not a priority such as high, medium or low.".to_string()
+                },
+            ]
+        );
+    }
+
+    // Must return `Err` if a mapped column is missing from the header.
+    #[test]
+    fn ticket_import_returns_err_when_mapped_column_is_missing() {
+        let content = "Summary,Details\nIssue with x,After doing x\n";
+        assert_err!(TicketImport::parse(ImportFormat::Csv, content, &mapping()));
+    }
+
+    // Must read the issues, their labels and their state, but not the pull requests.
+    #[test]
+    fn ticket_import_reads_github_issues() {
+        let content = r#"[
+            {
+                "number": 1, "title": "Issue with x", "body": null, "state": "closed",
+                "user": { "login": "alice" }, "labels": [{ "name": "Priority: High" }],
+                "created_at": "2026-10-01T08:00:00Z", "closed_at": "2026-10-02T08:00:00Z"
+            },
+            {
+                "number": 2, "title": "Fix x", "body": "Fixes #1", "state": "closed",
+                "user": { "login": "bob" }, "pull_request": {}
+            },
+            { "number": 3, "body": "No title", "state": "open" }
+        ]"#;
+
+        let import = assert_ok!(TicketImport::parse(
+            ImportFormat::GithubJson,
+            content,
+            &mapping()
+        ));
+        assert_eq!(import.tickets.len(), 1);
+        let ticket = &import.tickets[0];
+        assert_eq!(
+            ticket.description.as_ref(),
+            "Imported from the GitHub issue #1."
+        );
+        assert_eq!(ticket.priority, "high");
+        assert!(!ticket.is_open);
+        assert!(ticket.closed_at.is_some());
+        assert_eq!(import.errors.len(), 1);
+        assert_eq!(import.errors[0].row, 3);
+
+        assert_err!(TicketImport::parse(
+            ImportFormat::GithubJson,
+            "{}",
+            &mapping()
+        ));
+    }
+}
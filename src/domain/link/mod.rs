@@ -0,0 +1,5 @@
+mod new_ticket_link;
+mod ticket_relation;
+
+pub use new_ticket_link::NewTicketLink;
+pub use ticket_relation::{TicketLinkKind, TicketRelation};
@@ -0,0 +1,52 @@
+use crate::domain::{TicketLinkKind, TicketRelation};
+
+/// Representation of a new link between two tickets.
+#[derive(Debug)]
+pub struct NewTicketLink {
+    pub source_id: i32,
+    pub target_id: i32,
+    pub kind: TicketLinkKind,
+}
+
+impl NewTicketLink {
+    /// Returns the link giving the ticket the relation with the other ticket.
+    pub fn parse(ticket_id: i32, relation: TicketRelation, other_id: i32) -> Result<Self, String> {
+        if ticket_id == other_id {
+            return Err("a ticket cannot be linked to itself.".to_string());
+        }
+
+        let (source_id, target_id) = if relation.is_outward {
+            (ticket_id, other_id)
+        } else {
+            (other_id, ticket_id)
+        };
+
+        Ok(Self {
+            source_id,
+            target_id,
+            kind: relation.kind,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::{NewTicketLink, TicketLinkKind, TicketRelation};
+    use claims::{assert_err, assert_ok};
+
+    // Must return `Err` if the ticket is linked to itself.
+    #[test]
+    fn new_ticket_link_returns_err_when_self_link() {
+        let relation = TicketRelation::new(TicketLinkKind::Blocks, true);
+        assert_err!(NewTicketLink::parse(1, relation, 1));
+    }
+
+    // Must store the inverse relations from the other ticket.
+    #[test]
+    fn new_ticket_link_swaps_tickets_when_inverse_relation() {
+        let relation = TicketRelation::new(TicketLinkKind::ParentOf, false);
+        let link = assert_ok!(NewTicketLink::parse(1, relation, 2));
+        assert_eq!((link.source_id, link.target_id), (2, 1));
+        assert_eq!(link.kind, TicketLinkKind::ParentOf);
+    }
+}
@@ -0,0 +1,160 @@
+/// Representation of the kinds of links between tickets, read from the source to the target.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TicketLinkKind {
+    Blocks,
+    Duplicates,
+    RelatesTo,
+    ParentOf,
+}
+
+impl TicketLinkKind {
+    /// Extracts a string slice containing the entire `String`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TicketLinkKind::Blocks => "blocks",
+            TicketLinkKind::Duplicates => "duplicates",
+            TicketLinkKind::RelatesTo => "relates_to",
+            TicketLinkKind::ParentOf => "parent_of",
+        }
+    }
+
+    /// Returns `true` if following the links of this kind must never lead back to the start.
+    pub fn is_acyclic(&self) -> bool {
+        !matches!(self, TicketLinkKind::RelatesTo)
+    }
+}
+
+impl TryFrom<String> for TicketLinkKind {
+    type Error = String;
+
+    /// Performs the conversion.
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        match s.to_lowercase().as_str() {
+            "blocks" => Ok(Self::Blocks),
+            "duplicates" => Ok(Self::Duplicates),
+            "relates_to" => Ok(Self::RelatesTo),
+            "parent_of" => Ok(Self::ParentOf),
+            other => Err(format!("`{other}` is not a supported link kind.")),
+        }
+    }
+}
+
+/// Representation of a link seen from one of its tickets,
+/// outward from the source and inward from the target.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TicketRelation {
+    pub kind: TicketLinkKind,
+    pub is_outward: bool,
+}
+
+impl TicketRelation {
+    /// Returns the relations a user can pick, the inverse ones included.
+    pub fn all() -> [Self; 7] {
+        use TicketLinkKind::*;
+
+        [
+            Self::new(Blocks, true),
+            Self::new(Blocks, false),
+            Self::new(Duplicates, true),
+            Self::new(Duplicates, false),
+            Self::new(RelatesTo, true),
+            Self::new(ParentOf, true),
+            Self::new(ParentOf, false),
+        ]
+    }
+
+    /// Returns the relation of the given kind and direction.
+    ///
+    /// # Implementation Notes
+    ///
+    /// A `relates to` link reads the same both ways, it is always outward.
+    pub fn new(kind: TicketLinkKind, is_outward: bool) -> Self {
+        Self {
+            kind,
+            is_outward: is_outward || kind == TicketLinkKind::RelatesTo,
+        }
+    }
+
+    /// Extracts a string slice containing the entire `String`.
+    pub fn as_str(&self) -> &'static str {
+        match (self.kind, self.is_outward) {
+            (TicketLinkKind::Blocks, true) => "blocks",
+            (TicketLinkKind::Blocks, false) => "blocked_by",
+            (TicketLinkKind::Duplicates, true) => "duplicates",
+            (TicketLinkKind::Duplicates, false) => "duplicated_by",
+            (TicketLinkKind::RelatesTo, _) => "relates_to",
+            (TicketLinkKind::ParentOf, true) => "parent_of",
+            (TicketLinkKind::ParentOf, false) => "child_of",
+        }
+    }
+
+    /// Returns the relation as displayed to the users.
+    pub fn label(&self) -> &'static str {
+        match (self.kind, self.is_outward) {
+            (TicketLinkKind::Blocks, true) => "blocks",
+            (TicketLinkKind::Blocks, false) => "is blocked by",
+            (TicketLinkKind::Duplicates, true) => "duplicates",
+            (TicketLinkKind::Duplicates, false) => "is duplicated by",
+            (TicketLinkKind::RelatesTo, _) => "relates to",
+            (TicketLinkKind::ParentOf, true) => "is parent of",
+            (TicketLinkKind::ParentOf, false) => "is child of",
+        }
+    }
+}
+
+impl TryFrom<String> for TicketRelation {
+    type Error = String;
+
+    /// Performs the conversion.
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        Self::all()
+            .into_iter()
+            .find(|relation| relation.as_str() == s.to_lowercase())
+            .ok_or_else(|| format!("`{s}` is not a supported relation."))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::{TicketLinkKind, TicketRelation};
+    use claims::{assert_err, assert_ok_eq};
+
+    // Must return `Ok` if the input is a supported kind.
+    #[test]
+    fn ticket_link_kind_returns_ok_when_valid() {
+        for relation in TicketRelation::all() {
+            let kind = relation.kind;
+            assert_ok_eq!(TicketLinkKind::try_from(kind.as_str().to_string()), kind);
+        }
+    }
+
+    // Must return `Ok` if the input is a supported relation, inverse ones included.
+    #[test]
+    fn ticket_relation_returns_ok_when_valid() {
+        for relation in TicketRelation::all() {
+            assert_ok_eq!(
+                TicketRelation::try_from(relation.as_str().to_string()),
+                relation
+            );
+        }
+
+        let relation = TicketRelation::try_from("child_of".to_string()).unwrap();
+        assert_eq!(relation.kind, TicketLinkKind::ParentOf);
+        assert!(!relation.is_outward);
+    }
+
+    // Must read a `relates to` link the same both ways.
+    #[test]
+    fn ticket_relation_is_symmetric_for_relates_to() {
+        let relation = TicketRelation::new(TicketLinkKind::RelatesTo, false);
+        assert_eq!(relation.label(), "relates to");
+        assert!(relation.is_outward);
+    }
+
+    // Must return `Err` if the input is not a supported relation.
+    #[test]
+    fn ticket_relation_returns_err_when_unknown() {
+        assert_err!(TicketRelation::try_from("clones".to_string()));
+        assert_err!(TicketLinkKind::try_from("clones".to_string()));
+    }
+}
@@ -0,0 +1,149 @@
+use crate::domain::{ValidMilestone, ValidTicket};
+use std::fmt::Write;
+
+/// Representation of the Markdown release note of a milestone.
+#[derive(Debug)]
+pub struct Changelog(String);
+
+impl Changelog {
+    /// Returns the release note listing the closed tickets of the milestone.
+    ///
+    /// # Implementation Notes
+    ///
+    /// The tickets have no label nor type, they are grouped by priority instead.
+    pub fn generate(milestone: &ValidMilestone, tickets: &[ValidTicket]) -> Self {
+        let mut markdown = format!("# {}\n", escape(&milestone.name));
+        if let Some(target_on) = milestone.target_on {
+            writeln!(markdown, "\nTarget date: {}", target_on).unwrap();
+        }
+
+        let closed: Vec<&ValidTicket> = tickets.iter().filter(|ticket| !ticket.is_open).collect();
+        if closed.is_empty() {
+            markdown.push_str("\nNo ticket has been closed yet.\n");
+        }
+
+        for (priority, heading) in [
+            ("high", "High priority"),
+            ("medium", "Medium priority"),
+            ("low", "Low priority"),
+        ] {
+            let mut group = closed
+                .iter()
+                .filter(|ticket| ticket.priority == priority)
+                .peekable();
+            if group.peek().is_none() {
+                continue;
+            }
+
+            writeln!(markdown, "\n## {}\n", heading).unwrap();
+            for ticket in group {
+                writeln!(markdown, "- {} ({})", escape(&ticket.title), ticket.key).unwrap();
+            }
+        }
+
+        Self(markdown)
+    }
+}
+
+impl AsRef<str> for Changelog {
+    /// Performs the conversion.
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+/// Escapes the characters having a meaning in Markdown.
+fn escape(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        if matches!(
+            c,
+            '\\' | '`' | '*' | '_' | '[' | ']' | '<' | '>' | '#' | '|'
+        ) {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::{Changelog, ValidMilestone, ValidTicket};
+    use chrono::{NaiveDate, TimeZone, Utc};
+
+    /// Returns the milestone `v1.0`.
+    fn milestone() -> ValidMilestone {
+        ValidMilestone {
+            id: 1,
+            project_id: 1,
+            name: "v1.0".to_string(),
+            target_on: NaiveDate::from_ymd_opt(2026, 10, 31),
+            is_open: true,
+            created_at: Utc.with_ymd_and_hms(2026, 10, 1, 9, 0, 0).unwrap(),
+        }
+    }
+
+    /// Returns a ticket of the milestone.
+    fn ticket(id: i32, title: &str, priority: &str, is_open: bool) -> ValidTicket {
+        ValidTicket {
+            id,
+            title: title.to_string(),
+            description: "After doing x".to_string(),
+            created_at: Utc.with_ymd_and_hms(2026, 10, 1, 9, 0, 0).unwrap(),
+            created_by: "admin".to_string(),
+            is_open,
+            priority: priority.to_string(),
+            version: 1,
+            project_id: 1,
+            key: format!("MAIN-{id}"),
+            rank: id.to_string(),
+            due_on: None,
+            assigned_to: None,
+            first_responded_at: None,
+            closed_at: None,
+            estimate_minutes: None,
+            milestone_id: Some(1),
+            sprint_id: None,
+            story_points: None,
+        }
+    }
+
+    // Must list the closed tickets grouped by priority, the highest first.
+    #[test]
+    fn changelog_groups_closed_tickets_by_priority() {
+        let tickets = [
+            ticket(1, "Fix the login", "low", false),
+            ticket(2, "Crash on *save*", "high", false),
+            ticket(3, "Still in progress", "high", true),
+        ];
+
+        let changelog = Changelog::generate(&milestone(), &tickets);
+        assert_eq!(
+            changelog.as_ref(),
+            "# v1.0\n\
+            \n\
+            Target date: 2026-10-31\n\
+            \n\
+            ## High priority\n\
+            \n\
+            - Crash on \\*save\\* (MAIN-2)\n\
+            \n\
+            ## Low priority\n\
+            \n\
+            - Fix the login (MAIN-1)\n"
+        );
+    }
+
+    // Must say so when no ticket has been closed.
+    #[test]
+    fn changelog_is_empty_without_closed_tickets() {
+        let tickets = [ticket(1, "Still in progress", "high", true)];
+
+        let changelog = Changelog::generate(&milestone(), &tickets);
+        assert!(changelog
+            .as_ref()
+            .ends_with("\nNo ticket has been closed yet.\n"));
+    }
+}
@@ -0,0 +1,9 @@
+mod changelog;
+mod milestone_name;
+mod new_milestone;
+mod valid_milestone;
+
+pub use changelog::Changelog;
+pub use milestone_name::MilestoneName;
+pub use new_milestone::NewMilestone;
+pub use valid_milestone::ValidMilestone;
@@ -0,0 +1,7 @@
+use crate::domain::{MilestoneName, TicketDueDate};
+
+/// Representation of a new milestone of a project.
+pub struct NewMilestone {
+    pub name: MilestoneName,
+    pub target_date: Option<TicketDueDate>,
+}
@@ -0,0 +1,12 @@
+use chrono::{DateTime, NaiveDate, Utc};
+
+/// Representation of a valid milestone.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ValidMilestone {
+    pub id: i32,
+    pub project_id: i32,
+    pub name: String,
+    pub target_on: Option<NaiveDate>,
+    pub is_open: bool,
+    pub created_at: DateTime<Utc>,
+}
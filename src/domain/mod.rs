@@ -1,5 +1,36 @@
+pub mod attachment;
+pub mod board;
+pub mod dashboard;
+pub mod import;
+pub mod link;
+pub mod milestone;
+pub mod notification;
+pub mod project;
+pub mod report;
+pub mod sla;
+pub mod sprint;
 pub mod ticket;
+pub mod time_entry;
 pub mod user;
 
-pub use ticket::{NewTicket, TicketDescription, TicketTitle, ValidTicket};
-pub use user::{NewUser, UserPassword, UserUsername};
+pub use attachment::{AttachmentFileName, NewAttachment, ValidAttachment};
+pub use board::{BoardColumn, BoardGrouping};
+pub use dashboard::{DailyActivity, DashboardMetrics, PriorityCount, RecentTicket};
+pub use import::{
+    ColumnMapping, ImportError, ImportFormat, ImportedTicket, RawTicket, TicketImport,
+};
+pub use link::{NewTicketLink, TicketLinkKind, TicketRelation};
+pub use milestone::{Changelog, MilestoneName, NewMilestone, ValidMilestone};
+pub use notification::{Mentions, NotificationKind, ValidNotification};
+pub use project::{NewProject, ProjectKey, ProjectName, ValidProject};
+pub use report::{Report, ReportKind, ReportRange, ReportRow};
+pub use sla::{BusinessCalendar, SlaPolicies, SlaPolicy, SlaTarget, SlaTargetStatus};
+pub use sprint::{Burndown, NewSprint, SprintName, SprintSnapshot, StoryPoints, ValidSprint};
+pub use ticket::{
+    NewTicket, TicketChange, TicketDescription, TicketDueDate, TicketEvent, TicketEventKind,
+    TicketRank, TicketReference, TicketTitle, ValidTicket,
+};
+pub use time_entry::{
+    NewTimeEntry, TimeEntryDuration, TimeEntryNote, Timesheet, TimesheetEntry, TimesheetRow,
+};
+pub use user::{NewUser, UserPassword, UserRole, UserUsername};
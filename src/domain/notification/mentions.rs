@@ -0,0 +1,67 @@
+/// Punctuation ending a sentence or an aside, rather than a username.
+const TRAILING_PUNCTUATION: [char; 7] = ['.', ',', ';', ':', '!', '?', ')'];
+
+/// Representation of the usernames mentioned in a text, such as `@alice`.
+#[derive(Debug, PartialEq)]
+pub struct Mentions(Vec<String>);
+
+impl Mentions {
+    /// Returns the usernames mentioned in the text, each one once.
+    ///
+    /// # Implementation Notes
+    ///
+    /// A mention starts a word and ends at the next whitespace-like character,
+    /// trailing punctuation is not part of the username.
+    pub fn parse(s: &str) -> Self {
+        let mut usernames: Vec<String> = Vec::new();
+        for word in s.split_whitespace() {
+            let Some(username) = word.strip_prefix('@') else {
+                continue;
+            };
+            let username = username.trim_end_matches(TRAILING_PUNCTUATION);
+            if !username.is_empty() && !usernames.iter().any(|u| u == username) {
+                usernames.push(username.to_string());
+            }
+        }
+
+        Self(usernames)
+    }
+
+    /// Returns `true` if no username is mentioned.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl AsRef<[String]> for Mentions {
+    /// Performs the conversion.
+    fn as_ref(&self) -> &[String] {
+        &self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::Mentions;
+
+    // Must return the usernames starting a word with `@`, without trailing punctuation.
+    #[test]
+    fn mentions_returns_the_mentioned_usernames() {
+        let mentions = Mentions::parse("Ask @alice, then @bob.\n(cc @carol_2)");
+        assert_eq!(mentions.as_ref(), ["alice", "bob", "carol_2"]);
+    }
+
+    // Must return every username once.
+    #[test]
+    fn mentions_returns_each_username_once() {
+        let mentions = Mentions::parse("@alice and @alice!");
+        assert_eq!(mentions.as_ref(), ["alice"]);
+    }
+
+    // Must ignore the `@` inside a word and the lonely ones.
+    #[test]
+    fn mentions_ignores_email_addresses_and_lonely_signs() {
+        let mentions = Mentions::parse("Write to alice@example.com @ noon");
+        assert!(mentions.is_empty());
+    }
+}
@@ -0,0 +1,7 @@
+mod mentions;
+mod notification_kind;
+mod valid_notification;
+
+pub use mentions::Mentions;
+pub use notification_kind::NotificationKind;
+pub use valid_notification::ValidNotification;
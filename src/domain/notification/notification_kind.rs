@@ -0,0 +1,65 @@
+/// Representation of the ticket events producing a notification.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum NotificationKind {
+    Assignment,
+    FieldChange,
+    Mention,
+    StatusChange,
+    SlaBreach,
+}
+
+impl NotificationKind {
+    /// Extracts a string slice containing the entire `String`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            NotificationKind::Assignment => "assignment",
+            NotificationKind::FieldChange => "field_change",
+            NotificationKind::Mention => "mention",
+            NotificationKind::StatusChange => "status_change",
+            NotificationKind::SlaBreach => "sla_breach",
+        }
+    }
+}
+
+impl TryFrom<String> for NotificationKind {
+    type Error = String;
+
+    /// Performs the conversion.
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        match s.to_lowercase().as_str() {
+            "assignment" => Ok(Self::Assignment),
+            "field_change" => Ok(Self::FieldChange),
+            "mention" => Ok(Self::Mention),
+            "status_change" => Ok(Self::StatusChange),
+            "sla_breach" => Ok(Self::SlaBreach),
+            other => Err(format!("`{other}` is not a supported notification kind.")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::NotificationKind;
+    use claims::{assert_err, assert_ok_eq};
+
+    // Must return `Ok` if the input is a supported kind.
+    #[test]
+    fn notification_kind_returns_ok_when_valid() {
+        for kind in [
+            NotificationKind::Assignment,
+            NotificationKind::FieldChange,
+            NotificationKind::Mention,
+            NotificationKind::StatusChange,
+            NotificationKind::SlaBreach,
+        ] {
+            assert_ok_eq!(NotificationKind::try_from(kind.as_str().to_string()), kind);
+        }
+    }
+
+    // Must return `Err` if the input is not a supported kind.
+    #[test]
+    fn notification_kind_returns_err_when_unknown() {
+        let kind = "unknown".to_string();
+        assert_err!(NotificationKind::try_from(kind));
+    }
+}
@@ -0,0 +1,21 @@
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+/// Representation of a valid notification.
+#[derive(Debug, PartialEq)]
+pub struct ValidNotification {
+    pub id: i32,
+    pub user_id: Uuid,
+    pub ticket_id: i32,
+    pub kind: String,
+    pub message: String,
+    pub created_at: DateTime<Utc>,
+    pub read_at: Option<DateTime<Utc>>,
+}
+
+impl ValidNotification {
+    /// Returns `true` if the notification has not been read yet.
+    pub fn is_unread(&self) -> bool {
+        self.read_at.is_none()
+    }
+}
@@ -0,0 +1,9 @@
+mod new_project;
+mod project_key;
+mod project_name;
+mod valid_project;
+
+pub use new_project::NewProject;
+pub use project_key::ProjectKey;
+pub use project_name::ProjectName;
+pub use valid_project::ValidProject;
@@ -0,0 +1,7 @@
+use crate::domain::{ProjectKey, ProjectName};
+
+/// Representation of a new project.
+pub struct NewProject {
+    pub key: ProjectKey,
+    pub name: ProjectName,
+}
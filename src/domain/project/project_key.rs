@@ -0,0 +1,82 @@
+/// Representation of a project's key, the prefix of its ticket keys.
+#[derive(Debug)]
+pub struct ProjectKey(String);
+
+impl ProjectKey {
+    /// Returns a valid project key, in uppercase.
+    pub fn parse(s: String) -> Result<Self, String> {
+        let s = s.trim().to_uppercase();
+
+        // Check if the input is too short or too long.
+        let has_valid_length = (2..=10).contains(&s.len());
+        // Check if the input starts with a letter.
+        let starts_with_letter = s.starts_with(|c: char| c.is_ascii_uppercase());
+        // Check if the input only contains letters and digits.
+        let is_alphanumeric = s
+            .chars()
+            .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit());
+
+        // Validate whether the input is a valid key or not.
+        if !has_valid_length {
+            Err("project key must be between 2 and 10 characters long.".to_string())
+        } else if !starts_with_letter || !is_alphanumeric {
+            Err("project key must be a letter followed by letters or digits.".to_string())
+        } else {
+            Ok(Self(s))
+        }
+    }
+
+    /// Returns the key of the ticket with the given number in this project.
+    pub fn ticket_key(&self, number: i32) -> String {
+        format!("{}-{}", self.0, number)
+    }
+}
+
+impl AsRef<str> for ProjectKey {
+    /// Performs the conversion.
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::ProjectKey;
+    use claims::{assert_err, assert_ok};
+
+    // Must return `Err` if the input is too short.
+    #[test]
+    fn project_key_returns_err_when_too_short() {
+        let key = "W".to_string();
+        assert_err!(ProjectKey::parse(key));
+    }
+
+    // Must return `Err` if the input is too long.
+    #[test]
+    fn project_key_returns_err_when_too_long() {
+        let key = "W".repeat(11);
+        assert_err!(ProjectKey::parse(key));
+    }
+
+    // Must return `Err` if the input does not start with a letter.
+    #[test]
+    fn project_key_returns_err_when_starting_with_a_digit() {
+        let key = "1WEB".to_string();
+        assert_err!(ProjectKey::parse(key));
+    }
+
+    // Must return `Err` if the input contains a separator.
+    #[test]
+    fn project_key_returns_err_when_containing_a_separator() {
+        let key = "WEB-APP".to_string();
+        assert_err!(ProjectKey::parse(key));
+    }
+
+    // Must return the input in uppercase if valid.
+    #[test]
+    fn project_key_returns_ok_in_uppercase_when_valid() {
+        let key = ProjectKey::parse("web2".to_string());
+        assert_ok!(&key);
+        assert_eq!(key.unwrap().ticket_key(42), "WEB2-42");
+    }
+}
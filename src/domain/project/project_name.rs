@@ -0,0 +1,58 @@
+use unicode_segmentation::UnicodeSegmentation;
+
+/// Representation of a project's name.
+#[derive(Debug)]
+pub struct ProjectName(String);
+
+impl ProjectName {
+    /// Returns a valid project name.
+    pub fn parse(s: String) -> Result<Self, String> {
+        // Check if the input is empty or with a trailing whitespace-like character.
+        let is_empty_or_whitespace = s.trim().is_empty();
+        // Check if the input is too long.
+        let is_too_long = s.graphemes(true).count() > 64;
+
+        // Validate whether the input is a valid name or not.
+        if is_empty_or_whitespace {
+            Err("project name cannot be empty.".to_string())
+        } else if is_too_long {
+            Err("project name cannot be longer than 64 characters.".to_string())
+        } else {
+            Ok(Self(s))
+        }
+    }
+}
+
+impl AsRef<str> for ProjectName {
+    /// Performs the conversion.
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::ProjectName;
+    use claims::{assert_err, assert_ok};
+
+    // Must return `Err` if the input is using only whitespace.
+    #[test]
+    fn project_name_returns_err_when_filled_with_whitespace() {
+        let name = " ".to_string();
+        assert_err!(ProjectName::parse(name));
+    }
+
+    // Must return `Err` if the input is bigger than 64 characters.
+    #[test]
+    fn project_name_returns_err_when_too_long() {
+        let name = "a".repeat(65);
+        assert_err!(ProjectName::parse(name));
+    }
+
+    // Must return `Ok` if the input is valid.
+    #[test]
+    fn project_name_returns_ok_when_valid() {
+        let name = "Website".to_string();
+        assert_ok!(ProjectName::parse(name));
+    }
+}
@@ -0,0 +1,11 @@
+use chrono::{DateTime, Utc};
+
+/// Representation of a valid project.
+#[derive(Debug, PartialEq)]
+pub struct ValidProject {
+    pub id: i32,
+    pub key: String,
+    pub name: String,
+    pub next_ticket_number: i32,
+    pub created_at: DateTime<Utc>,
+}
@@ -0,0 +1,7 @@
+mod report_kind;
+mod report_range;
+mod report_table;
+
+pub use report_kind::ReportKind;
+pub use report_range::ReportRange;
+pub use report_table::{Report, ReportRow};
@@ -0,0 +1,77 @@
+/// Representation of the kinds of report.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ReportKind {
+    CreatedClosed,
+    CumulativeFlow,
+    OpenTicketAge,
+    Throughput,
+}
+
+impl ReportKind {
+    /// Returns every kind of report.
+    pub fn all() -> [Self; 4] {
+        [
+            Self::CreatedClosed,
+            Self::CumulativeFlow,
+            Self::OpenTicketAge,
+            Self::Throughput,
+        ]
+    }
+
+    /// Returns a valid kind of report.
+    pub fn parse(s: &str) -> Result<Self, String> {
+        Self::all()
+            .into_iter()
+            .find(|kind| kind.as_str() == s)
+            .ok_or_else(|| format!("`{s}` is not a report."))
+    }
+
+    /// Returns the kind as used in the URLs.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::CreatedClosed => "created-closed",
+            Self::CumulativeFlow => "cumulative-flow",
+            Self::OpenTicketAge => "open-ticket-age",
+            Self::Throughput => "throughput",
+        }
+    }
+
+    /// Returns the title of the report.
+    pub fn title(&self) -> &'static str {
+        match self {
+            Self::CreatedClosed => "Created and closed tickets",
+            Self::CumulativeFlow => "Cumulative flow",
+            Self::OpenTicketAge => "Age of open tickets by priority",
+            Self::Throughput => "Throughput per user",
+        }
+    }
+
+    /// Returns the name of the column labelling the rows of the report.
+    pub fn label_column(&self) -> &'static str {
+        match self {
+            Self::CreatedClosed | Self::CumulativeFlow => "date",
+            Self::OpenTicketAge => "priority",
+            Self::Throughput => "user",
+        }
+    }
+
+    /// Returns `true` if the rows of the report are the days of the range.
+    pub fn is_time_series(&self) -> bool {
+        matches!(self, Self::CreatedClosed | Self::CumulativeFlow)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::ReportKind;
+    use claims::{assert_err, assert_ok_eq};
+
+    // Must parse the kinds as used in the URLs.
+    #[test]
+    fn report_kind_parses_its_url_form() {
+        for kind in ReportKind::all() {
+            assert_ok_eq!(ReportKind::parse(kind.as_str()), kind);
+        }
+        assert_err!(ReportKind::parse("burndown"));
+    }
+}
@@ -0,0 +1,68 @@
+use chrono::{Duration, NaiveDate};
+
+/// Representation of the days covered by a report, both included.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ReportRange {
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+}
+
+impl ReportRange {
+    /// Returns a valid range, the last 30 days by default, covering at most a year.
+    pub fn parse(
+        from: Option<NaiveDate>,
+        to: Option<NaiveDate>,
+        today: NaiveDate,
+    ) -> Result<Self, String> {
+        let to = to.unwrap_or(today);
+        let from = from.unwrap_or(to - Duration::days(29));
+
+        if from > to {
+            Err("the start of the range cannot be after its end.".to_string())
+        } else if (to - from).num_days() >= 366 {
+            Err("a report cannot cover more than 366 days.".to_string())
+        } else {
+            Ok(Self { from, to })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::ReportRange;
+    use chrono::NaiveDate;
+    use claims::{assert_err, assert_ok_eq};
+
+    /// Returns the given day of 2026.
+    fn day(month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, month, day).unwrap()
+    }
+
+    // Must cover the last 30 days when no bound is given.
+    #[test]
+    fn report_range_defaults_to_the_last_30_days() {
+        let range = ReportRange::parse(None, None, day(10, 30));
+        assert_ok_eq!(
+            range,
+            ReportRange {
+                from: day(10, 1),
+                to: day(10, 30)
+            }
+        );
+    }
+
+    // Must return `Err` if the range is reversed or longer than a year.
+    #[test]
+    fn report_range_returns_err_when_invalid() {
+        assert_err!(ReportRange::parse(
+            Some(day(10, 2)),
+            Some(day(10, 1)),
+            day(10, 30)
+        ));
+        assert_err!(ReportRange::parse(
+            Some(day(1, 1)),
+            Some(NaiveDate::from_ymd_opt(2027, 1, 2).unwrap()),
+            day(10, 30)
+        ));
+    }
+}
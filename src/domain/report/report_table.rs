@@ -0,0 +1,283 @@
+use crate::domain::{ReportKind, ReportRange};
+use serde_json::{Map, Value};
+use std::fmt::Write;
+
+/// Width of the chart, in pixels.
+const WIDTH: f64 = 640.0;
+/// Height of the chart, in pixels.
+const HEIGHT: f64 = 320.0;
+/// Space left around the plot for the labels, in pixels.
+const MARGIN: f64 = 40.0;
+/// Colors of the series of a chart, in the order of the columns.
+const COLORS: [&str; 4] = ["steelblue", "green", "orange", "purple"];
+
+/// Representation of a line of a report, such as a day or a user.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ReportRow {
+    pub label: String,
+    pub values: Vec<i64>,
+}
+
+/// Representation of a report as a table, with one value per column on each row.
+#[derive(Debug)]
+pub struct Report {
+    pub kind: ReportKind,
+    pub range: ReportRange,
+    pub columns: Vec<String>,
+    pub rows: Vec<ReportRow>,
+}
+
+impl Report {
+    /// Returns the report in the CSV format, one line per row.
+    pub fn to_csv(&self) -> Result<String, anyhow::Error> {
+        let mut writer = csv::Writer::from_writer(Vec::new());
+
+        let mut header = vec![self.kind.label_column().to_string()];
+        header.extend(self.columns.iter().cloned());
+        writer.write_record(&header)?;
+        for row in &self.rows {
+            let mut record = vec![row.label.clone()];
+            record.extend(row.values.iter().map(|value| value.to_string()));
+            writer.write_record(&record)?;
+        }
+
+        Ok(String::from_utf8(writer.into_inner()?)?)
+    }
+
+    /// Returns the report in the JSON format, one object per row.
+    pub fn to_json(&self) -> Value {
+        let rows: Vec<Value> = self
+            .rows
+            .iter()
+            .map(|row| {
+                let mut object = Map::new();
+                object.insert(
+                    self.kind.label_column().to_string(),
+                    row.label.clone().into(),
+                );
+                for (column, value) in self.columns.iter().zip(&row.values) {
+                    object.insert(column.clone(), (*value).into());
+                }
+
+                Value::Object(object)
+            })
+            .collect();
+
+        serde_json::json!({
+            "report": self.kind.as_str(),
+            "from": self.range.from.to_string(),
+            "to": self.range.to.to_string(),
+            "rows": rows,
+        })
+    }
+
+    /// Returns the chart of the report as an SVG image,
+    /// with a line per column for the time series and grouped bars otherwise.
+    pub fn to_svg(&self) -> String {
+        let max_value = self
+            .rows
+            .iter()
+            .flat_map(|row| row.values.iter().copied())
+            .max()
+            .unwrap_or(0)
+            .max(1) as f64;
+        let y = |value: i64| HEIGHT - MARGIN - value as f64 * (HEIGHT - 2.0 * MARGIN) / max_value;
+
+        let mut svg = format!(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" class="report-chart" viewBox="0 0 {WIDTH} {HEIGHT}" role="img">"#
+        );
+        write!(svg, "<title>{}</title>", self.kind.title()).unwrap();
+
+        // Axes.
+        write!(
+            svg,
+            r#"<polyline class="report-axis" fill="none" stroke="currentColor" points="{MARGIN},{MARGIN} {MARGIN},{bottom} {right},{bottom}"/>"#,
+            bottom = HEIGHT - MARGIN,
+            right = WIDTH - MARGIN
+        )
+        .unwrap();
+        write!(
+            svg,
+            r#"<text x="{}" y="{}" text-anchor="end">{}</text>"#,
+            MARGIN - 5.0,
+            MARGIN + 5.0,
+            max_value
+        )
+        .unwrap();
+
+        // Legend.
+        for (index, column) in self.columns.iter().enumerate() {
+            write!(
+                svg,
+                r#"<text x="{:.1}" y="{}" fill="{}">{}</text>"#,
+                MARGIN + index as f64 * 120.0,
+                MARGIN - 15.0,
+                COLORS[index % COLORS.len()],
+                escape(column)
+            )
+            .unwrap();
+        }
+
+        let plot_width = WIDTH - 2.0 * MARGIN;
+        if self.kind.is_time_series() {
+            let step = plot_width / (self.rows.len().max(2) - 1) as f64;
+            for (index, _) in self.columns.iter().enumerate() {
+                let points: Vec<String> = self
+                    .rows
+                    .iter()
+                    .enumerate()
+                    .map(|(offset, row)| {
+                        format!(
+                            "{:.1},{:.1}",
+                            MARGIN + offset as f64 * step,
+                            y(row.values[index])
+                        )
+                    })
+                    .collect();
+                write!(
+                    svg,
+                    r#"<polyline class="report-series" fill="none" stroke="{}" stroke-width="2" points="{}"/>"#,
+                    COLORS[index % COLORS.len()],
+                    points.join(" ")
+                )
+                .unwrap();
+            }
+            if let (Some(first), Some(last)) = (self.rows.first(), self.rows.last()) {
+                for (label, x, anchor) in [
+                    (&first.label, MARGIN, "start"),
+                    (&last.label, WIDTH - MARGIN, "end"),
+                ] {
+                    write!(
+                        svg,
+                        r#"<text x="{}" y="{}" text-anchor="{anchor}">{}</text>"#,
+                        x,
+                        HEIGHT - MARGIN + 20.0,
+                        escape(label)
+                    )
+                    .unwrap();
+                }
+            }
+        } else {
+            let group_width = plot_width / self.rows.len().max(1) as f64;
+            let bar_width = group_width * 0.8 / self.columns.len().max(1) as f64;
+            for (offset, row) in self.rows.iter().enumerate() {
+                let group_x = MARGIN + offset as f64 * group_width + group_width * 0.1;
+                for (index, value) in row.values.iter().enumerate() {
+                    write!(
+                        svg,
+                        r#"<rect class="report-bar" fill="{}" x="{:.1}" y="{:.1}" width="{:.1}" height="{:.1}"><title>{}: {}</title></rect>"#,
+                        COLORS[index % COLORS.len()],
+                        group_x + index as f64 * bar_width,
+                        y(*value),
+                        bar_width,
+                        HEIGHT - MARGIN - y(*value),
+                        escape(&self.columns[index]),
+                        value
+                    )
+                    .unwrap();
+                }
+                write!(
+                    svg,
+                    r#"<text x="{:.1}" y="{}" text-anchor="middle">{}</text>"#,
+                    MARGIN + (offset as f64 + 0.5) * group_width,
+                    HEIGHT - MARGIN + 20.0,
+                    escape(&row.label)
+                )
+                .unwrap();
+            }
+        }
+
+        svg.push_str("</svg>");
+
+        svg
+    }
+}
+
+/// Escapes the characters having a meaning in XML.
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::{Report, ReportKind, ReportRange, ReportRow};
+    use chrono::NaiveDate;
+
+    /// Returns a report with the given kind and rows.
+    fn report(kind: ReportKind, columns: &[&str], rows: &[(&str, &[i64])]) -> Report {
+        Report {
+            kind,
+            range: ReportRange {
+                from: NaiveDate::from_ymd_opt(2026, 10, 1).unwrap(),
+                to: NaiveDate::from_ymd_opt(2026, 10, 2).unwrap(),
+            },
+            columns: columns.iter().map(|c| c.to_string()).collect(),
+            rows: rows
+                .iter()
+                .map(|(label, values)| ReportRow {
+                    label: label.to_string(),
+                    values: values.to_vec(),
+                })
+                .collect(),
+        }
+    }
+
+    // Must export one line per row, the label first.
+    #[test]
+    fn report_exports_rows_as_csv_and_json() {
+        let report = report(
+            ReportKind::CreatedClosed,
+            &["created", "closed"],
+            &[("2026-10-01", &[3, 1]), ("2026-10-02", &[0, 2])],
+        );
+
+        assert_eq!(
+            report.to_csv().unwrap(),
+            "date,created,closed\n2026-10-01,3,1\n2026-10-02,0,2\n"
+        );
+        assert_eq!(
+            report.to_json(),
+            serde_json::json!({
+                "report": "created-closed",
+                "from": "2026-10-01",
+                "to": "2026-10-02",
+                "rows": [
+                    { "date": "2026-10-01", "created": 3, "closed": 1 },
+                    { "date": "2026-10-02", "created": 0, "closed": 2 },
+                ],
+            })
+        );
+    }
+
+    // Must draw a line per column for the time series.
+    #[test]
+    fn report_draws_lines_for_time_series() {
+        let report = report(
+            ReportKind::CumulativeFlow,
+            &["open", "closed"],
+            &[("2026-10-01", &[4, 0]), ("2026-10-02", &[2, 2])],
+        );
+
+        let svg = report.to_svg();
+        assert_eq!(svg.matches("report-series").count(), 2);
+        assert!(svg.contains(r#"points="40.0,40.0 600.0,160.0""#));
+        assert!(!svg.contains("report-bar"));
+    }
+
+    // Must draw a group of bars per row otherwise.
+    #[test]
+    fn report_draws_bars_for_categories() {
+        let report = report(
+            ReportKind::Throughput,
+            &["closed"],
+            &[("alice", &[5]), ("<bob>", &[1])],
+        );
+
+        let svg = report.to_svg();
+        assert_eq!(svg.matches("<rect").count(), 2);
+        assert!(svg.contains("&lt;bob&gt;"));
+    }
+}
@@ -0,0 +1,199 @@
+use chrono::{DateTime, Duration, FixedOffset, NaiveDate, TimeZone, Utc, Weekday};
+use std::collections::HashSet;
+
+/// Representation of the business hours of the support desk.
+#[derive(Clone, Debug)]
+pub struct BusinessCalendar {
+    offset: FixedOffset,
+    work_days: Vec<Weekday>,
+    start_hour: u32,
+    end_hour: u32,
+    holidays: HashSet<NaiveDate>,
+}
+
+impl BusinessCalendar {
+    /// Returns a valid calendar, open from the start hour to the end hour of the work days,
+    /// in the time zone at the given offset from UTC.
+    pub fn parse(
+        utc_offset_minutes: i32,
+        work_days: &[String],
+        start_hour: u32,
+        end_hour: u32,
+        holidays: &[NaiveDate],
+    ) -> Result<Self, String> {
+        let offset = FixedOffset::east_opt(utc_offset_minutes * 60)
+            .ok_or_else(|| format!("`{utc_offset_minutes}` is not a valid UTC offset."))?;
+        let work_days = work_days
+            .iter()
+            .map(|day| {
+                day.parse::<Weekday>()
+                    .map_err(|_| format!("`{day}` is not a day of the week."))
+            })
+            .collect::<Result<Vec<Weekday>, String>>()?;
+
+        if work_days.is_empty() {
+            Err("the calendar must have at least one work day.".to_string())
+        } else if start_hour >= end_hour || end_hour > 24 {
+            Err("the business hours must start before they end, within the day.".to_string())
+        } else {
+            Ok(Self {
+                offset,
+                work_days,
+                start_hour,
+                end_hour,
+                holidays: holidays.iter().copied().collect(),
+            })
+        }
+    }
+
+    /// Returns the business hours of the day, if it is a work day.
+    fn business_hours(&self, date: NaiveDate) -> Option<(DateTime<Utc>, DateTime<Utc>)> {
+        use chrono::Datelike;
+
+        if !self.work_days.contains(&date.weekday()) || self.holidays.contains(&date) {
+            return None;
+        }
+
+        let midnight = self
+            .offset
+            .from_local_datetime(&date.and_hms_opt(0, 0, 0)?)
+            .single()?
+            .with_timezone(&Utc);
+
+        Some((
+            midnight + Duration::hours(self.start_hour.into()),
+            midnight + Duration::hours(self.end_hour.into()),
+        ))
+    }
+
+    /// Returns the local date of the instant.
+    fn local_date(&self, instant: DateTime<Utc>) -> NaiveDate {
+        instant.with_timezone(&self.offset).date_naive()
+    }
+
+    /// Returns the business time elapsed between two instants, zero if they are in the wrong order.
+    pub fn business_time_between(&self, from: DateTime<Utc>, to: DateTime<Utc>) -> Duration {
+        let mut elapsed = Duration::zero();
+        let mut date = self.local_date(from);
+
+        while date <= self.local_date(to) {
+            if let Some((start, end)) = self.business_hours(date) {
+                let start = start.max(from);
+                let end = end.min(to);
+                if start < end {
+                    elapsed = elapsed + (end - start);
+                }
+            }
+            date = date.succ_opt().unwrap();
+        }
+
+        elapsed
+    }
+
+    /// Returns the instant when the given business time has elapsed since the start.
+    pub fn add_business_time(&self, from: DateTime<Utc>, duration: Duration) -> DateTime<Utc> {
+        let mut remaining = duration;
+        let mut date = self.local_date(from);
+
+        loop {
+            if let Some((start, end)) = self.business_hours(date) {
+                let start = start.max(from);
+                if start < end {
+                    if remaining <= end - start {
+                        return start + remaining;
+                    }
+                    remaining = remaining - (end - start);
+                }
+            }
+            date = date.succ_opt().unwrap();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::BusinessCalendar;
+    use chrono::{DateTime, Duration, NaiveDate, TimeZone, Utc};
+    use claims::{assert_err, assert_ok};
+
+    /// Returns a calendar open from 9 to 17 on weekdays, in UTC, closed on Christmas.
+    fn calendar() -> BusinessCalendar {
+        let work_days = ["mon", "tue", "wed", "thu", "fri"].map(String::from);
+        let holidays = [NaiveDate::from_ymd_opt(2026, 12, 25).unwrap()];
+
+        BusinessCalendar::parse(0, &work_days, 9, 17, &holidays).unwrap()
+    }
+
+    /// Returns the instant of the given date and hour, in UTC.
+    fn at(year: i32, month: u32, day: u32, hour: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(year, month, day, hour, 0, 0).unwrap()
+    }
+
+    // Must return `Ok` if the calendar is valid.
+    #[test]
+    fn business_calendar_returns_ok_when_valid() {
+        let work_days = ["Monday".to_string()];
+        assert_ok!(BusinessCalendar::parse(120, &work_days, 0, 24, &[]));
+    }
+
+    // Must return `Err` if the calendar is invalid.
+    #[test]
+    fn business_calendar_returns_err_when_invalid() {
+        let work_days = ["mon".to_string()];
+        assert_err!(BusinessCalendar::parse(0, &[], 9, 17, &[]));
+        assert_err!(BusinessCalendar::parse(
+            0,
+            &["someday".to_string()],
+            9,
+            17,
+            &[]
+        ));
+        assert_err!(BusinessCalendar::parse(0, &work_days, 17, 9, &[]));
+        assert_err!(BusinessCalendar::parse(0, &work_days, 9, 25, &[]));
+    }
+
+    // Must only count the business hours, skipping nights, weekends and holidays.
+    #[test]
+    fn business_time_between_skips_closed_hours() {
+        let calendar = calendar();
+
+        // From Friday 16:00 to Monday 10:00.
+        let elapsed = calendar.business_time_between(at(2026, 10, 16, 16), at(2026, 10, 19, 10));
+        assert_eq!(elapsed, Duration::hours(2));
+
+        // From Thursday 24 December 16:00 to Monday 28 December 10:00.
+        let elapsed = calendar.business_time_between(at(2026, 12, 24, 16), at(2026, 12, 28, 10));
+        assert_eq!(elapsed, Duration::hours(2));
+
+        let elapsed = calendar.business_time_between(at(2026, 10, 19, 10), at(2026, 10, 16, 16));
+        assert_eq!(elapsed, Duration::zero());
+    }
+
+    // Must return the instant when the business time has elapsed.
+    #[test]
+    fn add_business_time_skips_closed_hours() {
+        let calendar = calendar();
+
+        // From Friday 16:00, 4 business hours later is Monday 12:00.
+        let deadline = calendar.add_business_time(at(2026, 10, 16, 16), Duration::hours(4));
+        assert_eq!(deadline, at(2026, 10, 19, 12));
+
+        // From Saturday, the clock starts on Monday morning.
+        let deadline = calendar.add_business_time(at(2026, 10, 17, 11), Duration::hours(8));
+        assert_eq!(deadline, at(2026, 10, 19, 17));
+
+        let deadline = calendar.add_business_time(at(2026, 10, 19, 10), Duration::zero());
+        assert_eq!(deadline, at(2026, 10, 19, 10));
+    }
+
+    // Must follow the time zone of the calendar.
+    #[test]
+    fn business_calendar_follows_its_utc_offset() {
+        let work_days = ["mon", "tue", "wed", "thu", "fri"].map(String::from);
+        let calendar = BusinessCalendar::parse(120, &work_days, 9, 17, &[]).unwrap();
+
+        // 9:00 at UTC+2 is 7:00 UTC.
+        let deadline = calendar.add_business_time(at(2026, 10, 19, 0), Duration::hours(1));
+        assert_eq!(deadline, at(2026, 10, 19, 8));
+    }
+}
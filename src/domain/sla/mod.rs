@@ -0,0 +1,5 @@
+mod business_calendar;
+mod sla_policy;
+
+pub use business_calendar::BusinessCalendar;
+pub use sla_policy::{SlaPolicies, SlaPolicy, SlaTarget, SlaTargetStatus};
@@ -0,0 +1,258 @@
+use crate::domain::{BusinessCalendar, ValidTicket};
+use chrono::{DateTime, Duration, Utc};
+use std::collections::HashMap;
+
+/// Representation of the targets of a service level agreement.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SlaTarget {
+    FirstResponse,
+    Resolution,
+}
+
+impl SlaTarget {
+    /// Extracts a string slice containing the entire `String`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SlaTarget::FirstResponse => "first_response",
+            SlaTarget::Resolution => "resolution",
+        }
+    }
+
+    /// Returns the target as displayed to the users.
+    pub fn label(&self) -> &'static str {
+        match self {
+            SlaTarget::FirstResponse => "first response",
+            SlaTarget::Resolution => "resolution",
+        }
+    }
+}
+
+/// Representation of the status of a ticket regarding one target.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SlaTargetStatus {
+    Met,
+    /// Business time left before the deadline.
+    Pending(Duration),
+    /// Instant when the deadline has been missed.
+    Breached(DateTime<Utc>),
+}
+
+impl SlaTargetStatus {
+    /// Returns the status as displayed to the users.
+    pub fn label(&self) -> String {
+        match self {
+            SlaTargetStatus::Met => "met".to_string(),
+            SlaTargetStatus::Pending(remaining) => {
+                let minutes = remaining.num_minutes();
+                format!("{}h {:02}m left", minutes / 60, minutes % 60)
+            }
+            SlaTargetStatus::Breached(deadline) => format!("breached the {deadline}"),
+        }
+    }
+
+    /// Returns `true` if the deadline has been missed.
+    pub fn is_breached(&self) -> bool {
+        matches!(self, SlaTargetStatus::Breached(_))
+    }
+}
+
+/// Representation of the business time allowed to reach each target.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SlaPolicy {
+    pub first_response: Duration,
+    pub resolution: Duration,
+}
+
+/// Representation of the service level agreements of the support desk, by priority.
+#[derive(Clone, Debug)]
+pub struct SlaPolicies {
+    pub calendar: BusinessCalendar,
+    pub policies: HashMap<String, SlaPolicy>,
+}
+
+impl SlaPolicies {
+    /// Returns the status of the ticket regarding both targets,
+    /// none if no policy covers its priority.
+    ///
+    /// # Implementation Notes
+    ///
+    /// The first response is the first change made by someone else than the creator,
+    /// the resolution is the closing of the ticket.
+    pub fn statuses(
+        &self,
+        ticket: &ValidTicket,
+        now: DateTime<Utc>,
+    ) -> Vec<(SlaTarget, SlaTargetStatus)> {
+        let Some(policy) = self.policies.get(&ticket.priority) else {
+            return Vec::new();
+        };
+
+        [
+            (
+                SlaTarget::FirstResponse,
+                policy.first_response,
+                ticket.first_responded_at,
+            ),
+            (SlaTarget::Resolution, policy.resolution, ticket.closed_at),
+        ]
+        .into_iter()
+        .map(|(target, allowed, reached_at)| {
+            let deadline = self.calendar.add_business_time(ticket.created_at, allowed);
+            let status = match reached_at {
+                Some(reached_at) if reached_at <= deadline => SlaTargetStatus::Met,
+                Some(_) => SlaTargetStatus::Breached(deadline),
+                None if now < deadline => {
+                    SlaTargetStatus::Pending(self.calendar.business_time_between(now, deadline))
+                }
+                None => SlaTargetStatus::Breached(deadline),
+            };
+
+            (target, status)
+        })
+        .collect()
+    }
+
+    /// Returns a summary of the most urgent target of the ticket and whether it is breached.
+    pub fn summary(&self, ticket: &ValidTicket, now: DateTime<Utc>) -> Option<(String, bool)> {
+        let statuses = self.statuses(ticket, now);
+
+        // A target missed in the past stays breached.
+        if let Some((target, _)) = statuses.iter().find(|(_, status)| status.is_breached()) {
+            return Some((format!("SLA breached: {}", target.label()), true));
+        }
+
+        statuses
+            .iter()
+            .filter_map(|(target, status)| match status {
+                SlaTargetStatus::Pending(remaining) => Some((target, status, *remaining)),
+                _ => None,
+            })
+            .min_by_key(|(_, _, remaining)| *remaining)
+            .map(|(target, status, _)| {
+                (format!("{} for {}", status.label(), target.label()), false)
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::{
+        BusinessCalendar, SlaPolicies, SlaPolicy, SlaTarget, SlaTargetStatus, ValidTicket,
+    };
+    use chrono::{DateTime, Duration, TimeZone, Utc};
+    use std::collections::HashMap;
+
+    /// Returns policies allowing 1 business hour to respond and 8 to resolve the high priority tickets.
+    fn policies() -> SlaPolicies {
+        let work_days = ["mon", "tue", "wed", "thu", "fri"].map(String::from);
+        let policy = SlaPolicy {
+            first_response: Duration::hours(1),
+            resolution: Duration::hours(8),
+        };
+
+        SlaPolicies {
+            calendar: BusinessCalendar::parse(0, &work_days, 9, 17, &[]).unwrap(),
+            policies: HashMap::from([("high".to_string(), policy)]),
+        }
+    }
+
+    /// Returns a high priority ticket created on Monday 19 October 2026 at 9:00 UTC.
+    fn ticket() -> ValidTicket {
+        ValidTicket {
+            id: 1,
+            title: "Issue with x".to_string(),
+            description: "After doing x".to_string(),
+            created_at: at(9, 0),
+            created_by: "admin".to_string(),
+            is_open: true,
+            priority: "high".to_string(),
+            version: 1,
+            project_id: 1,
+            key: "MAIN-1".to_string(),
+            rank: "1".to_string(),
+            due_on: None,
+            assigned_to: None,
+            first_responded_at: None,
+            closed_at: None,
+            estimate_minutes: None,
+            milestone_id: None,
+            sprint_id: None,
+            story_points: None,
+        }
+    }
+
+    /// Returns the instant of the given time on Monday 19 October 2026, in UTC.
+    fn at(hour: u32, minute: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 10, 19, hour, minute, 0).unwrap()
+    }
+
+    // Must return the business time left before each deadline.
+    #[test]
+    fn sla_policies_return_the_time_left() {
+        let statuses = policies().statuses(&ticket(), at(9, 30));
+        assert_eq!(
+            statuses,
+            [
+                (
+                    SlaTarget::FirstResponse,
+                    SlaTargetStatus::Pending(Duration::minutes(30))
+                ),
+                (
+                    SlaTarget::Resolution,
+                    SlaTargetStatus::Pending(Duration::minutes(450))
+                ),
+            ]
+        );
+
+        let summary = policies().summary(&ticket(), at(9, 30));
+        assert_eq!(
+            summary,
+            Some(("0h 30m left for first response".to_string(), false))
+        );
+    }
+
+    // Must flag the missed deadlines, even when reached late.
+    #[test]
+    fn sla_policies_flag_breaches() {
+        let statuses = policies().statuses(&ticket(), at(10, 30));
+        assert_eq!(
+            statuses[0],
+            (
+                SlaTarget::FirstResponse,
+                SlaTargetStatus::Breached(at(10, 0))
+            )
+        );
+
+        let mut ticket = ticket();
+        ticket.first_responded_at = Some(at(10, 15));
+        let summary = policies().summary(&ticket, at(11, 0));
+        assert_eq!(
+            summary,
+            Some(("SLA breached: first response".to_string(), true))
+        );
+    }
+
+    // Must consider the targets reached in time as met.
+    #[test]
+    fn sla_policies_consider_reached_targets_as_met() {
+        let mut ticket = ticket();
+        ticket.first_responded_at = Some(at(9, 45));
+        ticket.closed_at = Some(at(16, 0));
+        ticket.is_open = false;
+
+        let statuses = policies().statuses(&ticket, at(18, 0));
+        assert!(statuses
+            .iter()
+            .all(|(_, status)| *status == SlaTargetStatus::Met));
+        assert_eq!(policies().summary(&ticket, at(18, 0)), None);
+    }
+
+    // Must not apply any target when no policy covers the priority.
+    #[test]
+    fn sla_policies_ignore_priorities_without_policy() {
+        let mut ticket = ticket();
+        ticket.priority = "low".to_string();
+
+        assert!(policies().statuses(&ticket, at(18, 0)).is_empty());
+    }
+}
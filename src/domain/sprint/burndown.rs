@@ -0,0 +1,203 @@
+use crate::domain::ValidSprint;
+use chrono::NaiveDate;
+use std::fmt::Write;
+
+/// Width of the chart, in pixels.
+const WIDTH: f64 = 600.0;
+/// Height of the chart, in pixels.
+const HEIGHT: f64 = 300.0;
+/// Space left around the plot for the labels, in pixels.
+const MARGIN: f64 = 40.0;
+
+/// Representation of the points of a sprint recorded at the end of a day.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SprintSnapshot {
+    pub taken_on: NaiveDate,
+    pub remaining_points: i32,
+    pub total_points: i32,
+}
+
+/// Representation of the burndown chart of a sprint.
+#[derive(Debug)]
+pub struct Burndown {
+    starts_on: NaiveDate,
+    ends_on: NaiveDate,
+    name: String,
+    snapshots: Vec<SprintSnapshot>,
+}
+
+impl Burndown {
+    /// Returns the burndown of the sprint, ignoring the snapshots taken outside of it.
+    pub fn new(sprint: &ValidSprint, mut snapshots: Vec<SprintSnapshot>) -> Self {
+        snapshots.retain(|s| sprint.starts_on <= s.taken_on && s.taken_on <= sprint.ends_on);
+        snapshots.sort_by_key(|s| s.taken_on);
+
+        Self {
+            starts_on: sprint.starts_on,
+            ends_on: sprint.ends_on,
+            name: sprint.name.clone(),
+            snapshots,
+        }
+    }
+
+    /// Returns the snapshots of the sprint, ordered by day.
+    pub fn snapshots(&self) -> &[SprintSnapshot] {
+        &self.snapshots
+    }
+
+    /// Returns the chart as an SVG image, with the ideal burndown as a dashed line.
+    ///
+    /// # Implementation Notes
+    ///
+    /// The ideal line starts from the points committed on the first snapshot.
+    pub fn to_svg(&self) -> String {
+        let days = (self.ends_on - self.starts_on).num_days().max(1) as f64;
+        let max_points = self
+            .snapshots
+            .iter()
+            .map(|s| s.total_points.max(s.remaining_points))
+            .max()
+            .unwrap_or(0)
+            .max(1) as f64;
+        let x = |day: NaiveDate| {
+            MARGIN + (day - self.starts_on).num_days() as f64 * (WIDTH - 2.0 * MARGIN) / days
+        };
+        let y = |points: i32| {
+            HEIGHT - MARGIN - f64::from(points) * (HEIGHT - 2.0 * MARGIN) / max_points
+        };
+
+        let mut svg = format!(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" class="burndown" viewBox="0 0 {WIDTH} {HEIGHT}" role="img">"#
+        );
+        write!(svg, "<title>Burndown of {}</title>", escape(&self.name)).unwrap();
+
+        // Axes.
+        write!(
+            svg,
+            r#"<polyline class="burndown-axis" fill="none" stroke="currentColor" points="{MARGIN},{MARGIN} {MARGIN},{bottom} {right},{bottom}"/>"#,
+            bottom = HEIGHT - MARGIN,
+            right = WIDTH - MARGIN
+        )
+        .unwrap();
+        write!(
+            svg,
+            r#"<text x="{}" y="{}" text-anchor="end">{}</text>"#,
+            MARGIN - 5.0,
+            MARGIN + 5.0,
+            max_points
+        )
+        .unwrap();
+        write!(
+            svg,
+            r#"<text x="{}" y="{}" text-anchor="end">0</text>"#,
+            MARGIN - 5.0,
+            HEIGHT - MARGIN
+        )
+        .unwrap();
+        for (day, anchor) in [(self.starts_on, "start"), (self.ends_on, "end")] {
+            write!(
+                svg,
+                r#"<text x="{:.1}" y="{}" text-anchor="{anchor}">{day}</text>"#,
+                x(day),
+                HEIGHT - MARGIN + 20.0
+            )
+            .unwrap();
+        }
+
+        if let Some(first) = self.snapshots.first() {
+            write!(
+                svg,
+                r#"<line class="burndown-ideal" stroke="gray" stroke-dasharray="4" x1="{:.1}" y1="{:.1}" x2="{:.1}" y2="{:.1}"/>"#,
+                x(self.starts_on),
+                y(first.total_points),
+                x(self.ends_on),
+                y(0)
+            )
+            .unwrap();
+
+            let points: Vec<String> = self
+                .snapshots
+                .iter()
+                .map(|s| format!("{:.1},{:.1}", x(s.taken_on), y(s.remaining_points)))
+                .collect();
+            write!(
+                svg,
+                r#"<polyline class="burndown-remaining" fill="none" stroke="steelblue" stroke-width="2" points="{}"/>"#,
+                points.join(" ")
+            )
+            .unwrap();
+        }
+
+        svg.push_str("</svg>");
+
+        svg
+    }
+}
+
+/// Escapes the characters having a meaning in XML.
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::{Burndown, SprintSnapshot, ValidSprint};
+    use chrono::{NaiveDate, TimeZone, Utc};
+
+    /// Returns the given day of October 2026.
+    fn day(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 10, day).unwrap()
+    }
+
+    /// Returns a sprint of ten days.
+    fn sprint() -> ValidSprint {
+        ValidSprint {
+            id: 1,
+            project_id: 1,
+            name: "Sprint <1>".to_string(),
+            starts_on: day(19),
+            ends_on: day(29),
+            created_at: Utc.with_ymd_and_hms(2026, 10, 18, 9, 0, 0).unwrap(),
+        }
+    }
+
+    /// Returns a snapshot of the given day.
+    fn snapshot(taken_on: NaiveDate, remaining_points: i32) -> SprintSnapshot {
+        SprintSnapshot {
+            taken_on,
+            remaining_points,
+            total_points: 20,
+        }
+    }
+
+    // Must plot the remaining points of each day of the sprint, in order.
+    #[test]
+    fn burndown_plots_the_remaining_points_by_day() {
+        let snapshots = vec![
+            snapshot(day(20), 15),
+            snapshot(day(19), 20),
+            snapshot(day(18), 3),
+            snapshot(day(29), 0),
+        ];
+        let burndown = Burndown::new(&sprint(), snapshots);
+        assert_eq!(burndown.snapshots().len(), 3);
+
+        let svg = burndown.to_svg();
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.ends_with("</svg>"));
+        assert!(svg.contains("<title>Burndown of Sprint &lt;1&gt;</title>"));
+        assert!(svg.contains(r#"points="40.0,40.0 92.0,95.0 560.0,260.0""#));
+        assert!(svg.contains(r#"x1="40.0" y1="40.0" x2="560.0" y2="260.0""#));
+    }
+
+    // Must only draw the axes until a snapshot has been recorded.
+    #[test]
+    fn burndown_without_snapshots_only_draws_the_axes() {
+        let svg = Burndown::new(&sprint(), Vec::new()).to_svg();
+        assert!(svg.contains("burndown-axis"));
+        assert!(!svg.contains("burndown-remaining"));
+    }
+}
@@ -0,0 +1,11 @@
+mod burndown;
+mod new_sprint;
+mod sprint_name;
+mod story_points;
+mod valid_sprint;
+
+pub use burndown::{Burndown, SprintSnapshot};
+pub use new_sprint::NewSprint;
+pub use sprint_name::SprintName;
+pub use story_points::StoryPoints;
+pub use valid_sprint::ValidSprint;
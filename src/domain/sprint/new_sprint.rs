@@ -0,0 +1,63 @@
+use crate::domain::SprintName;
+use chrono::NaiveDate;
+
+/// Representation of a new sprint of a project.
+#[derive(Debug)]
+pub struct NewSprint {
+    pub name: SprintName,
+    pub starts_on: NaiveDate,
+    pub ends_on: NaiveDate,
+}
+
+impl NewSprint {
+    /// Returns a valid sprint, which ends after it starts and lasts at most eight weeks.
+    pub fn parse(name: SprintName, starts_on: &str, ends_on: &str) -> Result<Self, String> {
+        let starts_on = parse_date(starts_on)?;
+        let ends_on = parse_date(ends_on)?;
+
+        if ends_on < starts_on {
+            Err("a sprint cannot end before it starts.".to_string())
+        } else if (ends_on - starts_on).num_days() >= 8 * 7 {
+            Err("a sprint cannot last longer than 8 weeks.".to_string())
+        } else {
+            Ok(Self {
+                name,
+                starts_on,
+                ends_on,
+            })
+        }
+    }
+}
+
+/// Returns the date of the input.
+fn parse_date(s: &str) -> Result<NaiveDate, String> {
+    let s = s.trim();
+
+    NaiveDate::parse_from_str(s, "%Y-%m-%d")
+        .map_err(|_| format!("`{s}` is not a date such as 2026-10-31."))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::{NewSprint, SprintName};
+    use claims::{assert_err, assert_ok};
+
+    /// Returns the name of the sprint.
+    fn name() -> SprintName {
+        SprintName::parse("Sprint 1".to_string()).unwrap()
+    }
+
+    // Must return `Ok` if the sprint lasts two weeks.
+    #[test]
+    fn new_sprint_returns_ok_when_valid() {
+        assert_ok!(NewSprint::parse(name(), "2026-10-19", "2026-10-30"));
+    }
+
+    // Must return `Err` if the sprint ends before it starts or lasts too long.
+    #[test]
+    fn new_sprint_returns_err_when_dates_are_invalid() {
+        assert_err!(NewSprint::parse(name(), "2026-10-19", "2026-10-18"));
+        assert_err!(NewSprint::parse(name(), "2026-10-19", "2026-12-31"));
+        assert_err!(NewSprint::parse(name(), "2026-10-19", "next week"));
+    }
+}
@@ -0,0 +1,52 @@
+/// Representation of the relative effort of a ticket.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct StoryPoints(i32);
+
+impl StoryPoints {
+    /// Returns valid story points, none when the input is empty.
+    pub fn parse(s: &str) -> Result<Option<Self>, String> {
+        let s = s.trim();
+
+        if s.is_empty() {
+            return Ok(None);
+        }
+
+        match s.parse::<i32>() {
+            Ok(points) if (0..=100).contains(&points) => Ok(Some(Self(points))),
+            _ => Err(format!(
+                "`{s}` is not a number of story points between 0 and 100."
+            )),
+        }
+    }
+
+    /// Returns the number of points.
+    pub fn points(&self) -> i32 {
+        self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::StoryPoints;
+    use claims::{assert_err, assert_ok_eq};
+
+    // Must return `None` if the input is empty.
+    #[test]
+    fn story_points_returns_none_when_empty() {
+        assert_ok_eq!(StoryPoints::parse(" "), None);
+    }
+
+    // Must return `Ok` if the input is a small number.
+    #[test]
+    fn story_points_returns_ok_when_valid() {
+        assert_eq!(StoryPoints::parse("8").unwrap().unwrap().points(), 8);
+    }
+
+    // Must return `Err` if the input is negative, too big or not a number.
+    #[test]
+    fn story_points_returns_err_when_invalid() {
+        assert_err!(StoryPoints::parse("-1"));
+        assert_err!(StoryPoints::parse("101"));
+        assert_err!(StoryPoints::parse("big"));
+    }
+}
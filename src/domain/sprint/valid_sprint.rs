@@ -0,0 +1,24 @@
+use chrono::{DateTime, NaiveDate, Utc};
+
+/// Representation of a valid sprint.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ValidSprint {
+    pub id: i32,
+    pub project_id: i32,
+    pub name: String,
+    pub starts_on: NaiveDate,
+    pub ends_on: NaiveDate,
+    pub created_at: DateTime<Utc>,
+}
+
+impl ValidSprint {
+    /// Returns whether the sprint is over at the given day.
+    pub fn has_ended(&self, today: NaiveDate) -> bool {
+        self.ends_on < today
+    }
+
+    /// Returns whether the sprint is running at the given day.
+    pub fn is_active(&self, today: NaiveDate) -> bool {
+        self.starts_on <= today && today <= self.ends_on
+    }
+}
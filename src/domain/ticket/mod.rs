@@ -1,9 +1,19 @@
 mod new_ticket;
+mod ticket_change;
 mod ticket_description;
+mod ticket_due_date;
+mod ticket_event;
+mod ticket_rank;
+mod ticket_reference;
 mod ticket_title;
 mod valid_ticket;
 
 pub use new_ticket::NewTicket;
+pub use ticket_change::TicketChange;
 pub use ticket_description::TicketDescription;
+pub use ticket_due_date::TicketDueDate;
+pub use ticket_event::{TicketEvent, TicketEventKind};
+pub use ticket_rank::TicketRank;
+pub use ticket_reference::TicketReference;
 pub use ticket_title::TicketTitle;
 pub use valid_ticket::ValidTicket;
@@ -1,8 +1,9 @@
-use crate::domain::{TicketDescription, TicketTitle};
+use crate::domain::{TicketDescription, TicketDueDate, TicketTitle};
 
 /// Representation of a new ticket.
 pub struct NewTicket {
     pub title: TicketTitle,
     pub description: TicketDescription,
     pub priority: String,
+    pub due_date: Option<TicketDueDate>,
 }
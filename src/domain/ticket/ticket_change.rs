@@ -0,0 +1,14 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+/// Representation of a change made to a field of a ticket.
+#[derive(Debug, PartialEq, Serialize)]
+pub struct TicketChange {
+    pub ticket_id: i32,
+    pub version: i32,
+    pub field: String,
+    pub old_value: String,
+    pub new_value: String,
+    pub changed_by: String,
+    pub changed_at: DateTime<Utc>,
+}
@@ -0,0 +1,55 @@
+use chrono::NaiveDate;
+
+/// Representation of the day a ticket is due.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TicketDueDate(NaiveDate);
+
+impl TicketDueDate {
+    /// Returns a valid due date, none when the input is empty.
+    pub fn parse(s: &str) -> Result<Option<Self>, String> {
+        let s = s.trim();
+
+        if s.is_empty() {
+            return Ok(None);
+        }
+
+        NaiveDate::parse_from_str(s, "%Y-%m-%d")
+            .map(|date| Some(Self(date)))
+            .map_err(|_| format!("`{s}` is not a due date such as 2026-10-31."))
+    }
+
+    /// Returns the day the ticket is due.
+    pub fn date(&self) -> NaiveDate {
+        self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::TicketDueDate;
+    use chrono::NaiveDate;
+    use claims::{assert_err, assert_ok_eq};
+
+    // Must return `None` if the input is empty.
+    #[test]
+    fn ticket_due_date_returns_none_when_empty() {
+        assert_ok_eq!(TicketDueDate::parse("  "), None);
+    }
+
+    // Must return `Ok` if the input is a date.
+    #[test]
+    fn ticket_due_date_returns_ok_when_valid() {
+        let due_date = TicketDueDate::parse("2026-10-31").unwrap().unwrap();
+        assert_eq!(
+            due_date.date(),
+            NaiveDate::from_ymd_opt(2026, 10, 31).unwrap()
+        );
+    }
+
+    // Must return `Err` if the input is not a date.
+    #[test]
+    fn ticket_due_date_returns_err_when_invalid() {
+        assert_err!(TicketDueDate::parse("31/10/2026"));
+        assert_err!(TicketDueDate::parse("2026-02-30"));
+    }
+}
@@ -0,0 +1,51 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Representation of an event that happened on a ticket.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TicketEvent {
+    pub ticket_id: i32,
+    pub project_id: i32,
+    pub kind: TicketEventKind,
+    pub triggered_by: Uuid,
+}
+
+/// Representation of the kinds of ticket events.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TicketEventKind {
+    Created,
+    Closed,
+    Updated,
+}
+
+impl TicketEvent {
+    /// Returns the name of the Server-Sent Event targeting this ticket only.
+    pub fn sse_name(&self) -> String {
+        format!("ticket-{}", self.ticket_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::{TicketEvent, TicketEventKind};
+    use uuid::Uuid;
+
+    // Must survive a round trip through its JSON representation.
+    #[test]
+    fn ticket_event_round_trips_through_json() {
+        let event = TicketEvent {
+            ticket_id: 42,
+            project_id: 1,
+            kind: TicketEventKind::Closed,
+            triggered_by: Uuid::nil(),
+        };
+
+        let json = serde_json::to_string(&event).unwrap();
+        assert_eq!(
+            json,
+            r#"{"ticket_id":42,"project_id":1,"kind":"closed","triggered_by":"00000000-0000-0000-0000-000000000000"}"#
+        );
+        assert_eq!(serde_json::from_str::<TicketEvent>(&json).unwrap(), event);
+    }
+}
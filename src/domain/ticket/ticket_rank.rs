@@ -0,0 +1,128 @@
+/// Digits of a rank, in ascending byte order.
+const DIGITS: &[u8] = b"0123456789abcdefghijklmnopqrstuvwxyz";
+
+/// Representation of the rank of a ticket, ordering the tickets of a board column.
+///
+/// # Implementation Notes
+///
+/// A rank is a fraction written in base 36 without its leading `0.`,
+/// there is always a rank between two others, so that moving a ticket
+/// only rewrites the rank of this ticket and never the ranks of its neighbours.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct TicketRank(String);
+
+impl TicketRank {
+    /// Returns a rank strictly between the given ranks,
+    /// `None` standing for the start or the end of the column.
+    pub fn between(before: Option<&str>, after: Option<&str>) -> Self {
+        let before = before.unwrap_or_default().as_bytes();
+        let mut after = after.map(str::as_bytes);
+        let is_appending = after.is_none();
+        let mut rank = Vec::new();
+
+        for i in 0.. {
+            // Missing digits of `before` are zeros, those of the end of the column are 36.
+            let low = before.get(i).map_or(0, |&c| digit(c));
+            let high = after.map_or(DIGITS.len(), |after| {
+                after.get(i).map_or(DIGITS.len(), |&c| digit(c))
+            });
+
+            if low == high {
+                rank.push(DIGITS[low]);
+            } else if high - low > 1 {
+                // Prefer the next digit when appending, the ranks then grow slowly.
+                let middle = if is_appending {
+                    low + 1
+                } else {
+                    (low + high) / 2
+                };
+                rank.push(DIGITS[middle]);
+                break;
+            } else {
+                // Any rank starting with `before` up to here is lower than `after`.
+                rank.push(DIGITS[low]);
+                after = None;
+            }
+        }
+
+        Self(String::from_utf8(rank).unwrap())
+    }
+}
+
+/// Returns the value of a digit, unknown characters counting as zeros.
+fn digit(c: u8) -> usize {
+    DIGITS.iter().position(|&d| d == c).unwrap_or(0)
+}
+
+impl AsRef<str> for TicketRank {
+    /// Performs the conversion.
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::TicketRank;
+
+    // Must return a rank strictly between the given ranks.
+    #[test]
+    fn ticket_rank_returns_a_rank_between_neighbours() {
+        for (before, after) in [
+            ("0000000001i", "0000000002i"),
+            ("a", "b"),
+            ("az", "b"),
+            ("n", "n1"),
+            ("0000000099i", "1"),
+        ] {
+            let rank = TicketRank::between(Some(before), Some(after));
+            assert!(before < rank.as_ref() && rank.as_ref() < after);
+        }
+    }
+
+    // Must return a rank at the start or the end of the column.
+    #[test]
+    fn ticket_rank_returns_a_rank_at_the_edges() {
+        let rank = TicketRank::between(None, Some("0000000001i"));
+        assert!(rank.as_ref() < "0000000001i");
+        assert!(!rank.as_ref().ends_with('0'));
+
+        let rank = TicketRank::between(Some("0000000001i"), None);
+        assert!(rank.as_ref() > "0000000001i");
+
+        let rank = TicketRank::between(None, None);
+        assert_eq!(rank.as_ref(), "1");
+    }
+
+    // Must keep the ranks short when appending many tickets.
+    #[test]
+    fn ticket_rank_grows_slowly_when_appending() {
+        let mut last = TicketRank::between(None, None);
+        for _ in 0..1000 {
+            let rank = TicketRank::between(Some(last.as_ref()), None);
+            assert!(rank > last);
+            last = rank;
+        }
+
+        assert!(last.as_ref().len() <= 30);
+    }
+
+    // Must keep finding ranks when inserting again and again at the same place.
+    #[test]
+    fn ticket_rank_never_runs_out_of_ranks() {
+        let first = TicketRank::between(None, None);
+        let mut after = TicketRank::between(Some(first.as_ref()), None);
+        for _ in 0..200 {
+            let rank = TicketRank::between(Some(first.as_ref()), Some(after.as_ref()));
+            assert!(first < rank && rank < after);
+            after = rank;
+        }
+
+        let mut before = first;
+        for _ in 0..200 {
+            let rank = TicketRank::between(None, Some(before.as_ref()));
+            assert!(rank < before);
+            before = rank;
+        }
+    }
+}
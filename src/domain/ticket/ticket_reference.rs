@@ -0,0 +1,64 @@
+use crate::domain::ProjectKey;
+
+/// Representation of a reference to a ticket, found in its URL.
+#[derive(Debug, PartialEq)]
+pub enum TicketReference {
+    /// The global id of the ticket, used by the URLs predating projects.
+    Id(i32),
+    /// The key of the ticket, such as `WEB-42`.
+    Key(String),
+}
+
+impl TicketReference {
+    /// Returns a valid ticket reference.
+    pub fn parse(s: &str) -> Result<Self, String> {
+        if s.chars().all(|c| c.is_ascii_digit()) {
+            return s
+                .parse()
+                .map(Self::Id)
+                .map_err(|_| format!("`{}` is not a valid ticket reference.", s));
+        }
+
+        let (project_key, number) = s
+            .rsplit_once('-')
+            .ok_or_else(|| format!("`{}` is not a valid ticket reference.", s))?;
+        let project_key = ProjectKey::parse(project_key.to_string())?;
+        let number = number
+            .parse::<i32>()
+            .ok()
+            .filter(|number| *number > 0)
+            .ok_or_else(|| format!("`{}` is not a valid ticket reference.", s))?;
+
+        Ok(Self::Key(project_key.ticket_key(number)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::TicketReference;
+    use claims::assert_err;
+
+    // Must return an id if the input is a number.
+    #[test]
+    fn ticket_reference_returns_an_id_when_number() {
+        assert_eq!(TicketReference::parse("42"), Ok(TicketReference::Id(42)));
+    }
+
+    // Must return a key in uppercase if the input is a ticket key.
+    #[test]
+    fn ticket_reference_returns_a_key_when_ticket_key() {
+        assert_eq!(
+            TicketReference::parse("web-42"),
+            Ok(TicketReference::Key("WEB-42".to_string()))
+        );
+    }
+
+    // Must return `Err` if the input is neither a number nor a ticket key.
+    #[test]
+    fn ticket_reference_returns_err_when_invalid() {
+        assert_err!(TicketReference::parse("WEB"));
+        assert_err!(TicketReference::parse("WEB-"));
+        assert_err!(TicketReference::parse("WEB-0"));
+        assert_err!(TicketReference::parse("-42"));
+    }
+}
@@ -1,7 +1,8 @@
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::Serialize;
 
 /// Representation of a valid ticket.
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Serialize)]
 pub struct ValidTicket {
     pub id: i32,
     pub title: String,
@@ -10,4 +11,16 @@ pub struct ValidTicket {
     pub created_by: String,
     pub is_open: bool,
     pub priority: String,
+    pub version: i32,
+    pub project_id: i32,
+    pub key: String,
+    pub rank: String,
+    pub due_on: Option<NaiveDate>,
+    pub assigned_to: Option<String>,
+    pub first_responded_at: Option<DateTime<Utc>>,
+    pub closed_at: Option<DateTime<Utc>>,
+    pub estimate_minutes: Option<i32>,
+    pub milestone_id: Option<i32>,
+    pub sprint_id: Option<i32>,
+    pub story_points: Option<i32>,
 }
@@ -0,0 +1,9 @@
+mod new_time_entry;
+mod time_entry_duration;
+mod time_entry_note;
+mod timesheet;
+
+pub use new_time_entry::NewTimeEntry;
+pub use time_entry_duration::TimeEntryDuration;
+pub use time_entry_note::TimeEntryNote;
+pub use timesheet::{Timesheet, TimesheetEntry, TimesheetRow};
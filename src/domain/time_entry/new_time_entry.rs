@@ -0,0 +1,69 @@
+use crate::domain::{TimeEntryDuration, TimeEntryNote};
+use chrono::NaiveDate;
+
+/// Representation of a new time entry logged against a ticket.
+#[derive(Debug)]
+pub struct NewTimeEntry {
+    pub ticket_id: i32,
+    pub spent_on: NaiveDate,
+    pub duration: TimeEntryDuration,
+    pub note: TimeEntryNote,
+}
+
+impl NewTimeEntry {
+    /// Returns a valid time entry, which cannot exceed a day of work nor be in the future.
+    pub fn parse(
+        ticket_id: i32,
+        spent_on: NaiveDate,
+        duration: TimeEntryDuration,
+        note: TimeEntryNote,
+        today: NaiveDate,
+    ) -> Result<Self, String> {
+        if duration.minutes() > 24 * 60 {
+            Err("a time entry cannot be longer than 24 hours.".to_string())
+        } else if spent_on > today {
+            Err("a time entry cannot be logged in the future.".to_string())
+        } else {
+            Ok(Self {
+                ticket_id,
+                spent_on,
+                duration,
+                note,
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::{NewTimeEntry, TimeEntryDuration, TimeEntryNote};
+    use chrono::NaiveDate;
+    use claims::{assert_err, assert_ok};
+
+    /// Returns the given day of October 2026.
+    fn day(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 10, day).unwrap()
+    }
+
+    /// Returns an empty note.
+    fn note() -> TimeEntryNote {
+        TimeEntryNote::parse(String::new()).unwrap()
+    }
+
+    // Must return `Ok` if the entry covers at most a day in the past.
+    #[test]
+    fn new_time_entry_returns_ok_when_valid() {
+        let duration = TimeEntryDuration::from_minutes(24 * 60);
+        assert_ok!(NewTimeEntry::parse(1, day(18), duration, note(), day(18)));
+    }
+
+    // Must return `Err` if the entry is too long or in the future.
+    #[test]
+    fn new_time_entry_returns_err_when_invalid() {
+        let duration = TimeEntryDuration::from_minutes(24 * 60 + 1);
+        assert_err!(NewTimeEntry::parse(1, day(18), duration, note(), day(18)));
+
+        let duration = TimeEntryDuration::from_minutes(60);
+        assert_err!(NewTimeEntry::parse(1, day(19), duration, note(), day(18)));
+    }
+}
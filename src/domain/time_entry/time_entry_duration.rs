@@ -0,0 +1,95 @@
+use std::fmt::Display;
+
+/// Representation of a duration of work, in minutes.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TimeEntryDuration(i32);
+
+impl TimeEntryDuration {
+    /// Returns a valid duration from an input such as `1h 30m`, `2h`, `45m` or `90`.
+    pub fn parse(s: &str) -> Result<Self, String> {
+        let invalid = || format!("`{s}` is not a duration such as 1h 30m.");
+
+        let mut minutes: i64 = 0;
+        let mut number = String::new();
+        for c in s.trim().chars().filter(|c| !c.is_whitespace()) {
+            match c {
+                '0'..='9' => number.push(c),
+                'h' | 'H' | 'm' | 'M' if !number.is_empty() => {
+                    let value: i64 = number.parse().map_err(|_| invalid())?;
+                    let factor = if c.eq_ignore_ascii_case(&'h') { 60 } else { 1 };
+                    minutes = minutes.saturating_add(value.saturating_mul(factor));
+                    number.clear();
+                }
+                _ => return Err(invalid()),
+            }
+        }
+        // A number without unit is a number of minutes.
+        if !number.is_empty() {
+            let value: i64 = number.parse().map_err(|_| invalid())?;
+            minutes = minutes.saturating_add(value);
+        }
+
+        if minutes <= 0 {
+            Err(invalid())
+        } else if minutes > 10_000 * 60 {
+            Err("a duration cannot be longer than 10000 hours.".to_string())
+        } else {
+            Ok(Self(minutes as i32))
+        }
+    }
+
+    /// Returns the duration from a number of minutes stored in the database.
+    pub fn from_minutes(minutes: i32) -> Self {
+        Self(minutes)
+    }
+
+    /// Returns the number of minutes.
+    pub fn minutes(&self) -> i32 {
+        self.0
+    }
+}
+
+impl Display for TimeEntryDuration {
+    /// Formats the value using the given formatter.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}h {:02}m", self.0 / 60, self.0 % 60)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::TimeEntryDuration;
+    use claims::{assert_err, assert_ok_eq};
+
+    // Must return `Ok` if the input is a duration with or without units.
+    #[test]
+    fn time_entry_duration_returns_ok_when_valid() {
+        for (input, minutes) in [
+            ("1h 30m", 90),
+            ("2h", 120),
+            ("45m", 45),
+            ("90", 90),
+            ("1H5M", 65),
+        ] {
+            assert_ok_eq!(
+                TimeEntryDuration::parse(input),
+                TimeEntryDuration::from_minutes(minutes)
+            );
+        }
+    }
+
+    // Must return `Err` if the input is not a positive duration.
+    #[test]
+    fn time_entry_duration_returns_err_when_invalid() {
+        for input in ["", "0", "0h", "h", "1.5h", "-1h", "1d", "10001h"] {
+            assert_err!(TimeEntryDuration::parse(input));
+        }
+    }
+
+    // Must display the hours and the minutes.
+    #[test]
+    fn time_entry_duration_displays_hours_and_minutes() {
+        assert_eq!(TimeEntryDuration::from_minutes(95).to_string(), "1h 35m");
+        assert_eq!(TimeEntryDuration::from_minutes(0).to_string(), "0h 00m");
+    }
+}
@@ -0,0 +1,44 @@
+use unicode_segmentation::UnicodeSegmentation;
+
+/// Representation of the note describing a time entry.
+#[derive(Debug)]
+pub struct TimeEntryNote(String);
+
+impl TimeEntryNote {
+    /// Returns a valid note, which can be empty.
+    pub fn parse(s: String) -> Result<Self, String> {
+        let s = s.trim();
+        if s.graphemes(true).count() > 500 {
+            Err("time entry note cannot be longer than 500 characters.".to_string())
+        } else {
+            Ok(Self(s.to_string()))
+        }
+    }
+}
+
+impl AsRef<str> for TimeEntryNote {
+    /// Performs the conversion.
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::TimeEntryNote;
+    use claims::{assert_err, assert_ok};
+
+    // Must return `Ok` if the input is empty or short enough.
+    #[test]
+    fn time_entry_note_returns_ok_when_valid() {
+        assert_ok!(TimeEntryNote::parse("".to_string()));
+        assert_ok!(TimeEntryNote::parse("ё".repeat(500)));
+        assert_ok!(TimeEntryNote::parse(format!(" {} ", "a".repeat(500))));
+    }
+
+    // Must return `Err` if the input is too long.
+    #[test]
+    fn time_entry_note_returns_err_when_too_long() {
+        assert_err!(TimeEntryNote::parse("a".repeat(501)));
+    }
+}
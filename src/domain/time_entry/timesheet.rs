@@ -0,0 +1,223 @@
+use crate::domain::TimeEntryDuration;
+use chrono::{Datelike, Duration, NaiveDate};
+
+/// Representation of a time entry as listed in a timesheet.
+#[derive(Debug)]
+pub struct TimesheetEntry {
+    pub ticket_key: String,
+    pub ticket_title: String,
+    pub spent_on: NaiveDate,
+    pub duration_minutes: i32,
+    pub note: String,
+}
+
+/// Representation of the time spent on a ticket during each day of the week.
+#[derive(Debug, PartialEq)]
+pub struct TimesheetRow {
+    pub ticket_key: String,
+    pub ticket_title: String,
+    pub minutes: [i32; 7],
+}
+
+impl TimesheetRow {
+    /// Returns the time spent on each day, empty for the days without work.
+    pub fn day_labels(&self) -> Vec<String> {
+        self.minutes.iter().map(|m| label(*m)).collect()
+    }
+
+    /// Returns the time spent on the ticket during the week.
+    pub fn total_label(&self) -> String {
+        label(self.minutes.iter().sum())
+    }
+}
+
+/// Representation of the time logged by a user during a week, from Monday to Sunday.
+#[derive(Debug)]
+pub struct Timesheet {
+    pub username: String,
+    pub week_start: NaiveDate,
+    pub rows: Vec<TimesheetRow>,
+    pub entries: Vec<TimesheetEntry>,
+}
+
+impl Timesheet {
+    /// Returns the timesheet of the week containing the given day,
+    /// with one row per ticket ordered by key, ignoring the entries of other weeks.
+    pub fn new(username: String, day: NaiveDate, mut entries: Vec<TimesheetEntry>) -> Self {
+        let week_start = Self::week_start(day);
+        entries.retain(|entry| (0..7).contains(&(entry.spent_on - week_start).num_days()));
+
+        let mut rows: Vec<TimesheetRow> = Vec::new();
+        for entry in &entries {
+            let offset = (entry.spent_on - week_start).num_days();
+
+            let row = match rows.iter().position(|r| r.ticket_key == entry.ticket_key) {
+                Some(position) => &mut rows[position],
+                None => {
+                    rows.push(TimesheetRow {
+                        ticket_key: entry.ticket_key.clone(),
+                        ticket_title: entry.ticket_title.clone(),
+                        minutes: [0; 7],
+                    });
+                    rows.last_mut().unwrap()
+                }
+            };
+            row.minutes[offset as usize] += entry.duration_minutes;
+        }
+        rows.sort_by(|a, b| sort_key(&a.ticket_key).cmp(&sort_key(&b.ticket_key)));
+
+        Self {
+            username,
+            week_start,
+            rows,
+            entries,
+        }
+    }
+
+    /// Returns the Monday of the week containing the given day.
+    pub fn week_start(day: NaiveDate) -> NaiveDate {
+        day - Duration::days(day.weekday().num_days_from_monday().into())
+    }
+
+    /// Returns the days of the week.
+    pub fn days(&self) -> Vec<NaiveDate> {
+        (0..7)
+            .map(|offset| self.week_start + Duration::days(offset))
+            .collect()
+    }
+
+    /// Returns the Monday of the previous week.
+    pub fn previous_week(&self) -> NaiveDate {
+        self.week_start - Duration::days(7)
+    }
+
+    /// Returns the Monday of the next week.
+    pub fn next_week(&self) -> NaiveDate {
+        self.week_start + Duration::days(7)
+    }
+
+    /// Returns the time logged on each day of the week, empty for the days without work.
+    pub fn day_total_labels(&self) -> Vec<String> {
+        (0..7)
+            .map(|day| label(self.rows.iter().map(|row| row.minutes[day]).sum()))
+            .collect()
+    }
+
+    /// Returns the time logged during the week.
+    pub fn total_label(&self) -> String {
+        label(self.rows.iter().flat_map(|row| row.minutes).sum())
+    }
+
+    /// Returns the entries of the timesheet in the CSV format, one line per entry.
+    pub fn to_csv(&self) -> Result<String, anyhow::Error> {
+        let mut writer = csv::Writer::from_writer(Vec::new());
+        writer.write_record(["user", "date", "ticket", "title", "hours", "note"])?;
+        for entry in &self.entries {
+            writer.write_record([
+                self.username.as_str(),
+                &entry.spent_on.to_string(),
+                &entry.ticket_key,
+                &entry.ticket_title,
+                &format!("{:.2}", f64::from(entry.duration_minutes) / 60.0),
+                &entry.note,
+            ])?;
+        }
+
+        Ok(String::from_utf8(writer.into_inner()?)?)
+    }
+}
+
+/// Returns the project key and the number of a ticket key, so that `MAIN-9` comes before `MAIN-10`.
+fn sort_key(ticket_key: &str) -> (&str, i32) {
+    match ticket_key.rsplit_once('-') {
+        Some((project_key, number)) => (project_key, number.parse().unwrap_or_default()),
+        None => (ticket_key, 0),
+    }
+}
+
+/// Returns the duration as displayed in the timesheet, empty when nothing has been logged.
+fn label(minutes: i32) -> String {
+    if minutes == 0 {
+        String::new()
+    } else {
+        TimeEntryDuration::from_minutes(minutes).to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::{Timesheet, TimesheetEntry};
+    use chrono::NaiveDate;
+
+    /// Returns the given day of October 2026.
+    fn day(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 10, day).unwrap()
+    }
+
+    /// Returns an entry of the given ticket.
+    fn entry(ticket_key: &str, spent_on: NaiveDate, duration_minutes: i32) -> TimesheetEntry {
+        TimesheetEntry {
+            ticket_key: ticket_key.to_string(),
+            ticket_title: format!("Issue {ticket_key}"),
+            spent_on,
+            duration_minutes,
+            note: "Fixed, \"finally\"".to_string(),
+        }
+    }
+
+    // Must sum the entries by ticket and day, from Monday to Sunday.
+    #[test]
+    fn timesheet_sums_entries_by_ticket_and_day() {
+        let entries = vec![
+            entry("MAIN-2", day(12), 30),
+            entry("MAIN-1", day(12), 60),
+            entry("MAIN-2", day(12), 15),
+            entry("MAIN-1", day(18), 120),
+            entry("MAIN-3", day(19), 60),
+        ];
+        // Thursday 15 October 2026 belongs to the week starting on Monday 12.
+        let timesheet = Timesheet::new("admin".to_string(), day(15), entries);
+
+        assert_eq!(timesheet.week_start, day(12));
+        assert_eq!(timesheet.rows.len(), 2);
+        assert_eq!(timesheet.rows[0].ticket_key, "MAIN-1");
+        assert_eq!(timesheet.rows[0].minutes, [60, 0, 0, 0, 0, 0, 120]);
+        assert_eq!(timesheet.rows[1].minutes, [45, 0, 0, 0, 0, 0, 0]);
+        assert_eq!(timesheet.day_total_labels()[0], "1h 45m");
+        assert_eq!(timesheet.day_total_labels()[1], "");
+        assert_eq!(timesheet.total_label(), "3h 45m");
+        assert_eq!(timesheet.entries.len(), 4);
+    }
+
+    // Must order the rows by project, then by ticket number.
+    #[test]
+    fn timesheet_orders_rows_by_ticket_number() {
+        let entries = vec![
+            entry("MAIN-10", day(12), 30),
+            entry("WEB-1", day(12), 30),
+            entry("MAIN-9", day(12), 30),
+        ];
+        let timesheet = Timesheet::new("admin".to_string(), day(12), entries);
+
+        let keys: Vec<&str> = timesheet
+            .rows
+            .iter()
+            .map(|row| row.ticket_key.as_str())
+            .collect();
+        assert_eq!(keys, vec!["MAIN-9", "MAIN-10", "WEB-1"]);
+    }
+
+    // Must export one line per entry, escaping the fields.
+    #[test]
+    fn timesheet_exports_entries_as_csv() {
+        let entries = vec![entry("MAIN-1", day(12), 90)];
+        let timesheet = Timesheet::new("admin".to_string(), day(12), entries);
+
+        let csv = timesheet.to_csv().unwrap();
+        assert_eq!(
+            csv,
+            "user,date,ticket,title,hours,note\n\
+            admin,2026-10-12,MAIN-1,Issue MAIN-1,1.50,\"Fixed, \"\"finally\"\"\"\n"
+        );
+    }
+}
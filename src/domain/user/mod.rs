@@ -1,7 +1,9 @@
 mod new_user;
 mod user_password;
+mod user_role;
 mod user_username;
 
 pub use new_user::NewUser;
 pub use user_password::UserPassword;
+pub use user_role::UserRole;
 pub use user_username::UserUsername;
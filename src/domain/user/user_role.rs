@@ -0,0 +1,50 @@
+/// Representation of the role of a user.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum UserRole {
+    Admin,
+    User,
+}
+
+impl UserRole {
+    /// Extracts a string slice containing the entire `String`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            UserRole::Admin => "admin",
+            UserRole::User => "user",
+        }
+    }
+}
+
+impl TryFrom<String> for UserRole {
+    type Error = String;
+
+    /// Performs the conversion.
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        match s.to_lowercase().as_str() {
+            "admin" => Ok(Self::Admin),
+            "user" => Ok(Self::User),
+            other => Err(format!("`{other}` is not a supported role.")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::UserRole;
+    use claims::{assert_err, assert_ok_eq};
+
+    // Must return `Ok` if the input is a supported role.
+    #[test]
+    fn user_role_returns_ok_when_valid() {
+        for role in [UserRole::Admin, UserRole::User] {
+            assert_ok_eq!(UserRole::try_from(role.as_str().to_string()), role);
+        }
+    }
+
+    // Must return `Err` if the input is not a supported role.
+    #[test]
+    fn user_role_returns_err_when_unknown() {
+        let role = "owner".to_string();
+        assert_err!(UserRole::try_from(role));
+    }
+}
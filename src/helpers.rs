@@ -1,3 +1,4 @@
+use crate::domain::UserRole;
 use anyhow::Context;
 use sqlx::PgPool;
 use uuid::Uuid;
@@ -19,3 +20,21 @@ pub async fn get_username(pool: &PgPool, user_id: Uuid) -> Result<String, anyhow
 
     Ok(row.username)
 }
+
+/// Returns the role of the user.
+#[tracing::instrument(name = "Getting user role", skip(pool))]
+pub async fn get_user_role(pool: &PgPool, user_id: Uuid) -> Result<UserRole, anyhow::Error> {
+    let row = sqlx::query!(
+        r#"
+        SELECT role
+        FROM users
+        WHERE user_id = $1
+        "#,
+        user_id
+    )
+    .fetch_one(pool)
+    .await
+    .context("Failed to retrieve the user role")?;
+
+    UserRole::try_from(row.role).map_err(anyhow::Error::msg)
+}
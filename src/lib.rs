@@ -1,10 +1,24 @@
+pub mod admin;
+pub mod archive;
 pub mod authentication;
+pub mod bootstrap;
 pub mod configuration;
+pub mod correlation_id;
+pub mod dashboard_cache;
 pub mod domain;
 pub mod error;
 pub mod helpers;
+pub mod markdown;
+pub mod metrics;
+pub mod migration;
+pub mod retention_worker;
 pub mod routes;
 pub mod session_state;
+pub mod shutdown;
+pub mod sla_worker;
+pub mod sprint_worker;
 pub mod startup;
+pub mod storage;
 pub mod telemetry;
+pub mod ticket_events;
 pub mod utils;
@@ -1,19 +1,275 @@
+use anyhow::Context;
+use clap::{Parser, Subcommand};
+use secrecy::Secret;
+use std::{
+    fmt::{Debug, Display},
+    io::{BufRead, Write},
+    path::PathBuf,
+};
 use tessera::{
-    configuration::get_configuration,
+    admin::{check_config, create_user, deactivate_user, reset_password},
+    archive::{export_archive, import_archive},
+    configuration::{get_configuration, Settings},
+    domain::UserRole,
+    migration::run_migrations,
+    retention_worker,
+    shutdown::{wait_for_termination_signal, Shutdown},
+    sla_worker, sprint_worker,
     startup::Application,
-    telemetry::{get_subscriber, init_subscriber},
+    telemetry::{get_subscriber, get_tracer, init_subscriber, shutdown_tracer},
 };
+use tokio::task::{JoinError, JoinHandle};
+
+/// Representation of the command line of the application.
+#[derive(Parser)]
+#[command(name = "tessera", about = "A ticket tracker")]
+struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+/// Representation of the commands of the application.
+#[derive(Subcommand)]
+enum Command {
+    /// Runs the application and its workers, the default command.
+    Serve,
+    /// Runs the pending migrations of the database.
+    Migrate,
+    /// Creates a user, whose password is read from the standard input.
+    CreateUser {
+        /// Username of the user.
+        username: String,
+        /// Role of the user, either `admin` or `user`.
+        #[arg(long, default_value = "user")]
+        role: String,
+    },
+    /// Replaces the password of a user, read from the standard input.
+    ResetPassword {
+        /// Username of the user.
+        username: String,
+    },
+    /// Prevents a user from logging in and ends their sessions.
+    DeactivateUser {
+        /// Username of the user.
+        username: String,
+    },
+    /// Checks the configuration and the connections to the database and to Redis.
+    CheckConfig,
+    /// Exports every table and every attachment into an archive.
+    Export {
+        /// Path of the archive to write.
+        path: PathBuf,
+        /// Exports the password hashes of the users too.
+        #[arg(long)]
+        include_password_hashes: bool,
+    },
+    /// Migrates an empty database and then restore an archive into it.
+    Import {
+        /// Path of the archive to read.
+        path: PathBuf,
+    },
+}
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
+    let cli = Cli::parse();
+
+    let configuration = get_configuration().context("Failed to get the configuration values")?;
+
     // Initialize telemetry.
-    let subscriber = get_subscriber("tessera".into(), "info".into(), std::io::stdout);
+    let tracer = get_tracer(&configuration.tracing).context("Failed to create the tracer")?;
+    let subscriber = get_subscriber("tessera".into(), "info".into(), std::io::stdout, tracer);
     init_subscriber(subscriber);
 
-    let configuration = get_configuration().expect("Failed to get the configuration values");
+    let outcome = run(cli.command.unwrap_or(Command::Serve), configuration).await;
+    shutdown_tracer();
+
+    outcome
+}
+
+/// Runs the given command.
+async fn run(command: Command, configuration: Settings) -> anyhow::Result<()> {
+    match command {
+        Command::Serve => serve(configuration).await,
+        Command::Migrate => {
+            let pool = configuration.database.get_connection_pool();
+            run_migrations(&pool).await?;
+            tracing::info!("Migrated the database");
+
+            Ok(())
+        }
+        Command::CreateUser { username, role } => {
+            let role = UserRole::try_from(role).map_err(anyhow::Error::msg)?;
+            let password = read_password()?;
+            let pool = configuration.database.get_connection_pool();
+            let user_id = create_user(&pool, username, password, role).await?;
+            tracing::info!(%user_id, "Created the user");
+
+            Ok(())
+        }
+        Command::ResetPassword { username } => {
+            let password = read_password()?;
+            let pool = configuration.database.get_connection_pool();
+            reset_password(&pool, &username, password).await?;
+            tracing::info!("Reset the password of {}", username);
+
+            Ok(())
+        }
+        Command::DeactivateUser { username } => {
+            let pool = configuration.database.get_connection_pool();
+            deactivate_user(&pool, &username).await?;
+            tracing::info!("Deactivated {}", username);
+
+            Ok(())
+        }
+        Command::CheckConfig => {
+            check_config(&configuration).await?;
+            tracing::info!("The configuration is valid");
 
-    let application = Application::new(configuration).await?;
-    application.run_until_stopped().await?;
+            Ok(())
+        }
+        Command::Export {
+            path,
+            include_password_hashes,
+        } => {
+            let pool = configuration.database.get_connection_pool();
+            let storage = configuration.attachments.storage.get_storage()?;
+            let manifest = export_archive(&pool, &*storage, &path, include_password_hashes).await?;
+            tracing::info!(?manifest, "Exported the archive to {}", path.display());
+
+            Ok(())
+        }
+        Command::Import { path } => {
+            let pool = configuration.database.get_connection_pool();
+            run_migrations(&pool).await?;
+            let storage = configuration.attachments.storage.get_storage()?;
+            let manifest = import_archive(&pool, &*storage, &path).await?;
+            tracing::info!(?manifest, "Imported the archive from {}", path.display());
+
+            Ok(())
+        }
+    }
+}
+
+/// Reads a password from the first line of the standard input.
+///
+/// # Implementation Notes
+///
+/// The password is never taken as an argument, which would keep it in the shell history.
+fn read_password() -> anyhow::Result<Secret<String>> {
+    eprint!("Password: ");
+    std::io::stderr().flush()?;
+    let mut password = String::new();
+    std::io::stdin()
+        .lock()
+        .read_line(&mut password)
+        .context("Failed to read the password")?;
+
+    Ok(Secret::new(
+        password.trim_end_matches(['\r', '\n']).to_string(),
+    ))
+}
+
+/// Runs the application and its workers until a termination signal is received or one of them exits.
+///
+/// # Implementation Notes
+///
+/// Every task is then stopped and given the shutdown delay and the grace period to finish.
+async fn serve(configuration: Settings) -> anyhow::Result<()> {
+    let application = Application::new(configuration.clone()).await?;
+    let shutdown = application.shutdown();
+    let shutdown_timeout = configuration.application.shutdown_delay()
+        + configuration.application.shutdown_grace_period();
+
+    let tasks = [
+        tokio::spawn(supervise(
+            "API",
+            tokio::spawn(async move { Ok(application.run_until_stopped().await?) }),
+            shutdown.clone(),
+        )),
+        tokio::spawn(supervise(
+            "Retention worker",
+            tokio::spawn(retention_worker::run_worker_until_stopped(
+                configuration.clone(),
+                shutdown.clone(),
+            )),
+            shutdown.clone(),
+        )),
+        tokio::spawn(supervise(
+            "SLA worker",
+            tokio::spawn(sla_worker::run_worker_until_stopped(
+                configuration.clone(),
+                shutdown.clone(),
+            )),
+            shutdown.clone(),
+        )),
+        tokio::spawn(supervise(
+            "Sprint worker",
+            tokio::spawn(sprint_worker::run_worker_until_stopped(
+                configuration,
+                shutdown.clone(),
+            )),
+            shutdown.clone(),
+        )),
+    ];
+
+    // Stop as soon as a termination signal is received or one of the tasks exits.
+    tokio::select! {
+        outcome = wait_for_termination_signal() => {
+            outcome.context("Failed to listen to the termination signals")?;
+            tracing::info!("Received a termination signal");
+        }
+        _ = shutdown.wait() => {}
+    };
+    shutdown.trigger();
+
+    let stopped = async {
+        for task in tasks {
+            let _ = task.await;
+        }
+    };
+    if tokio::time::timeout(shutdown_timeout, stopped)
+        .await
+        .is_err()
+    {
+        tracing::warn!("Some tasks did not stop within the grace period");
+    }
 
     Ok(())
 }
+
+/// Reports the exit of a task and then triggers the shutdown of the other ones.
+async fn supervise(
+    task_name: &'static str,
+    task: JoinHandle<anyhow::Result<()>>,
+    shutdown: Shutdown,
+) {
+    let outcome = task.await;
+    report_exit(task_name, outcome);
+    shutdown.trigger();
+}
+
+/// Logs how a task exited.
+fn report_exit(task_name: &str, outcome: Result<Result<(), impl Debug + Display>, JoinError>) {
+    match outcome {
+        Ok(Ok(())) => {
+            tracing::info!("{} has exited", task_name)
+        }
+        Ok(Err(e)) => {
+            tracing::error!(
+                error.cause_chain = ?e,
+                error.message = %e,
+                "{} failed",
+                task_name
+            )
+        }
+        Err(e) => {
+            tracing::error!(
+                error.cause_chain = ?e,
+                error.message = %e,
+                "{} task failed to complete",
+                task_name
+            )
+        }
+    }
+}
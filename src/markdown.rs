@@ -0,0 +1,143 @@
+use pulldown_cmark::{escape::escape_html, html, CodeBlockKind, Event, Options, Parser, Tag};
+use std::sync::OnceLock;
+use syntect::{
+    html::{ClassStyle, ClassedHTMLGenerator},
+    parsing::SyntaxSet,
+    util::LinesWithEndings,
+};
+
+/// Prefix of the CSS classes used to highlight code, see `static/css/highlight.css`.
+const HIGHLIGHT_CLASS_STYLE: ClassStyle = ClassStyle::SpacedPrefixed { prefix: "hl-" };
+
+/// Returns the syntax definitions, loaded only once.
+fn syntax_set() -> &'static SyntaxSet {
+    static SYNTAX_SET: OnceLock<SyntaxSet> = OnceLock::new();
+
+    SYNTAX_SET.get_or_init(SyntaxSet::load_defaults_newlines)
+}
+
+/// Renders CommonMark as sanitized HTML.
+///
+/// # Implementation Notes
+///
+/// Line breaks are kept as is, so that plain text such as stack traces stays readable.
+pub fn render_markdown(markdown: &str) -> String {
+    let options =
+        Options::ENABLE_TABLES | Options::ENABLE_STRIKETHROUGH | Options::ENABLE_TASKLISTS;
+
+    let mut events = Vec::new();
+    let mut code_block: Option<(String, String)> = None;
+    for event in Parser::new_ext(markdown, options) {
+        match (event, code_block.as_mut()) {
+            (Event::Start(Tag::CodeBlock(kind)), _) => {
+                let language = match kind {
+                    CodeBlockKind::Fenced(info) => {
+                        info.split_whitespace().next().unwrap_or("").into()
+                    }
+                    CodeBlockKind::Indented => String::new(),
+                };
+                code_block = Some((language, String::new()));
+            }
+            (Event::Text(text), Some((_, code))) => code.push_str(&text),
+            (Event::End(Tag::CodeBlock(_)), Some((language, code))) => {
+                events.push(Event::Html(highlight_code(code, language).into()));
+                code_block = None;
+            }
+            (Event::SoftBreak, _) => events.push(Event::HardBreak),
+            (event, _) => events.push(event),
+        }
+    }
+
+    let mut unsafe_html = String::new();
+    html::push_html(&mut unsafe_html, events.into_iter());
+
+    ammonia::Builder::default()
+        .add_tag_attributes("pre", &["class"])
+        .add_tag_attributes("span", &["class"])
+        .clean(&unsafe_html)
+        .to_string()
+}
+
+/// Returns the code as HTML highlighted with CSS classes.
+///
+/// # Implementation Notes
+///
+/// Unknown languages are rendered as plain text.
+fn highlight_code(code: &str, language: &str) -> String {
+    let syntax_set = syntax_set();
+    let syntax = syntax_set
+        .find_syntax_by_token(language)
+        .unwrap_or_else(|| syntax_set.find_syntax_plain_text());
+
+    let mut generator =
+        ClassedHTMLGenerator::new_with_class_style(syntax, syntax_set, HIGHLIGHT_CLASS_STYLE);
+    let is_highlighted = LinesWithEndings::from(code).all(|line| {
+        generator
+            .parse_html_for_line_which_includes_newline(line)
+            .is_ok()
+    });
+
+    let body = if is_highlighted {
+        generator.finalize()
+    } else {
+        let mut escaped = String::new();
+        escape_html(&mut escaped, code).unwrap();
+        escaped
+    };
+
+    format!(r#"<pre class="hl-code"><code>{}</code></pre>"#, body)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::markdown::render_markdown;
+
+    // Must render CommonMark.
+    #[test]
+    fn render_markdown_renders_commonmark() {
+        let html = render_markdown("# Title\n\nSome *emphasis* and `code`.");
+        assert!(html.contains("<h1>Title</h1>"));
+        assert!(html.contains("<em>emphasis</em>"));
+        assert!(html.contains("<code>code</code>"));
+    }
+
+    // Must keep the line breaks of plain text.
+    #[test]
+    fn render_markdown_keeps_line_breaks() {
+        let html = render_markdown("thread 'main' panicked\nat src/main.rs:1:1");
+        assert!(html.contains("panicked<br>"));
+    }
+
+    // Must highlight fenced code blocks.
+    #[test]
+    fn render_markdown_highlights_code_blocks() {
+        let html = render_markdown("```rust\nfn main() {}\n```");
+        assert!(html.contains(r#"<pre class="hl-code">"#));
+        assert!(html.contains("hl-rust"));
+    }
+
+    // Must escape code blocks in unknown languages.
+    #[test]
+    fn render_markdown_escapes_code_blocks_in_unknown_languages() {
+        let html = render_markdown("```unknown\n<script>alert(1)</script>\n```");
+        assert!(!html.contains("<script>"));
+        assert!(html.contains("&lt;script&gt;"));
+    }
+
+    // Must remove scripts.
+    #[test]
+    fn render_markdown_removes_scripts() {
+        let html = render_markdown("Hello <script>alert(1)</script>");
+        assert!(!html.contains("<script>"));
+        assert!(!html.contains("alert(1)"));
+    }
+
+    // Must remove event handlers and dangerous links.
+    #[test]
+    fn render_markdown_removes_event_handlers_and_dangerous_links() {
+        let html =
+            render_markdown("<img src=\"x\" onerror=\"alert(1)\"> [link](javascript:alert(1))");
+        assert!(!html.contains("onerror"));
+        assert!(!html.contains("javascript:"));
+    }
+}
@@ -0,0 +1,177 @@
+use actix_web::{
+    body::MessageBody,
+    dev::{ServiceRequest, ServiceResponse},
+};
+use actix_web_lab::middleware::Next;
+use anyhow::Context;
+use prometheus::{
+    Histogram, HistogramOpts, HistogramVec, IntCounterVec, IntGaugeVec, Opts, Registry, TextEncoder,
+};
+use sqlx::PgPool;
+use std::{
+    sync::{Mutex, OnceLock},
+    time::Instant,
+};
+
+/// Metrics of the process, shared by every instance of the application it runs.
+static METRICS: OnceLock<Metrics> = OnceLock::new();
+
+/// Representation of the Prometheus metrics of the application.
+pub struct Metrics {
+    registry: Registry,
+    render_lock: Mutex<()>,
+    pub http_requests: IntCounterVec,
+    pub http_request_duration: HistogramVec,
+    pub db_pool_connections: IntGaugeVec,
+    pub password_hash_duration: HistogramVec,
+    pub logins: IntCounterVec,
+    pub open_tickets: IntGaugeVec,
+}
+
+impl Metrics {
+    /// Returns the metrics of the process.
+    pub fn global() -> &'static Self {
+        METRICS.get_or_init(Self::new)
+    }
+
+    /// Creates the metrics and registers them.
+    fn new() -> Self {
+        let http_labels = ["method", "route", "status"];
+        let metrics = Self {
+            registry: Registry::new_custom(Some("tessera".to_string()), None)
+                .expect("Failed to create the metrics registry"),
+            render_lock: Mutex::new(()),
+            http_requests: IntCounterVec::new(
+                Opts::new("http_requests_total", "Number of HTTP requests."),
+                &http_labels,
+            )
+            .unwrap(),
+            http_request_duration: HistogramVec::new(
+                HistogramOpts::new(
+                    "http_request_duration_seconds",
+                    "Duration of the HTTP requests.",
+                ),
+                &http_labels,
+            )
+            .unwrap(),
+            db_pool_connections: IntGaugeVec::new(
+                Opts::new(
+                    "db_pool_connections",
+                    "Number of connections of the database pool, by state.",
+                ),
+                &["state"],
+            )
+            .unwrap(),
+            password_hash_duration: HistogramVec::new(
+                HistogramOpts::new(
+                    "password_hash_duration_seconds",
+                    "Duration of the computation and of the verification of password hashes.",
+                )
+                .buckets(vec![0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0]),
+                &["operation"],
+            )
+            .unwrap(),
+            logins: IntCounterVec::new(
+                Opts::new("logins_total", "Number of login attempts, by outcome."),
+                &["outcome"],
+            )
+            .unwrap(),
+            open_tickets: IntGaugeVec::new(
+                Opts::new("open_tickets", "Number of open tickets, by priority."),
+                &["priority"],
+            )
+            .unwrap(),
+        };
+
+        let collectors: [Box<dyn prometheus::core::Collector>; 6] = [
+            Box::new(metrics.http_requests.clone()),
+            Box::new(metrics.http_request_duration.clone()),
+            Box::new(metrics.db_pool_connections.clone()),
+            Box::new(metrics.password_hash_duration.clone()),
+            Box::new(metrics.logins.clone()),
+            Box::new(metrics.open_tickets.clone()),
+        ];
+        for collector in collectors {
+            metrics
+                .registry
+                .register(collector)
+                .expect("Failed to register the metric");
+        }
+
+        metrics
+    }
+
+    /// Returns the password hash duration histogram of the given operation.
+    pub fn password_hash_timer(&self, operation: &str) -> Histogram {
+        self.password_hash_duration.with_label_values(&[operation])
+    }
+
+    /// Refreshes the gauges read from the database
+    /// and then return every metric in the Prometheus text format.
+    pub async fn render(&self, pool: &PgPool) -> Result<String, anyhow::Error> {
+        let open_tickets = sqlx::query!(
+            r#"
+            SELECT priority, COUNT(*) AS "count!"
+            FROM tickets
+            WHERE is_open
+            GROUP BY priority
+            "#
+        )
+        .fetch_all(pool)
+        .await
+        .context("Failed to count the open tickets by priority")?;
+
+        // Refresh and gather the gauges at once, concurrent renders could mix them otherwise.
+        let _guard = self.render_lock.lock().unwrap();
+        let idle_connections = pool.num_idle() as i64;
+        let connections = [
+            ("idle", idle_connections),
+            ("in_use", pool.size() as i64 - idle_connections),
+        ];
+        for (state, count) in connections {
+            self.db_pool_connections
+                .with_label_values(&[state])
+                .set(count);
+        }
+        // Remove the priorities that no open ticket has anymore.
+        self.open_tickets.reset();
+        for row in open_tickets {
+            self.open_tickets
+                .with_label_values(&[&row.priority])
+                .set(row.count);
+        }
+
+        TextEncoder::new()
+            .encode_to_string(&self.registry.gather())
+            .context("Failed to encode the metrics")
+    }
+}
+
+/// Middleware used to count the HTTP requests and measure their duration,
+/// by route pattern rather than by path to keep the number of series bounded.
+pub async fn record_http_metrics(
+    req: ServiceRequest,
+    next: Next<impl MessageBody>,
+) -> Result<ServiceResponse<impl MessageBody>, actix_web::Error> {
+    let method = req.method().to_string();
+    let route = req
+        .match_pattern()
+        .unwrap_or_else(|| "unmatched".to_string());
+    let started_at = Instant::now();
+
+    let outcome = next.call(req).await;
+
+    let status = match &outcome {
+        Ok(response) => response.status(),
+        Err(e) => e.as_response_error().status_code(),
+    };
+    let labels = [method.as_str(), route.as_str(), status.as_str()];
+    let metrics = Metrics::global();
+    metrics.http_requests.with_label_values(&labels).inc();
+    metrics
+        .http_request_duration
+        .with_label_values(&labels)
+        .observe(started_at.elapsed().as_secs_f64());
+
+    outcome
+}
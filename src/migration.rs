@@ -0,0 +1,114 @@
+use anyhow::Context;
+use sqlx::{migrate::Migrator, PgConnection, PgPool};
+
+/// Migrations of the database, embedded into the binary.
+///
+/// # Implementation Notes
+///
+/// The migrations removed from the source, such as the one creating the default
+/// administrator, are ignored on the databases which have already applied them.
+pub static MIGRATOR: Migrator = Migrator {
+    ignore_missing: true,
+    ..sqlx::migrate!("./migrations")
+};
+
+/// Key of the advisory lock held while the schema is checked and migrated, `tessera` in ASCII.
+const MIGRATION_LOCK_KEY: i64 = 0x0074_6573_7365_7261;
+
+/// Checks that the schema of the database is not newer than the binary
+/// and then run the pending migrations.
+///
+/// # Implementation Notes
+///
+/// An advisory lock is held from the check to the last migration,
+/// so that several instances starting together run the migrations once.
+///
+/// The statement timeout of the requests is lifted meanwhile, migrations such as
+/// index builds or backfills, or waiting for the lock, may take much longer.
+#[tracing::instrument(name = "Running the migrations", skip(pool))]
+pub async fn run_migrations(pool: &PgPool) -> Result<(), anyhow::Error> {
+    let mut connection = pool
+        .acquire()
+        .await
+        .context("Failed to acquire a Postgres connection from the pool")?;
+    sqlx::query("SET statement_timeout = 0")
+        .execute(&mut *connection)
+        .await
+        .context("Failed to lift the statement timeout")?;
+    sqlx::query("SELECT pg_advisory_lock($1)")
+        .bind(MIGRATION_LOCK_KEY)
+        .execute(&mut *connection)
+        .await
+        .context("Failed to acquire the migration lock")?;
+
+    let outcome = async {
+        check_schema_version(&mut connection).await?;
+        MIGRATOR
+            .run_direct(&mut *connection)
+            .await
+            .context("Failed to migrate the database")
+    }
+    .await;
+
+    sqlx::query("SELECT pg_advisory_unlock($1)")
+        .bind(MIGRATION_LOCK_KEY)
+        .execute(&mut *connection)
+        .await
+        .context("Failed to release the migration lock")?;
+    sqlx::query("RESET statement_timeout")
+        .execute(&mut *connection)
+        .await
+        .context("Failed to restore the statement timeout")?;
+
+    outcome
+}
+
+/// Checks that the database has been migrated and that its schema
+/// is not newer than the binary, without migrating it.
+#[tracing::instrument(name = "Checking the schema version", skip(pool))]
+pub async fn check_schema(pool: &PgPool) -> Result<(), anyhow::Error> {
+    let mut connection = pool
+        .acquire()
+        .await
+        .context("Failed to acquire a Postgres connection from the pool")?;
+
+    if get_schema_version(&mut connection).await?.is_none() {
+        anyhow::bail!("The database has not been migrated, run `tessera migrate` first");
+    }
+
+    check_schema_version(&mut connection).await
+}
+
+/// Returns an error if the database has applied a migration unknown to the binary.
+async fn check_schema_version(connection: &mut PgConnection) -> Result<(), anyhow::Error> {
+    let applied_version = get_schema_version(connection).await?;
+    let known_version = MIGRATOR.iter().map(|migration| migration.version).max();
+
+    match applied_version {
+        Some(applied_version) if Some(applied_version) > known_version => anyhow::bail!(
+            "The database schema is at version {}, which is newer than the version {} \
+            of this binary",
+            applied_version,
+            known_version.unwrap_or_default()
+        ),
+        _ => Ok(()),
+    }
+}
+
+/// Returns the version of the last migration applied to the database, if any.
+async fn get_schema_version(connection: &mut PgConnection) -> Result<Option<i64>, anyhow::Error> {
+    // The table does not exist until the first migration.
+    let is_migrated: bool =
+        sqlx::query_scalar("SELECT to_regclass('_sqlx_migrations') IS NOT NULL")
+            .fetch_one(&mut *connection)
+            .await
+            .context("Failed to check if the database has been migrated")?;
+    if !is_migrated {
+        return Ok(None);
+    }
+
+    sqlx::query_scalar("SELECT MAX(version) FROM _sqlx_migrations")
+        .fetch_one(&mut *connection)
+        .await
+        .context("Failed to get the version of the schema")
+}
@@ -0,0 +1,46 @@
+use crate::{
+    configuration::{NotificationSettings, Settings},
+    routes::delete_expired_notifications,
+    shutdown::Shutdown,
+};
+use sqlx::PgPool;
+
+/// Runs the retention worker until stopped.
+pub async fn run_worker_until_stopped(
+    configuration: Settings,
+    shutdown: Shutdown,
+) -> Result<(), anyhow::Error> {
+    let connection_pool = configuration.database.get_connection_pool();
+
+    let outcome = worker_loop(&connection_pool, configuration.notifications, shutdown).await;
+    connection_pool.close().await;
+
+    outcome
+}
+
+/// Periodically cleans up the data that exceeded its retention period, until the shutdown.
+async fn worker_loop(
+    pool: &PgPool,
+    settings: NotificationSettings,
+    shutdown: Shutdown,
+) -> Result<(), anyhow::Error> {
+    loop {
+        match delete_expired_notifications(pool, settings.retention()).await {
+            Ok(deleted) => {
+                tracing::info!(deleted, "Cleaned up the expired notifications");
+            }
+            Err(e) => {
+                tracing::error!(
+                    error.cause_chain = ?e,
+                    error.message = %e,
+                    "Failed to clean up the expired notifications"
+                );
+            }
+        }
+
+        tokio::select! {
+            _ = tokio::time::sleep(settings.cleanup_interval()) => {}
+            _ = shutdown.wait() => return Ok(()),
+        }
+    }
+}
@@ -0,0 +1,167 @@
+use crate::{
+    authentication::UserId,
+    correlation_id::CorrelationId,
+    domain::{TicketChange, ValidTicket},
+    error::error_chain_fmt,
+    routes::{can_access_ticket, close_ticket_at_version, get_ticket, TicketUpdate},
+};
+use actix_web::{
+    http::{
+        header::{ETag, EntityTag, IfMatch},
+        StatusCode,
+    },
+    web, HttpResponse, ResponseError,
+};
+use anyhow::Context;
+use sqlx::PgPool;
+use std::fmt::Debug;
+use uuid::Uuid;
+
+/// Representation of a JSON API error.
+#[derive(thiserror::Error)]
+pub enum ApiError {
+    #[error("The ticket does not exist.")]
+    NotFoundError,
+    #[error("The `If-Match` header must contain the version of the ticket.")]
+    PreconditionRequiredError,
+    #[error("The ticket has been changed by someone else.")]
+    ConflictError(Vec<TicketChange>),
+    #[error("Something went wrong.")]
+    UnexpectedError(#[from] anyhow::Error),
+}
+
+impl Debug for ApiError {
+    /// Formats the value using the given formatter.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        error_chain_fmt(self, f)
+    }
+}
+
+impl ResponseError for ApiError {
+    /// Creates full response for error.
+    fn error_response(&self) -> HttpResponse {
+        let request_id = CorrelationId::current().map(|id| id.to_string());
+        let body = match self {
+            ApiError::ConflictError(changes) => serde_json::json!({
+                "error": self.to_string(),
+                "changes": changes,
+                "request_id": request_id,
+            }),
+            _ => serde_json::json!({ "error": self.to_string(), "request_id": request_id }),
+        };
+
+        HttpResponse::build(self.status_code()).json(body)
+    }
+
+    /// Returns appropriate status code for error.
+    fn status_code(&self) -> StatusCode {
+        match self {
+            ApiError::NotFoundError => StatusCode::NOT_FOUND,
+            ApiError::PreconditionRequiredError => StatusCode::PRECONDITION_REQUIRED,
+            ApiError::ConflictError(_) => StatusCode::CONFLICT,
+            ApiError::UnexpectedError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+}
+
+/// Returns a `200 OK` with the ticket as JSON and its version as `ETag`.
+fn ticket_response(ticket: &ValidTicket) -> HttpResponse {
+    HttpResponse::Ok()
+        .insert_header(ETag(EntityTag::new_strong(ticket.version.to_string())))
+        .json(ticket)
+}
+
+/// Returns a `404 Not Found` if the user is not a member of the project of the ticket.
+async fn ensure_ticket_access(
+    pool: &PgPool,
+    ticket_id: i32,
+    user_id: Uuid,
+) -> Result<(), ApiError> {
+    let can_access = can_access_ticket(pool, ticket_id, user_id)
+        .await
+        .context("Failed to check the project membership")?;
+
+    if can_access {
+        Ok(())
+    } else {
+        Err(ApiError::NotFoundError)
+    }
+}
+
+/// Returns the ticket as JSON.
+#[tracing::instrument(
+    name = "Seeing ticket as JSON",
+    skip(pool, user_id, ticket_id),
+    fields(
+        user_id=%&*user_id,
+        ticket_id=%ticket_id.0
+    )
+)]
+pub async fn api_see_ticket(
+    pool: web::Data<PgPool>,
+    user_id: web::ReqData<UserId>,
+    ticket_id: web::Path<(i32,)>,
+) -> Result<HttpResponse, ApiError> {
+    let ticket_id = ticket_id.into_inner().0;
+    ensure_ticket_access(&pool, ticket_id, **user_id).await?;
+
+    let ticket = match get_ticket(&pool, ticket_id).await {
+        Ok(ticket) => ticket,
+        Err(sqlx::Error::RowNotFound) => return Err(ApiError::NotFoundError),
+        Err(e) => {
+            return Err(anyhow::Error::new(e)
+                .context("Failed to get the ticket details from the tickets table")
+                .into())
+        }
+    };
+
+    Ok(ticket_response(&ticket))
+}
+
+/// Closes the ticket if its version matches the `If-Match` header.
+#[tracing::instrument(
+    name = "Closing ticket as JSON",
+    skip(pool, if_match, user_id, ticket_id),
+    fields(
+        user_id=%&*user_id,
+        ticket_id=%ticket_id.0
+    )
+)]
+pub async fn api_close_ticket(
+    pool: web::Data<PgPool>,
+    if_match: Option<web::Header<IfMatch>>,
+    user_id: web::ReqData<UserId>,
+    ticket_id: web::Path<(i32,)>,
+) -> Result<HttpResponse, ApiError> {
+    let ticket_id = ticket_id.into_inner().0;
+    ensure_ticket_access(&pool, ticket_id, **user_id).await?;
+
+    let version = match if_match.map(|h| h.into_inner()) {
+        // Accept the current version, whatever it is.
+        Some(IfMatch::Any) => {
+            get_ticket(&pool, ticket_id)
+                .await
+                .map_err(|_| ApiError::NotFoundError)?
+                .version
+        }
+        Some(IfMatch::Items(tags)) => tags
+            .iter()
+            .find_map(|tag| tag.tag().parse::<i32>().ok())
+            .ok_or(ApiError::PreconditionRequiredError)?,
+        None => return Err(ApiError::PreconditionRequiredError),
+    };
+
+    match close_ticket_at_version(&pool, ticket_id, version, **user_id, None).await? {
+        TicketUpdate::Updated(ticket) => Ok(ticket_response(&ticket)),
+        TicketUpdate::Unchanged => {
+            let ticket = get_ticket(&pool, ticket_id)
+                .await
+                .context("Failed to get the ticket details from the tickets table")?;
+
+            Ok(ticket_response(&ticket))
+        }
+        TicketUpdate::Conflict(changes) => Err(ApiError::ConflictError(changes)),
+        TicketUpdate::NotFound => Err(ApiError::NotFoundError),
+        TicketUpdate::Rejected(e) => Err(anyhow::anyhow!(e).into()),
+    }
+}
@@ -0,0 +1,524 @@
+use crate::{
+    authentication::UserId,
+    configuration::AttachmentSettings,
+    domain::{NewAttachment, ValidAttachment},
+    error::error_chain_fmt,
+    routes::{can_access_ticket, get_ticket},
+    storage::AttachmentStorage,
+    utils::{error_page, see_other},
+};
+use actix_multipart::Multipart;
+use actix_web::{
+    http::{
+        header::{
+            ContentDisposition, ContentType, DispositionParam, DispositionType,
+            X_CONTENT_TYPE_OPTIONS,
+        },
+        StatusCode,
+    },
+    web::{self, BytesMut},
+    HttpResponse, ResponseError,
+};
+use actix_web_flash_messages::FlashMessage;
+use anyhow::Context;
+use chrono::Utc;
+use futures_util::StreamExt;
+use image::ImageOutputFormat;
+use sqlx::{PgPool, Postgres, Transaction};
+use std::{collections::HashMap, fmt::Debug, io::Cursor};
+use uuid::Uuid;
+
+/// Maximum width and height of a thumbnail, in pixels.
+const THUMBNAIL_SIZE: u32 = 200;
+
+/// Representation of a multipart form, split into its text fields and its attachments.
+pub struct MultipartForm {
+    pub fields: HashMap<String, String>,
+    pub attachments: Vec<NewAttachment>,
+}
+
+/// Representation of an attachment whose content is stored, but whose details are not saved yet.
+pub struct StoredAttachment {
+    id: Uuid,
+    attachment: NewAttachment,
+    storage_key: String,
+    thumbnail_key: Option<String>,
+}
+
+/// Representation of an attachment error.
+#[derive(thiserror::Error)]
+pub enum AttachmentError {
+    #[error("The attachment does not exist.")]
+    NotFoundError,
+    #[error("Something went wrong.")]
+    UnexpectedError(#[from] anyhow::Error),
+}
+
+impl Debug for AttachmentError {
+    /// Formats the value using the given formatter.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        error_chain_fmt(self, f)
+    }
+}
+
+impl ResponseError for AttachmentError {
+    /// Creates full response for error.
+    fn error_response(&self) -> HttpResponse {
+        match self {
+            AttachmentError::NotFoundError => HttpResponse::build(self.status_code())
+                .content_type(ContentType::plaintext())
+                .body(self.to_string()),
+            AttachmentError::UnexpectedError(_) => error_page(self.status_code()),
+        }
+    }
+
+    /// Returns appropriate status code for error.
+    fn status_code(&self) -> StatusCode {
+        match self {
+            AttachmentError::NotFoundError => StatusCode::NOT_FOUND,
+            AttachmentError::UnexpectedError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+}
+
+/// Reads a multipart form and then return its text fields and its valid attachments.
+///
+/// # Implementation Notes
+///
+/// Reading stops as soon as a part, the number of parts or the whole form exceeds its limit,
+/// an oversized upload is never buffered entirely.
+pub async fn read_multipart_form(
+    mut payload: Multipart,
+    settings: &AttachmentSettings,
+) -> Result<MultipartForm, String> {
+    let mut form = MultipartForm {
+        fields: HashMap::new(),
+        attachments: Vec::new(),
+    };
+    let mut parts = 0;
+    let mut form_size = 0;
+
+    while let Some(field) = payload.next().await {
+        let mut field = field.map_err(|e| format!("malformed form data: {}.", e))?;
+        parts += 1;
+        if parts > settings.max_parts {
+            return Err(format!(
+                "the form cannot have more than {} parts.",
+                settings.max_parts
+            ));
+        }
+        let name = field.name().to_string();
+        let file_name = field
+            .content_disposition()
+            .get_filename()
+            .map(ToString::to_string);
+        let content_type = field
+            .content_type()
+            .map(|mime| mime.essence_str().to_string())
+            .unwrap_or_default();
+
+        let mut content = BytesMut::new();
+        while let Some(chunk) = field.next().await {
+            let chunk = chunk.map_err(|e| format!("malformed form data: {}.", e))?;
+            if content.len() + chunk.len() > settings.max_size_bytes {
+                return Err(format!(
+                    "`{}` cannot be larger than {} bytes.",
+                    file_name.as_ref().unwrap_or(&name),
+                    settings.max_size_bytes
+                ));
+            }
+            form_size += chunk.len();
+            if form_size > settings.max_form_size_bytes {
+                return Err(format!(
+                    "the form cannot be larger than {} bytes.",
+                    settings.max_form_size_bytes
+                ));
+            }
+            content.extend_from_slice(&chunk);
+        }
+
+        match file_name {
+            // Browsers send an empty part when no file is selected.
+            Some(file_name) if file_name.is_empty() && content.is_empty() => {}
+            Some(file_name) => form.attachments.push(NewAttachment::parse(
+                file_name,
+                content_type,
+                content.freeze(),
+                settings,
+            )?),
+            None => {
+                let value = String::from_utf8(content.to_vec())
+                    .map_err(|_| format!("`{}` must be valid UTF-8.", name))?;
+                form.fields.insert(name, value);
+            }
+        }
+    }
+
+    Ok(form)
+}
+
+/// Returns a PNG thumbnail of the image.
+fn create_thumbnail(content: &[u8]) -> Result<Vec<u8>, image::ImageError> {
+    let mut thumbnail = Vec::new();
+    image::load_from_memory(content)?
+        .thumbnail(THUMBNAIL_SIZE, THUMBNAIL_SIZE)
+        .write_to(&mut Cursor::new(&mut thumbnail), ImageOutputFormat::Png)?;
+
+    Ok(thumbnail)
+}
+
+/// Stores the contents of the attachments and their thumbnails, and then return them.
+///
+/// # Implementation Notes
+///
+/// The contents are stored before any row refers to them,
+/// the contents already stored are deleted when one of them cannot be stored.
+pub async fn store_attachments(
+    storage: &dyn AttachmentStorage,
+    attachments: Vec<NewAttachment>,
+) -> Result<Vec<StoredAttachment>, anyhow::Error> {
+    let mut stored_attachments = Vec::new();
+    for attachment in attachments {
+        match store_attachment(storage, attachment).await {
+            Ok(stored_attachment) => stored_attachments.push(stored_attachment),
+            Err(e) => {
+                delete_stored_attachments(storage, &stored_attachments).await;
+
+                return Err(e);
+            }
+        }
+    }
+
+    Ok(stored_attachments)
+}
+
+/// Stores the content of the attachment and its thumbnail, and then return their keys.
+#[tracing::instrument(
+    name = "Storing a new attachment",
+    skip(storage, attachment),
+    fields(
+        file_name = %attachment.file_name.as_ref(),
+        size_bytes = attachment.content.len()
+    )
+)]
+async fn store_attachment(
+    storage: &dyn AttachmentStorage,
+    attachment: NewAttachment,
+) -> Result<StoredAttachment, anyhow::Error> {
+    let id = Uuid::new_v4();
+    let storage_key = format!("attachments/{}", id);
+
+    storage
+        .put(
+            &storage_key,
+            &attachment.content_type,
+            attachment.content.clone(),
+        )
+        .await?;
+    let mut stored_attachment = StoredAttachment {
+        id,
+        attachment,
+        storage_key,
+        thumbnail_key: None,
+    };
+
+    if stored_attachment.attachment.is_image() {
+        let content = stored_attachment.attachment.content.clone();
+        match web::block(move || create_thumbnail(&content)).await? {
+            Ok(thumbnail) => {
+                let thumbnail_key = format!("{}-thumbnail", stored_attachment.storage_key);
+                if let Err(e) = storage
+                    .put(&thumbnail_key, "image/png", thumbnail.into())
+                    .await
+                {
+                    delete_stored_attachments(storage, &[stored_attachment]).await;
+
+                    return Err(e);
+                }
+                stored_attachment.thumbnail_key = Some(thumbnail_key);
+            }
+            // Images in an unsupported format are kept, without a thumbnail.
+            Err(e) => tracing::warn!(error.message = %e, "Failed to create a thumbnail"),
+        }
+    }
+
+    Ok(stored_attachment)
+}
+
+/// Deletes the stored contents of the attachments, on a best-effort basis.
+pub async fn delete_stored_attachments(
+    storage: &dyn AttachmentStorage,
+    stored_attachments: &[StoredAttachment],
+) {
+    for stored_attachment in stored_attachments {
+        let _ = storage.delete(&stored_attachment.storage_key).await;
+        if let Some(thumbnail_key) = &stored_attachment.thumbnail_key {
+            let _ = storage.delete(thumbnail_key).await;
+        }
+    }
+}
+
+/// Inserts the details of the stored attachments into the `attachments` table.
+#[tracing::instrument(
+    name = "Inserting the new attachments details into the attachments table",
+    skip(transaction, stored_attachments)
+)]
+pub async fn insert_attachments(
+    transaction: &mut Transaction<'_, Postgres>,
+    ticket_id: i32,
+    stored_attachments: &[StoredAttachment],
+    uploaded_by: Uuid,
+) -> Result<(), sqlx::Error> {
+    for stored_attachment in stored_attachments {
+        let attachment = &stored_attachment.attachment;
+        sqlx::query!(
+            r#"
+            INSERT INTO attachments (id, ticket_id, file_name, content_type, size_bytes, storage_key, thumbnail_key, uploaded_by, uploaded_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+            "#,
+            stored_attachment.id,
+            ticket_id,
+            attachment.file_name.as_ref(),
+            attachment.content_type,
+            attachment.content.len() as i64,
+            stored_attachment.storage_key,
+            stored_attachment.thumbnail_key,
+            uploaded_by,
+            Utc::now(),
+        )
+        .execute(&mut *transaction)
+        .await?;
+    }
+
+    Ok(())
+}
+
+/// Returns the attachments of a ticket.
+#[tracing::instrument(
+    name = "Getting attachments details from the attachments table",
+    skip(pool)
+)]
+pub async fn get_attachments(
+    pool: &PgPool,
+    ticket_id: i32,
+) -> Result<Vec<ValidAttachment>, sqlx::Error> {
+    let attachments = sqlx::query_as!(
+        ValidAttachment,
+        r#"
+        SELECT *
+        FROM attachments
+        WHERE ticket_id = $1
+        ORDER BY uploaded_at
+        "#,
+        ticket_id
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(attachments)
+}
+
+/// Returns an attachment of a ticket.
+#[tracing::instrument(
+    name = "Getting attachment details from the attachments table",
+    skip(pool)
+)]
+pub async fn get_attachment(
+    pool: &PgPool,
+    ticket_id: i32,
+    id: Uuid,
+) -> Result<Option<ValidAttachment>, sqlx::Error> {
+    let attachment = sqlx::query_as!(
+        ValidAttachment,
+        r#"
+        SELECT *
+        FROM attachments
+        WHERE id = $1 AND ticket_id = $2
+        "#,
+        id,
+        ticket_id
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(attachment)
+}
+
+/// Adds attachments to a ticket.
+#[tracing::instrument(
+    name = "Adding attachments",
+    skip(pool, storage, settings, payload, user_id, ticket_id),
+    fields(
+        user_id=%&*user_id,
+        ticket_id=%ticket_id.0
+    )
+)]
+pub async fn upload_attachments(
+    pool: web::Data<PgPool>,
+    storage: web::Data<dyn AttachmentStorage>,
+    settings: web::Data<AttachmentSettings>,
+    payload: Multipart,
+    user_id: web::ReqData<UserId>,
+    ticket_id: web::Path<(i32,)>,
+) -> Result<HttpResponse, AttachmentError> {
+    let ticket_id = ticket_id.into_inner().0;
+    let location = format!("/dashboard/tickets/{}", ticket_id);
+
+    let can_access = can_access_ticket(&pool, ticket_id, **user_id)
+        .await
+        .context("Failed to check the project membership")?;
+
+    // Tickets of other projects are treated as if they did not exist.
+    match get_ticket(&pool, ticket_id).await {
+        Ok(_) if can_access => {}
+        Ok(_) | Err(sqlx::Error::RowNotFound) => {
+            // Send notification.
+            FlashMessage::error("This ticket does not exist.").send();
+
+            return Ok(see_other("/dashboard/tickets"));
+        }
+        Err(e) => {
+            return Err(anyhow::Error::new(e)
+                .context("Failed to get the ticket details from the tickets table")
+                .into())
+        }
+    }
+
+    let attachments = match read_multipart_form(payload, &settings).await {
+        Ok(form) if form.attachments.is_empty() => {
+            // Send notification.
+            FlashMessage::error("Select at least one file to attach.").send();
+
+            return Ok(see_other(location.as_str()));
+        }
+        Ok(form) => form.attachments,
+        Err(e) => {
+            // Send notification.
+            FlashMessage::error(format!("Validation error: {}", e)).send();
+
+            return Ok(see_other(location.as_str()));
+        }
+    };
+
+    let stored_attachments = store_attachments(&**storage, attachments).await?;
+    if let Err(e) = save_attachments(&pool, ticket_id, &stored_attachments, **user_id).await {
+        delete_stored_attachments(&**storage, &stored_attachments).await;
+
+        return Err(e.into());
+    }
+
+    // Send notification.
+    FlashMessage::info("You have successfully attached the files.").send();
+
+    Ok(see_other(location.as_str()))
+}
+
+/// Saves the details of the stored attachments of a ticket, all of them or none.
+async fn save_attachments(
+    pool: &PgPool,
+    ticket_id: i32,
+    stored_attachments: &[StoredAttachment],
+    uploaded_by: Uuid,
+) -> Result<(), anyhow::Error> {
+    let mut transaction = pool
+        .begin()
+        .await
+        .context("Failed to acquire a Postgres connection from the pool")?;
+    insert_attachments(&mut transaction, ticket_id, stored_attachments, uploaded_by)
+        .await
+        .context("Failed to insert the new attachments details into the attachments table")?;
+    transaction
+        .commit()
+        .await
+        .context("Failed to commit SQL transaction to store the attachments")?;
+
+    Ok(())
+}
+
+/// Downloads an attachment of a ticket.
+#[tracing::instrument(
+    name = "Downloading attachment",
+    skip(pool, storage, user_id, path),
+    fields(
+        user_id=%&*user_id,
+        ticket_id=%path.0,
+        attachment_id=%path.1
+    )
+)]
+pub async fn download_attachment(
+    pool: web::Data<PgPool>,
+    storage: web::Data<dyn AttachmentStorage>,
+    user_id: web::ReqData<UserId>,
+    path: web::Path<(i32, Uuid)>,
+) -> Result<HttpResponse, AttachmentError> {
+    let (ticket_id, id) = path.into_inner();
+
+    // Tickets of other projects are treated as if they did not exist.
+    if !can_access_ticket(&pool, ticket_id, **user_id)
+        .await
+        .context("Failed to check the project membership")?
+    {
+        return Err(AttachmentError::NotFoundError);
+    }
+
+    let attachment = get_attachment(&pool, ticket_id, id)
+        .await
+        .context("Failed to get the attachment details from the attachments table")?
+        .ok_or(AttachmentError::NotFoundError)?;
+    let content = storage.get(&attachment.storage_key).await?;
+
+    // Only images are displayed by the browser, anything else is saved.
+    let disposition = if attachment.is_image() {
+        DispositionType::Inline
+    } else {
+        DispositionType::Attachment
+    };
+
+    Ok(HttpResponse::Ok()
+        .content_type(attachment.content_type)
+        .insert_header(ContentDisposition {
+            disposition,
+            parameters: vec![DispositionParam::Filename(attachment.file_name)],
+        })
+        .insert_header((X_CONTENT_TYPE_OPTIONS, "nosniff"))
+        .body(content))
+}
+
+/// Downloads the thumbnail of an image attached to a ticket.
+#[tracing::instrument(
+    name = "Downloading attachment thumbnail",
+    skip(pool, storage, user_id, path),
+    fields(
+        user_id=%&*user_id,
+        ticket_id=%path.0,
+        attachment_id=%path.1
+    )
+)]
+pub async fn download_attachment_thumbnail(
+    pool: web::Data<PgPool>,
+    storage: web::Data<dyn AttachmentStorage>,
+    user_id: web::ReqData<UserId>,
+    path: web::Path<(i32, Uuid)>,
+) -> Result<HttpResponse, AttachmentError> {
+    let (ticket_id, id) = path.into_inner();
+
+    // Tickets of other projects are treated as if they did not exist.
+    if !can_access_ticket(&pool, ticket_id, **user_id)
+        .await
+        .context("Failed to check the project membership")?
+    {
+        return Err(AttachmentError::NotFoundError);
+    }
+
+    let thumbnail_key = get_attachment(&pool, ticket_id, id)
+        .await
+        .context("Failed to get the attachment details from the attachments table")?
+        .and_then(|attachment| attachment.thumbnail_key)
+        .ok_or(AttachmentError::NotFoundError)?;
+    let content = storage.get(&thumbnail_key).await?;
+
+    Ok(HttpResponse::Ok()
+        .content_type(ContentType::png())
+        .insert_header((X_CONTENT_TYPE_OPTIONS, "nosniff"))
+        .body(content))
+}
@@ -0,0 +1,632 @@
+use crate::{
+    authentication::UserId,
+    domain::{
+        BoardColumn, BoardGrouping, NotificationKind, TicketChange, TicketEvent, TicketEventKind,
+        TicketRank, UserRole, ValidProject, ValidTicket,
+    },
+    helpers::{get_user_role, get_username},
+    routes::{
+        can_access_ticket, get_current_project, get_ticket_changes_since, insert_ticket_change,
+        lock_ticket, notify_ticket_creator, update_is_open, TicketError, TicketUpdate,
+    },
+    session_state::TypedSession,
+    startup::ReadPool,
+    ticket_events::publish_ticket_event,
+    utils::{e500, see_other},
+};
+use actix_web::{http::header::ContentType, web, HttpResponse};
+use actix_web_flash_messages::{FlashMessage, IncomingFlashMessages};
+use anyhow::Context;
+use askama::Template;
+use chrono::Utc;
+use serde::Deserialize;
+use sqlx::{PgPool, Postgres, Transaction};
+use std::{collections::HashMap, fmt::Write};
+use uuid::Uuid;
+
+/// Representation of the board template.
+#[derive(Template)]
+#[template(path = "board.html")]
+struct BoardTemplate {
+    msg_html: String,
+    project: Option<ValidProject>,
+    grouping: BoardGrouping,
+    columns: Vec<BoardColumnView>,
+    is_admin: bool,
+}
+
+/// Representation of a column of the board with its tickets.
+struct BoardColumnView {
+    column: BoardColumn,
+    tickets: Vec<ValidTicket>,
+    wip_limit: Option<i32>,
+}
+
+impl BoardColumnView {
+    /// Returns `true` if the column holds more tickets than its WIP limit.
+    fn is_over_limit(&self) -> bool {
+        self.wip_limit
+            .is_some_and(|wip_limit| self.tickets.len() > wip_limit as usize)
+    }
+}
+
+/// Representation of the grouping of the board sent with the query string.
+#[derive(Deserialize)]
+pub struct BoardQuery {
+    group: Option<String>,
+}
+
+/// Representation of a ticket moved on the board with form data.
+///
+/// The neighbours are the tickets displayed right above and right below
+/// the place where the ticket has been dropped.
+#[derive(Deserialize)]
+pub struct MoveTicketFormData {
+    column: String,
+    version: i32,
+    before_id: Option<i32>,
+    after_id: Option<i32>,
+}
+
+/// Representation of a WIP limit set with form data, empty to remove it.
+#[derive(Deserialize)]
+pub struct WipLimitFormData {
+    column: String,
+    wip_limit: String,
+}
+
+/// Returns the location of the board split by the given grouping.
+fn board_location(grouping: BoardGrouping) -> String {
+    format!("/dashboard/board?group={}", grouping.as_str())
+}
+
+/// Sees the board of the current project.
+#[tracing::instrument(
+    name = "Seeing board",
+    skip(pool, read_pool, session, flash_messages, user_id, query),
+    fields(
+        user_id=%&*user_id
+    )
+)]
+pub async fn see_board(
+    pool: web::Data<PgPool>,
+    read_pool: web::Data<ReadPool>,
+    session: TypedSession,
+    flash_messages: IncomingFlashMessages,
+    user_id: web::ReqData<UserId>,
+    query: web::Query<BoardQuery>,
+) -> Result<HttpResponse, actix_web::Error> {
+    // Get notification.
+    let mut msg_html = String::new();
+    for m in flash_messages.iter() {
+        writeln!(msg_html, "{}", m.content()).unwrap();
+    }
+
+    let grouping = query
+        .0
+        .group
+        .and_then(|group| BoardGrouping::try_from(group).ok())
+        .unwrap_or(BoardGrouping::Status);
+    let project = get_current_project(&pool, &session, **user_id)
+        .await
+        .map_err(e500)?;
+    let columns = match &project {
+        Some(project) => get_board_columns(&read_pool.0, project.id, grouping)
+            .await
+            .map_err(e500)?,
+        None => Vec::new(),
+    };
+    let is_admin = get_user_role(&pool, **user_id).await.map_err(e500)? == UserRole::Admin;
+
+    let body = BoardTemplate {
+        msg_html,
+        project,
+        grouping,
+        columns,
+        is_admin,
+    }
+    .render()
+    .unwrap();
+
+    Ok(HttpResponse::Ok()
+        .content_type(ContentType::html())
+        .body(body))
+}
+
+/// Returns the columns of the board of a project, with their tickets in rank order.
+async fn get_board_columns(
+    pool: &PgPool,
+    project_id: i32,
+    grouping: BoardGrouping,
+) -> Result<Vec<BoardColumnView>, anyhow::Error> {
+    let tickets = get_ranked_tickets(pool, project_id)
+        .await
+        .context("Failed to get the tickets details from the tickets table")?;
+    let wip_limits = get_wip_limits(pool, project_id)
+        .await
+        .context("Failed to get the WIP limits from the board_wip_limits table")?;
+
+    let mut columns: Vec<BoardColumnView> = grouping
+        .columns()
+        .iter()
+        .map(|column| BoardColumnView {
+            column: *column,
+            tickets: Vec::new(),
+            wip_limit: wip_limits.get(column.as_str()).copied(),
+        })
+        .collect();
+    for ticket in tickets {
+        if let Some(view) = columns
+            .iter_mut()
+            .find(|view| view.column.contains(&ticket))
+        {
+            view.tickets.push(ticket);
+        }
+    }
+
+    Ok(columns)
+}
+
+/// Returns the tickets of a project in rank order.
+///
+/// # Implementation Notes
+///
+/// Tickets dropped at the same place at the same time share a rank,
+/// their ids keep their order stable.
+#[tracing::instrument(name = "Getting ranked tickets from the tickets table", skip(pool))]
+async fn get_ranked_tickets(
+    pool: &PgPool,
+    project_id: i32,
+) -> Result<Vec<ValidTicket>, sqlx::Error> {
+    let tickets = sqlx::query_as!(
+        ValidTicket,
+        "SELECT * FROM tickets WHERE project_id = $1 ORDER BY rank, id",
+        project_id
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(tickets)
+}
+
+/// Moves a ticket on the board, performing the transition of its new column.
+#[tracing::instrument(
+    name = "Moving ticket",
+    skip(pool, form, user_id, ticket_id),
+    fields(
+        user_id=%&*user_id,
+        ticket_id=%ticket_id.0,
+        column=%form.column
+    )
+)]
+pub async fn move_ticket(
+    pool: web::Data<PgPool>,
+    form: web::Form<MoveTicketFormData>,
+    user_id: web::ReqData<UserId>,
+    ticket_id: web::Path<(i32,)>,
+) -> Result<HttpResponse, actix_web::Error> {
+    let ticket_id = ticket_id.into_inner().0;
+    let column = match BoardColumn::try_from(form.column.clone()) {
+        Ok(column) => column,
+        Err(e) => {
+            // Send notification.
+            FlashMessage::error(format!("Validation error: {}", e)).send();
+
+            return Ok(see_other(&board_location(BoardGrouping::Status)));
+        }
+    };
+    let location = board_location(column.grouping());
+
+    // Tickets of other projects are treated as if they did not exist.
+    let update = if can_access_ticket(&pool, ticket_id, **user_id)
+        .await
+        .context("Failed to check the project membership")
+        .map_err(e500)?
+    {
+        move_ticket_at_version(
+            &pool,
+            ticket_id,
+            form.version,
+            **user_id,
+            column,
+            (form.before_id, form.after_id),
+        )
+        .await
+        .map_err(e500)?
+    } else {
+        TicketUpdate::NotFound
+    };
+
+    match update {
+        TicketUpdate::Updated(ticket) => {
+            // Warn when the column now holds more tickets than its WIP limit.
+            let columns = get_board_columns(&pool, ticket.project_id, column.grouping())
+                .await
+                .map_err(e500)?;
+            if let Some(view) = columns
+                .iter()
+                .find(|view| view.column == column && view.is_over_limit())
+            {
+                // Send notification.
+                FlashMessage::warning(format!(
+                    "The column {} exceeds its WIP limit of {}.",
+                    column.as_str(),
+                    view.wip_limit.unwrap_or_default()
+                ))
+                .send();
+            }
+        }
+        TicketUpdate::Unchanged => {}
+        TicketUpdate::Rejected(e) => {
+            // Send notification.
+            FlashMessage::error(format!("Validation error: {}", e)).send();
+        }
+        TicketUpdate::NotFound => {
+            // Send notification.
+            FlashMessage::error("This ticket does not exist.").send();
+        }
+        TicketUpdate::Conflict(changes) => {
+            return Err(TicketError::Conflict(ticket_id, changes).into());
+        }
+    }
+
+    Ok(see_other(&location))
+}
+
+/// Moves a ticket between its neighbours of the given column, if nobody changed it since the version.
+///
+/// # Implementation Notes
+///
+/// Only the rank of the moved ticket is written, so concurrent moves of other tickets
+/// never conflict. The rank is not a change of the ticket and keeps its version,
+/// while the transition to another column increments it.
+pub async fn move_ticket_at_version(
+    pool: &PgPool,
+    id: i32,
+    version: i32,
+    user_id: Uuid,
+    column: BoardColumn,
+    (before_id, after_id): (Option<i32>, Option<i32>),
+) -> Result<TicketUpdate, anyhow::Error> {
+    let username = get_username(pool, user_id).await?;
+
+    let mut transaction = pool
+        .begin()
+        .await
+        .context("Failed to acquire a Postgres connection from the pool")?;
+
+    let ticket = match lock_ticket(&mut transaction, id)
+        .await
+        .context("Failed to lock the ticket from the tickets table")?
+    {
+        Some(ticket) => ticket,
+        None => return Ok(TicketUpdate::NotFound),
+    };
+
+    // Someone else has changed the ticket in the meantime.
+    if ticket.version != version {
+        let changes = get_ticket_changes_since(&mut transaction, id, version)
+            .await
+            .context("Failed to get the ticket changes from the ticket_changes table")?;
+
+        return Ok(TicketUpdate::Conflict(changes));
+    }
+
+    // Perform the transition to the new column.
+    let (field, old_value, new_value) = match column {
+        BoardColumn::Open | BoardColumn::Closed => {
+            let status = |is_open: bool| if is_open { "open" } else { "closed" };
+            (
+                "status",
+                status(ticket.is_open),
+                status(column == BoardColumn::Open),
+            )
+        }
+        _ => ("priority", ticket.priority.as_str(), column.as_str()),
+    };
+    let transition = (old_value != new_value).then(|| TicketChange {
+        ticket_id: id,
+        version: ticket.version + 1,
+        field: field.to_string(),
+        old_value: old_value.to_string(),
+        new_value: new_value.to_string(),
+        changed_by: username.clone(),
+        changed_at: Utc::now(),
+    });
+    if let Some(change) = &transition {
+        match column {
+            BoardColumn::Open | BoardColumn::Closed => {
+                update_is_open(&mut transaction, id, column == BoardColumn::Open)
+                    .await
+                    .context("Failed to update the `is_open` field from the tickets table")?;
+            }
+            _ => {
+                update_priority(&mut transaction, id, column.as_str())
+                    .await
+                    .context("Failed to update the `priority` field from the tickets table")?;
+            }
+        }
+        insert_ticket_change(&mut transaction, change)
+            .await
+            .context("Failed to insert the ticket change into the ticket_changes table")?;
+    }
+
+    // Rank the ticket between its neighbours.
+    let before = get_rank(&mut transaction, before_id, ticket.project_id)
+        .await
+        .context("Failed to get the rank of the ticket above")?;
+    let mut after = get_rank(&mut transaction, after_id, ticket.project_id)
+        .await
+        .context("Failed to get the rank of the ticket below")?;
+    // The neighbours have been moved in the meantime, rank the ticket after the one above.
+    if before.is_some() && after.is_some() && before >= after {
+        after = None;
+    }
+    let rank = TicketRank::between(before.as_deref(), after.as_deref());
+    let ticket = update_rank(&mut transaction, id, &rank)
+        .await
+        .context("Failed to update the `rank` field from the tickets table")?;
+
+    transaction
+        .commit()
+        .await
+        .context("Failed to commit SQL transaction to move the ticket")?;
+
+    // The ticket is moved from now on, failing to spread the news must not fail the request.
+    //
+    // Let the other users know that the ticket has changed.
+    let kind = match (&transition, column) {
+        (Some(_), BoardColumn::Closed) => TicketEventKind::Closed,
+        _ => TicketEventKind::Updated,
+    };
+    let event = TicketEvent {
+        ticket_id: id,
+        project_id: ticket.project_id,
+        kind,
+        triggered_by: user_id,
+    };
+    if let Err(e) = publish_ticket_event(pool, &event).await {
+        tracing::error!(
+            error.cause_chain = ?e,
+            error.message = %e,
+            "Failed to publish the ticket event"
+        );
+    }
+
+    // Notify the creator of the ticket.
+    if transition.is_some_and(|change| change.field == "status") {
+        let action = match column {
+            BoardColumn::Closed => "closed",
+            _ => "reopened",
+        };
+        let message = format!("{} {} the ticket {}.", username, action, ticket.key);
+        if let Err(e) =
+            notify_ticket_creator(pool, id, user_id, NotificationKind::StatusChange, &message).await
+        {
+            tracing::error!(
+                error.cause_chain = ?e,
+                error.message = %e,
+                "Failed to insert the notification into the notifications table"
+            );
+        }
+    }
+
+    Ok(TicketUpdate::Updated(Box::new(ticket)))
+}
+
+/// Returns the rank of a ticket of the project, if any.
+#[tracing::instrument(name = "Getting rank from the tickets table", skip(transaction))]
+async fn get_rank(
+    transaction: &mut Transaction<'_, Postgres>,
+    id: Option<i32>,
+    project_id: i32,
+) -> Result<Option<String>, sqlx::Error> {
+    let Some(id) = id else {
+        return Ok(None);
+    };
+
+    let row = sqlx::query!(
+        r#"SELECT rank FROM tickets WHERE id = $1 AND project_id = $2"#,
+        id,
+        project_id
+    )
+    .fetch_optional(transaction)
+    .await?;
+
+    Ok(row.map(|row| row.rank))
+}
+
+/// Updates the `priority` field from the tickets table.
+///
+/// # Implementation Notes
+///
+/// Every write increments the version of the ticket.
+#[tracing::instrument(
+    name = "Updating the `priority` field from the tickets table",
+    skip(transaction, id)
+)]
+async fn update_priority(
+    transaction: &mut Transaction<'_, Postgres>,
+    id: i32,
+    priority: &str,
+) -> Result<(), sqlx::Error> {
+    sqlx::query!(
+        r#"
+        UPDATE tickets
+        SET priority = $1, version = version + 1
+        WHERE id = $2
+        "#,
+        priority,
+        id
+    )
+    .execute(transaction)
+    .await?;
+
+    Ok(())
+}
+
+/// Updates the `rank` field from the tickets table and then return the new ticket.
+#[tracing::instrument(
+    name = "Updating the `rank` field from the tickets table",
+    skip(transaction, id)
+)]
+async fn update_rank(
+    transaction: &mut Transaction<'_, Postgres>,
+    id: i32,
+    rank: &TicketRank,
+) -> Result<ValidTicket, sqlx::Error> {
+    let ticket = sqlx::query_as!(
+        ValidTicket,
+        r#"
+        UPDATE tickets
+        SET rank = $1
+        WHERE id = $2
+        RETURNING *
+        "#,
+        rank.as_ref(),
+        id
+    )
+    .fetch_one(transaction)
+    .await?;
+
+    Ok(ticket)
+}
+
+/// Sets the WIP limit of a column of the board of the current project.
+#[tracing::instrument(
+    name = "Setting WIP limit",
+    skip(pool, session, form, user_id),
+    fields(
+        user_id=%&*user_id,
+        column=%form.column
+    )
+)]
+pub async fn set_wip_limit(
+    pool: web::Data<PgPool>,
+    session: TypedSession,
+    form: web::Form<WipLimitFormData>,
+    user_id: web::ReqData<UserId>,
+) -> Result<HttpResponse, actix_web::Error> {
+    let column = match BoardColumn::try_from(form.column.clone()) {
+        Ok(column) => column,
+        Err(e) => {
+            // Send notification.
+            FlashMessage::error(format!("Validation error: {}", e)).send();
+
+            return Ok(see_other(&board_location(BoardGrouping::Status)));
+        }
+    };
+    let location = board_location(column.grouping());
+
+    if get_user_role(&pool, **user_id).await.map_err(e500)? != UserRole::Admin {
+        // Send notification.
+        FlashMessage::error("Only administrators can set WIP limits.").send();
+
+        return Ok(see_other(&location));
+    }
+
+    let wip_limit = match form.wip_limit.trim() {
+        "" | "0" => None,
+        wip_limit => match wip_limit.parse::<i32>() {
+            Ok(wip_limit) if wip_limit > 0 => Some(wip_limit),
+            _ => {
+                // Send notification.
+                FlashMessage::error("Validation error: WIP limit must be a positive number.")
+                    .send();
+
+                return Ok(see_other(&location));
+            }
+        },
+    };
+
+    let Some(project) = get_current_project(&pool, &session, **user_id)
+        .await
+        .map_err(e500)?
+    else {
+        // Send notification.
+        FlashMessage::error("This project does not exist.").send();
+
+        return Ok(see_other(&location));
+    };
+
+    save_wip_limit(&pool, project.id, column, wip_limit)
+        .await
+        .context("Failed to save the WIP limit into the board_wip_limits table")
+        .map_err(e500)?;
+
+    // Send notification.
+    match wip_limit {
+        Some(wip_limit) => FlashMessage::info(format!(
+            "You have successfully set the WIP limit of the column {} to {}.",
+            column.as_str(),
+            wip_limit
+        ))
+        .send(),
+        None => FlashMessage::info(format!(
+            "You have successfully removed the WIP limit of the column {}.",
+            column.as_str()
+        ))
+        .send(),
+    }
+
+    Ok(see_other(&location))
+}
+
+/// Returns the WIP limits of the board of a project, by column.
+#[tracing::instrument(
+    name = "Getting WIP limits from the board_wip_limits table",
+    skip(pool)
+)]
+pub async fn get_wip_limits(
+    pool: &PgPool,
+    project_id: i32,
+) -> Result<HashMap<String, i32>, sqlx::Error> {
+    let rows = sqlx::query!(
+        r#"SELECT board_column, wip_limit FROM board_wip_limits WHERE project_id = $1"#,
+        project_id
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| (row.board_column, row.wip_limit))
+        .collect())
+}
+
+/// Saves the WIP limit of a column into the `board_wip_limits` table, removing it when `None`.
+#[tracing::instrument(name = "Saving WIP limit into the board_wip_limits table", skip(pool))]
+async fn save_wip_limit(
+    pool: &PgPool,
+    project_id: i32,
+    column: BoardColumn,
+    wip_limit: Option<i32>,
+) -> Result<(), sqlx::Error> {
+    match wip_limit {
+        Some(wip_limit) => {
+            sqlx::query!(
+                r#"
+                INSERT INTO board_wip_limits (project_id, board_column, wip_limit)
+                VALUES ($1, $2, $3)
+                ON CONFLICT (project_id, board_column) DO UPDATE SET wip_limit = EXCLUDED.wip_limit
+                "#,
+                project_id,
+                column.as_str(),
+                wip_limit
+            )
+            .execute(pool)
+            .await?;
+        }
+        None => {
+            sqlx::query!(
+                r#"DELETE FROM board_wip_limits WHERE project_id = $1 AND board_column = $2"#,
+                project_id,
+                column.as_str()
+            )
+            .execute(pool)
+            .await?;
+        }
+    }
+
+    Ok(())
+}
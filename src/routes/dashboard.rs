@@ -1,26 +1,250 @@
-use crate::{helpers::get_username, session_state::TypedSession, utils::e500};
+use crate::{
+    dashboard_cache::DashboardCache,
+    domain::{DailyActivity, DashboardMetrics, RecentTicket, ValidProject, ValidTicket},
+    helpers::get_username,
+    routes::get_current_project,
+    session_state::TypedSession,
+    utils::e500,
+};
 use actix_web::{http::header::ContentType, web, HttpResponse};
+use anyhow::Context;
 use askama::Template;
+use chrono::{Duration, NaiveDate, TimeZone, Utc};
 use sqlx::PgPool;
+use std::sync::Arc;
+
+/// Number of days covered by the activity of the dashboard.
+const ACTIVITY_DAYS: i64 = 30;
 
 /// Representation of the dashboard template.
 #[derive(Template)]
 #[template(path = "dashboard.html")]
 struct DashboardTemplate {
     username: String,
+    project: Option<ValidProject>,
+    metrics: Option<Arc<DashboardMetrics>>,
+    assigned_tickets: Vec<ValidTicket>,
 }
 
-/// Returns the dashboard of the application.
+/// Returns the dashboard of the application, with the metrics of the current project.
+#[tracing::instrument(name = "Seeing dashboard", skip(pool, session, cache))]
 pub async fn dashboard(
     pool: web::Data<PgPool>,
     session: TypedSession,
+    cache: web::Data<DashboardCache>,
 ) -> Result<HttpResponse, actix_web::Error> {
     let user_id = session.get_user_id().map_err(e500)?.unwrap();
     let username = get_username(&pool, user_id).await.map_err(e500)?;
 
-    let body = DashboardTemplate { username }.render().unwrap();
+    let project = get_current_project(&pool, &session, user_id)
+        .await
+        .map_err(e500)?;
+    let (metrics, assigned_tickets) = match &project {
+        Some(project) => {
+            let metrics = match cache.get(project.id) {
+                Some(metrics) => metrics,
+                None => {
+                    let metrics = get_dashboard_metrics(&pool, project.id, Utc::now().date_naive())
+                        .await
+                        .map_err(e500)?;
+                    cache.insert(project.id, metrics)
+                }
+            };
+            let assigned_tickets = get_assigned_tickets(&pool, project.id, &username)
+                .await
+                .context("Failed to get the assigned tickets from the tickets table")
+                .map_err(e500)?;
+
+            (Some(metrics), assigned_tickets)
+        }
+        None => (None, Vec::new()),
+    };
+
+    let body = DashboardTemplate {
+        username,
+        project,
+        metrics,
+        assigned_tickets,
+    }
+    .render()
+    .unwrap();
 
     Ok(HttpResponse::Ok()
         .content_type(ContentType::html())
         .body(body))
 }
+
+/// Returns the metrics of the project, computed with one aggregate query each.
+#[tracing::instrument(name = "Computing dashboard metrics", skip(pool))]
+pub async fn get_dashboard_metrics(
+    pool: &PgPool,
+    project_id: i32,
+    today: NaiveDate,
+) -> Result<DashboardMetrics, anyhow::Error> {
+    let first_day = today - Duration::days(ACTIVITY_DAYS - 1);
+
+    let (counts, daily_activity, median_seconds, recently_updated) = tokio::try_join!(
+        get_status_priority_counts(pool, project_id),
+        get_daily_activity(pool, project_id, first_day, today),
+        get_median_seconds_to_close(pool, project_id, first_day),
+        get_recently_updated_tickets(pool, project_id),
+    )
+    .context("Failed to compute the dashboard metrics from the tickets table")?;
+
+    Ok(DashboardMetrics {
+        priority_counts: DashboardMetrics::count_by_priority(&counts),
+        daily_activity,
+        median_time_to_close: median_seconds.map(|s| Duration::seconds(s as i64)),
+        recently_updated,
+        computed_at: Utc::now(),
+    })
+}
+
+/// Returns the number of tickets of the project by status and priority.
+async fn get_status_priority_counts(
+    pool: &PgPool,
+    project_id: i32,
+) -> Result<Vec<(bool, String, i64)>, sqlx::Error> {
+    let rows = sqlx::query!(
+        r#"
+        SELECT is_open, priority, COUNT(*) AS "count!"
+        FROM tickets
+        WHERE project_id = $1
+        GROUP BY is_open, priority
+        "#,
+        project_id
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| (row.is_open, row.priority, row.count))
+        .collect())
+}
+
+/// Returns the number of tickets of the project created and closed during each day of the period.
+pub async fn get_daily_activity(
+    pool: &PgPool,
+    project_id: i32,
+    first_day: NaiveDate,
+    last_day: NaiveDate,
+) -> Result<Vec<DailyActivity>, sqlx::Error> {
+    let since = Utc.from_utc_datetime(&first_day.and_hms_opt(0, 0, 0).unwrap());
+    let activity = sqlx::query_as!(
+        DailyActivity,
+        r#"
+        WITH created AS (
+            SELECT (created_at AT TIME ZONE 'UTC')::DATE AS day, COUNT(*) AS count
+            FROM tickets
+            WHERE project_id = $1 AND created_at >= $2
+            GROUP BY 1
+        ), closed AS (
+            SELECT (closed_at AT TIME ZONE 'UTC')::DATE AS day, COUNT(*) AS count
+            FROM tickets
+            WHERE project_id = $1 AND closed_at >= $2 AND is_open = FALSE
+            GROUP BY 1
+        )
+        SELECT days.day::DATE AS "day!",
+            COALESCE(created.count, 0) AS "created!",
+            COALESCE(closed.count, 0) AS "closed!"
+        FROM generate_series($3::DATE, $4::DATE, INTERVAL '1 day') AS days (day)
+        LEFT JOIN created ON created.day = days.day::DATE
+        LEFT JOIN closed ON closed.day = days.day::DATE
+        ORDER BY 1
+        "#,
+        project_id,
+        since,
+        first_day,
+        last_day
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(activity)
+}
+
+/// Returns the median time to close of the tickets of the project closed since the given day, in seconds.
+async fn get_median_seconds_to_close(
+    pool: &PgPool,
+    project_id: i32,
+    first_day: NaiveDate,
+) -> Result<Option<f64>, sqlx::Error> {
+    let since = Utc.from_utc_datetime(&first_day.and_hms_opt(0, 0, 0).unwrap());
+    let row = sqlx::query!(
+        r#"
+        SELECT percentile_cont(0.5) WITHIN GROUP (
+            ORDER BY EXTRACT(EPOCH FROM closed_at - created_at)
+        ) AS median_seconds
+        FROM tickets
+        WHERE project_id = $1 AND is_open = FALSE AND closed_at >= $2
+        "#,
+        project_id,
+        since
+    )
+    .fetch_one(pool)
+    .await?;
+
+    Ok(row.median_seconds)
+}
+
+/// Returns the tickets of the project that have been created or changed most recently.
+async fn get_recently_updated_tickets(
+    pool: &PgPool,
+    project_id: i32,
+) -> Result<Vec<RecentTicket>, sqlx::Error> {
+    let tickets = sqlx::query_as!(
+        RecentTicket,
+        r#"
+        SELECT tickets.key, tickets.title, tickets.is_open, activity.updated_at AS "updated_at!"
+        FROM (
+            SELECT ticket_id, MAX(updated_at) AS updated_at
+            FROM (
+                SELECT ticket_changes.ticket_id, ticket_changes.changed_at AS updated_at
+                FROM ticket_changes
+                JOIN tickets ON tickets.id = ticket_changes.ticket_id
+                WHERE tickets.project_id = $1
+                UNION ALL
+                SELECT id, created_at
+                FROM tickets
+                WHERE project_id = $1
+            ) AS events
+            GROUP BY ticket_id
+            ORDER BY 2 DESC
+            LIMIT 10
+        ) AS activity
+        JOIN tickets ON tickets.id = activity.ticket_id
+        ORDER BY activity.updated_at DESC
+        "#,
+        project_id
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(tickets)
+}
+
+/// Returns the open tickets of the project assigned to the user, the most urgent first.
+#[tracing::instrument(name = "Getting assigned tickets", skip(pool))]
+async fn get_assigned_tickets(
+    pool: &PgPool,
+    project_id: i32,
+    username: &str,
+) -> Result<Vec<ValidTicket>, sqlx::Error> {
+    let tickets = sqlx::query_as!(
+        ValidTicket,
+        r#"
+        SELECT *
+        FROM tickets
+        WHERE project_id = $1 AND assigned_to = $2 AND is_open = TRUE
+        ORDER BY due_on NULLS LAST, rank, id
+        LIMIT 10
+        "#,
+        project_id,
+        username
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(tickets)
+}
@@ -1,6 +1,100 @@
-use actix_web::HttpResponse;
+use crate::{configuration::ApplicationSettings, shutdown::Shutdown};
+use actix_web::{web, HttpResponse};
+use serde::Serialize;
+use sqlx::PgPool;
+use std::{future::Future, time::Instant};
 
-/// Checks if the application is up and ready to accept incoming requests.
+/// Representation of the state of a dependency of the application.
+#[derive(Serialize)]
+struct DependencyCheck {
+    is_healthy: bool,
+    latency_ms: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+/// Representation of the readiness of the application.
+#[derive(Serialize)]
+struct Readiness {
+    is_ready: bool,
+    is_shutting_down: bool,
+    postgres: DependencyCheck,
+    redis: DependencyCheck,
+}
+
+/// Checks if the application is up, without checking its dependencies.
 pub async fn health_check() -> HttpResponse {
     HttpResponse::Ok().finish()
 }
+
+/// Checks if the application can reach Postgres and Redis
+/// and is therefore ready to accept incoming requests.
+///
+/// # Implementation Notes
+///
+/// Every dependency is checked concurrently and given up after the readiness timeout,
+/// a `503 Service Unavailable` is returned as soon as one of them is unhealthy
+/// or once the application is shutting down.
+#[tracing::instrument(
+    name = "Checking readiness",
+    skip(pool, redis_client, settings, shutdown)
+)]
+pub async fn ready(
+    pool: web::Data<PgPool>,
+    redis_client: web::Data<redis::Client>,
+    settings: web::Data<ApplicationSettings>,
+    shutdown: web::Data<Shutdown>,
+) -> HttpResponse {
+    let timeout = settings.readiness_timeout();
+    let (postgres, redis) = tokio::join!(
+        check_dependency(timeout, async {
+            sqlx::query("SELECT 1")
+                .execute(pool.get_ref())
+                .await
+                .map(|_| ())
+                .map_err(|e| e.to_string())
+        }),
+        check_dependency(timeout, async {
+            let mut connection = redis_client
+                .get_async_connection()
+                .await
+                .map_err(|e| e.to_string())?;
+            redis::cmd("PING")
+                .query_async::<_, String>(&mut connection)
+                .await
+                .map(|_| ())
+                .map_err(|e| e.to_string())
+        }),
+    );
+
+    let is_shutting_down = shutdown.is_triggered();
+    let readiness = Readiness {
+        is_ready: !is_shutting_down && postgres.is_healthy && redis.is_healthy,
+        is_shutting_down,
+        postgres,
+        redis,
+    };
+    if readiness.is_ready {
+        HttpResponse::Ok().json(readiness)
+    } else {
+        HttpResponse::ServiceUnavailable().json(readiness)
+    }
+}
+
+/// Runs the check of a dependency and then return its outcome and its latency.
+async fn check_dependency(
+    timeout: std::time::Duration,
+    check: impl Future<Output = Result<(), String>>,
+) -> DependencyCheck {
+    let started_at = Instant::now();
+    let outcome = match tokio::time::timeout(timeout, check).await {
+        Ok(outcome) => outcome,
+        Err(_) => Err(format!("timed out after {} ms", timeout.as_millis())),
+    };
+
+    DependencyCheck {
+        is_healthy: outcome.is_ok(),
+        latency_ms: started_at.elapsed().as_secs_f64() * 1000.0,
+        error: outcome.err(),
+    }
+}
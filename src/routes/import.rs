@@ -0,0 +1,426 @@
+use crate::{
+    authentication::{compute_password_hash, UserId},
+    configuration::ImportSettings,
+    domain::{ColumnMapping, ImportError, ImportFormat, ImportedTicket, TicketImport, UserRole},
+    helpers::get_user_role,
+    routes::{get_current_project, reserve_ticket_key},
+    session_state::TypedSession,
+    telemetry::spawn_blocking_with_tracing,
+    utils::{e500, see_other},
+};
+use actix_multipart::Multipart;
+use actix_web::{http::header::ContentType, web, HttpResponse};
+use actix_web_flash_messages::{FlashMessage, IncomingFlashMessages};
+use anyhow::Context;
+use askama::Template;
+use chrono::Utc;
+use futures_util::StreamExt;
+use secrecy::{ExposeSecret, Secret};
+use sqlx::{PgPool, Postgres, Transaction};
+use std::{collections::HashMap, fmt::Write};
+use uuid::Uuid;
+
+/// Maximum number of fields of the import form, which has 8 of them.
+const MAX_FORM_FIELDS: usize = 16;
+
+/// Id of the placeholder user, telling it apart from a user registered under its username.
+const PLACEHOLDER_USER_ID: Uuid = Uuid::from_u128(1);
+
+/// Representation of the outcome of an import, or of its dry run.
+struct ImportReport {
+    is_dry_run: bool,
+    valid_tickets: usize,
+    errors: Vec<ImportError>,
+    unknown_authors: Vec<String>,
+}
+
+/// Representation of the import page.
+#[derive(Template)]
+#[template(path = "import.html")]
+struct ImportTemplate {
+    msg_html: String,
+    is_admin: bool,
+    format: ImportFormat,
+    mapping: ColumnMapping,
+    placeholder_username: String,
+    report: Option<ImportReport>,
+}
+
+/// Returns the import form of the application.
+#[tracing::instrument(
+    name = "Seeing import form",
+    skip(pool, settings, flash_messages, user_id),
+    fields(
+        user_id=%&*user_id
+    )
+)]
+pub async fn import_form(
+    pool: web::Data<PgPool>,
+    settings: web::Data<ImportSettings>,
+    flash_messages: IncomingFlashMessages,
+    user_id: web::ReqData<UserId>,
+) -> Result<HttpResponse, actix_web::Error> {
+    // Get notification.
+    let mut msg_html = String::new();
+    for m in flash_messages.iter() {
+        writeln!(msg_html, "{}", m.content()).unwrap();
+    }
+
+    let is_admin = get_user_role(&pool, **user_id).await.map_err(e500)? == UserRole::Admin;
+    let body = ImportTemplate {
+        msg_html,
+        is_admin,
+        format: ImportFormat::Csv,
+        mapping: ColumnMapping::default(),
+        placeholder_username: settings.placeholder_username.clone(),
+        report: None,
+    }
+    .render()
+    .unwrap();
+
+    Ok(HttpResponse::Ok()
+        .content_type(ContentType::html())
+        .body(body))
+}
+
+/// Imports tickets into the current project from a CSV file or a GitHub issues export.
+///
+/// # Implementation Notes
+///
+/// Every row is validated first and nothing is imported while one of them is invalid,
+/// a dry run stops after the validation. The tickets are then imported in a single
+/// transaction, the unknown authors being replaced by a placeholder user.
+#[tracing::instrument(
+    name = "Importing tickets",
+    skip(pool, settings, session, payload, user_id),
+    fields(
+        user_id=%&*user_id
+    )
+)]
+pub async fn import_tickets(
+    pool: web::Data<PgPool>,
+    settings: web::Data<ImportSettings>,
+    session: TypedSession,
+    payload: Multipart,
+    user_id: web::ReqData<UserId>,
+) -> Result<HttpResponse, actix_web::Error> {
+    if get_user_role(&pool, **user_id).await.map_err(e500)? != UserRole::Admin {
+        // Send notification.
+        FlashMessage::error("Only administrators can import tickets.").send();
+
+        return Ok(see_other("/dashboard/import"));
+    }
+
+    let mut fields = match read_import_form(payload, settings.max_size_bytes).await {
+        Ok(fields) => fields,
+        Err(e) => {
+            // Send notification.
+            FlashMessage::error(format!("Validation error: {}", e)).send();
+
+            return Ok(see_other("/dashboard/import"));
+        }
+    };
+    let mut field = |name: &str| fields.remove(name).unwrap_or_default();
+    let is_dry_run = field("mode") != "import";
+    let content = field("file");
+    let parsed = ImportFormat::try_from(field("format")).and_then(|format| {
+        let mapping = ColumnMapping::parse(
+            field("title_column"),
+            field("description_column"),
+            field("priority_column"),
+            field("author_column"),
+            field("status_column"),
+        )?;
+        let import = TicketImport::parse(format, &content, &mapping)?;
+
+        Ok((format, mapping, import))
+    });
+    let (format, mapping, import) = match parsed {
+        Ok(parsed) => parsed,
+        Err(e) => {
+            // Send notification.
+            FlashMessage::error(format!("Validation error: {}", e)).send();
+
+            return Ok(see_other("/dashboard/import"));
+        }
+    };
+
+    let Some(project) = get_current_project(&pool, &session, **user_id)
+        .await
+        .map_err(e500)?
+    else {
+        // Send notification.
+        FlashMessage::error("You are not a member of any project yet.").send();
+
+        return Ok(see_other("/dashboard/import"));
+    };
+
+    let authors = import.authors();
+    let known_authors = get_existing_usernames(&pool, &authors)
+        .await
+        .context("Failed to get the authors from the users table")
+        .map_err(e500)?;
+    let unknown_authors = authors
+        .into_iter()
+        .filter(|author| !known_authors.contains(author))
+        .collect();
+
+    let msg_html = if is_dry_run {
+        format!(
+            "Dry run: {} tickets can be imported into the project {}, {} rows have errors.",
+            import.tickets.len(),
+            project.key,
+            import.errors.len()
+        )
+    } else if !import.errors.is_empty() {
+        format!(
+            "Nothing has been imported, fix the {} rows with errors first.",
+            import.errors.len()
+        )
+    } else if needs_placeholder(&import.tickets, &known_authors)
+        && !is_placeholder_username_available(&pool, &settings.placeholder_username)
+            .await
+            .context("Failed to check the placeholder user in the users table")
+            .map_err(e500)?
+    {
+        format!(
+            "Nothing has been imported, the placeholder username {} belongs to another user.",
+            settings.placeholder_username
+        )
+    } else {
+        insert_imported_tickets(
+            &pool,
+            project.id,
+            &import.tickets,
+            &known_authors,
+            &settings.placeholder_username,
+        )
+        .await
+        .map_err(e500)?;
+
+        format!(
+            "You have successfully imported {} tickets into the project {}.",
+            import.tickets.len(),
+            project.key
+        )
+    };
+
+    let body = ImportTemplate {
+        msg_html,
+        is_admin: true,
+        format,
+        mapping,
+        placeholder_username: settings.placeholder_username.clone(),
+        report: Some(ImportReport {
+            is_dry_run,
+            valid_tickets: import.tickets.len(),
+            errors: import.errors,
+            unknown_authors,
+        }),
+    }
+    .render()
+    .unwrap();
+
+    Ok(HttpResponse::Ok()
+        .content_type(ContentType::html())
+        .body(body))
+}
+
+/// Reads the import form and then return its fields, the file being read as text.
+async fn read_import_form(
+    mut payload: Multipart,
+    max_size_bytes: usize,
+) -> Result<HashMap<String, String>, String> {
+    let mut fields = HashMap::new();
+
+    while let Some(field) = payload.next().await {
+        let mut field = field.map_err(|e| format!("malformed form data: {}.", e))?;
+        if fields.len() == MAX_FORM_FIELDS {
+            return Err(format!(
+                "the form cannot have more than {} fields.",
+                MAX_FORM_FIELDS
+            ));
+        }
+        let name = field.name().to_string();
+
+        let mut content = Vec::new();
+        while let Some(chunk) = field.next().await {
+            let chunk = chunk.map_err(|e| format!("malformed form data: {}.", e))?;
+            if content.len() + chunk.len() > max_size_bytes {
+                return Err(format!(
+                    "the file cannot be larger than {} bytes.",
+                    max_size_bytes
+                ));
+            }
+            content.extend_from_slice(&chunk);
+        }
+
+        let value =
+            String::from_utf8(content).map_err(|_| format!("`{}` must be valid UTF-8.", name))?;
+        fields.insert(name, value);
+    }
+
+    Ok(fields)
+}
+
+/// Returns the usernames of the list that belong to existing users.
+async fn get_existing_usernames(
+    pool: &PgPool,
+    usernames: &[String],
+) -> Result<Vec<String>, sqlx::Error> {
+    let rows = sqlx::query!(
+        r#"SELECT username FROM users WHERE username = ANY($1)"#,
+        usernames
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows.into_iter().map(|row| row.username).collect())
+}
+
+/// Inserts the imported tickets into the `tickets` table, all or none of them.
+#[tracing::instrument(
+    name = "Inserting the imported tickets into the tickets table",
+    skip(pool, tickets, known_authors)
+)]
+async fn insert_imported_tickets(
+    pool: &PgPool,
+    project_id: i32,
+    tickets: &[ImportedTicket],
+    known_authors: &[String],
+    placeholder_username: &str,
+) -> Result<(), anyhow::Error> {
+    let mut transaction = pool
+        .begin()
+        .await
+        .context("Failed to acquire a Postgres connection from the pool")?;
+
+    if needs_placeholder(tickets, known_authors) {
+        insert_placeholder_user(&mut transaction, placeholder_username).await?;
+    }
+
+    let now = Utc::now();
+    for ticket in tickets {
+        let created_by = ticket
+            .author
+            .as_ref()
+            .filter(|author| known_authors.contains(author))
+            .map_or(placeholder_username, String::as_str);
+        let (ticket_key, rank) = reserve_ticket_key(&mut transaction, project_id)
+            .await
+            .context("Failed to reserve a key from the projects table")?;
+
+        sqlx::query!(
+            r#"
+            INSERT INTO tickets (title, description, created_at, created_by, is_open, priority, version, project_id, key, rank, closed_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+            "#,
+            ticket.title.as_ref(),
+            ticket.description.as_ref(),
+            ticket.created_at.unwrap_or(now),
+            created_by,
+            ticket.is_open,
+            ticket.priority,
+            1,
+            project_id,
+            ticket_key,
+            rank.as_ref(),
+            ticket
+                .closed_at
+                .or_else(|| (!ticket.is_open).then_some(now)),
+        )
+        .execute(&mut transaction)
+        .await
+        .context("Failed to insert the imported ticket into the tickets table")?;
+    }
+
+    transaction
+        .commit()
+        .await
+        .context("Failed to commit SQL transaction to store the imported tickets")?;
+
+    Ok(())
+}
+
+/// Returns `true` if one of the tickets has no author or an unknown one.
+fn needs_placeholder(tickets: &[ImportedTicket], known_authors: &[String]) -> bool {
+    tickets.iter().any(|ticket| {
+        ticket
+            .author
+            .as_ref()
+            .map_or(true, |author| !known_authors.contains(author))
+    })
+}
+
+/// Returns `true` if the username is free or already belongs to the placeholder user,
+/// and if the placeholder user does not exist under another username.
+async fn is_placeholder_username_available(
+    pool: &PgPool,
+    username: &str,
+) -> Result<bool, sqlx::Error> {
+    let row = sqlx::query!(
+        r#"
+        SELECT NOT EXISTS (
+            SELECT 1 FROM users WHERE (username = $1) <> (user_id = $2)
+        ) AS "is_available!"
+        "#,
+        username,
+        PLACEHOLDER_USER_ID
+    )
+    .fetch_one(pool)
+    .await?;
+
+    Ok(row.is_available)
+}
+
+/// Inserts the placeholder user into the `users` table, unless it exists already.
+///
+/// # Implementation Notes
+///
+/// Its password is random and never shown, nobody can log in as the placeholder user.
+/// The tickets are never attributed to a user who registered the placeholder username.
+async fn insert_placeholder_user(
+    transaction: &mut Transaction<'_, Postgres>,
+    username: &str,
+) -> Result<(), anyhow::Error> {
+    let password = Secret::new(Uuid::new_v4().to_string());
+    let password_hash = spawn_blocking_with_tracing(move || compute_password_hash(password))
+        .await?
+        .context("Failed to hash password")?;
+
+    sqlx::query!(
+        r#"
+        INSERT INTO users (user_id, username, password_hash)
+        VALUES ($1, $2, $3)
+        ON CONFLICT DO NOTHING
+        "#,
+        PLACEHOLDER_USER_ID,
+        username,
+        password_hash.expose_secret(),
+    )
+    .execute(&mut *transaction)
+    .await
+    .context("Failed to insert the placeholder user into the users table")?;
+
+    let is_placeholder = sqlx::query!(
+        r#"
+        SELECT EXISTS (
+            SELECT 1 FROM users WHERE user_id = $1 AND username = $2
+        ) AS "is_placeholder!"
+        "#,
+        PLACEHOLDER_USER_ID,
+        username
+    )
+    .fetch_one(&mut *transaction)
+    .await
+    .context("Failed to get the placeholder user from the users table")?
+    .is_placeholder;
+    if !is_placeholder {
+        anyhow::bail!(
+            "The placeholder username {} belongs to another user",
+            username
+        );
+    }
+
+    Ok(())
+}
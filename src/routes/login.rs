@@ -1,6 +1,8 @@
 use crate::{
     authentication::{validate_credentials, AuthError, Credentials},
+    correlation_id::CorrelationId,
     error::error_chain_fmt,
+    metrics::Metrics,
     session_state::TypedSession,
 };
 use actix_web::{error::InternalError, http, web, HttpResponse, ResponseError};
@@ -71,6 +73,7 @@ pub async fn login_form(flash_messages: IncomingFlashMessages) -> HttpResponse {
         user_id=tracing::field::Empty
     )
 )]
+#[allow(clippy::needless_borrows_for_generic_args)]
 pub async fn login(
     pool: web::Data<PgPool>,
     session: TypedSession,
@@ -85,7 +88,20 @@ pub async fn login(
     tracing::Span::current().record("username", &tracing::field::display(&credentials.username));
 
     // Check if the credentials belong to an existing user.
-    match validate_credentials(&pool, credentials).await {
+    let outcome = validate_credentials(&pool, credentials).await;
+
+    // Count the login attempt.
+    let outcome_label = match &outcome {
+        Ok(_) => "success",
+        Err(AuthError::InvalidCredentials(_)) => "failure",
+        Err(AuthError::UnexpectedError(_)) => "error",
+    };
+    Metrics::global()
+        .logins
+        .with_label_values(&[outcome_label])
+        .inc();
+
+    match outcome {
         Ok(user_id) => {
             // Record the id of the user.
             tracing::Span::current().record("user_id", &tracing::field::display(&user_id));
@@ -115,8 +131,14 @@ pub async fn login(
 
 // Redirects to the login page with a notification as an error message.
 fn login_redirect(e: LoginError) -> InternalError<LoginError> {
-    // Send notification.
-    FlashMessage::error(e.to_string()).send();
+    // Send notification, along with the correlation id of the request for unexpected errors.
+    let message = match (&e, CorrelationId::current()) {
+        (LoginError::UnexpectedError(_), Some(correlation_id)) => {
+            format!("{} Request id: {}.", e, correlation_id)
+        }
+        _ => e.to_string(),
+    };
+    FlashMessage::error(message).send();
 
     let response = HttpResponse::SeeOther()
         .insert_header((http::header::LOCATION, "/login"))
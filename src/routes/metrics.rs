@@ -0,0 +1,12 @@
+use crate::{metrics::Metrics, utils::e500};
+use actix_web::{web, HttpResponse};
+use sqlx::PgPool;
+
+/// Returns the metrics of the application in the Prometheus text format.
+pub async fn export_metrics(pool: web::Data<PgPool>) -> Result<HttpResponse, actix_web::Error> {
+    let body = Metrics::global().render(&pool).await.map_err(e500)?;
+
+    Ok(HttpResponse::Ok()
+        .content_type(prometheus::TEXT_FORMAT)
+        .body(body))
+}
@@ -0,0 +1,550 @@
+use crate::{
+    authentication::UserId,
+    domain::{Changelog, MilestoneName, NewMilestone, TicketDueDate, ValidMilestone, ValidTicket},
+    markdown::render_markdown,
+    routes::{get_current_project, is_project_member},
+    session_state::TypedSession,
+    startup::ReadPool,
+    utils::{e500, see_other},
+};
+use actix_web::{
+    http::header::{ContentDisposition, ContentType, DispositionParam, DispositionType},
+    web, HttpResponse,
+};
+use actix_web_flash_messages::{FlashMessage, IncomingFlashMessages};
+use anyhow::Context;
+use askama::Template;
+use chrono::Utc;
+use serde::Deserialize;
+use sqlx::PgPool;
+use std::fmt::Write;
+use uuid::Uuid;
+
+/// Representation of a milestone with the progress of its tickets.
+struct MilestoneOverview {
+    milestone: ValidMilestone,
+    open_count: usize,
+    closed_count: usize,
+}
+
+/// Representation of the milestones template.
+#[derive(Template)]
+#[template(path = "milestones.html")]
+struct MilestonesTemplate {
+    msg_html: String,
+    milestones: Vec<MilestoneOverview>,
+    has_project: bool,
+}
+
+/// Representation of the milestone template.
+#[derive(Template)]
+#[template(path = "milestone.html")]
+struct MilestoneTemplate {
+    msg_html: String,
+    overview: MilestoneOverview,
+    tickets: Vec<ValidTicket>,
+}
+
+/// Representation of the changelog template.
+#[derive(Template)]
+#[template(path = "changelog.html")]
+struct ChangelogTemplate {
+    milestone: ValidMilestone,
+    changelog: Changelog,
+    changelog_html: String,
+}
+
+/// Representation of a new milestone created with form data.
+#[derive(Deserialize)]
+pub struct NewMilestoneFormData {
+    name: String,
+    #[serde(default)]
+    target_date: String,
+}
+
+impl TryFrom<NewMilestoneFormData> for NewMilestone {
+    type Error = String;
+
+    /// Performs the conversion.
+    fn try_from(value: NewMilestoneFormData) -> Result<Self, Self::Error> {
+        let name = MilestoneName::parse(value.name)?;
+        let target_date = TicketDueDate::parse(&value.target_date)?;
+
+        Ok(Self { name, target_date })
+    }
+}
+
+/// Sees the milestones of the current project.
+#[tracing::instrument(
+    name = "Seeing milestones",
+    skip(pool, read_pool, session, flash_messages, user_id),
+    fields(
+        user_id=%&*user_id
+    )
+)]
+pub async fn see_milestones(
+    pool: web::Data<PgPool>,
+    read_pool: web::Data<ReadPool>,
+    session: TypedSession,
+    flash_messages: IncomingFlashMessages,
+    user_id: web::ReqData<UserId>,
+) -> Result<HttpResponse, actix_web::Error> {
+    // Get notification.
+    let mut msg_html = String::new();
+    for m in flash_messages.iter() {
+        writeln!(msg_html, "{}", m.content()).unwrap();
+    }
+
+    let project = get_current_project(&pool, &session, **user_id)
+        .await
+        .map_err(e500)?;
+    let milestones = match &project {
+        Some(project) => get_project_milestone_overviews(&read_pool.0, project.id)
+            .await
+            .context("Failed to get the milestones details from the milestones table")
+            .map_err(e500)?,
+        None => Vec::new(),
+    };
+
+    let body = MilestonesTemplate {
+        msg_html,
+        milestones,
+        has_project: project.is_some(),
+    }
+    .render()
+    .unwrap();
+
+    Ok(HttpResponse::Ok()
+        .content_type(ContentType::html())
+        .body(body))
+}
+
+/// Creates a new milestone in the current project.
+#[tracing::instrument(
+    name = "Creating a new milestone",
+    skip(pool, session, form, user_id),
+    fields(
+        milestone_name = %form.name,
+        user_id = %&*user_id
+    )
+)]
+pub async fn create_milestone(
+    pool: web::Data<PgPool>,
+    session: TypedSession,
+    form: web::Form<NewMilestoneFormData>,
+    user_id: web::ReqData<UserId>,
+) -> Result<HttpResponse, actix_web::Error> {
+    let new_milestone: NewMilestone = match form.0.try_into() {
+        Ok(new_milestone) => new_milestone,
+        Err(e) => {
+            // Send notification.
+            FlashMessage::error(format!("Validation error: {}", e)).send();
+
+            return Ok(see_other("/dashboard/milestones"));
+        }
+    };
+
+    let Some(project) = get_current_project(&pool, &session, **user_id)
+        .await
+        .map_err(e500)?
+    else {
+        // Send notification.
+        FlashMessage::error("You are not a member of any project yet.").send();
+
+        return Ok(see_other("/dashboard/milestones"));
+    };
+
+    match insert_milestone(&pool, project.id, &new_milestone).await {
+        Ok(()) => {}
+        Err(sqlx::Error::Database(e)) if e.code().as_deref() == Some("23505") => {
+            // Send notification.
+            FlashMessage::error("This milestone name is already taken.").send();
+
+            return Ok(see_other("/dashboard/milestones"));
+        }
+        Err(e) => return Err(e500(e)),
+    }
+
+    // Send notification.
+    FlashMessage::info(format!(
+        "You have successfully created the milestone {}.",
+        new_milestone.name.as_ref()
+    ))
+    .send();
+
+    Ok(see_other("/dashboard/milestones"))
+}
+
+/// Sees a milestone with the progress of its tickets.
+#[tracing::instrument(
+    name = "Seeing milestone",
+    skip(pool, flash_messages, user_id, milestone_id),
+    fields(
+        user_id=%&*user_id,
+        milestone_id=%milestone_id.0
+    )
+)]
+pub async fn see_milestone(
+    pool: web::Data<PgPool>,
+    flash_messages: IncomingFlashMessages,
+    user_id: web::ReqData<UserId>,
+    milestone_id: web::Path<(i32,)>,
+) -> Result<HttpResponse, actix_web::Error> {
+    // Get notification.
+    let mut msg_html = String::new();
+    for m in flash_messages.iter() {
+        writeln!(msg_html, "{}", m.content()).unwrap();
+    }
+
+    let Some(milestone) = get_accessible_milestone(&pool, milestone_id.0, **user_id).await? else {
+        return Ok(see_other("/dashboard/milestones"));
+    };
+    let (overview, tickets) = get_milestone_overview(&pool, milestone).await?;
+
+    let body = MilestoneTemplate {
+        msg_html,
+        overview,
+        tickets,
+    }
+    .render()
+    .unwrap();
+
+    Ok(HttpResponse::Ok()
+        .content_type(ContentType::html())
+        .body(body))
+}
+
+/// Closes a milestone.
+#[tracing::instrument(
+    name = "Closing milestone",
+    skip(pool, user_id, milestone_id),
+    fields(
+        user_id=%&*user_id,
+        milestone_id=%milestone_id.0
+    )
+)]
+pub async fn close_milestone(
+    pool: web::Data<PgPool>,
+    user_id: web::ReqData<UserId>,
+    milestone_id: web::Path<(i32,)>,
+) -> Result<HttpResponse, actix_web::Error> {
+    change_milestone_status(&pool, milestone_id.0, **user_id, false).await
+}
+
+/// Reopens a milestone.
+#[tracing::instrument(
+    name = "Reopening milestone",
+    skip(pool, user_id, milestone_id),
+    fields(
+        user_id=%&*user_id,
+        milestone_id=%milestone_id.0
+    )
+)]
+pub async fn reopen_milestone(
+    pool: web::Data<PgPool>,
+    user_id: web::ReqData<UserId>,
+    milestone_id: web::Path<(i32,)>,
+) -> Result<HttpResponse, actix_web::Error> {
+    change_milestone_status(&pool, milestone_id.0, **user_id, true).await
+}
+
+/// Sees the release note of a milestone, generated from its closed tickets.
+#[tracing::instrument(
+    name = "Generating changelog",
+    skip(pool, user_id, milestone_id),
+    fields(
+        user_id=%&*user_id,
+        milestone_id=%milestone_id.0
+    )
+)]
+pub async fn see_changelog(
+    pool: web::Data<PgPool>,
+    user_id: web::ReqData<UserId>,
+    milestone_id: web::Path<(i32,)>,
+) -> Result<HttpResponse, actix_web::Error> {
+    let Some((milestone, changelog)) = generate_changelog(&pool, milestone_id.0, **user_id).await?
+    else {
+        return Ok(see_other("/dashboard/milestones"));
+    };
+    let changelog_html = render_markdown(changelog.as_ref());
+
+    let body = ChangelogTemplate {
+        milestone,
+        changelog,
+        changelog_html,
+    }
+    .render()
+    .unwrap();
+
+    Ok(HttpResponse::Ok()
+        .content_type(ContentType::html())
+        .body(body))
+}
+
+/// Downloads the release note of a milestone as a Markdown file.
+#[tracing::instrument(
+    name = "Downloading changelog",
+    skip(pool, user_id, milestone_id),
+    fields(
+        user_id=%&*user_id,
+        milestone_id=%milestone_id.0
+    )
+)]
+pub async fn download_changelog(
+    pool: web::Data<PgPool>,
+    user_id: web::ReqData<UserId>,
+    milestone_id: web::Path<(i32,)>,
+) -> Result<HttpResponse, actix_web::Error> {
+    let Some((milestone, changelog)) = generate_changelog(&pool, milestone_id.0, **user_id).await?
+    else {
+        return Ok(see_other("/dashboard/milestones"));
+    };
+
+    Ok(HttpResponse::Ok()
+        .content_type("text/markdown; charset=utf-8")
+        .insert_header(ContentDisposition {
+            disposition: DispositionType::Attachment,
+            parameters: vec![DispositionParam::Filename(format!(
+                "CHANGELOG-{}.md",
+                milestone.name
+            ))],
+        })
+        .body(changelog.as_ref().to_string()))
+}
+
+/// Returns the milestone with its release note, none if the user cannot access it.
+async fn generate_changelog(
+    pool: &PgPool,
+    milestone_id: i32,
+    user_id: Uuid,
+) -> Result<Option<(ValidMilestone, Changelog)>, actix_web::Error> {
+    let Some(milestone) = get_accessible_milestone(pool, milestone_id, user_id).await? else {
+        return Ok(None);
+    };
+    let tickets = get_milestone_tickets(pool, milestone.id)
+        .await
+        .context("Failed to get the tickets details from the tickets table")
+        .map_err(e500)?;
+    let changelog = Changelog::generate(&milestone, &tickets);
+
+    Ok(Some((milestone, changelog)))
+}
+
+/// Opens or closes the milestone.
+async fn change_milestone_status(
+    pool: &PgPool,
+    milestone_id: i32,
+    user_id: Uuid,
+    is_open: bool,
+) -> Result<HttpResponse, actix_web::Error> {
+    let Some(milestone) = get_accessible_milestone(pool, milestone_id, user_id).await? else {
+        return Ok(see_other("/dashboard/milestones"));
+    };
+
+    sqlx::query!(
+        r#"
+        UPDATE milestones
+        SET is_open = $1
+        WHERE id = $2
+        "#,
+        is_open,
+        milestone.id
+    )
+    .execute(pool)
+    .await
+    .context("Failed to update the `is_open` field from the milestones table")
+    .map_err(e500)?;
+
+    // Send notification.
+    let action = if is_open { "reopened" } else { "closed" };
+    FlashMessage::info(format!(
+        "You have successfully {} the milestone {}.",
+        action, milestone.name
+    ))
+    .send();
+
+    Ok(see_other(&format!(
+        "/dashboard/milestones/{}",
+        milestone.id
+    )))
+}
+
+/// Returns the milestone if the user is a member of its project, or sends a notification.
+async fn get_accessible_milestone(
+    pool: &PgPool,
+    milestone_id: i32,
+    user_id: Uuid,
+) -> Result<Option<ValidMilestone>, actix_web::Error> {
+    let milestone = get_milestone(pool, milestone_id).await.map_err(e500)?;
+
+    // Milestones of other projects are treated as if they did not exist.
+    let milestone = match milestone {
+        Some(milestone)
+            if is_project_member(pool, milestone.project_id, user_id)
+                .await
+                .map_err(e500)? =>
+        {
+            Some(milestone)
+        }
+        _ => {
+            // Send notification.
+            FlashMessage::error("This milestone does not exist.").send();
+
+            None
+        }
+    };
+
+    Ok(milestone)
+}
+
+/// Returns the milestone with the progress of its tickets, and the tickets.
+async fn get_milestone_overview(
+    pool: &PgPool,
+    milestone: ValidMilestone,
+) -> Result<(MilestoneOverview, Vec<ValidTicket>), actix_web::Error> {
+    let tickets = get_milestone_tickets(pool, milestone.id)
+        .await
+        .context("Failed to get the tickets details from the tickets table")
+        .map_err(e500)?;
+    let open_count = tickets.iter().filter(|ticket| ticket.is_open).count();
+
+    Ok((
+        MilestoneOverview {
+            milestone,
+            open_count,
+            closed_count: tickets.len() - open_count,
+        },
+        tickets,
+    ))
+}
+
+/// Inserts the new milestone details into the `milestones` table.
+#[tracing::instrument(
+    name = "Inserting the new milestone details into the milestones table",
+    skip(pool, new_milestone)
+)]
+async fn insert_milestone(
+    pool: &PgPool,
+    project_id: i32,
+    new_milestone: &NewMilestone,
+) -> Result<(), sqlx::Error> {
+    sqlx::query!(
+        r#"
+        INSERT INTO milestones (project_id, name, target_on, created_at)
+        VALUES ($1, $2, $3, $4)
+        "#,
+        project_id,
+        new_milestone.name.as_ref(),
+        new_milestone.target_date.as_ref().map(|d| d.date()),
+        Utc::now()
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// Returns the milestone.
+#[tracing::instrument(
+    name = "Getting milestone details from the milestones table",
+    skip(pool)
+)]
+pub async fn get_milestone(
+    pool: &PgPool,
+    milestone_id: i32,
+) -> Result<Option<ValidMilestone>, sqlx::Error> {
+    let milestone = sqlx::query_as!(
+        ValidMilestone,
+        r#"SELECT * FROM milestones WHERE id = $1"#,
+        milestone_id
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(milestone)
+}
+
+/// Returns the milestones of the project, the open ones first, by target date.
+#[tracing::instrument(
+    name = "Getting milestones details from the milestones table",
+    skip(pool)
+)]
+pub async fn get_project_milestones(
+    pool: &PgPool,
+    project_id: i32,
+) -> Result<Vec<ValidMilestone>, sqlx::Error> {
+    let milestones = sqlx::query_as!(
+        ValidMilestone,
+        r#"
+        SELECT *
+        FROM milestones
+        WHERE project_id = $1
+        ORDER BY is_open DESC, target_on NULLS LAST, id
+        "#,
+        project_id
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(milestones)
+}
+
+/// Returns the milestones of the project with their progress,
+/// the open ones first, by target date.
+#[tracing::instrument(
+    name = "Getting milestones progress from the milestones table",
+    skip(pool)
+)]
+async fn get_project_milestone_overviews(
+    pool: &PgPool,
+    project_id: i32,
+) -> Result<Vec<MilestoneOverview>, sqlx::Error> {
+    let rows = sqlx::query!(
+        r#"
+        SELECT milestones.*,
+            COUNT(tickets.id) FILTER (WHERE tickets.is_open) AS "open_count!",
+            COUNT(tickets.id) FILTER (WHERE NOT tickets.is_open) AS "closed_count!"
+        FROM milestones
+        LEFT JOIN tickets ON tickets.milestone_id = milestones.id
+        WHERE milestones.project_id = $1
+        GROUP BY milestones.id
+        ORDER BY milestones.is_open DESC, milestones.target_on NULLS LAST, milestones.id
+        "#,
+        project_id
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| MilestoneOverview {
+            milestone: ValidMilestone {
+                id: row.id,
+                project_id: row.project_id,
+                name: row.name,
+                target_on: row.target_on,
+                is_open: row.is_open,
+                created_at: row.created_at,
+            },
+            open_count: row.open_count as usize,
+            closed_count: row.closed_count as usize,
+        })
+        .collect())
+}
+
+/// Returns the tickets of the milestone.
+#[tracing::instrument(name = "Getting the tickets of a milestone", skip(pool))]
+async fn get_milestone_tickets(
+    pool: &PgPool,
+    milestone_id: i32,
+) -> Result<Vec<ValidTicket>, sqlx::Error> {
+    let tickets = sqlx::query_as!(
+        ValidTicket,
+        "SELECT * FROM tickets WHERE milestone_id = $1 ORDER BY id",
+        milestone_id
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(tickets)
+}
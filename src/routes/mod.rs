@@ -1,20 +1,79 @@
+mod api;
+mod attachments;
+mod board;
 mod dashboard;
 mod health_check;
 mod home;
+mod import;
 mod login;
 mod logout;
+mod metrics;
+mod milestones;
+mod notifications;
 mod password;
+mod projects;
 mod register;
+mod reports;
+mod setup;
+mod sprints;
+mod ticket_events;
+mod ticket_fields;
+mod ticket_links;
 mod tickets;
+mod time_entries;
 
-pub use dashboard::dashboard;
-pub use health_check::health_check;
+pub use api::{api_close_ticket, api_see_ticket, ApiError};
+pub use attachments::{
+    delete_stored_attachments, download_attachment, download_attachment_thumbnail, get_attachment,
+    get_attachments, insert_attachments, read_multipart_form, store_attachments,
+    upload_attachments, AttachmentError, MultipartForm, StoredAttachment,
+};
+pub use board::{get_wip_limits, move_ticket, move_ticket_at_version, see_board, set_wip_limit};
+pub use dashboard::{dashboard, get_daily_activity, get_dashboard_metrics};
+pub use health_check::{health_check, ready};
 pub use home::home;
+pub use import::{import_form, import_tickets};
 pub use login::{login, login_form};
 pub use logout::logout;
+pub use metrics::export_metrics;
+pub use milestones::{
+    close_milestone, create_milestone, download_changelog, get_milestone, get_project_milestones,
+    reopen_milestone, see_changelog, see_milestone, see_milestones,
+};
+pub use notifications::{
+    count_unread_notifications, delete_expired_notifications, get_notifications,
+    notify_mentioned_users, notify_ticket_assignee, notify_ticket_creator, read_all_notifications,
+    read_notification, see_notifications, unread_notifications,
+};
 pub use password::{change_password, change_password_form};
+pub use projects::{
+    add_project_member, can_access_ticket, create_project, get_current_project,
+    get_member_projects, get_project_by_key, get_user_id_by_username, is_project_member,
+    join_default_project, project_switcher, see_projects, switch_project,
+};
 pub use register::{register, register_form};
+pub use reports::{export_report_csv, export_report_json, get_report, see_reports};
+pub use setup::{setup, setup_form};
+pub use sprints::{
+    create_sprint, get_project_sprints, get_sprint, plan_ticket, see_backlog, see_sprint,
+    see_sprints,
+};
+pub use ticket_events::ticket_events;
+pub use ticket_fields::{
+    assign_ticket, change_sprint, set_due_date, set_estimate, set_milestone, set_sprint,
+    set_story_points, update_ticket_field_at_version, SprintFormData, TicketFieldUpdate,
+};
+pub use ticket_links::{
+    create_ticket_link, delete_ticket_link, find_accessible_ticket, get_ticket_links,
+    insert_ticket_link, TicketLinkError, TicketLinkOverview,
+};
 pub use tickets::{
-    close_ticket, create_ticket, create_ticket_form, get_ticket, get_tickets, see_ticket,
-    see_tickets,
+    close_ticket, close_ticket_at_version, create_ticket, create_ticket_form,
+    create_ticket_with_attachments, get_ticket, get_ticket_by_key, get_ticket_changes_since,
+    get_tickets, insert_ticket_change, lock_ticket, preview_ticket_description, reserve_ticket_key,
+    see_ticket, see_tickets, update_is_open, TicketError, TicketUpdate,
+};
+pub use time_entries::{
+    export_timesheet, get_time_entries, insert_time_entry, log_time, see_timesheet,
+    TimeEntryOverview,
 };
@@ -0,0 +1,310 @@
+use crate::{
+    authentication::UserId,
+    domain::{Mentions, NotificationKind, ValidNotification},
+    utils::{e500, see_other},
+};
+use actix_web::{http::header::ContentType, web, HttpResponse};
+use actix_web_flash_messages::{FlashMessage, IncomingFlashMessages};
+use askama::Template;
+use chrono::{DateTime, Utc};
+use sqlx::{PgExecutor, PgPool};
+use std::fmt::Write;
+use uuid::Uuid;
+
+/// Representation of the notifications template.
+#[derive(Template)]
+#[template(path = "notifications.html")]
+struct NotificationsTemplate {
+    msg_html: String,
+    notifications: Vec<ValidNotification>,
+}
+
+/// Sees notifications.
+#[tracing::instrument(
+    name = "Seeing notifications",
+    skip(pool, flash_messages, user_id),
+    fields(
+        user_id=%&*user_id
+    )
+)]
+pub async fn see_notifications(
+    pool: web::Data<PgPool>,
+    flash_messages: IncomingFlashMessages,
+    user_id: web::ReqData<UserId>,
+) -> Result<HttpResponse, actix_web::Error> {
+    // Get notification.
+    let mut msg_html = String::new();
+    for m in flash_messages.iter() {
+        writeln!(msg_html, "{}", m.content()).unwrap();
+    }
+
+    let notifications = get_notifications(&pool, **user_id).await.map_err(e500)?;
+
+    let body = NotificationsTemplate {
+        msg_html,
+        notifications,
+    }
+    .render()
+    .unwrap();
+
+    Ok(HttpResponse::Ok()
+        .content_type(ContentType::html())
+        .body(body))
+}
+
+/// Returns the number of unread notifications.
+///
+/// # Implementation Notes
+///
+/// Polled by the bell of the aside menu.
+pub async fn unread_notifications(
+    pool: web::Data<PgPool>,
+    user_id: web::ReqData<UserId>,
+) -> Result<HttpResponse, actix_web::Error> {
+    let count = count_unread_notifications(&pool, **user_id)
+        .await
+        .map_err(e500)?;
+
+    Ok(HttpResponse::Ok()
+        .content_type(ContentType::html())
+        .body(count.to_string()))
+}
+
+/// Marks a notification as read.
+#[tracing::instrument(
+    name = "Marking a notification as read",
+    skip(pool, user_id, notification_id),
+    fields(
+        user_id=%&*user_id,
+        notification_id=%notification_id.0
+    )
+)]
+pub async fn read_notification(
+    pool: web::Data<PgPool>,
+    user_id: web::ReqData<UserId>,
+    notification_id: web::Path<(i32,)>,
+) -> Result<HttpResponse, actix_web::Error> {
+    let notification_id = notification_id.into_inner().0;
+
+    mark_notifications_as_read(&pool, **user_id, Some(notification_id), Utc::now())
+        .await
+        .map_err(e500)?;
+
+    Ok(see_other("/dashboard/notifications"))
+}
+
+/// Marks every notification as read.
+#[tracing::instrument(
+    name = "Marking every notification as read",
+    skip(pool, user_id),
+    fields(
+        user_id=%&*user_id
+    )
+)]
+pub async fn read_all_notifications(
+    pool: web::Data<PgPool>,
+    user_id: web::ReqData<UserId>,
+) -> Result<HttpResponse, actix_web::Error> {
+    mark_notifications_as_read(&pool, **user_id, None, Utc::now())
+        .await
+        .map_err(e500)?;
+
+    // Send notification.
+    FlashMessage::info("You have marked every notification as read.").send();
+
+    Ok(see_other("/dashboard/notifications"))
+}
+
+/// Returns the notifications of a user, most recent first.
+#[tracing::instrument(
+    name = "Getting notifications details from the notifications table",
+    skip(pool)
+)]
+pub async fn get_notifications(
+    pool: &PgPool,
+    user_id: Uuid,
+) -> Result<Vec<ValidNotification>, sqlx::Error> {
+    let notifications = sqlx::query_as!(
+        ValidNotification,
+        r#"
+        SELECT *
+        FROM notifications
+        WHERE user_id = $1
+        ORDER BY created_at DESC, id DESC
+        "#,
+        user_id
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(notifications)
+}
+
+/// Returns the number of unread notifications of a user.
+#[tracing::instrument(name = "Counting unread notifications", skip(pool))]
+pub async fn count_unread_notifications(pool: &PgPool, user_id: Uuid) -> Result<i64, sqlx::Error> {
+    let row = sqlx::query!(
+        r#"
+        SELECT COUNT(*) AS "count!"
+        FROM notifications
+        WHERE user_id = $1 AND read_at IS NULL
+        "#,
+        user_id
+    )
+    .fetch_one(pool)
+    .await?;
+
+    Ok(row.count)
+}
+
+/// Sets the `read_at` field of the unread notifications of a user.
+///
+/// # Implementation Notes
+///
+/// Every unread notification is updated when no id is given.
+#[tracing::instrument(
+    name = "Updating the `read_at` field from the notifications table",
+    skip(pool)
+)]
+async fn mark_notifications_as_read(
+    pool: &PgPool,
+    user_id: Uuid,
+    id: Option<i32>,
+    read_at: DateTime<Utc>,
+) -> Result<(), sqlx::Error> {
+    sqlx::query!(
+        r#"
+        UPDATE notifications
+        SET read_at = $1
+        WHERE user_id = $2 AND read_at IS NULL AND ($3::INTEGER IS NULL OR id = $3)
+        "#,
+        read_at,
+        user_id,
+        id
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// Notifies the creator of a ticket about an event triggered by another user.
+#[tracing::instrument(
+    name = "Inserting a notification for the ticket creator into the notifications table",
+    skip(pool, message)
+)]
+pub async fn notify_ticket_creator(
+    pool: &PgPool,
+    ticket_id: i32,
+    triggered_by: Uuid,
+    kind: NotificationKind,
+    message: &str,
+) -> Result<(), sqlx::Error> {
+    sqlx::query!(
+        r#"
+        INSERT INTO notifications (user_id, ticket_id, kind, message, created_at)
+        SELECT users.user_id, tickets.id, $3, $4, $5
+        FROM tickets
+        JOIN users ON users.username = tickets.created_by
+        WHERE tickets.id = $1 AND users.user_id <> $2
+        "#,
+        ticket_id,
+        triggered_by,
+        kind.as_str(),
+        message,
+        Utc::now(),
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// Notifies the assignee of a ticket about an event triggered by another user.
+///
+/// # Implementation Notes
+///
+/// Runs on a pool or within the transaction of the event.
+#[tracing::instrument(
+    name = "Inserting a notification for the ticket assignee into the notifications table",
+    skip(executor, message)
+)]
+pub async fn notify_ticket_assignee(
+    executor: impl PgExecutor<'_>,
+    ticket_id: i32,
+    triggered_by: Option<Uuid>,
+    kind: NotificationKind,
+    message: &str,
+) -> Result<(), sqlx::Error> {
+    sqlx::query!(
+        r#"
+        INSERT INTO notifications (user_id, ticket_id, kind, message, created_at)
+        SELECT users.user_id, tickets.id, $3, $4, $5
+        FROM tickets
+        JOIN users ON users.username = tickets.assigned_to
+        WHERE tickets.id = $1 AND users.user_id IS DISTINCT FROM $2
+        "#,
+        ticket_id,
+        triggered_by,
+        kind.as_str(),
+        message,
+        Utc::now(),
+    )
+    .execute(executor)
+    .await?;
+
+    Ok(())
+}
+
+/// Notifies the users mentioned about a ticket, when they are members of its project.
+#[tracing::instrument(
+    name = "Inserting a notification for the mentioned users into the notifications table",
+    skip(pool, mentions, message)
+)]
+pub async fn notify_mentioned_users(
+    pool: &PgPool,
+    ticket_id: i32,
+    triggered_by: Uuid,
+    mentions: &Mentions,
+    message: &str,
+) -> Result<(), sqlx::Error> {
+    sqlx::query!(
+        r#"
+        INSERT INTO notifications (user_id, ticket_id, kind, message, created_at)
+        SELECT users.user_id, tickets.id, $4, $5, $6
+        FROM tickets
+        JOIN project_members ON project_members.project_id = tickets.project_id
+        JOIN users ON users.user_id = project_members.user_id
+        WHERE tickets.id = $1 AND users.user_id <> $2 AND users.username = ANY($3)
+        "#,
+        ticket_id,
+        triggered_by,
+        mentions.as_ref(),
+        NotificationKind::Mention.as_str(),
+        message,
+        Utc::now(),
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// Deletes the notifications read before the retention period.
+#[tracing::instrument(name = "Deleting expired notifications", skip(pool))]
+pub async fn delete_expired_notifications(
+    pool: &PgPool,
+    retention: chrono::Duration,
+) -> Result<u64, sqlx::Error> {
+    let result = sqlx::query!(
+        r#"
+        DELETE FROM notifications
+        WHERE read_at < $1
+        "#,
+        Utc::now() - retention
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected())
+}
@@ -0,0 +1,547 @@
+use crate::{
+    authentication::UserId,
+    domain::{NewProject, ProjectKey, ProjectName, ValidProject},
+    session_state::TypedSession,
+    utils::{e500, see_other},
+};
+use actix_web::{http::header::ContentType, web, HttpResponse};
+use actix_web_flash_messages::{FlashMessage, IncomingFlashMessages};
+use anyhow::Context;
+use askama::Template;
+use chrono::Utc;
+use serde::Deserialize;
+use sqlx::{PgPool, Postgres, Transaction};
+use std::fmt::Write;
+use uuid::Uuid;
+
+/// Representation of a project with its members.
+struct ProjectOverview {
+    project: ValidProject,
+    members: Vec<String>,
+}
+
+/// Representation of the projects template.
+#[derive(Template)]
+#[template(path = "projects.html")]
+struct ProjectsTemplate {
+    msg_html: String,
+    projects: Vec<ProjectOverview>,
+}
+
+/// Representation of the project switcher template.
+#[derive(Template)]
+#[template(path = "project_switcher.html")]
+struct ProjectSwitcherTemplate {
+    projects: Vec<ValidProject>,
+    current_project_id: i32,
+}
+
+/// Representation of a new project created with form data.
+#[derive(Deserialize)]
+pub struct NewProjectFormData {
+    key: String,
+    name: String,
+}
+
+impl TryFrom<NewProjectFormData> for NewProject {
+    type Error = String;
+
+    /// Performs the conversion.
+    fn try_from(value: NewProjectFormData) -> Result<Self, Self::Error> {
+        let key = ProjectKey::parse(value.key)?;
+        let name = ProjectName::parse(value.name)?;
+
+        Ok(Self { key, name })
+    }
+}
+
+/// Representation of a new project member sent with form data.
+#[derive(Deserialize)]
+pub struct NewMemberFormData {
+    username: String,
+}
+
+/// Representation of the project to switch to, sent with form data.
+#[derive(Deserialize)]
+pub struct SwitchProjectFormData {
+    project_id: i32,
+}
+
+/// Sees the projects of the user.
+#[tracing::instrument(
+    name = "Seeing projects",
+    skip(pool, flash_messages, user_id),
+    fields(
+        user_id=%&*user_id
+    )
+)]
+pub async fn see_projects(
+    pool: web::Data<PgPool>,
+    flash_messages: IncomingFlashMessages,
+    user_id: web::ReqData<UserId>,
+) -> Result<HttpResponse, actix_web::Error> {
+    // Get notification.
+    let mut msg_html = String::new();
+    for m in flash_messages.iter() {
+        writeln!(msg_html, "{}", m.content()).unwrap();
+    }
+
+    let mut projects = Vec::new();
+    for project in get_member_projects(&pool, **user_id).await.map_err(e500)? {
+        let members = get_project_members(&pool, project.id).await.map_err(e500)?;
+        projects.push(ProjectOverview { project, members });
+    }
+
+    let body = ProjectsTemplate { msg_html, projects }.render().unwrap();
+
+    Ok(HttpResponse::Ok()
+        .content_type(ContentType::html())
+        .body(body))
+}
+
+/// Creates a new project, the user being its first member.
+#[tracing::instrument(
+    name = "Creating a new project",
+    skip(pool, session, form, user_id),
+    fields(
+        project_key = %form.key,
+        user_id = %&*user_id
+    )
+)]
+pub async fn create_project(
+    pool: web::Data<PgPool>,
+    session: TypedSession,
+    form: web::Form<NewProjectFormData>,
+    user_id: web::ReqData<UserId>,
+) -> Result<HttpResponse, actix_web::Error> {
+    let new_project: NewProject = match form.0.try_into() {
+        Ok(new_project) => new_project,
+        Err(e) => {
+            // Send notification.
+            FlashMessage::error(format!("Validation error: {}", e)).send();
+
+            return Ok(see_other("/dashboard/projects"));
+        }
+    };
+
+    let mut transaction = pool
+        .begin()
+        .await
+        .context("Failed to acquire a Postgres connection from the pool")
+        .map_err(e500)?;
+    let project_id = match insert_project(&mut transaction, &new_project).await {
+        Ok(project_id) => project_id,
+        Err(sqlx::Error::Database(e)) if e.code().as_deref() == Some("23505") => {
+            // Send notification.
+            FlashMessage::error("This project key is already taken.").send();
+
+            return Ok(see_other("/dashboard/projects"));
+        }
+        Err(e) => return Err(e500(e)),
+    };
+    insert_project_member(&mut transaction, project_id, **user_id)
+        .await
+        .map_err(e500)?;
+    transaction
+        .commit()
+        .await
+        .context("Failed to commit SQL transaction to store a new project")
+        .map_err(e500)?;
+
+    // Switch to the new project.
+    session.insert_project_id(project_id).map_err(e500)?;
+
+    // Send notification.
+    FlashMessage::info("You have successfully created a new project.").send();
+
+    Ok(see_other("/dashboard/projects"))
+}
+
+/// Adds a user to a project the user is a member of.
+#[tracing::instrument(
+    name = "Adding a project member",
+    skip(pool, form, user_id, project_key),
+    fields(
+        user_id = %&*user_id,
+        project_key = %project_key.0,
+        username = %form.username
+    )
+)]
+pub async fn add_project_member(
+    pool: web::Data<PgPool>,
+    form: web::Form<NewMemberFormData>,
+    user_id: web::ReqData<UserId>,
+    project_key: web::Path<(String,)>,
+) -> Result<HttpResponse, actix_web::Error> {
+    let project_key = project_key.into_inner().0;
+
+    let project = match get_project_by_key(&pool, &project_key)
+        .await
+        .map_err(e500)?
+    {
+        Some(project)
+            if is_project_member(&pool, project.id, **user_id)
+                .await
+                .map_err(e500)? =>
+        {
+            project
+        }
+        // Do not reveal the projects the user is not a member of.
+        _ => {
+            // Send notification.
+            FlashMessage::error("This project does not exist.").send();
+
+            return Ok(see_other("/dashboard/projects"));
+        }
+    };
+
+    let new_member_id = match get_user_id_by_username(&pool, &form.username)
+        .await
+        .map_err(e500)?
+    {
+        Some(new_member_id) => new_member_id,
+        None => {
+            // Send notification.
+            FlashMessage::error("This user does not exist.").send();
+
+            return Ok(see_other("/dashboard/projects"));
+        }
+    };
+
+    let mut transaction = pool
+        .begin()
+        .await
+        .context("Failed to acquire a Postgres connection from the pool")
+        .map_err(e500)?;
+    let is_added = insert_project_member(&mut transaction, project.id, new_member_id)
+        .await
+        .map_err(e500)?;
+    transaction
+        .commit()
+        .await
+        .context("Failed to commit SQL transaction to store a new project member")
+        .map_err(e500)?;
+
+    // Send notification.
+    if is_added {
+        FlashMessage::info(format!(
+            "You have successfully added {} to {}.",
+            form.username, project.name
+        ))
+        .send();
+    } else {
+        FlashMessage::error(format!(
+            "{} is already a member of {}.",
+            form.username, project.name
+        ))
+        .send();
+    }
+
+    Ok(see_other("/dashboard/projects"))
+}
+
+/// Returns the project switcher of the dashboard.
+pub async fn project_switcher(
+    pool: web::Data<PgPool>,
+    session: TypedSession,
+    user_id: web::ReqData<UserId>,
+) -> Result<HttpResponse, actix_web::Error> {
+    let projects = get_member_projects(&pool, **user_id).await.map_err(e500)?;
+    let current_project_id = current_project(&projects, &session)
+        .map_err(e500)?
+        .map_or(0, |project| project.id);
+
+    let body = ProjectSwitcherTemplate {
+        projects,
+        current_project_id,
+    }
+    .render()
+    .unwrap();
+
+    Ok(HttpResponse::Ok()
+        .content_type(ContentType::html())
+        .body(body))
+}
+
+/// Switches to another project the user is a member of.
+#[tracing::instrument(
+    name = "Switching project",
+    skip(pool, session, form, user_id),
+    fields(
+        user_id = %&*user_id,
+        project_id = %form.project_id
+    )
+)]
+pub async fn switch_project(
+    pool: web::Data<PgPool>,
+    session: TypedSession,
+    form: web::Form<SwitchProjectFormData>,
+    user_id: web::ReqData<UserId>,
+) -> Result<HttpResponse, actix_web::Error> {
+    if is_project_member(&pool, form.project_id, **user_id)
+        .await
+        .map_err(e500)?
+    {
+        session.insert_project_id(form.project_id).map_err(e500)?;
+    } else {
+        // Send notification.
+        FlashMessage::error("This project does not exist.").send();
+    }
+
+    Ok(see_other("/dashboard/tickets"))
+}
+
+/// Returns the project selected in the session,
+/// defaulting to the first project the user is a member of.
+fn current_project<'a>(
+    projects: &'a [ValidProject],
+    session: &TypedSession,
+) -> Result<Option<&'a ValidProject>, anyhow::Error> {
+    let project_id = session
+        .get_project_id()
+        .context("Failed to get the project id from the session")?;
+
+    Ok(projects
+        .iter()
+        .find(|project| Some(project.id) == project_id)
+        .or_else(|| projects.first()))
+}
+
+/// Returns the project the user is currently working on, if any.
+pub async fn get_current_project(
+    pool: &PgPool,
+    session: &TypedSession,
+    user_id: Uuid,
+) -> Result<Option<ValidProject>, anyhow::Error> {
+    let projects = get_member_projects(pool, user_id)
+        .await
+        .context("Failed to get the projects details from the projects table")?;
+    let project_id = current_project(&projects, session)?.map(|project| project.id);
+
+    Ok(projects
+        .into_iter()
+        .find(|project| Some(project.id) == project_id))
+}
+
+/// Inserts the new project details into the `projects` table and then return its id.
+#[tracing::instrument(
+    name = "Inserting the new project details into the projects table",
+    skip(transaction, new_project)
+)]
+async fn insert_project(
+    transaction: &mut Transaction<'_, Postgres>,
+    new_project: &NewProject,
+) -> Result<i32, sqlx::Error> {
+    let row = sqlx::query!(
+        r#"
+        INSERT INTO projects (key, name, next_ticket_number, created_at)
+        VALUES ($1, $2, $3, $4)
+        RETURNING id
+        "#,
+        new_project.key.as_ref(),
+        new_project.name.as_ref(),
+        1,
+        Utc::now(),
+    )
+    .fetch_one(transaction)
+    .await?;
+
+    Ok(row.id)
+}
+
+/// Inserts a member into the `project_members` table
+/// and then return `false` if the user was already a member.
+#[tracing::instrument(
+    name = "Inserting a member into the project_members table",
+    skip(transaction)
+)]
+async fn insert_project_member(
+    transaction: &mut Transaction<'_, Postgres>,
+    project_id: i32,
+    user_id: Uuid,
+) -> Result<bool, sqlx::Error> {
+    let result = sqlx::query!(
+        r#"
+        INSERT INTO project_members (project_id, user_id, joined_at)
+        VALUES ($1, $2, $3)
+        ON CONFLICT DO NOTHING
+        "#,
+        project_id,
+        user_id,
+        Utc::now(),
+    )
+    .execute(transaction)
+    .await?;
+
+    Ok(result.rows_affected() == 1)
+}
+
+/// Inserts a new user into the `project_members` table of the default project.
+///
+/// # Implementation Notes
+///
+/// The new users join the default project like the users predating the projects,
+/// they can use the application before being added to other projects.
+#[tracing::instrument(
+    name = "Inserting a member into the project_members table of the default project",
+    skip(transaction)
+)]
+pub async fn join_default_project(
+    transaction: &mut Transaction<'_, Postgres>,
+    user_id: Uuid,
+) -> Result<(), sqlx::Error> {
+    sqlx::query!(
+        r#"
+        INSERT INTO project_members (project_id, user_id, joined_at)
+        SELECT id, $1, $2
+        FROM projects
+        WHERE key = 'MAIN'
+        ON CONFLICT DO NOTHING
+        "#,
+        user_id,
+        Utc::now(),
+    )
+    .execute(transaction)
+    .await?;
+
+    Ok(())
+}
+
+/// Returns the projects a user is a member of.
+#[tracing::instrument(name = "Getting projects details from the projects table", skip(pool))]
+pub async fn get_member_projects(
+    pool: &PgPool,
+    user_id: Uuid,
+) -> Result<Vec<ValidProject>, sqlx::Error> {
+    let projects = sqlx::query_as!(
+        ValidProject,
+        r#"
+        SELECT projects.*
+        FROM projects
+        JOIN project_members ON project_members.project_id = projects.id
+        WHERE project_members.user_id = $1
+        ORDER BY projects.key
+        "#,
+        user_id
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(projects)
+}
+
+/// Returns the project with the given key.
+#[tracing::instrument(name = "Getting project details from the projects table", skip(pool))]
+pub async fn get_project_by_key(
+    pool: &PgPool,
+    key: &str,
+) -> Result<Option<ValidProject>, sqlx::Error> {
+    let project = sqlx::query_as!(
+        ValidProject,
+        r#"
+        SELECT *
+        FROM projects
+        WHERE key = $1
+        "#,
+        key.to_uppercase()
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(project)
+}
+
+/// Returns the usernames of the members of a project.
+#[tracing::instrument(
+    name = "Getting project members from the project_members table",
+    skip(pool)
+)]
+async fn get_project_members(pool: &PgPool, project_id: i32) -> Result<Vec<String>, sqlx::Error> {
+    let rows = sqlx::query!(
+        r#"
+        SELECT users.username
+        FROM users
+        JOIN project_members ON project_members.user_id = users.user_id
+        WHERE project_members.project_id = $1
+        ORDER BY users.username
+        "#,
+        project_id
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows.into_iter().map(|row| row.username).collect())
+}
+
+/// Returns the id of the user with the given username.
+#[tracing::instrument(name = "Getting user id", skip(pool))]
+pub async fn get_user_id_by_username(
+    pool: &PgPool,
+    username: &str,
+) -> Result<Option<Uuid>, sqlx::Error> {
+    let row = sqlx::query!(
+        r#"
+        SELECT user_id
+        FROM users
+        WHERE username = $1
+        "#,
+        username
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(row.map(|row| row.user_id))
+}
+
+/// Returns `true` if the user is a member of the project.
+#[tracing::instrument(name = "Checking project membership", skip(pool))]
+pub async fn is_project_member(
+    pool: &PgPool,
+    project_id: i32,
+    user_id: Uuid,
+) -> Result<bool, sqlx::Error> {
+    let row = sqlx::query!(
+        r#"
+        SELECT EXISTS (
+            SELECT 1
+            FROM project_members
+            WHERE project_id = $1 AND user_id = $2
+        ) AS "is_member!"
+        "#,
+        project_id,
+        user_id
+    )
+    .fetch_one(pool)
+    .await?;
+
+    Ok(row.is_member)
+}
+
+/// Returns `true` if the user is a member of the project of the ticket.
+///
+/// # Implementation Notes
+///
+/// Tickets of other projects are treated as if they did not exist.
+#[tracing::instrument(name = "Checking ticket access", skip(pool))]
+pub async fn can_access_ticket(
+    pool: &PgPool,
+    ticket_id: i32,
+    user_id: Uuid,
+) -> Result<bool, sqlx::Error> {
+    let row = sqlx::query!(
+        r#"
+        SELECT EXISTS (
+            SELECT 1
+            FROM tickets
+            JOIN project_members ON project_members.project_id = tickets.project_id
+            WHERE tickets.id = $1 AND project_members.user_id = $2
+        ) AS "is_member!"
+        "#,
+        ticket_id,
+        user_id
+    )
+    .fetch_one(pool)
+    .await?;
+
+    Ok(row.is_member)
+}
@@ -5,7 +5,8 @@ use crate::{
         UserPassword,
     },
     error::error_chain_fmt,
-    utils::see_other,
+    routes::join_default_project,
+    utils::{error_page, see_other},
 };
 use actix_web::{
     http::{self, header::ContentType, StatusCode},
@@ -74,6 +75,10 @@ impl Debug for RegisterError {
 impl ResponseError for RegisterError {
     /// Creates full response for error.
     fn error_response(&self) -> HttpResponse {
+        if let RegisterError::UnexpectedError(_) = self {
+            return error_page(self.status_code());
+        }
+
         let msg_html = self.to_string();
 
         let body = RegisterTemplate { msg_html }.render().unwrap();
@@ -116,6 +121,7 @@ pub async fn register_form(flash_messages: IncomingFlashMessages) -> HttpRespons
         user_id=tracing::field::Empty
     )
 )]
+#[allow(clippy::needless_borrows_for_generic_args)]
 pub async fn register(
     pool: web::Data<PgPool>,
     form: web::Form<RegisterFormData>,
@@ -142,12 +148,14 @@ pub async fn register(
     }
 }
 
-/// Inserts the new user details into the `users` table.
+/// Inserts the new user details into the `users` table, as a member of the default project.
 #[tracing::instrument(
     name = "Inserting the new user details into the users table",
     skip(pool, new_user)
 )]
 pub async fn insert_user(pool: &PgPool, new_user: &NewUser) -> Result<(), sqlx::Error> {
+    let mut transaction = pool.begin().await?;
+
     sqlx::query!(
         r#"
         INSERT INTO users (user_id, username, password_hash)
@@ -157,8 +165,12 @@ pub async fn insert_user(pool: &PgPool, new_user: &NewUser) -> Result<(), sqlx::
         new_user.username.as_ref(),
         new_user.password_hash.expose_secret(),
     )
-    .execute(pool)
+    .execute(&mut transaction)
     .await?;
 
+    join_default_project(&mut transaction, new_user.user_id).await?;
+
+    transaction.commit().await?;
+
     Ok(())
 }
@@ -0,0 +1,405 @@
+use crate::{
+    authentication::UserId,
+    domain::{Report, ReportKind, ReportRange, ReportRow},
+    routes::{get_current_project, get_daily_activity},
+    session_state::TypedSession,
+    startup::ReadPool,
+    utils::{e500, see_other},
+};
+use actix_web::{
+    http::header::{ContentDisposition, ContentType, DispositionParam, DispositionType},
+    web, HttpResponse,
+};
+use actix_web_flash_messages::{FlashMessage, IncomingFlashMessages};
+use anyhow::Context;
+use askama::Template;
+use chrono::{Duration, NaiveDate, TimeZone, Utc};
+use serde::Deserialize;
+use sqlx::PgPool;
+use std::fmt::Write;
+
+/// Representation of the report query.
+#[derive(Deserialize)]
+pub struct ReportQuery {
+    report: Option<String>,
+    from: Option<String>,
+    to: Option<String>,
+}
+
+/// Representation of the reports page.
+#[derive(Template)]
+#[template(path = "reports.html")]
+struct ReportsTemplate {
+    msg_html: String,
+    kinds: [ReportKind; 4],
+    range: ReportRange,
+    report: Option<Report>,
+    svg: String,
+}
+
+/// Returns the reports page, with the chart and the table of the requested report.
+#[tracing::instrument(
+    name = "Seeing reports",
+    skip(pool, read_pool, flash_messages, session, user_id, query),
+    fields(
+        user_id=%&*user_id
+    )
+)]
+pub async fn see_reports(
+    pool: web::Data<PgPool>,
+    read_pool: web::Data<ReadPool>,
+    flash_messages: IncomingFlashMessages,
+    session: TypedSession,
+    user_id: web::ReqData<UserId>,
+    query: web::Query<ReportQuery>,
+) -> Result<HttpResponse, actix_web::Error> {
+    // Get notification.
+    let mut msg_html = String::new();
+    for m in flash_messages.iter() {
+        writeln!(msg_html, "{}", m.content()).unwrap();
+    }
+
+    let (kind, range) = match parse_report_query(query.0) {
+        Ok(parsed) => parsed,
+        Err(e) => {
+            // Send notification.
+            FlashMessage::error(format!("Validation error: {}", e)).send();
+
+            return Ok(see_other("/dashboard/reports"));
+        }
+    };
+
+    let project = get_current_project(&pool, &session, **user_id)
+        .await
+        .map_err(e500)?;
+    let report = match project {
+        Some(project) => Some(
+            get_report(&read_pool.0, project.id, kind, range)
+                .await
+                .map_err(e500)?,
+        ),
+        None => None,
+    };
+    let svg = report.as_ref().map(Report::to_svg).unwrap_or_default();
+
+    let body = ReportsTemplate {
+        msg_html,
+        kinds: ReportKind::all(),
+        range,
+        report,
+        svg,
+    }
+    .render()
+    .unwrap();
+
+    Ok(HttpResponse::Ok()
+        .content_type(ContentType::html())
+        .body(body))
+}
+
+/// Exports the requested report of the current project in the CSV format.
+#[tracing::instrument(
+    name = "Exporting report as CSV",
+    skip(pool, read_pool, session, user_id, query),
+    fields(
+        user_id=%&*user_id
+    )
+)]
+pub async fn export_report_csv(
+    pool: web::Data<PgPool>,
+    read_pool: web::Data<ReadPool>,
+    session: TypedSession,
+    user_id: web::ReqData<UserId>,
+    query: web::Query<ReportQuery>,
+) -> Result<HttpResponse, actix_web::Error> {
+    let Some(report) =
+        get_requested_report(&pool, &read_pool.0, &session, **user_id, query.0).await?
+    else {
+        return Ok(see_other("/dashboard/reports"));
+    };
+    let csv = report.to_csv().map_err(e500)?;
+
+    Ok(HttpResponse::Ok()
+        .content_type("text/csv; charset=utf-8")
+        .insert_header(attachment(&report, "csv"))
+        .body(csv))
+}
+
+/// Exports the requested report of the current project in the JSON format.
+#[tracing::instrument(
+    name = "Exporting report as JSON",
+    skip(pool, read_pool, session, user_id, query),
+    fields(
+        user_id=%&*user_id
+    )
+)]
+pub async fn export_report_json(
+    pool: web::Data<PgPool>,
+    read_pool: web::Data<ReadPool>,
+    session: TypedSession,
+    user_id: web::ReqData<UserId>,
+    query: web::Query<ReportQuery>,
+) -> Result<HttpResponse, actix_web::Error> {
+    let Some(report) =
+        get_requested_report(&pool, &read_pool.0, &session, **user_id, query.0).await?
+    else {
+        return Ok(see_other("/dashboard/reports"));
+    };
+
+    Ok(HttpResponse::Ok()
+        .insert_header(attachment(&report, "json"))
+        .json(report.to_json()))
+}
+
+/// Returns the header making the browser download the report with the given extension.
+fn attachment(report: &Report, extension: &str) -> ContentDisposition {
+    ContentDisposition {
+        disposition: DispositionType::Attachment,
+        parameters: vec![DispositionParam::Filename(format!(
+            "{}-{}-{}.{}",
+            report.kind.as_str(),
+            report.range.from,
+            report.range.to,
+            extension
+        ))],
+    }
+}
+
+/// Returns the report requested by the query for the current project,
+/// none if the query is invalid or if the user is not a member of any project.
+async fn get_requested_report(
+    pool: &PgPool,
+    read_pool: &PgPool,
+    session: &TypedSession,
+    user_id: uuid::Uuid,
+    query: ReportQuery,
+) -> Result<Option<Report>, actix_web::Error> {
+    let (kind, range) = match parse_report_query(query) {
+        Ok(parsed) => parsed,
+        Err(e) => {
+            // Send notification.
+            FlashMessage::error(format!("Validation error: {}", e)).send();
+
+            return Ok(None);
+        }
+    };
+    let Some(project) = get_current_project(pool, session, user_id)
+        .await
+        .map_err(e500)?
+    else {
+        // Send notification.
+        FlashMessage::error("You are not a member of any project yet.").send();
+
+        return Ok(None);
+    };
+
+    let report = get_report(read_pool, project.id, kind, range)
+        .await
+        .map_err(e500)?;
+
+    Ok(Some(report))
+}
+
+/// Returns the kind and the range of the report requested by the query,
+/// the created and closed tickets of the last 30 days by default.
+fn parse_report_query(query: ReportQuery) -> Result<(ReportKind, ReportRange), String> {
+    let kind = match query.report.filter(|report| !report.is_empty()) {
+        Some(report) => ReportKind::parse(&report)?,
+        None => ReportKind::CreatedClosed,
+    };
+    let parse_day = |day: Option<String>| {
+        day.filter(|day| !day.is_empty())
+            .map(|day| {
+                day.parse::<NaiveDate>()
+                    .map_err(|_| format!("`{}` is not a date such as 2026-10-31.", day))
+            })
+            .transpose()
+    };
+    let range = ReportRange::parse(
+        parse_day(query.from)?,
+        parse_day(query.to)?,
+        Utc::now().date_naive(),
+    )?;
+
+    Ok((kind, range))
+}
+
+/// Returns the report of the project over the range.
+#[tracing::instrument(name = "Computing report", skip(pool))]
+pub async fn get_report(
+    pool: &PgPool,
+    project_id: i32,
+    kind: ReportKind,
+    range: ReportRange,
+) -> Result<Report, anyhow::Error> {
+    let (columns, rows) = match kind {
+        ReportKind::CreatedClosed => {
+            let activity = get_daily_activity(pool, project_id, range.from, range.to)
+                .await
+                .context("Failed to get the daily activity from the tickets table")?;
+            let rows = activity
+                .into_iter()
+                .map(|day| ReportRow {
+                    label: day.day.to_string(),
+                    values: vec![day.created, day.closed],
+                })
+                .collect();
+
+            (vec!["created", "closed"], rows)
+        }
+        ReportKind::CumulativeFlow => (
+            vec!["open", "closed"],
+            get_cumulative_flow(pool, project_id, range)
+                .await
+                .context("Failed to get the cumulative flow from the tickets table")?,
+        ),
+        ReportKind::OpenTicketAge => (
+            vec!["< 1 day", "1-7 days", "7-30 days", "> 30 days"],
+            get_open_ticket_age(pool, project_id)
+                .await
+                .context("Failed to get the age of the open tickets from the tickets table")?,
+        ),
+        ReportKind::Throughput => (
+            vec!["closed"],
+            get_throughput(pool, project_id, range)
+                .await
+                .context("Failed to get the throughput from the ticket_changes table")?,
+        ),
+    };
+
+    Ok(Report {
+        kind,
+        range,
+        columns: columns.into_iter().map(String::from).collect(),
+        rows,
+    })
+}
+
+/// Returns the number of tickets of the project open and closed at the end of each day of the range.
+async fn get_cumulative_flow(
+    pool: &PgPool,
+    project_id: i32,
+    range: ReportRange,
+) -> Result<Vec<ReportRow>, sqlx::Error> {
+    let rows = sqlx::query!(
+        r#"
+        WITH days AS (
+            SELECT day::DATE AS day, (day + INTERVAL '1 day') AT TIME ZONE 'UTC' AS next_day
+            FROM generate_series($2::DATE, $3::DATE, INTERVAL '1 day') AS days (day)
+        )
+        SELECT days.day AS "day!",
+            COUNT(tickets.id) FILTER (
+                WHERE tickets.is_open OR tickets.closed_at >= days.next_day
+            ) AS "open!",
+            COUNT(tickets.id) FILTER (
+                WHERE NOT tickets.is_open AND tickets.closed_at < days.next_day
+            ) AS "closed!"
+        FROM days
+        LEFT JOIN tickets ON tickets.project_id = $1 AND tickets.created_at < days.next_day
+        GROUP BY days.day
+        ORDER BY 1
+        "#,
+        project_id,
+        range.from,
+        range.to
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| ReportRow {
+            label: row.day.to_string(),
+            values: vec![row.open, row.closed],
+        })
+        .collect())
+}
+
+/// Returns the number of open tickets of the project, by priority and by age.
+///
+/// # Implementation Notes
+///
+/// The ages are taken now, the range does not apply,
+/// a ticket opened long before the range is still open during it.
+async fn get_open_ticket_age(
+    pool: &PgPool,
+    project_id: i32,
+) -> Result<Vec<ReportRow>, sqlx::Error> {
+    let now = Utc::now();
+    let rows = sqlx::query!(
+        r#"
+        SELECT priority,
+            COUNT(*) FILTER (WHERE created_at > $2) AS "under_a_day!",
+            COUNT(*) FILTER (WHERE created_at <= $2 AND created_at > $3) AS "under_a_week!",
+            COUNT(*) FILTER (WHERE created_at <= $3 AND created_at > $4) AS "under_a_month!",
+            COUNT(*) FILTER (WHERE created_at <= $4) AS "older!"
+        FROM tickets
+        WHERE project_id = $1 AND is_open = TRUE
+        GROUP BY priority
+        "#,
+        project_id,
+        now - Duration::days(1),
+        now - Duration::days(7),
+        now - Duration::days(30)
+    )
+    .fetch_all(pool)
+    .await?;
+
+    // Every priority is listed, even without open tickets.
+    Ok(["high", "medium", "low"]
+        .into_iter()
+        .map(|priority| ReportRow {
+            label: priority.to_string(),
+            values: rows
+                .iter()
+                .find(|row| row.priority == priority)
+                .map(|row| {
+                    vec![
+                        row.under_a_day,
+                        row.under_a_week,
+                        row.under_a_month,
+                        row.older,
+                    ]
+                })
+                .unwrap_or_else(|| vec![0; 4]),
+        })
+        .collect())
+}
+
+/// Returns the number of tickets of the project closed by each user during the range,
+/// the most productive first.
+async fn get_throughput(
+    pool: &PgPool,
+    project_id: i32,
+    range: ReportRange,
+) -> Result<Vec<ReportRow>, sqlx::Error> {
+    let since = Utc.from_utc_datetime(&range.from.and_hms_opt(0, 0, 0).unwrap());
+    let until = Utc.from_utc_datetime(&range.to.and_hms_opt(0, 0, 0).unwrap()) + Duration::days(1);
+    let rows = sqlx::query!(
+        r#"
+        SELECT ticket_changes.changed_by, COUNT(*) AS "closed!"
+        FROM ticket_changes
+        JOIN tickets ON tickets.id = ticket_changes.ticket_id
+        WHERE tickets.project_id = $1
+            AND ticket_changes.field = 'status'
+            AND ticket_changes.new_value = 'closed'
+            AND ticket_changes.changed_at >= $2
+            AND ticket_changes.changed_at < $3
+        GROUP BY ticket_changes.changed_by
+        ORDER BY 2 DESC, 1
+        "#,
+        project_id,
+        since,
+        until
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| ReportRow {
+            label: row.changed_by,
+            values: vec![row.closed],
+        })
+        .collect())
+}
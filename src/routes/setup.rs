@@ -0,0 +1,113 @@
+use crate::{
+    bootstrap::{create_admin, has_admin, SetupToken},
+    utils::{e500, see_other},
+};
+use actix_web::{http::header::ContentType, web, HttpResponse};
+use actix_web_flash_messages::{FlashMessage, IncomingFlashMessages};
+use askama::Template;
+use secrecy::Secret;
+use serde::Deserialize;
+use sqlx::PgPool;
+use std::fmt::Write;
+
+/// Representation of the setup template.
+#[derive(Template)]
+#[template(path = "setup.html")]
+struct SetupTemplate {
+    msg_html: String,
+    token: String,
+}
+
+/// Representation of the token given in the link printed to the logs.
+#[derive(Deserialize)]
+pub struct SetupQuery {
+    token: Option<String>,
+}
+
+/// Representation of the first administrator with form data.
+#[derive(Deserialize)]
+pub struct SetupFormData {
+    token: String,
+    username: String,
+    password: Secret<String>,
+}
+
+/// Returns the setup page of the application, used to create the first administrator.
+pub async fn setup_form(
+    pool: web::Data<PgPool>,
+    query: web::Query<SetupQuery>,
+    flash_messages: IncomingFlashMessages,
+) -> Result<HttpResponse, actix_web::Error> {
+    if has_admin(&pool).await.map_err(e500)? {
+        return Ok(see_other("/"));
+    }
+
+    // Get notification.
+    let mut msg_html = String::new();
+    for m in flash_messages.iter() {
+        writeln!(msg_html, "{}", m.content()).unwrap();
+    }
+
+    let body = SetupTemplate {
+        msg_html,
+        token: query.0.token.unwrap_or_default(),
+    }
+    .render()
+    .unwrap();
+
+    Ok(HttpResponse::Ok()
+        .content_type(ContentType::html())
+        .body(body))
+}
+
+/// Creates the first administrator.
+///
+/// # Implementation Notes
+///
+/// The token printed to the logs can only be used once,
+/// the administrator can then log in from the homepage.
+#[tracing::instrument(
+    name = "Creating the first administrator",
+    skip(pool, setup_token, form),
+    fields(
+        username = %form.0.username
+    )
+)]
+pub async fn setup(
+    pool: web::Data<PgPool>,
+    setup_token: web::Data<SetupToken>,
+    form: web::Form<SetupFormData>,
+) -> Result<HttpResponse, actix_web::Error> {
+    if has_admin(&pool).await.map_err(e500)? {
+        // Send notification.
+        FlashMessage::error("The application has already been set up.").send();
+
+        return Ok(see_other("/"));
+    }
+
+    let setup_url = format!("/setup?token={}", form.0.token);
+    if !setup_token.consume(&form.0.token) {
+        // Send notification.
+        FlashMessage::error("Validation error: the setup token is invalid.").send();
+
+        return Ok(see_other("/setup"));
+    }
+
+    let SetupFormData {
+        token,
+        username,
+        password,
+    } = form.0;
+    match create_admin(&pool, username, password).await {
+        Ok(_) => Ok(see_other("/")),
+        Err(e) => {
+            // Let the token be used again to fix the form.
+            setup_token.restore(token);
+
+            // Send notification.
+            FlashMessage::error(format!("Validation error: {}", e)).send();
+
+            Ok(see_other(&setup_url))
+        }
+    }
+}
@@ -0,0 +1,545 @@
+use crate::{
+    authentication::UserId,
+    domain::{Burndown, NewSprint, SprintName, SprintSnapshot, ValidSprint, ValidTicket},
+    routes::{change_sprint, get_current_project, is_project_member, SprintFormData, TicketError},
+    session_state::TypedSession,
+    startup::ReadPool,
+    utils::{e500, see_other},
+};
+use actix_web::{http::header::ContentType, web, HttpResponse};
+use actix_web_flash_messages::{FlashMessage, IncomingFlashMessages};
+use anyhow::Context;
+use askama::Template;
+use chrono::{NaiveDate, Utc};
+use serde::Deserialize;
+use sqlx::PgPool;
+use std::fmt::Write;
+use uuid::Uuid;
+
+/// Representation of a sprint with the story points of its tickets.
+struct SprintOverview {
+    sprint: ValidSprint,
+    status: &'static str,
+    remaining_points: i32,
+    total_points: i32,
+}
+
+impl SprintOverview {
+    /// Returns the overview of the sprint at the given day, with the points of its snapshot.
+    fn new(sprint: ValidSprint, snapshot: SprintSnapshot, today: NaiveDate) -> Self {
+        let status = if sprint.has_ended(today) {
+            "ended"
+        } else if sprint.is_active(today) {
+            "active"
+        } else {
+            "planned"
+        };
+        Self {
+            sprint,
+            status,
+            remaining_points: snapshot.remaining_points,
+            total_points: snapshot.total_points,
+        }
+    }
+}
+
+/// Representation of the sprints template.
+#[derive(Template)]
+#[template(path = "sprints.html")]
+struct SprintsTemplate {
+    msg_html: String,
+    sprints: Vec<SprintOverview>,
+    has_project: bool,
+}
+
+/// Representation of the sprint template.
+#[derive(Template)]
+#[template(path = "sprint.html")]
+struct SprintTemplate {
+    msg_html: String,
+    overview: SprintOverview,
+    tickets: Vec<ValidTicket>,
+    burndown_svg: String,
+}
+
+/// Representation of the backlog template.
+#[derive(Template)]
+#[template(path = "backlog.html")]
+struct BacklogTemplate {
+    msg_html: String,
+    tickets: Vec<ValidTicket>,
+    sprints: Vec<ValidSprint>,
+    has_project: bool,
+}
+
+/// Representation of a new sprint created with form data.
+#[derive(Deserialize)]
+pub struct NewSprintFormData {
+    name: String,
+    starts_on: String,
+    ends_on: String,
+}
+
+impl TryFrom<NewSprintFormData> for NewSprint {
+    type Error = String;
+
+    /// Performs the conversion.
+    fn try_from(value: NewSprintFormData) -> Result<Self, Self::Error> {
+        let name = SprintName::parse(value.name)?;
+
+        NewSprint::parse(name, &value.starts_on, &value.ends_on)
+    }
+}
+
+/// Sees the sprints of the current project.
+#[tracing::instrument(
+    name = "Seeing sprints",
+    skip(pool, read_pool, session, flash_messages, user_id),
+    fields(
+        user_id=%&*user_id
+    )
+)]
+pub async fn see_sprints(
+    pool: web::Data<PgPool>,
+    read_pool: web::Data<ReadPool>,
+    session: TypedSession,
+    flash_messages: IncomingFlashMessages,
+    user_id: web::ReqData<UserId>,
+) -> Result<HttpResponse, actix_web::Error> {
+    // Get notification.
+    let mut msg_html = String::new();
+    for m in flash_messages.iter() {
+        writeln!(msg_html, "{}", m.content()).unwrap();
+    }
+
+    let project = get_current_project(&pool, &session, **user_id)
+        .await
+        .map_err(e500)?;
+    let today = Utc::now().date_naive();
+    let sprints = match &project {
+        Some(project) => get_project_sprint_snapshots(&read_pool.0, project.id, today)
+            .await
+            .context("Failed to get the sprints details from the sprints table")
+            .map_err(e500)?
+            .into_iter()
+            .map(|(sprint, snapshot)| SprintOverview::new(sprint, snapshot, today))
+            .collect(),
+        None => Vec::new(),
+    };
+
+    let body = SprintsTemplate {
+        msg_html,
+        sprints,
+        has_project: project.is_some(),
+    }
+    .render()
+    .unwrap();
+
+    Ok(HttpResponse::Ok()
+        .content_type(ContentType::html())
+        .body(body))
+}
+
+/// Creates a new sprint in the current project.
+#[tracing::instrument(
+    name = "Creating a new sprint",
+    skip(pool, session, form, user_id),
+    fields(
+        sprint_name = %form.name,
+        user_id = %&*user_id
+    )
+)]
+pub async fn create_sprint(
+    pool: web::Data<PgPool>,
+    session: TypedSession,
+    form: web::Form<NewSprintFormData>,
+    user_id: web::ReqData<UserId>,
+) -> Result<HttpResponse, actix_web::Error> {
+    let new_sprint: NewSprint = match form.0.try_into() {
+        Ok(new_sprint) => new_sprint,
+        Err(e) => {
+            // Send notification.
+            FlashMessage::error(format!("Validation error: {}", e)).send();
+
+            return Ok(see_other("/dashboard/sprints"));
+        }
+    };
+
+    let Some(project) = get_current_project(&pool, &session, **user_id)
+        .await
+        .map_err(e500)?
+    else {
+        // Send notification.
+        FlashMessage::error("You are not a member of any project yet.").send();
+
+        return Ok(see_other("/dashboard/sprints"));
+    };
+
+    match insert_sprint(&pool, project.id, &new_sprint).await {
+        Ok(()) => {}
+        Err(sqlx::Error::Database(e)) if e.code().as_deref() == Some("23505") => {
+            // Send notification.
+            FlashMessage::error("This sprint name is already taken.").send();
+
+            return Ok(see_other("/dashboard/sprints"));
+        }
+        Err(e) => return Err(e500(e)),
+    }
+
+    // Send notification.
+    FlashMessage::info(format!(
+        "You have successfully created the sprint {}.",
+        new_sprint.name.as_ref()
+    ))
+    .send();
+
+    Ok(see_other("/dashboard/sprints"))
+}
+
+/// Sees a sprint with its tickets and its burndown chart.
+#[tracing::instrument(
+    name = "Seeing sprint",
+    skip(pool, flash_messages, user_id, sprint_id),
+    fields(
+        user_id=%&*user_id,
+        sprint_id=%sprint_id.0
+    )
+)]
+pub async fn see_sprint(
+    pool: web::Data<PgPool>,
+    flash_messages: IncomingFlashMessages,
+    user_id: web::ReqData<UserId>,
+    sprint_id: web::Path<(i32,)>,
+) -> Result<HttpResponse, actix_web::Error> {
+    // Get notification.
+    let mut msg_html = String::new();
+    for m in flash_messages.iter() {
+        writeln!(msg_html, "{}", m.content()).unwrap();
+    }
+
+    let Some(sprint) = get_accessible_sprint(&pool, sprint_id.0, **user_id).await? else {
+        return Ok(see_other("/dashboard/sprints"));
+    };
+    let tickets = get_sprint_tickets(&pool, sprint.id)
+        .await
+        .context("Failed to get the tickets details from the tickets table")
+        .map_err(e500)?;
+    let mut snapshots = get_sprint_snapshots(&pool, sprint.id)
+        .await
+        .context("Failed to get the snapshots from the sprint_snapshots table")
+        .map_err(e500)?;
+
+    // The snapshot of today is replaced by the current points until the day is over.
+    let today = Utc::now().date_naive();
+    if sprint.is_active(today) {
+        snapshots.retain(|snapshot| snapshot.taken_on != today);
+        snapshots.push(live_snapshot(&tickets, today));
+    }
+    let burndown_svg = Burndown::new(&sprint, snapshots).to_svg();
+
+    let body = SprintTemplate {
+        msg_html,
+        overview: SprintOverview::new(sprint, live_snapshot(&tickets, today), today),
+        tickets,
+        burndown_svg,
+    }
+    .render()
+    .unwrap();
+
+    Ok(HttpResponse::Ok()
+        .content_type(ContentType::html())
+        .body(body))
+}
+
+/// Sees the open tickets of the current project that are not planned in a sprint, ordered by rank.
+#[tracing::instrument(
+    name = "Seeing backlog",
+    skip(pool, read_pool, session, flash_messages, user_id),
+    fields(
+        user_id=%&*user_id
+    )
+)]
+pub async fn see_backlog(
+    pool: web::Data<PgPool>,
+    read_pool: web::Data<ReadPool>,
+    session: TypedSession,
+    flash_messages: IncomingFlashMessages,
+    user_id: web::ReqData<UserId>,
+) -> Result<HttpResponse, actix_web::Error> {
+    // Get notification.
+    let mut msg_html = String::new();
+    for m in flash_messages.iter() {
+        writeln!(msg_html, "{}", m.content()).unwrap();
+    }
+
+    let project = get_current_project(&pool, &session, **user_id)
+        .await
+        .map_err(e500)?;
+    let (tickets, sprints) = match &project {
+        Some(project) => {
+            let tickets = get_backlog_tickets(&read_pool.0, project.id)
+                .await
+                .context("Failed to get the tickets details from the tickets table")
+                .map_err(e500)?;
+            let today = Utc::now().date_naive();
+            let sprints = get_project_sprints(&read_pool.0, project.id)
+                .await
+                .map_err(e500)?
+                .into_iter()
+                .filter(|sprint| !sprint.has_ended(today))
+                .collect();
+
+            (tickets, sprints)
+        }
+        None => (Vec::new(), Vec::new()),
+    };
+
+    let body = BacklogTemplate {
+        msg_html,
+        tickets,
+        sprints,
+        has_project: project.is_some(),
+    }
+    .render()
+    .unwrap();
+
+    Ok(HttpResponse::Ok()
+        .content_type(ContentType::html())
+        .body(body))
+}
+
+/// Moves a ticket of the backlog into a sprint.
+#[tracing::instrument(
+    name = "Planning a ticket",
+    skip(pool, form, user_id, ticket_id),
+    fields(
+        user_id=%&*user_id,
+        ticket_id=%ticket_id.0
+    )
+)]
+pub async fn plan_ticket(
+    pool: web::Data<PgPool>,
+    form: web::Form<SprintFormData>,
+    user_id: web::ReqData<UserId>,
+    ticket_id: web::Path<(i32,)>,
+) -> Result<HttpResponse, TicketError> {
+    change_sprint(&pool, ticket_id.0, &form.0, **user_id).await?;
+
+    Ok(see_other("/dashboard/backlog"))
+}
+
+/// Returns the remaining and the total story points of the tickets at the given day.
+///
+/// # Implementation Notes
+///
+/// The tickets without story points count for nothing.
+fn live_snapshot(tickets: &[ValidTicket], today: NaiveDate) -> SprintSnapshot {
+    let points = |ticket: &ValidTicket| ticket.story_points.unwrap_or(0);
+
+    SprintSnapshot {
+        taken_on: today,
+        remaining_points: tickets.iter().filter(|t| t.is_open).map(points).sum(),
+        total_points: tickets.iter().map(points).sum(),
+    }
+}
+
+/// Returns the sprint if the user is a member of its project, or sends a notification.
+async fn get_accessible_sprint(
+    pool: &PgPool,
+    sprint_id: i32,
+    user_id: Uuid,
+) -> Result<Option<ValidSprint>, actix_web::Error> {
+    let sprint = get_sprint(pool, sprint_id).await.map_err(e500)?;
+
+    // Sprints of other projects are treated as if they did not exist.
+    let sprint = match sprint {
+        Some(sprint)
+            if is_project_member(pool, sprint.project_id, user_id)
+                .await
+                .map_err(e500)? =>
+        {
+            Some(sprint)
+        }
+        _ => {
+            // Send notification.
+            FlashMessage::error("This sprint does not exist.").send();
+
+            None
+        }
+    };
+
+    Ok(sprint)
+}
+
+/// Inserts the new sprint details into the `sprints` table.
+#[tracing::instrument(
+    name = "Inserting the new sprint details into the sprints table",
+    skip(pool, new_sprint)
+)]
+async fn insert_sprint(
+    pool: &PgPool,
+    project_id: i32,
+    new_sprint: &NewSprint,
+) -> Result<(), sqlx::Error> {
+    sqlx::query!(
+        r#"
+        INSERT INTO sprints (project_id, name, starts_on, ends_on, created_at)
+        VALUES ($1, $2, $3, $4, $5)
+        "#,
+        project_id,
+        new_sprint.name.as_ref(),
+        new_sprint.starts_on,
+        new_sprint.ends_on,
+        Utc::now()
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// Returns the sprint.
+#[tracing::instrument(name = "Getting sprint details from the sprints table", skip(pool))]
+pub async fn get_sprint(pool: &PgPool, sprint_id: i32) -> Result<Option<ValidSprint>, sqlx::Error> {
+    let sprint = sqlx::query_as!(
+        ValidSprint,
+        r#"SELECT * FROM sprints WHERE id = $1"#,
+        sprint_id
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(sprint)
+}
+
+/// Returns the sprints of the project, the most recent first.
+#[tracing::instrument(name = "Getting sprints details from the sprints table", skip(pool))]
+pub async fn get_project_sprints(
+    pool: &PgPool,
+    project_id: i32,
+) -> Result<Vec<ValidSprint>, sqlx::Error> {
+    let sprints = sqlx::query_as!(
+        ValidSprint,
+        r#"
+        SELECT *
+        FROM sprints
+        WHERE project_id = $1
+        ORDER BY starts_on DESC, id DESC
+        "#,
+        project_id
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(sprints)
+}
+
+/// Returns the sprints of the project with their current points, the most recent first.
+///
+/// # Implementation Notes
+///
+/// The tickets without story points count for nothing, as in [`live_snapshot`].
+#[tracing::instrument(name = "Getting sprints points from the sprints table", skip(pool))]
+async fn get_project_sprint_snapshots(
+    pool: &PgPool,
+    project_id: i32,
+    today: NaiveDate,
+) -> Result<Vec<(ValidSprint, SprintSnapshot)>, sqlx::Error> {
+    let rows = sqlx::query!(
+        r#"
+        SELECT sprints.*,
+            COALESCE(SUM(tickets.story_points) FILTER (WHERE tickets.is_open), 0)::INTEGER AS "remaining_points!",
+            COALESCE(SUM(tickets.story_points), 0)::INTEGER AS "total_points!"
+        FROM sprints
+        LEFT JOIN tickets ON tickets.sprint_id = sprints.id
+        WHERE sprints.project_id = $1
+        GROUP BY sprints.id
+        ORDER BY sprints.starts_on DESC, sprints.id DESC
+        "#,
+        project_id
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| {
+            (
+                ValidSprint {
+                    id: row.id,
+                    project_id: row.project_id,
+                    name: row.name,
+                    starts_on: row.starts_on,
+                    ends_on: row.ends_on,
+                    created_at: row.created_at,
+                },
+                SprintSnapshot {
+                    taken_on: today,
+                    remaining_points: row.remaining_points,
+                    total_points: row.total_points,
+                },
+            )
+        })
+        .collect())
+}
+
+/// Returns the tickets of the sprint, ordered by rank.
+#[tracing::instrument(name = "Getting the tickets of a sprint", skip(pool))]
+async fn get_sprint_tickets(
+    pool: &PgPool,
+    sprint_id: i32,
+) -> Result<Vec<ValidTicket>, sqlx::Error> {
+    let tickets = sqlx::query_as!(
+        ValidTicket,
+        "SELECT * FROM tickets WHERE sprint_id = $1 ORDER BY rank, id",
+        sprint_id
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(tickets)
+}
+
+/// Returns the open tickets of the project that are not planned in a sprint, ordered by rank.
+#[tracing::instrument(name = "Getting the tickets of the backlog", skip(pool))]
+async fn get_backlog_tickets(
+    pool: &PgPool,
+    project_id: i32,
+) -> Result<Vec<ValidTicket>, sqlx::Error> {
+    let tickets = sqlx::query_as!(
+        ValidTicket,
+        r#"
+        SELECT *
+        FROM tickets
+        WHERE project_id = $1 AND sprint_id IS NULL AND is_open = TRUE
+        ORDER BY rank, id
+        "#,
+        project_id
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(tickets)
+}
+
+/// Returns the daily snapshots of the sprint.
+#[tracing::instrument(name = "Getting the snapshots of a sprint", skip(pool))]
+async fn get_sprint_snapshots(
+    pool: &PgPool,
+    sprint_id: i32,
+) -> Result<Vec<SprintSnapshot>, sqlx::Error> {
+    let snapshots = sqlx::query_as!(
+        SprintSnapshot,
+        r#"
+        SELECT taken_on, remaining_points, total_points
+        FROM sprint_snapshots
+        WHERE sprint_id = $1
+        ORDER BY taken_on
+        "#,
+        sprint_id
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(snapshots)
+}
@@ -0,0 +1,70 @@
+use crate::{
+    authentication::UserId, routes::get_member_projects, shutdown::Shutdown,
+    ticket_events::TicketEventBroadcaster, utils::e500,
+};
+use actix_web::{web, Responder};
+use actix_web_lab::sse;
+use sqlx::PgPool;
+use std::{collections::HashSet, time::Duration};
+use tokio::sync::broadcast::error::RecvError;
+
+/// Streams the ticket events as Server-Sent Events.
+///
+/// # Implementation Notes
+///
+/// Every event is sent twice: once named `ticket` for the views listing tickets,
+/// and once named `ticket-{id}` for the views of this ticket only.
+///
+/// The events triggered by the user are skipped, their view is already up to date.
+/// So are the events of the projects the user was not a member of when connecting.
+///
+/// The stream ends when the application shuts down, so that it does not hold up the shutdown.
+pub async fn ticket_events(
+    pool: web::Data<PgPool>,
+    broadcaster: web::Data<TicketEventBroadcaster>,
+    shutdown: web::Data<Shutdown>,
+    user_id: web::ReqData<UserId>,
+) -> Result<impl Responder, actix_web::Error> {
+    let user_id = **user_id;
+    let project_ids: HashSet<i32> = get_member_projects(&pool, user_id)
+        .await
+        .map_err(e500)?
+        .into_iter()
+        .map(|project| project.id)
+        .collect();
+    let mut receiver = broadcaster.subscribe();
+    let (sender, stream) = sse::channel(10);
+
+    actix_web::rt::spawn(async move {
+        loop {
+            let received = tokio::select! {
+                received = receiver.recv() => received,
+                _ = shutdown.wait() => break,
+            };
+            let event = match received {
+                Ok(event) => event,
+                // Missed events are not replayed, the next one still refreshes the view.
+                Err(RecvError::Lagged(_)) => continue,
+                Err(RecvError::Closed) => break,
+            };
+
+            if event.triggered_by == user_id || !project_ids.contains(&event.project_id) {
+                continue;
+            }
+
+            let data = match sse::Data::new_json(&event) {
+                Ok(data) => data,
+                Err(_) => continue,
+            };
+
+            // Stop as soon as the client disconnects.
+            if sender.send(data.clone().event("ticket")).await.is_err()
+                || sender.send(data.event(event.sse_name())).await.is_err()
+            {
+                break;
+            }
+        }
+    });
+
+    Ok(stream.with_keep_alive(Duration::from_secs(15)))
+}
@@ -0,0 +1,768 @@
+use crate::{
+    authentication::UserId,
+    domain::{
+        NotificationKind, StoryPoints, TicketChange, TicketDueDate, TicketEvent, TicketEventKind,
+        TimeEntryDuration, ValidMilestone, ValidSprint, ValidTicket,
+    },
+    helpers::get_username,
+    routes::{
+        can_access_ticket, get_milestone, get_sprint, get_ticket_changes_since,
+        get_user_id_by_username, insert_ticket_change, is_project_member, lock_ticket,
+        notify_ticket_assignee, TicketError, TicketUpdate,
+    },
+    ticket_events::publish_ticket_event,
+    utils::see_other,
+};
+use actix_web::{web, HttpResponse};
+use actix_web_flash_messages::FlashMessage;
+use anyhow::Context;
+use chrono::{NaiveDate, Utc};
+use sqlx::{PgPool, Postgres, Transaction};
+use uuid::Uuid;
+
+/// Representation of the form data to set the due date of a ticket.
+#[derive(serde::Deserialize)]
+pub struct DueDateFormData {
+    version: i32,
+    due_date: String,
+}
+
+/// Representation of the form data to assign a ticket.
+#[derive(serde::Deserialize)]
+pub struct AssigneeFormData {
+    version: i32,
+    username: String,
+}
+
+/// Representation of the form data to set the original estimate of a ticket.
+#[derive(serde::Deserialize)]
+pub struct EstimateFormData {
+    version: i32,
+    estimate: String,
+}
+
+/// Representation of the form data to add a ticket to a milestone.
+#[derive(serde::Deserialize)]
+pub struct MilestoneFormData {
+    version: i32,
+    milestone_id: String,
+}
+
+/// Representation of the form data to move a ticket into a sprint.
+#[derive(serde::Deserialize)]
+pub struct SprintFormData {
+    version: i32,
+    sprint_id: String,
+}
+
+/// Representation of the form data to set the story points of a ticket.
+#[derive(serde::Deserialize)]
+pub struct StoryPointsFormData {
+    version: i32,
+    story_points: String,
+}
+
+/// Representation of a change of the planning fields of a ticket.
+#[derive(Debug)]
+pub enum TicketFieldUpdate {
+    DueDate(Option<NaiveDate>),
+    Assignee(Option<String>),
+    Estimate(Option<TimeEntryDuration>),
+    Milestone(Option<ValidMilestone>),
+    Sprint(Option<ValidSprint>),
+    StoryPoints(Option<StoryPoints>),
+}
+
+impl TicketFieldUpdate {
+    /// Returns the name of the field as recorded in the history.
+    fn field(&self) -> &'static str {
+        match self {
+            TicketFieldUpdate::DueDate(_) => "due_date",
+            TicketFieldUpdate::Assignee(_) => "assignee",
+            TicketFieldUpdate::Estimate(_) => "estimate",
+            TicketFieldUpdate::Milestone(_) => "milestone",
+            TicketFieldUpdate::Sprint(_) => "sprint",
+            TicketFieldUpdate::StoryPoints(_) => "story_points",
+        }
+    }
+
+    /// Returns the new value as recorded in the history.
+    fn new_value(&self) -> String {
+        match self {
+            TicketFieldUpdate::DueDate(due_on) => due_on.map(|d| d.to_string()),
+            TicketFieldUpdate::Assignee(assignee) => assignee.clone(),
+            TicketFieldUpdate::Estimate(estimate) => estimate.map(|e| e.to_string()),
+            TicketFieldUpdate::Milestone(milestone) => milestone.as_ref().map(|m| m.name.clone()),
+            TicketFieldUpdate::Sprint(sprint) => sprint.as_ref().map(|s| s.name.clone()),
+            TicketFieldUpdate::StoryPoints(points) => points.map(|p| p.points().to_string()),
+        }
+        .unwrap_or_else(|| "none".to_string())
+    }
+
+    /// Returns the current value of the ticket as recorded in the history.
+    async fn old_value(&self, pool: &PgPool, ticket: &ValidTicket) -> Result<String, sqlx::Error> {
+        let old_value = match self {
+            TicketFieldUpdate::DueDate(_) => ticket.due_on.map(|d| d.to_string()),
+            TicketFieldUpdate::Assignee(_) => ticket.assigned_to.clone(),
+            TicketFieldUpdate::Estimate(_) => ticket
+                .estimate_minutes
+                .map(|m| TimeEntryDuration::from_minutes(m).to_string()),
+            TicketFieldUpdate::Milestone(_) => match ticket.milestone_id {
+                Some(milestone_id) => get_milestone(pool, milestone_id).await?.map(|m| m.name),
+                None => None,
+            },
+            TicketFieldUpdate::Sprint(_) => match ticket.sprint_id {
+                Some(sprint_id) => get_sprint(pool, sprint_id).await?.map(|s| s.name),
+                None => None,
+            },
+            TicketFieldUpdate::StoryPoints(_) => ticket.story_points.map(|p| p.to_string()),
+        };
+
+        Ok(old_value.unwrap_or_else(|| "none".to_string()))
+    }
+}
+
+/// Sets the due date of a ticket.
+#[tracing::instrument(
+    name = "Setting the due date of a ticket",
+    skip(pool, form, user_id, ticket_id),
+    fields(
+        user_id=%&*user_id,
+        ticket_id=%ticket_id.0,
+        version=%form.version
+    )
+)]
+pub async fn set_due_date(
+    pool: web::Data<PgPool>,
+    form: web::Form<DueDateFormData>,
+    user_id: web::ReqData<UserId>,
+    ticket_id: web::Path<(i32,)>,
+) -> Result<HttpResponse, TicketError> {
+    let ticket_id = ticket_id.into_inner().0;
+    let location = format!("/dashboard/tickets/{}", ticket_id);
+
+    let due_date = match TicketDueDate::parse(&form.due_date) {
+        Ok(due_date) => due_date,
+        Err(e) => {
+            // Send notification.
+            FlashMessage::error(format!("Validation error: {}", e)).send();
+
+            return Ok(see_other(location.as_str()));
+        }
+    };
+    let change = TicketFieldUpdate::DueDate(due_date.map(|d| d.date()));
+
+    let update =
+        update_accessible_ticket(&pool, ticket_id, form.version, **user_id, &change).await?;
+    match update {
+        TicketUpdate::Updated(_) => {
+            // Send notification.
+            FlashMessage::info("You have successfully changed the due date of this ticket.").send();
+        }
+        other => handle_unsuccessful_update(ticket_id, other)?,
+    }
+
+    Ok(see_other(location.as_str()))
+}
+
+/// Assigns a ticket to a member of its project, or unassigns it.
+#[tracing::instrument(
+    name = "Assigning a ticket",
+    skip(pool, form, user_id, ticket_id),
+    fields(
+        user_id=%&*user_id,
+        ticket_id=%ticket_id.0,
+        version=%form.version
+    )
+)]
+pub async fn assign_ticket(
+    pool: web::Data<PgPool>,
+    form: web::Form<AssigneeFormData>,
+    user_id: web::ReqData<UserId>,
+    ticket_id: web::Path<(i32,)>,
+) -> Result<HttpResponse, TicketError> {
+    let ticket_id = ticket_id.into_inner().0;
+    let location = format!("/dashboard/tickets/{}", ticket_id);
+
+    let username = form.username.trim();
+    let change = TicketFieldUpdate::Assignee((!username.is_empty()).then(|| username.to_string()));
+
+    let update =
+        update_accessible_ticket(&pool, ticket_id, form.version, **user_id, &change).await?;
+    match update {
+        TicketUpdate::Updated(ticket) => {
+            let message = match ticket.assigned_to {
+                Some(assignee) => {
+                    format!("You have successfully assigned this ticket to {assignee}.")
+                }
+                None => "You have successfully unassigned this ticket.".to_string(),
+            };
+            // Send notification.
+            FlashMessage::info(message).send();
+        }
+        other => handle_unsuccessful_update(ticket_id, other)?,
+    }
+
+    Ok(see_other(location.as_str()))
+}
+
+/// Sets the original estimate of a ticket.
+#[tracing::instrument(
+    name = "Setting the estimate of a ticket",
+    skip(pool, form, user_id, ticket_id),
+    fields(
+        user_id=%&*user_id,
+        ticket_id=%ticket_id.0,
+        version=%form.version
+    )
+)]
+pub async fn set_estimate(
+    pool: web::Data<PgPool>,
+    form: web::Form<EstimateFormData>,
+    user_id: web::ReqData<UserId>,
+    ticket_id: web::Path<(i32,)>,
+) -> Result<HttpResponse, TicketError> {
+    let ticket_id = ticket_id.into_inner().0;
+    let location = format!("/dashboard/tickets/{}", ticket_id);
+
+    let estimate = match form.estimate.trim() {
+        "" => None,
+        estimate => match TimeEntryDuration::parse(estimate) {
+            Ok(estimate) => Some(estimate),
+            Err(e) => {
+                // Send notification.
+                FlashMessage::error(format!("Validation error: {}", e)).send();
+
+                return Ok(see_other(location.as_str()));
+            }
+        },
+    };
+    let change = TicketFieldUpdate::Estimate(estimate);
+
+    let update =
+        update_accessible_ticket(&pool, ticket_id, form.version, **user_id, &change).await?;
+    match update {
+        TicketUpdate::Updated(_) => {
+            // Send notification.
+            FlashMessage::info("You have successfully changed the estimate of this ticket.").send();
+        }
+        other => handle_unsuccessful_update(ticket_id, other)?,
+    }
+
+    Ok(see_other(location.as_str()))
+}
+
+/// Adds a ticket to a milestone of its project, or removes it from its milestone.
+#[tracing::instrument(
+    name = "Setting the milestone of a ticket",
+    skip(pool, form, user_id, ticket_id),
+    fields(
+        user_id=%&*user_id,
+        ticket_id=%ticket_id.0,
+        version=%form.version
+    )
+)]
+pub async fn set_milestone(
+    pool: web::Data<PgPool>,
+    form: web::Form<MilestoneFormData>,
+    user_id: web::ReqData<UserId>,
+    ticket_id: web::Path<(i32,)>,
+) -> Result<HttpResponse, TicketError> {
+    let ticket_id = ticket_id.into_inner().0;
+    let location = format!("/dashboard/tickets/{}", ticket_id);
+
+    let milestone = match form.milestone_id.trim() {
+        "" => None,
+        milestone_id => {
+            let milestone = match milestone_id.parse::<i32>() {
+                Ok(milestone_id) => get_milestone(&pool, milestone_id)
+                    .await
+                    .context("Failed to get the milestone details from the milestones table")?,
+                Err(_) => None,
+            };
+            match milestone {
+                Some(milestone) => Some(milestone),
+                None => {
+                    // Send notification.
+                    FlashMessage::error("This milestone does not exist.").send();
+
+                    return Ok(see_other(location.as_str()));
+                }
+            }
+        }
+    };
+    let change = TicketFieldUpdate::Milestone(milestone);
+
+    let update =
+        update_accessible_ticket(&pool, ticket_id, form.version, **user_id, &change).await?;
+    match update {
+        TicketUpdate::Updated(_) => {
+            // Send notification.
+            FlashMessage::info("You have successfully changed the milestone of this ticket.")
+                .send();
+        }
+        other => handle_unsuccessful_update(ticket_id, other)?,
+    }
+
+    Ok(see_other(location.as_str()))
+}
+
+/// Moves a ticket into a sprint of its project, or back to the backlog.
+#[tracing::instrument(
+    name = "Setting the sprint of a ticket",
+    skip(pool, form, user_id, ticket_id),
+    fields(
+        user_id=%&*user_id,
+        ticket_id=%ticket_id.0,
+        version=%form.version
+    )
+)]
+pub async fn set_sprint(
+    pool: web::Data<PgPool>,
+    form: web::Form<SprintFormData>,
+    user_id: web::ReqData<UserId>,
+    ticket_id: web::Path<(i32,)>,
+) -> Result<HttpResponse, TicketError> {
+    let ticket_id = ticket_id.into_inner().0;
+    change_sprint(&pool, ticket_id, &form.0, **user_id).await?;
+
+    Ok(see_other(&format!("/dashboard/tickets/{}", ticket_id)))
+}
+
+/// Moves a ticket into a sprint, or back to the backlog, and sends the notification of the outcome.
+pub async fn change_sprint(
+    pool: &PgPool,
+    ticket_id: i32,
+    form: &SprintFormData,
+    user_id: Uuid,
+) -> Result<(), TicketError> {
+    let sprint = match form.sprint_id.trim() {
+        "" => None,
+        sprint_id => {
+            let sprint = match sprint_id.parse::<i32>() {
+                Ok(sprint_id) => get_sprint(pool, sprint_id)
+                    .await
+                    .context("Failed to get the sprint details from the sprints table")?,
+                Err(_) => None,
+            };
+            match sprint {
+                Some(sprint) => Some(sprint),
+                None => {
+                    // Send notification.
+                    FlashMessage::error("This sprint does not exist.").send();
+
+                    return Ok(());
+                }
+            }
+        }
+    };
+    let change = TicketFieldUpdate::Sprint(sprint);
+
+    let update = update_accessible_ticket(pool, ticket_id, form.version, user_id, &change).await?;
+    match update {
+        TicketUpdate::Updated(ticket) => {
+            let message = match change {
+                TicketFieldUpdate::Sprint(Some(sprint)) => format!(
+                    "You have successfully moved the ticket {} into the sprint {}.",
+                    ticket.key, sprint.name
+                ),
+                _ => format!(
+                    "You have successfully moved the ticket {} back to the backlog.",
+                    ticket.key
+                ),
+            };
+            // Send notification.
+            FlashMessage::info(message).send();
+        }
+        other => handle_unsuccessful_update(ticket_id, other)?,
+    }
+
+    Ok(())
+}
+
+/// Sets the story points of a ticket.
+#[tracing::instrument(
+    name = "Setting the story points of a ticket",
+    skip(pool, form, user_id, ticket_id),
+    fields(
+        user_id=%&*user_id,
+        ticket_id=%ticket_id.0,
+        version=%form.version
+    )
+)]
+pub async fn set_story_points(
+    pool: web::Data<PgPool>,
+    form: web::Form<StoryPointsFormData>,
+    user_id: web::ReqData<UserId>,
+    ticket_id: web::Path<(i32,)>,
+) -> Result<HttpResponse, TicketError> {
+    let ticket_id = ticket_id.into_inner().0;
+    let location = format!("/dashboard/tickets/{}", ticket_id);
+
+    let story_points = match StoryPoints::parse(&form.story_points) {
+        Ok(story_points) => story_points,
+        Err(e) => {
+            // Send notification.
+            FlashMessage::error(format!("Validation error: {}", e)).send();
+
+            return Ok(see_other(location.as_str()));
+        }
+    };
+    let change = TicketFieldUpdate::StoryPoints(story_points);
+
+    let update =
+        update_accessible_ticket(&pool, ticket_id, form.version, **user_id, &change).await?;
+    match update {
+        TicketUpdate::Updated(_) => {
+            // Send notification.
+            FlashMessage::info("You have successfully changed the story points of this ticket.")
+                .send();
+        }
+        other => handle_unsuccessful_update(ticket_id, other)?,
+    }
+
+    Ok(see_other(location.as_str()))
+}
+
+/// Updates the ticket, treating the tickets of other projects as if they did not exist.
+async fn update_accessible_ticket(
+    pool: &PgPool,
+    ticket_id: i32,
+    version: i32,
+    user_id: Uuid,
+    change: &TicketFieldUpdate,
+) -> Result<TicketUpdate, TicketError> {
+    if can_access_ticket(pool, ticket_id, user_id)
+        .await
+        .context("Failed to check the project membership")?
+    {
+        Ok(update_ticket_field_at_version(pool, ticket_id, version, user_id, change).await?)
+    } else {
+        Ok(TicketUpdate::NotFound)
+    }
+}
+
+/// Sends the notification of an update that did not succeed.
+fn handle_unsuccessful_update(ticket_id: i32, update: TicketUpdate) -> Result<(), TicketError> {
+    match update {
+        TicketUpdate::Rejected(e) => {
+            // Send notification.
+            FlashMessage::error(format!("Validation error: {}", e)).send();
+        }
+        TicketUpdate::Updated(_) | TicketUpdate::Unchanged => {
+            // Send notification.
+            FlashMessage::info("This ticket is already up to date.").send();
+        }
+        TicketUpdate::NotFound => {
+            // Send notification.
+            FlashMessage::error("This ticket does not exist.").send();
+        }
+        TicketUpdate::Conflict(changes) => {
+            return Err(TicketError::Conflict(ticket_id, changes));
+        }
+    }
+
+    Ok(())
+}
+
+/// Changes a planning field of a ticket if it has not been changed since the given version.
+///
+/// # Implementation Notes
+///
+/// The ticket is locked until the change is committed,
+/// concurrent writes are serialized and only the first one succeeds.
+/// Only the members, the open milestones and the sprints that have not ended
+/// of the project of the ticket can be chosen.
+#[tracing::instrument(name = "Updating ticket field at a given version", skip(pool))]
+pub async fn update_ticket_field_at_version(
+    pool: &PgPool,
+    id: i32,
+    version: i32,
+    user_id: Uuid,
+    change: &TicketFieldUpdate,
+) -> Result<TicketUpdate, anyhow::Error> {
+    let username = get_username(pool, user_id).await?;
+
+    let mut transaction = pool
+        .begin()
+        .await
+        .context("Failed to acquire a Postgres connection from the pool")?;
+
+    let ticket = match lock_ticket(&mut transaction, id)
+        .await
+        .context("Failed to lock the ticket from the tickets table")?
+    {
+        Some(ticket) => ticket,
+        None => return Ok(TicketUpdate::NotFound),
+    };
+
+    // Someone else has changed the ticket in the meantime.
+    if ticket.version != version {
+        let changes = get_ticket_changes_since(&mut transaction, id, version)
+            .await
+            .context("Failed to get the ticket changes from the ticket_changes table")?;
+
+        return Ok(TicketUpdate::Conflict(changes));
+    }
+
+    let old_value = change
+        .old_value(pool, &ticket)
+        .await
+        .context("Failed to get the current value of the ticket")?;
+    if old_value == change.new_value() {
+        return Ok(TicketUpdate::Unchanged);
+    }
+
+    if let TicketFieldUpdate::Milestone(Some(milestone)) = change {
+        if milestone.project_id != ticket.project_id {
+            return Ok(TicketUpdate::Rejected(
+                "this milestone does not belong to the project of this ticket.".to_string(),
+            ));
+        }
+        if !milestone.is_open {
+            return Ok(TicketUpdate::Rejected(format!(
+                "the milestone {} is closed.",
+                milestone.name
+            )));
+        }
+    }
+
+    if let TicketFieldUpdate::Sprint(Some(sprint)) = change {
+        if sprint.project_id != ticket.project_id {
+            return Ok(TicketUpdate::Rejected(
+                "this sprint does not belong to the project of this ticket.".to_string(),
+            ));
+        }
+        if sprint.has_ended(Utc::now().date_naive()) {
+            return Ok(TicketUpdate::Rejected(format!(
+                "the sprint {} has ended.",
+                sprint.name
+            )));
+        }
+    }
+
+    if let TicketFieldUpdate::Assignee(Some(assignee)) = change {
+        let is_member = match get_user_id_by_username(pool, assignee)
+            .await
+            .context("Failed to get the user id from the users table")?
+        {
+            Some(assignee_id) => is_project_member(pool, ticket.project_id, assignee_id)
+                .await
+                .context("Failed to check the project membership")?,
+            None => false,
+        };
+        if !is_member {
+            return Ok(TicketUpdate::Rejected(format!(
+                "{assignee} is not a member of the project of this ticket."
+            )));
+        }
+    }
+
+    let ticket = update_field(&mut transaction, id, change)
+        .await
+        .context("Failed to update the ticket from the tickets table")?;
+    insert_ticket_change(
+        &mut transaction,
+        &TicketChange {
+            ticket_id: id,
+            version: ticket.version,
+            field: change.field().to_string(),
+            old_value,
+            new_value: change.new_value(),
+            changed_by: username.clone(),
+            changed_at: Utc::now(),
+        },
+    )
+    .await
+    .context("Failed to insert the ticket change into the ticket_changes table")?;
+
+    transaction
+        .commit()
+        .await
+        .context("Failed to commit SQL transaction to update the ticket")?;
+
+    // The ticket is updated from now on, failing to spread the news must not fail the request.
+    //
+    // Let the other users know that the ticket has changed.
+    let event = TicketEvent {
+        ticket_id: id,
+        project_id: ticket.project_id,
+        kind: TicketEventKind::Updated,
+        triggered_by: user_id,
+    };
+    if let Err(e) = publish_ticket_event(pool, &event).await {
+        tracing::error!(
+            error.cause_chain = ?e,
+            error.message = %e,
+            "Failed to publish the ticket event"
+        );
+    }
+
+    // Notify the assignee of the ticket.
+    let (kind, message) = match change {
+        TicketFieldUpdate::Assignee(_) => (
+            NotificationKind::Assignment,
+            format!("{} assigned you the ticket {}.", username, ticket.key),
+        ),
+        TicketFieldUpdate::DueDate(_) => (
+            NotificationKind::FieldChange,
+            format!(
+                "{} changed the due date of the ticket {} to {}.",
+                username,
+                ticket.key,
+                change.new_value()
+            ),
+        ),
+        TicketFieldUpdate::Estimate(_) => (
+            NotificationKind::FieldChange,
+            format!(
+                "{} changed the estimate of the ticket {} to {}.",
+                username,
+                ticket.key,
+                change.new_value()
+            ),
+        ),
+        TicketFieldUpdate::Milestone(_) => (
+            NotificationKind::FieldChange,
+            format!(
+                "{} changed the milestone of the ticket {} to {}.",
+                username,
+                ticket.key,
+                change.new_value()
+            ),
+        ),
+        TicketFieldUpdate::Sprint(_) => (
+            NotificationKind::FieldChange,
+            format!(
+                "{} changed the sprint of the ticket {} to {}.",
+                username,
+                ticket.key,
+                change.new_value()
+            ),
+        ),
+        TicketFieldUpdate::StoryPoints(_) => (
+            NotificationKind::FieldChange,
+            format!(
+                "{} changed the story points of the ticket {} to {}.",
+                username,
+                ticket.key,
+                change.new_value()
+            ),
+        ),
+    };
+    if let Err(e) = notify_ticket_assignee(pool, id, Some(user_id), kind, &message).await {
+        tracing::error!(
+            error.cause_chain = ?e,
+            error.message = %e,
+            "Failed to insert the notification into the notifications table"
+        );
+    }
+
+    Ok(TicketUpdate::Updated(Box::new(ticket)))
+}
+
+/// Updates a planning field from the tickets table and then return the new ticket.
+#[tracing::instrument(
+    name = "Updating a field from the tickets table",
+    skip(transaction, id)
+)]
+async fn update_field(
+    transaction: &mut Transaction<'_, Postgres>,
+    id: i32,
+    change: &TicketFieldUpdate,
+) -> Result<ValidTicket, sqlx::Error> {
+    let ticket = match change {
+        TicketFieldUpdate::DueDate(due_on) => {
+            sqlx::query_as!(
+                ValidTicket,
+                r#"
+                UPDATE tickets
+                SET due_on = $1, version = version + 1
+                WHERE id = $2
+                RETURNING *
+                "#,
+                *due_on,
+                id
+            )
+            .fetch_one(transaction)
+            .await?
+        }
+        TicketFieldUpdate::Assignee(assignee) => {
+            sqlx::query_as!(
+                ValidTicket,
+                r#"
+                UPDATE tickets
+                SET assigned_to = $1, version = version + 1
+                WHERE id = $2
+                RETURNING *
+                "#,
+                assignee.as_deref(),
+                id
+            )
+            .fetch_one(transaction)
+            .await?
+        }
+        TicketFieldUpdate::Milestone(milestone) => {
+            sqlx::query_as!(
+                ValidTicket,
+                r#"
+                UPDATE tickets
+                SET milestone_id = $1, version = version + 1
+                WHERE id = $2
+                RETURNING *
+                "#,
+                milestone.as_ref().map(|m| m.id),
+                id
+            )
+            .fetch_one(transaction)
+            .await?
+        }
+        TicketFieldUpdate::Sprint(sprint) => {
+            sqlx::query_as!(
+                ValidTicket,
+                r#"
+                UPDATE tickets
+                SET sprint_id = $1, version = version + 1
+                WHERE id = $2
+                RETURNING *
+                "#,
+                sprint.as_ref().map(|s| s.id),
+                id
+            )
+            .fetch_one(transaction)
+            .await?
+        }
+        TicketFieldUpdate::StoryPoints(points) => {
+            sqlx::query_as!(
+                ValidTicket,
+                r#"
+                UPDATE tickets
+                SET story_points = $1, version = version + 1
+                WHERE id = $2
+                RETURNING *
+                "#,
+                points.map(|p| p.points()),
+                id
+            )
+            .fetch_one(transaction)
+            .await?
+        }
+        TicketFieldUpdate::Estimate(estimate) => {
+            sqlx::query_as!(
+                ValidTicket,
+                r#"
+                UPDATE tickets
+                SET estimate_minutes = $1, version = version + 1
+                WHERE id = $2
+                RETURNING *
+                "#,
+                estimate.map(|e| e.minutes()),
+                id
+            )
+            .fetch_one(transaction)
+            .await?
+        }
+    };
+
+    Ok(ticket)
+}
@@ -0,0 +1,426 @@
+use crate::{
+    authentication::UserId,
+    domain::{
+        NewTicketLink, TicketEvent, TicketEventKind, TicketLinkKind, TicketReference,
+        TicketRelation, ValidTicket,
+    },
+    error::error_chain_fmt,
+    helpers::get_username,
+    routes::{can_access_ticket, get_ticket, get_ticket_by_key},
+    ticket_events::publish_ticket_event,
+    utils::{e500, see_other},
+};
+use actix_web::{web, HttpResponse};
+use actix_web_flash_messages::FlashMessage;
+use anyhow::Context;
+use chrono::Utc;
+use serde::Deserialize;
+use sqlx::{PgPool, Postgres, Transaction};
+use std::fmt::Debug;
+use uuid::Uuid;
+
+/// Key of the advisory lock serializing the creation of links.
+const TICKET_LINKS_LOCK_KEY: i64 = 0x7469_636b_6c6e;
+
+/// Representation of a link seen from one of its tickets.
+pub struct TicketLinkOverview {
+    pub id: i32,
+    pub relation: TicketRelation,
+    pub ticket_key: String,
+    pub ticket_title: String,
+    pub ticket_is_open: bool,
+}
+
+/// Representation of a new link created with form data.
+#[derive(Deserialize)]
+pub struct NewTicketLinkFormData {
+    relation: String,
+    reference: String,
+}
+
+/// Representation of a ticket link error.
+#[derive(thiserror::Error)]
+pub enum TicketLinkError {
+    #[error("Validation error: {0}")]
+    ValidationError(String),
+    #[error(transparent)]
+    UnexpectedError(#[from] anyhow::Error),
+}
+
+impl Debug for TicketLinkError {
+    /// Formats the value using the given formatter.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        error_chain_fmt(self, f)
+    }
+}
+
+/// Links a ticket to another one, found by key or by id.
+#[tracing::instrument(
+    name = "Linking tickets",
+    skip(pool, form, user_id, ticket_id),
+    fields(
+        user_id=%&*user_id,
+        ticket_id=%ticket_id.0,
+        relation=%form.relation,
+        reference=%form.reference
+    )
+)]
+pub async fn create_ticket_link(
+    pool: web::Data<PgPool>,
+    form: web::Form<NewTicketLinkFormData>,
+    user_id: web::ReqData<UserId>,
+    ticket_id: web::Path<(i32,)>,
+) -> Result<HttpResponse, actix_web::Error> {
+    let ticket_id = ticket_id.into_inner().0;
+    let location = format!("/dashboard/tickets/{}", ticket_id);
+
+    // Tickets of other projects are treated as if they did not exist.
+    let ticket = find_accessible_ticket(&pool, &ticket_id.to_string(), **user_id)
+        .await
+        .map_err(e500)?;
+    let other = find_accessible_ticket(&pool, &form.reference, **user_id)
+        .await
+        .map_err(e500)?;
+    let (ticket, other) = match (ticket, other) {
+        (Some(ticket), Some(other)) => (ticket, other),
+        (None, _) => {
+            // Send notification.
+            FlashMessage::error("This ticket does not exist.").send();
+
+            return Ok(see_other(&location));
+        }
+        (_, None) => {
+            // Send notification.
+            FlashMessage::error("The linked ticket does not exist.").send();
+
+            return Ok(see_other(&location));
+        }
+    };
+
+    let link = match TicketRelation::try_from(form.0.relation)
+        .and_then(|relation| NewTicketLink::parse(ticket.id, relation, other.id))
+    {
+        Ok(link) => link,
+        Err(e) => {
+            // Send notification.
+            FlashMessage::error(format!("Validation error: {}", e)).send();
+
+            return Ok(see_other(&location));
+        }
+    };
+
+    let created_by = get_username(&pool, **user_id).await.map_err(e500)?;
+    let mut transaction = pool
+        .begin()
+        .await
+        .context("Failed to acquire a Postgres connection from the pool")
+        .map_err(e500)?;
+    match insert_ticket_link(&mut transaction, &link, &created_by).await {
+        Ok(()) => {}
+        Err(TicketLinkError::ValidationError(e)) => {
+            // Send notification.
+            FlashMessage::error(format!("Validation error: {}", e)).send();
+
+            return Ok(see_other(&location));
+        }
+        Err(e) => return Err(e500(e)),
+    }
+    transaction
+        .commit()
+        .await
+        .context("Failed to commit SQL transaction to link the tickets")
+        .map_err(e500)?;
+
+    // Let the other users know that both tickets have changed.
+    publish_link_events(&pool, &[&ticket, &other], **user_id).await;
+
+    // Send notification.
+    FlashMessage::info("You have successfully linked the tickets.").send();
+
+    Ok(see_other(&location))
+}
+
+/// Removes a link of a ticket.
+#[tracing::instrument(
+    name = "Unlinking tickets",
+    skip(pool, user_id, path),
+    fields(
+        user_id=%&*user_id,
+        ticket_id=%path.0,
+        link_id=%path.1
+    )
+)]
+pub async fn delete_ticket_link(
+    pool: web::Data<PgPool>,
+    user_id: web::ReqData<UserId>,
+    path: web::Path<(i32, i32)>,
+) -> Result<HttpResponse, actix_web::Error> {
+    let (ticket_id, link_id) = path.into_inner();
+    let location = format!("/dashboard/tickets/{}", ticket_id);
+
+    if !can_access_ticket(&pool, ticket_id, **user_id)
+        .await
+        .context("Failed to check the project membership")
+        .map_err(e500)?
+    {
+        // Send notification.
+        FlashMessage::error("This ticket does not exist.").send();
+
+        return Ok(see_other(&location));
+    }
+
+    let Some((source_id, target_id)) = remove_ticket_link(&pool, ticket_id, link_id)
+        .await
+        .context("Failed to delete the link from the ticket_links table")
+        .map_err(e500)?
+    else {
+        // Send notification.
+        FlashMessage::error("This link does not exist.").send();
+
+        return Ok(see_other(&location));
+    };
+
+    // Let the other users know that both tickets have changed.
+    match (
+        get_ticket(&pool, source_id).await,
+        get_ticket(&pool, target_id).await,
+    ) {
+        (Ok(source), Ok(target)) => {
+            publish_link_events(&pool, &[&source, &target], **user_id).await
+        }
+        (Err(e), _) | (_, Err(e)) => {
+            tracing::error!(
+                error.cause_chain = ?e,
+                error.message = %e,
+                "Failed to get the linked tickets from the tickets table"
+            );
+        }
+    }
+
+    // Send notification.
+    FlashMessage::info("You have successfully removed the link.").send();
+
+    Ok(see_other(&location))
+}
+
+/// Returns the ticket found by key or by id, if the user can access it.
+pub async fn find_accessible_ticket(
+    pool: &PgPool,
+    reference: &str,
+    user_id: Uuid,
+) -> Result<Option<ValidTicket>, anyhow::Error> {
+    let Ok(reference) = TicketReference::parse(reference) else {
+        return Ok(None);
+    };
+
+    let ticket = match reference {
+        TicketReference::Id(id) => get_ticket(pool, id).await,
+        TicketReference::Key(key) => get_ticket_by_key(pool, &key).await,
+    };
+    let ticket = match ticket {
+        Ok(ticket) => ticket,
+        Err(sqlx::Error::RowNotFound) => return Ok(None),
+        Err(e) => return Err(e).context("Failed to get the ticket details from the tickets table"),
+    };
+
+    let can_access = can_access_ticket(pool, ticket.id, user_id)
+        .await
+        .context("Failed to check the project membership")?;
+
+    Ok(can_access.then_some(ticket))
+}
+
+/// Publishes a change event for every linked ticket.
+///
+/// # Implementation Notes
+///
+/// The change is already committed, so failures are logged rather than failing the request.
+async fn publish_link_events(pool: &PgPool, tickets: &[&ValidTicket], user_id: Uuid) {
+    for ticket in tickets {
+        let event = TicketEvent {
+            ticket_id: ticket.id,
+            project_id: ticket.project_id,
+            kind: TicketEventKind::Updated,
+            triggered_by: user_id,
+        };
+        if let Err(e) = publish_ticket_event(pool, &event).await {
+            tracing::error!(
+                error.cause_chain = ?e,
+                error.message = %e,
+                "Failed to publish the ticket event"
+            );
+        }
+    }
+}
+
+/// Inserts the link into the `ticket_links` table.
+///
+/// # Implementation Notes
+///
+/// The links are created one at a time until the end of the transaction,
+/// so that two links created concurrently cannot close a cycle.
+#[tracing::instrument(
+    name = "Inserting the link into the ticket_links table",
+    skip(transaction)
+)]
+pub async fn insert_ticket_link(
+    transaction: &mut Transaction<'_, Postgres>,
+    link: &NewTicketLink,
+    created_by: &str,
+) -> Result<(), TicketLinkError> {
+    sqlx::query("SELECT pg_advisory_xact_lock($1)")
+        .bind(TICKET_LINKS_LOCK_KEY)
+        .execute(&mut *transaction)
+        .await
+        .context("Failed to lock the ticket_links table")?;
+
+    if link.kind.is_acyclic() {
+        // The new link closes a cycle if the source can already be reached from the target.
+        let row = sqlx::query!(
+            r#"
+            WITH RECURSIVE reachable (id) AS (
+                SELECT target_id FROM ticket_links WHERE source_id = $1 AND kind = $3
+                UNION
+                SELECT ticket_links.target_id
+                FROM ticket_links
+                JOIN reachable ON ticket_links.source_id = reachable.id
+                WHERE ticket_links.kind = $3
+            )
+            SELECT EXISTS (SELECT 1 FROM reachable WHERE id = $2) AS "creates_cycle!"
+            "#,
+            link.target_id,
+            link.source_id,
+            link.kind.as_str()
+        )
+        .fetch_one(&mut *transaction)
+        .await
+        .context("Failed to look for a cycle in the ticket_links table")?;
+
+        if row.creates_cycle {
+            return Err(TicketLinkError::ValidationError(
+                "this link would create a cycle.".to_string(),
+            ));
+        }
+    } else {
+        let row = sqlx::query!(
+            r#"
+            SELECT EXISTS (
+                SELECT 1 FROM ticket_links WHERE source_id = $1 AND target_id = $2 AND kind = $3
+            ) AS "is_linked!"
+            "#,
+            link.target_id,
+            link.source_id,
+            link.kind.as_str()
+        )
+        .fetch_one(&mut *transaction)
+        .await
+        .context("Failed to look for the inverse link in the ticket_links table")?;
+
+        if row.is_linked {
+            return Err(TicketLinkError::ValidationError(
+                "these tickets are already linked.".to_string(),
+            ));
+        }
+    }
+
+    let result = sqlx::query!(
+        r#"
+        INSERT INTO ticket_links (source_id, target_id, kind, created_by, created_at)
+        VALUES ($1, $2, $3, $4, $5)
+        "#,
+        link.source_id,
+        link.target_id,
+        link.kind.as_str(),
+        created_by,
+        Utc::now(),
+    )
+    .execute(&mut *transaction)
+    .await;
+
+    match result {
+        Ok(_) => Ok(()),
+        Err(sqlx::Error::Database(e)) if e.code().as_deref() == Some("23505") => {
+            let message = match e.constraint() {
+                Some("ticket_links_one_parent") => "this ticket already has a parent.",
+                Some("ticket_links_one_canonical") => {
+                    "this ticket is already a duplicate of another one."
+                }
+                _ => "these tickets are already linked.",
+            };
+
+            Err(TicketLinkError::ValidationError(message.to_string()))
+        }
+        Err(e) => Err(anyhow::Error::new(e)
+            .context("Failed to insert the link into the ticket_links table")
+            .into()),
+    }
+}
+
+/// Deletes a link of the ticket from the `ticket_links` table and then return its tickets.
+#[tracing::instrument(name = "Deleting the link from the ticket_links table", skip(pool))]
+async fn remove_ticket_link(
+    pool: &PgPool,
+    ticket_id: i32,
+    link_id: i32,
+) -> Result<Option<(i32, i32)>, sqlx::Error> {
+    let row = sqlx::query!(
+        r#"
+        DELETE FROM ticket_links
+        WHERE id = $1 AND (source_id = $2 OR target_id = $2)
+        RETURNING source_id, target_id
+        "#,
+        link_id,
+        ticket_id
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(row.map(|row| (row.source_id, row.target_id)))
+}
+
+/// Returns the links of a ticket, both ways, to the tickets the user can access.
+#[tracing::instrument(name = "Getting links from the ticket_links table", skip(pool))]
+pub async fn get_ticket_links(
+    pool: &PgPool,
+    ticket_id: i32,
+    user_id: Uuid,
+) -> Result<Vec<TicketLinkOverview>, anyhow::Error> {
+    let rows = sqlx::query!(
+        r#"
+        SELECT ticket_links.id AS "id!", kind AS "kind!", is_outward AS "is_outward!",
+            tickets.key AS "key!", tickets.title AS "title!", tickets.is_open AS "is_open!"
+        FROM (
+            SELECT id, kind, target_id AS other_id, TRUE AS is_outward
+            FROM ticket_links
+            WHERE source_id = $1
+            UNION ALL
+            SELECT id, kind, source_id AS other_id, FALSE AS is_outward
+            FROM ticket_links
+            WHERE target_id = $1
+        ) AS ticket_links
+        JOIN tickets ON tickets.id = ticket_links.other_id
+        JOIN project_members ON project_members.project_id = tickets.project_id
+        WHERE project_members.user_id = $2
+        ORDER BY ticket_links.id
+        "#,
+        ticket_id,
+        user_id
+    )
+    .fetch_all(pool)
+    .await
+    .context("Failed to get the links from the ticket_links table")?;
+
+    rows.into_iter()
+        .map(|row| {
+            let kind = TicketLinkKind::try_from(row.kind).map_err(anyhow::Error::msg)?;
+
+            Ok(TicketLinkOverview {
+                id: row.id,
+                relation: TicketRelation::new(kind, row.is_outward),
+                ticket_key: row.key,
+                ticket_title: row.title,
+                ticket_is_open: row.is_open,
+            })
+        })
+        .collect()
+}
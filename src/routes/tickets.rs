@@ -1,10 +1,29 @@
 use crate::{
     authentication::UserId,
-    domain::{NewTicket, TicketDescription, TicketTitle, ValidTicket},
+    configuration::AttachmentSettings,
+    domain::{
+        Mentions, NewTicket, NewTicketLink, NotificationKind, SlaPolicies, SlaTarget,
+        SlaTargetStatus, TicketChange, TicketDescription, TicketDueDate, TicketEvent,
+        TicketEventKind, TicketLinkKind, TicketRank, TicketReference, TicketRelation, TicketTitle,
+        TimeEntryDuration, ValidAttachment, ValidMilestone, ValidProject, ValidSprint, ValidTicket,
+    },
     error::error_chain_fmt,
     helpers::get_username,
-    utils::see_other,
+    markdown::render_markdown,
+    routes::{
+        can_access_ticket, delete_stored_attachments, find_accessible_ticket, get_attachments,
+        get_current_project, get_project_milestones, get_project_sprints, get_ticket_links,
+        get_time_entries, insert_attachments, insert_ticket_link, notify_mentioned_users,
+        notify_ticket_creator, read_multipart_form, store_attachments, StoredAttachment,
+        TicketLinkError, TicketLinkOverview, TimeEntryOverview,
+    },
+    session_state::TypedSession,
+    startup::ReadPool,
+    storage::AttachmentStorage,
+    ticket_events::publish_ticket_event,
+    utils::{error_page, see_other},
 };
+use actix_multipart::Multipart;
 use actix_web::{
     http::{header::ContentType, StatusCode},
     web, HttpResponse, ResponseError,
@@ -14,8 +33,9 @@ use anyhow::Context;
 use askama::Template;
 use chrono::Utc;
 use serde::Deserialize;
-use sqlx::PgPool;
+use sqlx::{PgPool, Postgres, Transaction};
 use std::fmt::{Debug, Write};
+use uuid::Uuid;
 
 /// Representation of the create ticket template.
 #[derive(Template)]
@@ -28,7 +48,17 @@ struct CreateTicketTemplate {
 #[derive(Template)]
 #[template(path = "see_tickets.html")]
 struct SeeTicketsTemplate {
-    tickets: Vec<ValidTicket>,
+    msg_html: String,
+    project: Option<ValidProject>,
+    tickets: Vec<TicketListItem>,
+}
+
+/// Representation of a ticket of the list, with its deadlines.
+struct TicketListItem {
+    ticket: ValidTicket,
+    sla_summary: Option<String>,
+    is_sla_breached: bool,
+    is_overdue: bool,
 }
 
 /// Representation of the see ticket template.
@@ -37,6 +67,52 @@ struct SeeTicketsTemplate {
 struct SeeTicketTemplate {
     msg_html: String,
     ticket: ValidTicket,
+    description_html: String,
+    attachments: Vec<ValidAttachment>,
+    links: Vec<TicketLinkOverview>,
+    relations: [TicketRelation; 7],
+    sla_statuses: Vec<(SlaTarget, SlaTargetStatus)>,
+    duplicate_of: Option<String>,
+    subtasks_closed: usize,
+    subtasks_total: usize,
+    time_entries: Vec<TimeEntryOverview>,
+    logged: TimeEntryDuration,
+    estimate: Option<TimeEntryDuration>,
+    milestone: Option<ValidMilestone>,
+    milestones: Vec<ValidMilestone>,
+    sprint: Option<ValidSprint>,
+    sprints: Vec<ValidSprint>,
+}
+
+/// Representation of the ticket conflict template.
+#[derive(Template)]
+#[template(path = "ticket_conflict.html")]
+struct TicketConflictTemplate<'a> {
+    ticket_id: i32,
+    changes: &'a [TicketChange],
+}
+
+/// Representation of a ticket description to preview with form data.
+#[derive(Deserialize)]
+pub struct PreviewFormData {
+    description: String,
+}
+
+/// Representation of the version of a ticket sent with form data,
+/// and of the canonical ticket when closing a duplicate.
+#[derive(Deserialize)]
+pub struct CloseTicketFormData {
+    version: i32,
+    duplicate_of: Option<String>,
+}
+
+/// Representation of the outcome of a versioned update of a ticket.
+pub enum TicketUpdate {
+    Updated(Box<ValidTicket>),
+    Unchanged,
+    Conflict(Vec<TicketChange>),
+    NotFound,
+    Rejected(String),
 }
 
 /// Representation of a new ticket created with form data.
@@ -45,6 +121,8 @@ pub struct NewTicketFormData {
     title: String,
     description: String,
     priority: String,
+    #[serde(default)]
+    due_date: String,
 }
 
 impl TryFrom<NewTicketFormData> for NewTicket {
@@ -55,11 +133,13 @@ impl TryFrom<NewTicketFormData> for NewTicket {
         let title = TicketTitle::parse(value.title)?;
         let description = TicketDescription::parse(value.description)?;
         let priority = value.priority;
+        let due_date = TicketDueDate::parse(&value.due_date)?;
 
         Ok(Self {
             title,
             description,
             priority,
+            due_date,
         })
     }
 }
@@ -69,6 +149,8 @@ impl TryFrom<NewTicketFormData> for NewTicket {
 pub enum TicketError {
     #[error("Validation error: {0}")]
     ValidationError(String),
+    #[error("The ticket #{0} has been changed by someone else.")]
+    Conflict(i32, Vec<TicketChange>),
     #[error(transparent)]
     UnexpectedError(#[from] anyhow::Error),
 }
@@ -83,9 +165,20 @@ impl Debug for TicketError {
 impl ResponseError for TicketError {
     /// Creates full response for error.
     fn error_response(&self) -> HttpResponse {
-        let msg_html = self.to_string();
+        let body = match self {
+            TicketError::Conflict(ticket_id, changes) => TicketConflictTemplate {
+                ticket_id: *ticket_id,
+                changes,
+            }
+            .render()
+            .unwrap(),
+            TicketError::UnexpectedError(_) => return error_page(self.status_code()),
+            TicketError::ValidationError(_) => {
+                let msg_html = self.to_string();
 
-        let body = CreateTicketTemplate { msg_html }.render().unwrap();
+                CreateTicketTemplate { msg_html }.render().unwrap()
+            }
+        };
 
         HttpResponse::build(self.status_code())
             .content_type(ContentType::html())
@@ -96,6 +189,7 @@ impl ResponseError for TicketError {
     fn status_code(&self) -> StatusCode {
         match self {
             TicketError::ValidationError(_) => StatusCode::BAD_REQUEST,
+            TicketError::Conflict(_, _) => StatusCode::CONFLICT,
             TicketError::UnexpectedError(_) => StatusCode::INTERNAL_SERVER_ERROR,
         }
     }
@@ -121,7 +215,7 @@ pub async fn create_ticket_form(
 /// Creates a new ticket.
 #[tracing::instrument(
     name = "Creating a new ticket",
-    skip(pool, form, user_id),
+    skip(pool, session, form, user_id),
     fields(
         ticket_title = %form.title,
         ticket_description = %form.description,
@@ -130,17 +224,57 @@ pub async fn create_ticket_form(
 )]
 pub async fn create_ticket(
     pool: web::Data<PgPool>,
+    session: TypedSession,
     form: web::Form<NewTicketFormData>,
     user_id: web::ReqData<UserId>,
 ) -> Result<HttpResponse, TicketError> {
     let new_ticket = form.0.try_into().map_err(TicketError::ValidationError)?;
-    let created_by = get_username(&pool, **user_id)
+    save_new_ticket(&pool, &session, &new_ticket, &[], **user_id).await?;
+
+    // Send notification.
+    FlashMessage::info("You have successfully created a new ticket.").send();
+
+    Ok(see_other("/dashboard/tickets/new"))
+}
+
+/// Creates a new ticket with attachments, sent as a multipart form.
+#[tracing::instrument(
+    name = "Creating a new ticket with attachments",
+    skip(pool, session, storage, settings, payload, user_id),
+    fields(
+        user_id = %&*user_id
+    )
+)]
+pub async fn create_ticket_with_attachments(
+    pool: web::Data<PgPool>,
+    session: TypedSession,
+    storage: web::Data<dyn AttachmentStorage>,
+    settings: web::Data<AttachmentSettings>,
+    payload: Multipart,
+    user_id: web::ReqData<UserId>,
+) -> Result<HttpResponse, TicketError> {
+    let mut form = read_multipart_form(payload, &settings)
         .await
-        .map_err(TicketError::UnexpectedError)?;
+        .map_err(TicketError::ValidationError)?;
+    let mut field = |name: &str| form.fields.remove(name).unwrap_or_default();
+    let new_ticket = NewTicketFormData {
+        title: field("title"),
+        description: field("description"),
+        priority: field("priority"),
+        due_date: field("due_date"),
+    }
+    .try_into()
+    .map_err(TicketError::ValidationError)?;
 
-    insert_ticket(&pool, &new_ticket, created_by)
+    // The files are stored first, a ticket is never saved without its attachments.
+    let attachments = store_attachments(&**storage, form.attachments)
         .await
-        .context("Failed to insert the new ticket details into the tickets table")?;
+        .map_err(TicketError::UnexpectedError)?;
+    if let Err(e) = save_new_ticket(&pool, &session, &new_ticket, &attachments, **user_id).await {
+        delete_stored_attachments(&**storage, &attachments).await;
+
+        return Err(e);
+    }
 
     // Send notification.
     FlashMessage::info("You have successfully created a new ticket.").send();
@@ -148,20 +282,118 @@ pub async fn create_ticket(
     Ok(see_other("/dashboard/tickets/new"))
 }
 
-/// Inserts the new ticket details into the `tickets` table.
+/// Saves the new ticket in the current project with its stored attachments,
+/// lets the other users know about it and then return its id.
+async fn save_new_ticket(
+    pool: &PgPool,
+    session: &TypedSession,
+    new_ticket: &NewTicket,
+    attachments: &[StoredAttachment],
+    user_id: Uuid,
+) -> Result<i32, TicketError> {
+    let project = get_current_project(pool, session, user_id)
+        .await?
+        .ok_or_else(|| {
+            TicketError::ValidationError("create or join a project first.".to_string())
+        })?;
+    let created_by = get_username(pool, user_id)
+        .await
+        .map_err(TicketError::UnexpectedError)?;
+
+    let mut transaction = pool
+        .begin()
+        .await
+        .context("Failed to acquire a Postgres connection from the pool")?;
+    let ticket_id = insert_ticket(&mut transaction, new_ticket, created_by, project.id)
+        .await
+        .context("Failed to insert the new ticket details into the tickets table")?;
+    insert_attachments(&mut transaction, ticket_id, attachments, user_id)
+        .await
+        .context("Failed to insert the new attachments details into the attachments table")?;
+    transaction
+        .commit()
+        .await
+        .context("Failed to commit SQL transaction to store a new ticket")?;
+
+    // Let the other users know about the new ticket, it is saved from now on.
+    if let Err(e) = publish_ticket_event(
+        pool,
+        &TicketEvent {
+            ticket_id,
+            project_id: project.id,
+            kind: TicketEventKind::Created,
+            triggered_by: user_id,
+        },
+    )
+    .await
+    {
+        tracing::error!(
+            error.cause_chain = ?e,
+            error.message = %e,
+            "Failed to publish the ticket event"
+        );
+    }
+
+    // Notify the users mentioned in the description.
+    let mentions = Mentions::parse(new_ticket.description.as_ref());
+    if !mentions.is_empty() {
+        if let Err(e) = notify_mentions(pool, ticket_id, user_id, &mentions).await {
+            tracing::error!(
+                error.cause_chain = ?e,
+                error.message = %e,
+                "Failed to notify the mentioned users"
+            );
+        }
+    }
+
+    Ok(ticket_id)
+}
+
+/// Notifies the users mentioned in the description of a new ticket.
+async fn notify_mentions(
+    pool: &PgPool,
+    ticket_id: i32,
+    user_id: Uuid,
+    mentions: &Mentions,
+) -> Result<(), anyhow::Error> {
+    let ticket = get_ticket(pool, ticket_id)
+        .await
+        .context("Failed to get the ticket details from the tickets table")?;
+    let message = format!(
+        "{} mentioned you in the ticket {}.",
+        ticket.created_by, ticket.key
+    );
+    notify_mentioned_users(pool, ticket_id, user_id, mentions, &message)
+        .await
+        .context("Failed to insert the notification into the notifications table")?;
+
+    Ok(())
+}
+
+/// Inserts the new ticket details into the `tickets` table and then return its id.
+///
+/// # Implementation Notes
+///
+/// The project is locked until the transaction ends,
+/// concurrent tickets of a project get consecutive numbers
+/// and are ranked at the end of the board.
 #[tracing::instrument(
     name = "Inserting the new ticket details into the tickets table",
-    skip(pool, new_ticket)
+    skip(transaction, new_ticket)
 )]
 pub async fn insert_ticket(
-    pool: &PgPool,
+    transaction: &mut Transaction<'_, Postgres>,
     new_ticket: &NewTicket,
     created_by: String,
-) -> Result<(), sqlx::Error> {
-    sqlx::query!(
+    project_id: i32,
+) -> Result<i32, sqlx::Error> {
+    let (ticket_key, rank) = reserve_ticket_key(transaction, project_id).await?;
+
+    let row = sqlx::query!(
         r#"
-        INSERT INTO tickets (title, description, created_at, created_by, is_open, priority)
-        VALUES ($1, $2, $3, $4, $5, $6)
+        INSERT INTO tickets (title, description, created_at, created_by, is_open, priority, version, project_id, key, rank, due_on)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+        RETURNING id
         "#,
         new_ticket.title.as_ref(),
         new_ticket.description.as_ref(),
@@ -169,60 +401,166 @@ pub async fn insert_ticket(
         created_by,
         true,
         new_ticket.priority,
+        1,
+        project_id,
+        ticket_key,
+        rank.as_ref(),
+        new_ticket.due_date.map(|due_date| due_date.date()),
     )
-    .execute(pool)
+    .fetch_one(&mut *transaction)
     .await?;
 
-    Ok(())
+    Ok(row.id)
+}
+
+/// Reserves the key of the next ticket of the project and then return it
+/// with the rank placing the ticket at the end of the board.
+///
+/// # Implementation Notes
+///
+/// The project stays locked until the transaction ends.
+pub async fn reserve_ticket_key(
+    transaction: &mut Transaction<'_, Postgres>,
+    project_id: i32,
+) -> Result<(String, TicketRank), sqlx::Error> {
+    let project = sqlx::query!(
+        r#"
+        UPDATE projects
+        SET next_ticket_number = next_ticket_number + 1
+        WHERE id = $1
+        RETURNING key || '-' || (next_ticket_number - 1) AS "ticket_key!"
+        "#,
+        project_id
+    )
+    .fetch_one(&mut *transaction)
+    .await?;
+
+    let last_rank = sqlx::query!(
+        r#"SELECT MAX(rank) AS rank FROM tickets WHERE project_id = $1"#,
+        project_id
+    )
+    .fetch_one(&mut *transaction)
+    .await?
+    .rank;
+
+    Ok((
+        project.ticket_key,
+        TicketRank::between(last_rank.as_deref(), None),
+    ))
 }
 
-/// Sees tickets.
+/// Returns the ticket description rendered as it will be displayed.
+pub async fn preview_ticket_description(form: web::Form<PreviewFormData>) -> HttpResponse {
+    let body = render_markdown(&form.description);
+
+    HttpResponse::Ok()
+        .content_type(ContentType::html())
+        .body(body)
+}
+
+/// Sees the tickets of the current project.
 #[tracing::instrument(
     name = "Seeing tickets",
-    skip(pool, user_id),
+    skip(pool, read_pool, sla_policies, session, flash_messages, user_id),
     fields(
         user_id=%&*user_id
     )
 )]
 pub async fn see_tickets(
     pool: web::Data<PgPool>,
+    read_pool: web::Data<ReadPool>,
+    sla_policies: web::Data<SlaPolicies>,
+    session: TypedSession,
+    flash_messages: IncomingFlashMessages,
     user_id: web::ReqData<UserId>,
 ) -> Result<HttpResponse, TicketError> {
-    let tickets = get_tickets(&pool)
-        .await
-        .context("Failed to get the tickets details from the tickets table")?;
+    // Get notification.
+    let mut msg_html = String::new();
+    for m in flash_messages.iter() {
+        writeln!(msg_html, "{}", m.content()).unwrap();
+    }
 
-    let body = SeeTicketsTemplate { tickets }.render().unwrap();
+    let project = get_current_project(&pool, &session, **user_id).await?;
+    let tickets = match &project {
+        Some(project) => get_tickets(&read_pool.0, project.id)
+            .await
+            .context("Failed to get the tickets details from the tickets table")?,
+        None => Vec::new(),
+    };
+
+    // Show how long is left before the deadlines of the open tickets.
+    let now = Utc::now();
+    let tickets = tickets
+        .into_iter()
+        .map(|ticket| {
+            let (sla_summary, is_sla_breached) = match sla_policies.summary(&ticket, now) {
+                Some((summary, is_breached)) => (Some(summary), is_breached),
+                None => (None, false),
+            };
+            let is_overdue = ticket.is_open
+                && ticket
+                    .due_on
+                    .is_some_and(|due_on| due_on < now.date_naive());
+
+            TicketListItem {
+                ticket,
+                sla_summary,
+                is_sla_breached,
+                is_overdue,
+            }
+        })
+        .collect();
+
+    let body = SeeTicketsTemplate {
+        msg_html,
+        project,
+        tickets,
+    }
+    .render()
+    .unwrap();
 
     Ok(HttpResponse::Ok()
         .content_type(ContentType::html())
         .body(body))
 }
 
-/// Return tickets.
+/// Return the tickets of a project.
 #[tracing::instrument(name = "Getting tickets details from the tickets table", skip(pool))]
-pub async fn get_tickets(pool: &PgPool) -> Result<Vec<ValidTicket>, sqlx::Error> {
-    let tickets = sqlx::query_as!(ValidTicket, "SELECT * FROM tickets ORDER BY id")
-        .fetch_all(pool)
-        .await?;
+pub async fn get_tickets(pool: &PgPool, project_id: i32) -> Result<Vec<ValidTicket>, sqlx::Error> {
+    let tickets = sqlx::query_as!(
+        ValidTicket,
+        "SELECT * FROM tickets WHERE project_id = $1 ORDER BY id",
+        project_id
+    )
+    .fetch_all(pool)
+    .await?;
 
     Ok(tickets)
 }
 
-/// Sees ticket.
+/// Redirects to the tickets with a notification, when the ticket cannot be seen.
+fn ticket_not_found() -> HttpResponse {
+    // Send notification.
+    FlashMessage::error("This ticket does not exist.").send();
+
+    see_other("/dashboard/tickets")
+}
+
+/// Sees ticket, found by key or by id.
 #[tracing::instrument(
     name = "Seeing ticket",
-    skip(pool, flash_messages, user_id, ticket_id),
+    skip(pool, sla_policies, flash_messages, user_id, reference),
     fields(
         user_id=%&*user_id,
-        ticket_id=%ticket_id.0
+        reference=%reference.0
     )
 )]
 pub async fn see_ticket(
     pool: web::Data<PgPool>,
+    sla_policies: web::Data<SlaPolicies>,
     flash_messages: IncomingFlashMessages,
     user_id: web::ReqData<UserId>,
-    ticket_id: web::Path<(i32,)>,
+    reference: web::Path<(String,)>,
 ) -> Result<HttpResponse, TicketError> {
     // Get notification.
     let mut msg_html = String::new();
@@ -230,13 +568,109 @@ pub async fn see_ticket(
         writeln!(msg_html, "{}", m.content()).unwrap();
     }
 
-    let ticket_id = ticket_id.into_inner().0;
+    let reference =
+        TicketReference::parse(&reference.into_inner().0).map_err(TicketError::ValidationError)?;
+
+    let ticket = match reference {
+        TicketReference::Id(id) => get_ticket(&pool, id).await,
+        TicketReference::Key(key) => get_ticket_by_key(&pool, &key).await,
+    };
+    let ticket = match ticket {
+        Ok(ticket) => ticket,
+        Err(sqlx::Error::RowNotFound) => return Ok(ticket_not_found()),
+        Err(e) => {
+            return Err(anyhow::Error::new(e)
+                .context("Failed to get the ticket details from the tickets table")
+                .into())
+        }
+    };
 
-    let ticket = get_ticket(&pool, ticket_id)
+    // Tickets of other projects are treated as if they did not exist.
+    if !can_access_ticket(&pool, ticket.id, **user_id)
         .await
-        .context("Failed to get the ticket details from the tickets table")?;
+        .context("Failed to check the project membership")?
+    {
+        return Ok(ticket_not_found());
+    }
 
-    let body = SeeTicketTemplate { msg_html, ticket }.render().unwrap();
+    let description_html = render_markdown(&ticket.description);
+    let attachments = get_attachments(&pool, ticket.id)
+        .await
+        .context("Failed to get the attachments details from the attachments table")?;
+    let links = get_ticket_links(&pool, ticket.id, **user_id).await?;
+
+    // The canonical ticket of a duplicate and the progress of the sub-tasks.
+    let duplicate_of = links
+        .iter()
+        .find(|link| link.relation == TicketRelation::new(TicketLinkKind::Duplicates, true))
+        .map(|link| link.ticket_key.clone());
+    let subtasks: Vec<&TicketLinkOverview> = links
+        .iter()
+        .filter(|link| link.relation == TicketRelation::new(TicketLinkKind::ParentOf, true))
+        .collect();
+    let subtasks_closed = subtasks.iter().filter(|link| !link.ticket_is_open).count();
+    let subtasks_total = subtasks.len();
+    let sla_statuses = sla_policies.statuses(&ticket, Utc::now());
+
+    // The time logged against the original estimate.
+    let time_entries = get_time_entries(&pool, ticket.id)
+        .await
+        .context("Failed to get the time entries from the time_entries table")?;
+    let logged = TimeEntryDuration::from_minutes(
+        time_entries
+            .iter()
+            .map(|entry| entry.duration.minutes())
+            .sum(),
+    );
+    let estimate = ticket.estimate_minutes.map(TimeEntryDuration::from_minutes);
+
+    // The milestone of the ticket and the open ones it can be moved to.
+    let milestones = get_project_milestones(&pool, ticket.project_id)
+        .await
+        .context("Failed to get the milestones details from the milestones table")?;
+    let milestones: Vec<ValidMilestone> = milestones
+        .into_iter()
+        .filter(|milestone| milestone.is_open || Some(milestone.id) == ticket.milestone_id)
+        .collect();
+    let milestone = milestones
+        .iter()
+        .find(|milestone| Some(milestone.id) == ticket.milestone_id)
+        .cloned();
+
+    // The sprint of the ticket and the ones that have not ended yet.
+    let today = Utc::now().date_naive();
+    let sprints: Vec<ValidSprint> = get_project_sprints(&pool, ticket.project_id)
+        .await
+        .context("Failed to get the sprints details from the sprints table")?
+        .into_iter()
+        .filter(|sprint| !sprint.has_ended(today) || Some(sprint.id) == ticket.sprint_id)
+        .collect();
+    let sprint = sprints
+        .iter()
+        .find(|sprint| Some(sprint.id) == ticket.sprint_id)
+        .cloned();
+
+    let body = SeeTicketTemplate {
+        msg_html,
+        ticket,
+        description_html,
+        attachments,
+        links,
+        relations: TicketRelation::all(),
+        sla_statuses,
+        duplicate_of,
+        subtasks_closed,
+        subtasks_total,
+        time_entries,
+        logged,
+        estimate,
+        milestone,
+        milestones,
+        sprint,
+        sprints,
+    }
+    .render()
+    .unwrap();
 
     Ok(HttpResponse::Ok()
         .content_type(ContentType::html())
@@ -253,50 +687,343 @@ pub async fn get_ticket(pool: &PgPool, id: i32) -> Result<ValidTicket, sqlx::Err
     Ok(ticket)
 }
 
+/// Return ticket, found by key.
+#[tracing::instrument(name = "Getting ticket details from the tickets table", skip(pool))]
+pub async fn get_ticket_by_key(pool: &PgPool, key: &str) -> Result<ValidTicket, sqlx::Error> {
+    let ticket = sqlx::query_as!(ValidTicket, r#"SELECT * FROM tickets WHERE key = $1"#, key)
+        .fetch_one(pool)
+        .await?;
+
+    Ok(ticket)
+}
+
 /// Closes ticket.
 #[tracing::instrument(
     name = "Closing ticket",
-    skip(pool, user_id, ticket_id),
+    skip(pool, form, user_id, ticket_id),
     fields(
         user_id=%&*user_id,
-        ticket_id=%ticket_id.0
+        ticket_id=%ticket_id.0,
+        version=%form.version
     )
 )]
 pub async fn close_ticket(
     pool: web::Data<PgPool>,
+    form: web::Form<CloseTicketFormData>,
     user_id: web::ReqData<UserId>,
     ticket_id: web::Path<(i32,)>,
 ) -> Result<HttpResponse, TicketError> {
     let ticket_id = ticket_id.into_inner().0;
+    let location = format!("/dashboard/tickets/{}", ticket_id);
+
+    // Closing a duplicate points to the canonical ticket.
+    let duplicate_of = match form.duplicate_of.as_deref().map(str::trim) {
+        Some(reference) if !reference.is_empty() => {
+            match find_accessible_ticket(&pool, reference, **user_id).await? {
+                Some(canonical) => Some(canonical),
+                None => {
+                    // Send notification.
+                    FlashMessage::error("The canonical ticket does not exist.").send();
+
+                    return Ok(see_other(location.as_str()));
+                }
+            }
+        }
+        _ => None,
+    };
 
-    update_is_open(&pool, ticket_id, false)
+    // Tickets of other projects are treated as if they did not exist.
+    let update = if can_access_ticket(&pool, ticket_id, **user_id)
         .await
-        .context("Failed to update the `is_open` field from the tickets table")?;
+        .context("Failed to check the project membership")?
+    {
+        close_ticket_at_version(
+            &pool,
+            ticket_id,
+            form.version,
+            **user_id,
+            duplicate_of.as_ref(),
+        )
+        .await?
+    } else {
+        TicketUpdate::NotFound
+    };
 
-    // Send notification.
-    FlashMessage::info("You have successfully closed this ticket.").send();
+    match update {
+        TicketUpdate::Updated(_) if duplicate_of.is_some() => {
+            // Send notification.
+            FlashMessage::info("You have successfully closed this ticket as a duplicate.").send();
+        }
+        TicketUpdate::Updated(_) => {
+            // Send notification.
+            FlashMessage::info("You have successfully closed this ticket.").send();
+        }
+        TicketUpdate::Rejected(e) => {
+            // Send notification.
+            FlashMessage::error(format!("Validation error: {}", e)).send();
+        }
+        TicketUpdate::Unchanged => {
+            // Send notification.
+            FlashMessage::error("This ticket has already been closed.").send();
+        }
+        TicketUpdate::NotFound => {
+            // Send notification.
+            FlashMessage::error("This ticket does not exist.").send();
+        }
+        TicketUpdate::Conflict(changes) => {
+            return Err(TicketError::Conflict(ticket_id, changes));
+        }
+    }
 
-    let location = format!("/dashboard/tickets/{}", ticket_id);
     Ok(see_other(location.as_str()))
 }
 
-/// Updates the `is_open` field from the tickets table.
+/// Closes a ticket if it has not been changed since the given version.
+///
+/// # Implementation Notes
+///
+/// The ticket is locked until the change is committed,
+/// concurrent writes are serialized and only the first one succeeds.
+#[tracing::instrument(name = "Closing ticket at a given version", skip(pool))]
+pub async fn close_ticket_at_version(
+    pool: &PgPool,
+    id: i32,
+    version: i32,
+    user_id: Uuid,
+    duplicate_of: Option<&ValidTicket>,
+) -> Result<TicketUpdate, anyhow::Error> {
+    let username = get_username(pool, user_id).await?;
+
+    let mut transaction = pool
+        .begin()
+        .await
+        .context("Failed to acquire a Postgres connection from the pool")?;
+
+    let ticket = match lock_ticket(&mut transaction, id)
+        .await
+        .context("Failed to lock the ticket from the tickets table")?
+    {
+        Some(ticket) => ticket,
+        None => return Ok(TicketUpdate::NotFound),
+    };
+
+    // Someone else has changed the ticket in the meantime.
+    if ticket.version != version {
+        let changes = get_ticket_changes_since(&mut transaction, id, version)
+            .await
+            .context("Failed to get the ticket changes from the ticket_changes table")?;
+
+        return Ok(TicketUpdate::Conflict(changes));
+    }
+
+    if !ticket.is_open {
+        return Ok(TicketUpdate::Unchanged);
+    }
+
+    // Link the duplicate to its canonical ticket.
+    if let Some(canonical) = duplicate_of {
+        let relation = TicketRelation::new(TicketLinkKind::Duplicates, true);
+        let link = match NewTicketLink::parse(id, relation, canonical.id) {
+            Ok(link) => link,
+            Err(e) => return Ok(TicketUpdate::Rejected(e)),
+        };
+        match insert_ticket_link(&mut transaction, &link, &username).await {
+            Ok(()) => {}
+            Err(TicketLinkError::ValidationError(e)) => return Ok(TicketUpdate::Rejected(e)),
+            Err(TicketLinkError::UnexpectedError(e)) => return Err(e),
+        }
+    }
+
+    let ticket = update_is_open(&mut transaction, id, false)
+        .await
+        .context("Failed to update the `is_open` field from the tickets table")?;
+    insert_ticket_change(
+        &mut transaction,
+        &TicketChange {
+            ticket_id: id,
+            version: ticket.version,
+            field: "status".to_string(),
+            old_value: "open".to_string(),
+            new_value: "closed".to_string(),
+            changed_by: username.clone(),
+            changed_at: Utc::now(),
+        },
+    )
+    .await
+    .context("Failed to insert the ticket change into the ticket_changes table")?;
+
+    transaction
+        .commit()
+        .await
+        .context("Failed to commit SQL transaction to close the ticket")?;
+
+    // The ticket is closed from now on, failing to spread the news must not fail the request.
+    //
+    // Let the other users know that the ticket has changed.
+    let mut events = vec![TicketEvent {
+        ticket_id: id,
+        project_id: ticket.project_id,
+        kind: TicketEventKind::Closed,
+        triggered_by: user_id,
+    }];
+    if let Some(canonical) = duplicate_of {
+        events.push(TicketEvent {
+            ticket_id: canonical.id,
+            project_id: canonical.project_id,
+            kind: TicketEventKind::Updated,
+            triggered_by: user_id,
+        });
+    }
+    for event in &events {
+        if let Err(e) = publish_ticket_event(pool, event).await {
+            tracing::error!(
+                error.cause_chain = ?e,
+                error.message = %e,
+                "Failed to publish the ticket event"
+            );
+        }
+    }
+
+    // Notify the creator of the ticket.
+    let message = match duplicate_of {
+        Some(canonical) => format!(
+            "{} closed the ticket {} as a duplicate of {}.",
+            username, ticket.key, canonical.key
+        ),
+        None => format!("{} closed the ticket {}.", username, ticket.key),
+    };
+    if let Err(e) =
+        notify_ticket_creator(pool, id, user_id, NotificationKind::StatusChange, &message).await
+    {
+        tracing::error!(
+            error.cause_chain = ?e,
+            error.message = %e,
+            "Failed to insert the notification into the notifications table"
+        );
+    }
+
+    Ok(TicketUpdate::Updated(Box::new(ticket)))
+}
+
+/// Returns the ticket and lock it until the end of the transaction.
+#[tracing::instrument(name = "Locking ticket from the tickets table", skip(transaction, id))]
+pub async fn lock_ticket(
+    transaction: &mut Transaction<'_, Postgres>,
+    id: i32,
+) -> Result<Option<ValidTicket>, sqlx::Error> {
+    let ticket = sqlx::query_as!(
+        ValidTicket,
+        r#"SELECT * FROM tickets WHERE id = $1 FOR UPDATE"#,
+        id
+    )
+    .fetch_optional(transaction)
+    .await?;
+
+    Ok(ticket)
+}
+
+/// Updates the `is_open` field from the tickets table and then return the new ticket.
+///
+/// # Implementation Notes
+///
+/// Every write increments the version of the ticket.
+/// Closing the ticket records when, reopening it clears it.
 #[tracing::instrument(
     name = "Updating the `is_open` field from the tickets table",
-    skip(pool, id)
+    skip(transaction, id)
 )]
-async fn update_is_open(pool: &PgPool, id: i32, new_status: bool) -> Result<(), sqlx::Error> {
-    sqlx::query!(
+pub async fn update_is_open(
+    transaction: &mut Transaction<'_, Postgres>,
+    id: i32,
+    new_status: bool,
+) -> Result<ValidTicket, sqlx::Error> {
+    let ticket = sqlx::query_as!(
+        ValidTicket,
         r#"
         UPDATE tickets
-        SET is_open = $1
+        SET is_open = $1,
+            closed_at = CASE WHEN $1 THEN NULL ELSE now() END,
+            version = version + 1
         WHERE id = $2
+        RETURNING *
         "#,
         new_status,
         id
     )
-    .execute(pool)
+    .fetch_one(transaction)
+    .await?;
+
+    Ok(ticket)
+}
+
+/// Inserts a ticket change into the `ticket_changes` table.
+///
+/// # Implementation Notes
+///
+/// The first change made by someone else than the creator is the first response to the ticket.
+#[tracing::instrument(
+    name = "Inserting the ticket change into the ticket_changes table",
+    skip(transaction, change)
+)]
+pub async fn insert_ticket_change(
+    transaction: &mut Transaction<'_, Postgres>,
+    change: &TicketChange,
+) -> Result<(), sqlx::Error> {
+    sqlx::query!(
+        r#"
+        INSERT INTO ticket_changes (ticket_id, version, field, old_value, new_value, changed_by, changed_at)
+        VALUES ($1, $2, $3, $4, $5, $6, $7)
+        "#,
+        change.ticket_id,
+        change.version,
+        change.field,
+        change.old_value,
+        change.new_value,
+        change.changed_by,
+        change.changed_at,
+    )
+    .execute(&mut *transaction)
+    .await?;
+
+    sqlx::query!(
+        r#"
+        UPDATE tickets
+        SET first_responded_at = $1
+        WHERE id = $2 AND first_responded_at IS NULL AND created_by <> $3
+        "#,
+        change.changed_at,
+        change.ticket_id,
+        change.changed_by,
+    )
+    .execute(transaction)
     .await?;
 
     Ok(())
 }
+
+/// Returns the changes made to a ticket after the given version.
+#[tracing::instrument(
+    name = "Getting ticket changes from the ticket_changes table",
+    skip(transaction)
+)]
+pub async fn get_ticket_changes_since(
+    transaction: &mut Transaction<'_, Postgres>,
+    ticket_id: i32,
+    version: i32,
+) -> Result<Vec<TicketChange>, sqlx::Error> {
+    let changes = sqlx::query_as!(
+        TicketChange,
+        r#"
+        SELECT ticket_id, version, field, old_value, new_value, changed_by, changed_at
+        FROM ticket_changes
+        WHERE ticket_id = $1 AND version > $2
+        ORDER BY version, id
+        "#,
+        ticket_id,
+        version
+    )
+    .fetch_all(transaction)
+    .await?;
+
+    Ok(changes)
+}
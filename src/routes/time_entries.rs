@@ -0,0 +1,332 @@
+use crate::{
+    authentication::UserId,
+    domain::{
+        NewTimeEntry, TicketEvent, TicketEventKind, TimeEntryDuration, TimeEntryNote, Timesheet,
+        TimesheetEntry, UserRole,
+    },
+    helpers::{get_user_role, get_username},
+    routes::find_accessible_ticket,
+    startup::ReadPool,
+    ticket_events::publish_ticket_event,
+    utils::{e500, see_other},
+};
+use actix_web::{
+    http::header::{ContentDisposition, ContentType, DispositionParam, DispositionType},
+    web, HttpResponse,
+};
+use actix_web_flash_messages::{FlashMessage, IncomingFlashMessages};
+use anyhow::Context;
+use askama::Template;
+use chrono::{NaiveDate, Utc};
+use serde::Deserialize;
+use sqlx::PgPool;
+use std::fmt::Write;
+
+/// Representation of a time entry as listed on its ticket.
+pub struct TimeEntryOverview {
+    pub spent_on: NaiveDate,
+    pub logged_by: String,
+    pub duration: TimeEntryDuration,
+    pub note: String,
+}
+
+/// Representation of a new time entry created with form data.
+#[derive(Deserialize)]
+pub struct NewTimeEntryFormData {
+    duration: String,
+    spent_on: String,
+    #[serde(default)]
+    note: String,
+}
+
+/// Representation of the timesheet query.
+#[derive(Deserialize)]
+pub struct TimesheetQuery {
+    week: Option<NaiveDate>,
+    user: Option<String>,
+}
+
+/// Representation of the timesheet page.
+#[derive(Template)]
+#[template(path = "timesheet.html")]
+struct TimesheetTemplate {
+    msg_html: String,
+    timesheet: Timesheet,
+    is_admin: bool,
+}
+
+/// Logs time spent on a ticket.
+#[tracing::instrument(
+    name = "Logging time",
+    skip(pool, form, user_id, ticket_id),
+    fields(
+        user_id=%&*user_id,
+        ticket_id=%ticket_id.0
+    )
+)]
+pub async fn log_time(
+    pool: web::Data<PgPool>,
+    form: web::Form<NewTimeEntryFormData>,
+    user_id: web::ReqData<UserId>,
+    ticket_id: web::Path<(i32,)>,
+) -> Result<HttpResponse, actix_web::Error> {
+    let ticket_id = ticket_id.into_inner().0;
+    let location = format!("/dashboard/tickets/{}", ticket_id);
+
+    // Tickets of other projects are treated as if they did not exist.
+    let ticket = match find_accessible_ticket(&pool, &ticket_id.to_string(), **user_id)
+        .await
+        .map_err(e500)?
+    {
+        Some(ticket) => ticket,
+        None => {
+            // Send notification.
+            FlashMessage::error("This ticket does not exist.").send();
+
+            return Ok(see_other(&location));
+        }
+    };
+
+    let form = form.0;
+    let entry = TimeEntryDuration::parse(&form.duration).and_then(|duration| {
+        let spent_on = form
+            .spent_on
+            .parse::<NaiveDate>()
+            .map_err(|_| format!("`{}` is not a date such as 2026-10-31.", form.spent_on))?;
+        let note = TimeEntryNote::parse(form.note)?;
+
+        NewTimeEntry::parse(ticket.id, spent_on, duration, note, Utc::now().date_naive())
+    });
+    let entry = match entry {
+        Ok(entry) => entry,
+        Err(e) => {
+            // Send notification.
+            FlashMessage::error(format!("Validation error: {}", e)).send();
+
+            return Ok(see_other(&location));
+        }
+    };
+
+    let logged_by = get_username(&pool, **user_id).await.map_err(e500)?;
+    insert_time_entry(&pool, &entry, &logged_by)
+        .await
+        .context("Failed to insert the time entry into the time_entries table")
+        .map_err(e500)?;
+
+    // Let the other users know that the ticket has changed, the time is logged from now on.
+    if let Err(e) = publish_ticket_event(
+        &pool,
+        &TicketEvent {
+            ticket_id: ticket.id,
+            project_id: ticket.project_id,
+            kind: TicketEventKind::Updated,
+            triggered_by: **user_id,
+        },
+    )
+    .await
+    {
+        tracing::error!(
+            error.cause_chain = ?e,
+            error.message = %e,
+            "Failed to publish the ticket event"
+        );
+    }
+
+    // Send notification.
+    FlashMessage::info(format!(
+        "You have successfully logged {} on this ticket.",
+        entry.duration
+    ))
+    .send();
+
+    Ok(see_other(&location))
+}
+
+/// Returns the timesheet page of a user for a week.
+///
+/// # Implementation Notes
+///
+/// Only the administrators can see the timesheet of other users.
+#[tracing::instrument(
+    name = "Seeing timesheet",
+    skip(pool, read_pool, flash_messages, user_id, query),
+    fields(
+        user_id=%&*user_id
+    )
+)]
+pub async fn see_timesheet(
+    pool: web::Data<PgPool>,
+    read_pool: web::Data<ReadPool>,
+    flash_messages: IncomingFlashMessages,
+    user_id: web::ReqData<UserId>,
+    query: web::Query<TimesheetQuery>,
+) -> Result<HttpResponse, actix_web::Error> {
+    // Get notification.
+    let mut msg_html = String::new();
+    for m in flash_messages.iter() {
+        writeln!(msg_html, "{}", m.content()).unwrap();
+    }
+
+    let is_admin = get_user_role(&pool, **user_id).await.map_err(e500)? == UserRole::Admin;
+    let Some(timesheet) = get_timesheet(&pool, &read_pool.0, **user_id, query.0, is_admin).await?
+    else {
+        return Ok(see_other("/dashboard/timesheet"));
+    };
+
+    let body = TimesheetTemplate {
+        msg_html,
+        timesheet,
+        is_admin,
+    }
+    .render()
+    .unwrap();
+
+    Ok(HttpResponse::Ok()
+        .content_type(ContentType::html())
+        .body(body))
+}
+
+/// Exports the timesheet of a user for a week in the CSV format.
+#[tracing::instrument(
+    name = "Exporting timesheet",
+    skip(pool, read_pool, user_id, query),
+    fields(
+        user_id=%&*user_id
+    )
+)]
+pub async fn export_timesheet(
+    pool: web::Data<PgPool>,
+    read_pool: web::Data<ReadPool>,
+    user_id: web::ReqData<UserId>,
+    query: web::Query<TimesheetQuery>,
+) -> Result<HttpResponse, actix_web::Error> {
+    let is_admin = get_user_role(&pool, **user_id).await.map_err(e500)? == UserRole::Admin;
+    let Some(timesheet) = get_timesheet(&pool, &read_pool.0, **user_id, query.0, is_admin).await?
+    else {
+        return Ok(see_other("/dashboard/timesheet"));
+    };
+    let csv = timesheet.to_csv().map_err(e500)?;
+
+    Ok(HttpResponse::Ok()
+        .content_type("text/csv; charset=utf-8")
+        .insert_header(ContentDisposition {
+            disposition: DispositionType::Attachment,
+            parameters: vec![DispositionParam::Filename(format!(
+                "timesheet-{}-{}.csv",
+                timesheet.username, timesheet.week_start
+            ))],
+        })
+        .body(csv))
+}
+
+/// Returns the timesheet requested by the query, none if the user is not allowed to see it.
+async fn get_timesheet(
+    pool: &PgPool,
+    read_pool: &PgPool,
+    user_id: uuid::Uuid,
+    query: TimesheetQuery,
+    is_admin: bool,
+) -> Result<Option<Timesheet>, actix_web::Error> {
+    let current_username = get_username(pool, user_id).await.map_err(e500)?;
+    let username = match query.user.filter(|user| !user.trim().is_empty()) {
+        Some(user) if user != current_username && !is_admin => {
+            // Send notification.
+            FlashMessage::error("Only administrators can see the timesheet of other users.").send();
+
+            return Ok(None);
+        }
+        Some(user) => user,
+        None => current_username,
+    };
+
+    let day = query.week.unwrap_or_else(|| Utc::now().date_naive());
+    let week_start = Timesheet::week_start(day);
+    let entries = get_timesheet_entries(read_pool, &username, week_start)
+        .await
+        .context("Failed to get the time entries from the time_entries table")
+        .map_err(e500)?;
+
+    Ok(Some(Timesheet::new(username, week_start, entries)))
+}
+
+/// Inserts the time entry into the `time_entries` table.
+#[tracing::instrument(name = "Saving new time entry details in the database", skip(pool))]
+pub async fn insert_time_entry(
+    pool: &PgPool,
+    entry: &NewTimeEntry,
+    logged_by: &str,
+) -> Result<(), sqlx::Error> {
+    sqlx::query!(
+        r#"
+        INSERT INTO time_entries (ticket_id, logged_by, spent_on, duration_minutes, note, logged_at)
+        VALUES ($1, $2, $3, $4, $5, $6)
+        "#,
+        entry.ticket_id,
+        logged_by,
+        entry.spent_on,
+        entry.duration.minutes(),
+        entry.note.as_ref(),
+        Utc::now()
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// Returns the time entries of the ticket, the most recent first.
+#[tracing::instrument(name = "Getting time entries", skip(pool))]
+pub async fn get_time_entries(
+    pool: &PgPool,
+    ticket_id: i32,
+) -> Result<Vec<TimeEntryOverview>, sqlx::Error> {
+    let rows = sqlx::query!(
+        r#"
+        SELECT spent_on, logged_by, duration_minutes, note
+        FROM time_entries
+        WHERE ticket_id = $1
+        ORDER BY spent_on DESC, id DESC
+        "#,
+        ticket_id
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| TimeEntryOverview {
+            spent_on: row.spent_on,
+            logged_by: row.logged_by,
+            duration: TimeEntryDuration::from_minutes(row.duration_minutes),
+            note: row.note,
+        })
+        .collect())
+}
+
+/// Returns the time entries logged by the user during the week starting on the given Monday.
+#[tracing::instrument(name = "Getting timesheet entries", skip(pool))]
+async fn get_timesheet_entries(
+    pool: &PgPool,
+    username: &str,
+    week_start: NaiveDate,
+) -> Result<Vec<TimesheetEntry>, sqlx::Error> {
+    let entries = sqlx::query_as!(
+        TimesheetEntry,
+        r#"
+        SELECT tickets.key AS ticket_key, tickets.title AS ticket_title,
+            time_entries.spent_on, time_entries.duration_minutes, time_entries.note
+        FROM time_entries
+        JOIN tickets ON tickets.id = time_entries.ticket_id
+        WHERE time_entries.logged_by = $1
+            AND time_entries.spent_on >= $2
+            AND time_entries.spent_on < $2 + 7
+        ORDER BY time_entries.spent_on, time_entries.id
+        "#,
+        username,
+        week_start
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(entries)
+}
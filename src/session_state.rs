@@ -11,6 +11,7 @@ pub struct TypedSession(Session);
 
 impl TypedSession {
     const USER_ID_KEY: &'static str = "user_id";
+    const PROJECT_ID_KEY: &'static str = "project_id";
 
     /// Renews the session key, assigning existing session state to new key.
     pub fn renew(&self) {
@@ -27,6 +28,16 @@ impl TypedSession {
         self.0.get(Self::USER_ID_KEY)
     }
 
+    /// Inserts the `PROJECT_ID_KEY` key-value pair into the session.
+    pub fn insert_project_id(&self, project_id: i32) -> Result<(), SessionInsertError> {
+        self.0.insert(Self::PROJECT_ID_KEY, project_id)
+    }
+
+    /// Returns the `PROJECT_ID_KEY` key-value from the session.
+    pub fn get_project_id(&self) -> Result<Option<i32>, SessionGetError> {
+        self.0.get(Self::PROJECT_ID_KEY)
+    }
+
     /// Removes session from both client and server side.
     pub fn log_out(self) {
         self.0.purge()
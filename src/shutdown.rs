@@ -0,0 +1,56 @@
+use std::sync::Arc;
+use tokio::sync::watch;
+
+/// Representation of the shutdown of the application, shared by the server and its workers.
+#[derive(Clone)]
+pub struct Shutdown(Arc<watch::Sender<bool>>);
+
+impl Shutdown {
+    /// Creates a shutdown which has not been triggered yet.
+    pub fn new() -> Self {
+        let (sender, _) = watch::channel(false);
+
+        Self(Arc::new(sender))
+    }
+
+    /// Starts the shutdown of the application.
+    pub fn trigger(&self) {
+        self.0.send_replace(true);
+    }
+
+    /// Returns `true` once the shutdown has been triggered.
+    pub fn is_triggered(&self) -> bool {
+        *self.0.borrow()
+    }
+
+    /// Waits until the shutdown is triggered.
+    pub async fn wait(&self) {
+        let mut receiver = self.0.subscribe();
+        // The sender outlives the receiver, so waiting cannot fail.
+        let _ = receiver.wait_for(|is_triggered| *is_triggered).await;
+    }
+}
+
+impl Default for Shutdown {
+    /// Returns the "default value" for a type.
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Waits until the process is asked to terminate, either by `SIGTERM` or by `Ctrl-C`.
+pub async fn wait_for_termination_signal() -> Result<(), std::io::Error> {
+    #[cfg(unix)]
+    {
+        let mut terminate =
+            tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate())?;
+        tokio::select! {
+            _ = terminate.recv() => Ok(()),
+            outcome = tokio::signal::ctrl_c() => outcome,
+        }
+    }
+    #[cfg(not(unix))]
+    {
+        tokio::signal::ctrl_c().await
+    }
+}
@@ -0,0 +1,169 @@
+use crate::{
+    configuration::Settings,
+    domain::{NotificationKind, SlaPolicies, SlaTarget, SlaTargetStatus, ValidTicket},
+    routes::notify_ticket_assignee,
+    shutdown::Shutdown,
+};
+use anyhow::Context;
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use std::time::Duration;
+
+/// Runs the SLA worker until stopped.
+pub async fn run_worker_until_stopped(
+    configuration: Settings,
+    shutdown: Shutdown,
+) -> Result<(), anyhow::Error> {
+    let connection_pool = configuration.database.get_connection_pool();
+    let policies = configuration.sla.get_policies()?;
+
+    let outcome = worker_loop(
+        &connection_pool,
+        policies,
+        configuration.sla.check_interval(),
+        shutdown,
+    )
+    .await;
+    connection_pool.close().await;
+
+    outcome
+}
+
+/// Periodically flags the tickets that missed one of their targets, until the shutdown.
+async fn worker_loop(
+    pool: &PgPool,
+    policies: SlaPolicies,
+    check_interval: Duration,
+    shutdown: Shutdown,
+) -> Result<(), anyhow::Error> {
+    loop {
+        match flag_sla_breaches(pool, &policies, Utc::now()).await {
+            Ok(flagged) => {
+                tracing::info!(flagged, "Flagged the SLA breaches");
+            }
+            Err(e) => {
+                tracing::error!(
+                    error.cause_chain = ?e,
+                    error.message = %e,
+                    "Failed to flag the SLA breaches"
+                );
+            }
+        }
+
+        tokio::select! {
+            _ = tokio::time::sleep(check_interval) => {}
+            _ = shutdown.wait() => return Ok(()),
+        }
+    }
+}
+
+/// Records the targets missed by the tickets, notifies their assignees
+/// and then returns how many breaches have been flagged.
+///
+/// # Implementation Notes
+///
+/// A breach is only flagged once, even if several workers are running.
+/// A failing ticket is logged and skipped, it is checked again by the next run.
+#[tracing::instrument(name = "Flagging SLA breaches", skip(pool, policies))]
+pub async fn flag_sla_breaches(
+    pool: &PgPool,
+    policies: &SlaPolicies,
+    now: DateTime<Utc>,
+) -> Result<usize, anyhow::Error> {
+    let tickets = sqlx::query_as!(
+        ValidTicket,
+        r#"
+        SELECT *
+        FROM tickets
+        WHERE is_open = TRUE
+        "#
+    )
+    .fetch_all(pool)
+    .await
+    .context("Failed to get the open tickets from the tickets table")?;
+
+    let mut flagged = 0;
+    for ticket in tickets {
+        for (target, status) in policies.statuses(&ticket, now) {
+            let SlaTargetStatus::Breached(deadline) = status else {
+                continue;
+            };
+
+            match flag_sla_breach(pool, &ticket, target, deadline, now).await {
+                Ok(true) => flagged += 1,
+                Ok(false) => {}
+                Err(e) => {
+                    tracing::error!(
+                        error.cause_chain = ?e,
+                        error.message = %e,
+                        ticket_id = ticket.id,
+                        "Failed to flag the SLA breach"
+                    );
+                }
+            }
+        }
+    }
+
+    Ok(flagged)
+}
+
+/// Records the target missed by the ticket, notifies its assignee
+/// and then returns `false` if the breach was already flagged.
+///
+/// # Implementation Notes
+///
+/// The breach and its notification are saved together,
+/// so that a breach is never flagged without notifying the assignee.
+async fn flag_sla_breach(
+    pool: &PgPool,
+    ticket: &ValidTicket,
+    target: SlaTarget,
+    deadline: DateTime<Utc>,
+    now: DateTime<Utc>,
+) -> Result<bool, anyhow::Error> {
+    let mut transaction = pool
+        .begin()
+        .await
+        .context("Failed to acquire a Postgres connection from the pool")?;
+
+    let inserted = sqlx::query!(
+        r#"
+        INSERT INTO sla_breaches (ticket_id, target, breached_at, flagged_at)
+        VALUES ($1, $2, $3, $4)
+        ON CONFLICT DO NOTHING
+        RETURNING ticket_id
+        "#,
+        ticket.id,
+        target.as_str(),
+        deadline,
+        now
+    )
+    .fetch_optional(&mut transaction)
+    .await
+    .context("Failed to insert the breach into the sla_breaches table")?;
+    if inserted.is_none() {
+        return Ok(false);
+    }
+
+    let message = format!(
+        "The ticket {} breached its {} target.",
+        ticket.key,
+        target.label()
+    );
+    notify_ticket_assignee(
+        &mut transaction,
+        ticket.id,
+        None,
+        NotificationKind::SlaBreach,
+        &message,
+    )
+    .await
+    .context("Failed to insert the notification into the notifications table")?;
+
+    transaction
+        .commit()
+        .await
+        .context("Failed to commit SQL transaction to flag an SLA breach")?;
+
+    Ok(true)
+}
@@ -0,0 +1,85 @@
+use crate::{configuration::Settings, shutdown::Shutdown};
+use anyhow::Context;
+use chrono::{NaiveDate, Utc};
+use sqlx::PgPool;
+use std::time::Duration;
+
+/// Runs the sprint worker until stopped.
+pub async fn run_worker_until_stopped(
+    configuration: Settings,
+    shutdown: Shutdown,
+) -> Result<(), anyhow::Error> {
+    let connection_pool = configuration.database.get_connection_pool();
+
+    let outcome = worker_loop(
+        &connection_pool,
+        configuration.sprints.snapshot_interval(),
+        shutdown,
+    )
+    .await;
+    connection_pool.close().await;
+
+    outcome
+}
+
+/// Periodically records the remaining story points of the running sprints, until the shutdown.
+async fn worker_loop(
+    pool: &PgPool,
+    snapshot_interval: Duration,
+    shutdown: Shutdown,
+) -> Result<(), anyhow::Error> {
+    loop {
+        match record_sprint_snapshots(pool, Utc::now().date_naive()).await {
+            Ok(recorded) => {
+                tracing::info!(recorded, "Recorded the sprint snapshots");
+            }
+            Err(e) => {
+                tracing::error!(
+                    error.cause_chain = ?e,
+                    error.message = %e,
+                    "Failed to record the sprint snapshots"
+                );
+            }
+        }
+
+        tokio::select! {
+            _ = tokio::time::sleep(snapshot_interval) => {}
+            _ = shutdown.wait() => return Ok(()),
+        }
+    }
+}
+
+/// Records the remaining and the total story points of the sprints running at the given day
+/// and then returns how many snapshots have been recorded.
+///
+/// # Implementation Notes
+///
+/// The snapshot of a day is overwritten until the day is over,
+/// so the last one recorded holds the points left at the end of the day.
+#[tracing::instrument(name = "Recording sprint snapshots", skip(pool))]
+pub async fn record_sprint_snapshots(
+    pool: &PgPool,
+    today: NaiveDate,
+) -> Result<u64, anyhow::Error> {
+    let recorded = sqlx::query!(
+        r#"
+        INSERT INTO sprint_snapshots (sprint_id, taken_on, remaining_points, total_points)
+        SELECT sprints.id, $1,
+            COALESCE(SUM(tickets.story_points) FILTER (WHERE tickets.is_open), 0)::INTEGER,
+            COALESCE(SUM(tickets.story_points), 0)::INTEGER
+        FROM sprints
+        LEFT JOIN tickets ON tickets.sprint_id = sprints.id
+        WHERE sprints.starts_on <= $1 AND sprints.ends_on >= $1
+        GROUP BY sprints.id
+        ON CONFLICT (sprint_id, taken_on) DO UPDATE
+        SET remaining_points = EXCLUDED.remaining_points, total_points = EXCLUDED.total_points
+        "#,
+        today
+    )
+    .execute(pool)
+    .await
+    .context("Failed to insert the snapshots into the sprint_snapshots table")?
+    .rows_affected();
+
+    Ok(recorded)
+}
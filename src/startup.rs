@@ -1,35 +1,76 @@
 use crate::{
     authentication::reject_anonymous_users,
+    bootstrap::{bootstrap_admin, SetupToken},
     configuration::Settings,
+    correlation_id::{propagate_correlation_id, CorrelationRootSpanBuilder},
+    dashboard_cache::DashboardCache,
+    metrics::record_http_metrics,
+    migration::{check_schema, run_migrations},
     routes::{
-        change_password, change_password_form, close_ticket, create_ticket, create_ticket_form,
-        dashboard, health_check, home, login, login_form, logout, register, register_form,
-        see_ticket, see_tickets,
+        add_project_member, api_close_ticket, api_see_ticket, assign_ticket, change_password,
+        change_password_form, close_milestone, close_ticket, create_milestone, create_project,
+        create_sprint, create_ticket, create_ticket_form, create_ticket_link,
+        create_ticket_with_attachments, dashboard, delete_ticket_link, download_attachment,
+        download_attachment_thumbnail, download_changelog, export_metrics, export_report_csv,
+        export_report_json, export_timesheet, health_check, home, import_form, import_tickets,
+        log_time, login, login_form, logout, move_ticket, plan_ticket, preview_ticket_description,
+        project_switcher, read_all_notifications, read_notification, ready, register,
+        register_form, reopen_milestone, see_backlog, see_board, see_changelog, see_milestone,
+        see_milestones, see_notifications, see_projects, see_reports, see_sprint, see_sprints,
+        see_ticket, see_tickets, see_timesheet, set_due_date, set_estimate, set_milestone,
+        set_sprint, set_story_points, set_wip_limit, setup, setup_form, switch_project,
+        ticket_events, unread_notifications, upload_attachments,
     },
+    shutdown::Shutdown,
+    ticket_events::TicketEventBroadcaster,
+    utils::is_multipart_form,
 };
 use actix_files::Files;
 use actix_session::{storage::RedisSessionStore, SessionMiddleware};
-use actix_web::{cookie, dev::Server, web, App, HttpServer};
+use actix_web::{cookie, dev::Server, guard, web, App, HttpServer};
 use actix_web_flash_messages::{storage, FlashMessagesFramework};
 use actix_web_lab::middleware::from_fn;
 use secrecy::{ExposeSecret, Secret};
-use std::net::TcpListener;
+use sqlx::PgPool;
+use std::{net::TcpListener, time::Duration};
 use tracing_actix_web::TracingLogger;
 
 /// Representation of the HMAC secret.
 #[derive(Clone)]
 pub struct HmacSecret(pub Secret<String>);
 
+/// Representation of the connection pool serving the list and report queries.
+///
+/// # Implementation Notes
+///
+/// It is the pool of the read replica when configured, otherwise the pool of the primary database,
+/// so the pages it serves may lag slightly behind the latest changes.
+#[derive(Clone)]
+pub struct ReadPool(pub PgPool);
+
 /// Representation of the application.
 pub struct Application {
     server: Server,
     port: u16,
+    metrics_server: Option<Server>,
+    metrics_port: Option<u16>,
+    setup_token: web::Data<SetupToken>,
+    connection_pool: PgPool,
+    read_pool: PgPool,
+    shutdown: Shutdown,
+    shutdown_delay: Duration,
 }
 
 impl Application {
     /// Creates the application.
     pub async fn new(configuration: Settings) -> Result<Self, anyhow::Error> {
         let connection_pool = configuration.database.get_connection_pool();
+        if configuration.database.migrate_on_startup {
+            run_migrations(&connection_pool).await?;
+        } else {
+            check_schema(&connection_pool).await?;
+        }
+        let setup_token = bootstrap_admin(&connection_pool, &configuration.bootstrap).await?;
 
         let address = format!(
             "{}:{}",
@@ -37,9 +78,35 @@ impl Application {
         );
         let listener = TcpListener::bind(address)?;
         let port = listener.local_addr().unwrap().port();
+        // Expose the metrics on their own port when configured, to keep them private.
+        let metrics_listener = configuration
+            .application
+            .metrics_port
+            .map(|metrics_port| {
+                TcpListener::bind(format!(
+                    "{}:{}",
+                    configuration.application.host, metrics_port
+                ))
+            })
+            .transpose()?;
+        let metrics_port = metrics_listener
+            .as_ref()
+            .map(|listener| listener.local_addr().unwrap().port());
+        // Without their own port, the metrics are only public when asked for.
+        let has_public_metrics =
+            metrics_listener.is_none() && configuration.application.expose_metrics_on_main_port;
 
         // Create application data.
-        let db_pool = web::Data::new(connection_pool);
+        let shutdown = Shutdown::new();
+        let shutdown_delay = configuration.application.shutdown_delay();
+        let server_settings = configuration.application.clone();
+        let db_pool = web::Data::new(connection_pool.clone());
+        let read_pool = configuration
+            .database
+            .get_read_replica_pool()
+            .unwrap_or_else(|| connection_pool.clone());
+        let server_read_pool = web::Data::new(ReadPool(read_pool.clone()));
+        let application_settings = web::Data::new(configuration.application.clone());
         let hmac_secret = web::Data::new(HmacSecret(configuration.application.hmac_secret));
         let message_store = storage::CookieMessageStore::builder(cookie::Key::from(
             hmac_secret.0.expose_secret().as_bytes(),
@@ -47,16 +114,41 @@ impl Application {
         .build();
         let message_framework = FlashMessagesFramework::builder(message_store).build();
         let redis_store = RedisSessionStore::new(configuration.redis_uri.expose_secret()).await?;
+        let ticket_event_broadcaster = TicketEventBroadcaster::new(100);
+
+        // Forward the ticket events published by every instance of the application.
+        tokio::spawn(
+            ticket_event_broadcaster
+                .clone()
+                .run_until_stopped(db_pool.get_ref().clone(), shutdown.clone()),
+        );
+        let ticket_event_broadcaster = web::Data::new(ticket_event_broadcaster);
+        let attachment_storage = web::Data::from(configuration.attachments.storage.get_storage()?);
+        let attachment_settings = web::Data::new(configuration.attachments);
+        let sla_policies = web::Data::new(configuration.sla.get_policies()?);
+        let dashboard_cache =
+            web::Data::new(DashboardCache::new(configuration.dashboard.cache_ttl()));
+        let import_settings = web::Data::new(configuration.import);
+        let setup_token = web::Data::new(setup_token);
+        let redis_client = web::Data::new(redis::Client::open(
+            configuration.redis_uri.expose_secret().as_str(),
+        )?);
+        let server_setup_token = setup_token.clone();
+        let server_shutdown = web::Data::new(shutdown.clone());
 
         // Create the HTTP server.
         //
         // The HTTP server must be awaited or polled in order to start running.
+        let server_db_pool = db_pool.clone();
         let server = HttpServer::new(move || {
+            let db_pool = server_db_pool.clone();
             App::new()
                 // Serve static files.
                 .service(Files::new("/static", "static"))
                 // Middlewares.
-                .wrap(TracingLogger::default())
+                .wrap(from_fn(record_http_metrics))
+                .wrap(from_fn(propagate_correlation_id))
+                .wrap(TracingLogger::<CorrelationRootSpanBuilder>::new())
                 .wrap(message_framework.clone())
                 .wrap(SessionMiddleware::new(
                     redis_store.clone(),
@@ -65,31 +157,173 @@ impl Application {
                 // Endpoints.
                 .route("/", web::get().to(home))
                 .route("/health_check", web::get().to(health_check))
+                .route("/ready", web::get().to(ready))
+                .configure(|config| {
+                    if has_public_metrics {
+                        config.route("/metrics", web::get().to(export_metrics));
+                    }
+                })
                 .route("/register", web::get().to(register_form))
                 .route("/register", web::post().to(register))
                 .route("/login", web::get().to(login_form))
                 .route("/login", web::post().to(login))
+                .route("/setup", web::get().to(setup_form))
+                .route("/setup", web::post().to(setup))
                 .service(
                     web::scope("/dashboard")
                         .wrap(from_fn(reject_anonymous_users))
                         .route("/", web::get().to(dashboard))
                         .route("/tickets/new", web::get().to(create_ticket_form))
+                        .route(
+                            "/tickets/new",
+                            web::post()
+                                .guard(guard::fn_guard(is_multipart_form))
+                                .to(create_ticket_with_attachments),
+                        )
                         .route("/tickets/new", web::post().to(create_ticket))
+                        .route(
+                            "/tickets/preview",
+                            web::post().to(preview_ticket_description),
+                        )
                         .route("/tickets", web::get().to(see_tickets))
+                        .route("/tickets/events", web::get().to(ticket_events))
                         .route("/tickets/{id}", web::get().to(see_ticket))
                         .route("/tickets/{id}/close", web::post().to(close_ticket))
+                        .route("/tickets/{id}/assignee", web::post().to(assign_ticket))
+                        .route("/tickets/{id}/due-date", web::post().to(set_due_date))
+                        .route("/tickets/{id}/estimate", web::post().to(set_estimate))
+                        .route("/tickets/{id}/milestone", web::post().to(set_milestone))
+                        .route("/tickets/{id}/sprint", web::post().to(set_sprint))
+                        .route(
+                            "/tickets/{id}/story-points",
+                            web::post().to(set_story_points),
+                        )
+                        .route("/tickets/{id}/time-entries", web::post().to(log_time))
+                        .route("/tickets/{id}/links", web::post().to(create_ticket_link))
+                        .route(
+                            "/tickets/{id}/links/{link_id}/delete",
+                            web::post().to(delete_ticket_link),
+                        )
+                        .route(
+                            "/tickets/{id}/attachments",
+                            web::post().to(upload_attachments),
+                        )
+                        .route(
+                            "/tickets/{id}/attachments/{attachment_id}",
+                            web::get().to(download_attachment),
+                        )
+                        .route(
+                            "/tickets/{id}/attachments/{attachment_id}/thumbnail",
+                            web::get().to(download_attachment_thumbnail),
+                        )
+                        .route("/board", web::get().to(see_board))
+                        .route("/board/limits", web::post().to(set_wip_limit))
+                        .route("/board/tickets/{id}/move", web::post().to(move_ticket))
+                        .route("/milestones", web::get().to(see_milestones))
+                        .route("/milestones", web::post().to(create_milestone))
+                        .route("/milestones/{id}", web::get().to(see_milestone))
+                        .route("/milestones/{id}/close", web::post().to(close_milestone))
+                        .route("/milestones/{id}/reopen", web::post().to(reopen_milestone))
+                        .route("/milestones/{id}/changelog", web::get().to(see_changelog))
+                        .route(
+                            "/milestones/{id}/changelog.md",
+                            web::get().to(download_changelog),
+                        )
+                        .route("/sprints", web::get().to(see_sprints))
+                        .route("/sprints", web::post().to(create_sprint))
+                        .route("/sprints/{id}", web::get().to(see_sprint))
+                        .route("/backlog", web::get().to(see_backlog))
+                        .route("/backlog/tickets/{id}/sprint", web::post().to(plan_ticket))
+                        .route("/timesheet", web::get().to(see_timesheet))
+                        .route("/timesheet.csv", web::get().to(export_timesheet))
+                        .route("/reports", web::get().to(see_reports))
+                        .route("/import", web::get().to(import_form))
+                        .route("/import", web::post().to(import_tickets))
+                        .route("/reports.csv", web::get().to(export_report_csv))
+                        .route("/reports.json", web::get().to(export_report_json))
+                        .route("/notifications", web::get().to(see_notifications))
+                        .route("/notifications/unread", web::get().to(unread_notifications))
+                        .route(
+                            "/notifications/read",
+                            web::post().to(read_all_notifications),
+                        )
+                        .route(
+                            "/notifications/{id}/read",
+                            web::post().to(read_notification),
+                        )
+                        .route("/projects", web::get().to(see_projects))
+                        .route("/projects", web::post().to(create_project))
+                        .route("/projects/switcher", web::get().to(project_switcher))
+                        .route("/projects/current", web::post().to(switch_project))
+                        .route(
+                            "/projects/{key}/members",
+                            web::post().to(add_project_member),
+                        )
                         .route("/password", web::get().to(change_password_form))
                         .route("/password", web::post().to(change_password))
                         .route("/logout", web::post().to(logout)),
                 )
+                .service(
+                    web::scope("/api")
+                        .wrap(from_fn(reject_anonymous_users))
+                        .route("/tickets/{id}", web::get().to(api_see_ticket))
+                        .route("/tickets/{id}/close", web::post().to(api_close_ticket)),
+                )
                 // Set application data.
                 .app_data(db_pool.clone())
+                .app_data(server_read_pool.clone())
                 .app_data(hmac_secret.clone())
+                .app_data(ticket_event_broadcaster.clone())
+                .app_data(attachment_storage.clone())
+                .app_data(attachment_settings.clone())
+                .app_data(sla_policies.clone())
+                .app_data(dashboard_cache.clone())
+                .app_data(import_settings.clone())
+                .app_data(server_setup_token.clone())
+                .app_data(redis_client.clone())
+                .app_data(application_settings.clone())
+                .app_data(server_shutdown.clone())
         })
+        // The termination signals are handled by the caller, see `Application::shutdown`.
+        .disable_signals()
+        .keep_alive(server_settings.keep_alive())
+        .client_request_timeout(server_settings.client_request_timeout())
+        .client_disconnect_timeout(server_settings.client_disconnect_timeout())
+        .backlog(server_settings.backlog)
+        .shutdown_timeout(server_settings.shutdown_grace_period_seconds);
+        let server = match server_settings.workers {
+            Some(workers) => server.workers(workers),
+            None => server,
+        }
         .listen(listener)?
         .run();
 
-        Ok(Self { server, port })
+        let metrics_server = match metrics_listener {
+            Some(metrics_listener) => Some(
+                HttpServer::new(move || {
+                    App::new()
+                        .route("/metrics", web::get().to(export_metrics))
+                        .app_data(db_pool.clone())
+                })
+                .disable_signals()
+                .shutdown_timeout(server_settings.shutdown_grace_period_seconds)
+                .listen(metrics_listener)?
+                .run(),
+            ),
+            None => None,
+        };
+
+        Ok(Self {
+            server,
+            port,
+            metrics_server,
+            metrics_port,
+            setup_token,
+            connection_pool,
+            read_pool,
+            shutdown,
+            shutdown_delay,
+        })
     }
 
     /// Returns the port.
@@ -97,8 +331,54 @@ impl Application {
         self.port
     }
 
+    /// Returns the port of the metrics, if they have their own.
+    pub fn metrics_port(&self) -> Option<u16> {
+        self.metrics_port
+    }
+
+    /// Returns the token of the setup page, unless an administrator exists.
+    pub fn setup_token(&self) -> Option<Secret<String>> {
+        self.setup_token.get()
+    }
+
+    /// Returns the shutdown of the application, to be triggered in order to stop it.
+    pub fn shutdown(&self) -> Shutdown {
+        self.shutdown.clone()
+    }
+
     /// Runs the application until stopped.
+    ///
+    /// # Implementation Notes
+    ///
+    /// Once the shutdown is triggered, `/ready` reports it for the shutdown delay
+    /// so that the load balancers stop sending new requests,
+    /// the in-flight requests are then given the grace period to finish
+    /// before the connections to the database are closed.
     pub async fn run_until_stopped(self) -> Result<(), std::io::Error> {
-        self.server.await
+        let handles: Vec<_> = std::iter::once(self.server.handle())
+            .chain(self.metrics_server.as_ref().map(Server::handle))
+            .collect();
+        let shutdown = self.shutdown.clone();
+        let shutdown_delay = self.shutdown_delay;
+        // The servers only process their stop while being polled, so stop them from another task.
+        let stopping = tokio::spawn(async move {
+            shutdown.wait().await;
+            tracing::info!("Shutting down the application");
+            tokio::time::sleep(shutdown_delay).await;
+            for handle in handles {
+                handle.stop(true).await;
+            }
+        });
+
+        let outcome = match self.metrics_server {
+            Some(metrics_server) => tokio::try_join!(self.server, metrics_server).map(|_| ()),
+            None => self.server.await,
+        };
+        stopping.abort();
+        self.connection_pool.close().await;
+        self.read_pool.close().await;
+        tracing::info!("Stopped the application");
+
+        outcome
     }
 }
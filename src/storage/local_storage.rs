@@ -0,0 +1,64 @@
+use crate::storage::AttachmentStorage;
+use actix_web::web::Bytes;
+use anyhow::Context;
+use async_trait::async_trait;
+use std::path::PathBuf;
+
+/// Representation of a storage backend writing to the local filesystem.
+pub struct LocalStorage {
+    root: PathBuf,
+}
+
+impl LocalStorage {
+    /// Creates a storage writing under the given directory.
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    /// Returns the path of the file holding the given key.
+    fn path(&self, key: &str) -> PathBuf {
+        self.root.join(key)
+    }
+}
+
+#[async_trait]
+impl AttachmentStorage for LocalStorage {
+    /// Stores the content under the given key, replacing any previous content.
+    async fn put(
+        &self,
+        key: &str,
+        _content_type: &str,
+        content: Bytes,
+    ) -> Result<(), anyhow::Error> {
+        let path = self.path(key);
+
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent)
+                .await
+                .context("Failed to create the attachment directory")?;
+        }
+        tokio::fs::write(&path, content)
+            .await
+            .context("Failed to write the attachment file")?;
+
+        Ok(())
+    }
+
+    /// Returns the content stored under the given key.
+    async fn get(&self, key: &str) -> Result<Bytes, anyhow::Error> {
+        let content = tokio::fs::read(self.path(key))
+            .await
+            .context("Failed to read the attachment file")?;
+
+        Ok(content.into())
+    }
+
+    /// Deletes the content stored under the given key.
+    async fn delete(&self, key: &str) -> Result<(), anyhow::Error> {
+        tokio::fs::remove_file(self.path(key))
+            .await
+            .context("Failed to delete the attachment file")?;
+
+        Ok(())
+    }
+}
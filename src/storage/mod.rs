@@ -0,0 +1,26 @@
+mod local_storage;
+mod s3_storage;
+
+pub use local_storage::LocalStorage;
+pub use s3_storage::S3Storage;
+
+use actix_web::web::Bytes;
+use async_trait::async_trait;
+
+/// Representation of a storage backend for the content of the attachments.
+///
+/// # Implementation Notes
+///
+/// Keys are generated by the application, they are never taken from user input.
+#[async_trait]
+pub trait AttachmentStorage: Send + Sync {
+    /// Stores the content under the given key, replacing any previous content.
+    async fn put(&self, key: &str, content_type: &str, content: Bytes)
+        -> Result<(), anyhow::Error>;
+
+    /// Returns the content stored under the given key.
+    async fn get(&self, key: &str) -> Result<Bytes, anyhow::Error>;
+
+    /// Deletes the content stored under the given key.
+    async fn delete(&self, key: &str) -> Result<(), anyhow::Error>;
+}
@@ -0,0 +1,218 @@
+use crate::storage::AttachmentStorage;
+use actix_web::web::Bytes;
+use anyhow::Context;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
+use reqwest::{Client, Method, Url};
+use secrecy::{ExposeSecret, Secret};
+use sha2::{Digest, Sha256};
+
+/// Representation of a storage backend speaking the S3 API.
+///
+/// # Implementation Notes
+///
+/// Objects are addressed path-style, `{endpoint}/{bucket}/{key}`,
+/// which is supported by AWS as well as by self-hosted implementations such as MinIO.
+///
+/// Requests are signed with AWS Signature Version 4.
+pub struct S3Storage {
+    client: Client,
+    endpoint: Url,
+    bucket: String,
+    region: String,
+    access_key_id: String,
+    secret_access_key: Secret<String>,
+}
+
+impl S3Storage {
+    /// Creates a storage writing to the given bucket.
+    pub fn new(
+        endpoint: Url,
+        bucket: String,
+        region: String,
+        access_key_id: String,
+        secret_access_key: Secret<String>,
+    ) -> Self {
+        Self {
+            client: Client::new(),
+            endpoint,
+            bucket,
+            region,
+            access_key_id,
+            secret_access_key,
+        }
+    }
+
+    /// Returns the URL of the object holding the given key.
+    fn object_url(&self, key: &str) -> Result<Url, anyhow::Error> {
+        let url = format!(
+            "{}/{}/{}",
+            self.endpoint.as_str().trim_end_matches('/'),
+            self.bucket,
+            key
+        );
+
+        Url::parse(&url).context("Failed to build the object URL")
+    }
+
+    /// Sends a signed request and then return the response if successful.
+    async fn send(
+        &self,
+        method: Method,
+        key: &str,
+        content_type: Option<&str>,
+        content: Bytes,
+    ) -> Result<reqwest::Response, anyhow::Error> {
+        let url = self.object_url(key)?;
+        let payload_hash = hex::encode(Sha256::digest(&content));
+        let now = Utc::now();
+        let authorization = self.authorization(&method, &url, &payload_hash, now)?;
+
+        let mut request = self
+            .client
+            .request(method, url)
+            .header("Authorization", authorization)
+            .header("x-amz-content-sha256", payload_hash)
+            .header("x-amz-date", now.format("%Y%m%dT%H%M%SZ").to_string());
+        if let Some(content_type) = content_type {
+            request = request.header("Content-Type", content_type);
+        }
+
+        let response = request
+            .body(content)
+            .send()
+            .await
+            .context("Failed to send the request to the object storage")?
+            .error_for_status()
+            .context("The object storage rejected the request")?;
+
+        Ok(response)
+    }
+
+    /// Returns the `Authorization` header of a request.
+    fn authorization(
+        &self,
+        method: &Method,
+        url: &Url,
+        payload_hash: &str,
+        now: DateTime<Utc>,
+    ) -> Result<String, anyhow::Error> {
+        let amz_date = now.format("%Y%m%dT%H%M%SZ").to_string();
+        let date = now.format("%Y%m%d").to_string();
+
+        let host = match (url.host_str(), url.port()) {
+            (Some(host), Some(port)) => format!("{}:{}", host, port),
+            (Some(host), None) => host.to_string(),
+            (None, _) => anyhow::bail!("The object URL has no host"),
+        };
+
+        let signed_headers = "host;x-amz-content-sha256;x-amz-date";
+        let canonical_request = format!(
+            "{}\n{}\n\nhost:{}\nx-amz-content-sha256:{}\nx-amz-date:{}\n\n{}\n{}",
+            method.as_str(),
+            url.path(),
+            host,
+            payload_hash,
+            amz_date,
+            signed_headers,
+            payload_hash
+        );
+
+        let scope = format!("{}/{}/s3/aws4_request", date, self.region);
+        let string_to_sign = format!(
+            "AWS4-HMAC-SHA256\n{}\n{}\n{}",
+            amz_date,
+            scope,
+            hex::encode(Sha256::digest(canonical_request.as_bytes()))
+        );
+
+        let signing_key = signing_key(
+            self.secret_access_key.expose_secret(),
+            &date,
+            &self.region,
+            "s3",
+        );
+        let signature = hex::encode(hmac_sha256(&signing_key, string_to_sign.as_bytes()));
+
+        Ok(format!(
+            "AWS4-HMAC-SHA256 Credential={}/{}, SignedHeaders={}, Signature={}",
+            self.access_key_id, scope, signed_headers, signature
+        ))
+    }
+}
+
+#[async_trait]
+impl AttachmentStorage for S3Storage {
+    /// Stores the content under the given key, replacing any previous content.
+    async fn put(
+        &self,
+        key: &str,
+        content_type: &str,
+        content: Bytes,
+    ) -> Result<(), anyhow::Error> {
+        self.send(Method::PUT, key, Some(content_type), content)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Returns the content stored under the given key.
+    async fn get(&self, key: &str) -> Result<Bytes, anyhow::Error> {
+        let content = self
+            .send(Method::GET, key, None, Bytes::new())
+            .await?
+            .bytes()
+            .await
+            .context("Failed to read the object")?;
+
+        Ok(content)
+    }
+
+    /// Deletes the content stored under the given key.
+    async fn delete(&self, key: &str) -> Result<(), anyhow::Error> {
+        self.send(Method::DELETE, key, None, Bytes::new()).await?;
+
+        Ok(())
+    }
+}
+
+/// Returns the HMAC-SHA256 of the message.
+fn hmac_sha256(key: &[u8], message: &[u8]) -> Vec<u8> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts keys of any size");
+    mac.update(message);
+
+    mac.finalize().into_bytes().to_vec()
+}
+
+/// Returns the key signing the requests of a day, for a region and a service.
+fn signing_key(secret_access_key: &str, date: &str, region: &str, service: &str) -> Vec<u8> {
+    let date_key = hmac_sha256(
+        format!("AWS4{}", secret_access_key).as_bytes(),
+        date.as_bytes(),
+    );
+    let region_key = hmac_sha256(&date_key, region.as_bytes());
+    let service_key = hmac_sha256(&region_key, service.as_bytes());
+
+    hmac_sha256(&service_key, b"aws4_request")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::storage::s3_storage::signing_key;
+
+    // Must derive the signing key of the AWS documentation example.
+    #[test]
+    fn signing_key_matches_aws_example() {
+        let key = signing_key(
+            "wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY",
+            "20120215",
+            "us-east-1",
+            "iam",
+        );
+        assert_eq!(
+            hex::encode(key),
+            "f4780e2d9f65fa895f9c67b32ce1baf0b0d8a43505a000a1a9e090d414db404d"
+        );
+    }
+}
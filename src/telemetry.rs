@@ -1,29 +1,99 @@
+use crate::configuration::TracingSettings;
+use opentelemetry::{
+    global,
+    runtime::Tokio,
+    sdk::{
+        propagation::TraceContextPropagator,
+        trace::{self, Sampler, Tracer},
+        Resource,
+    },
+    trace::TraceError,
+    KeyValue,
+};
+use opentelemetry_otlp::WithExportConfig;
 use tokio::task::JoinHandle;
 use tracing::{subscriber::set_global_default, Subscriber};
 use tracing_bunyan_formatter::{BunyanFormattingLayer, JsonStorageLayer};
 use tracing_log::LogTracer;
-use tracing_subscriber::{fmt::MakeWriter, layer::SubscriberExt, EnvFilter, Registry};
+use tracing_subscriber::{fmt::MakeWriter, layer::SubscriberExt, EnvFilter, Layer, Registry};
 
 /// Returns a tracing `Subscriber` composed of multiple layers.
+///
+/// The spans are also exported through the given OpenTelemetry tracer, if any.
 pub fn get_subscriber<Sink>(
     name: String,
     env_filter: String,
     sink: Sink,
+    tracer: Option<Tracer>,
 ) -> impl Subscriber + Send + Sync
 where
     Sink: for<'a> MakeWriter<'a> + Send + Sync + 'static,
 {
     // Filter logs.
-    let env_filter =
-        EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new(env_filter));
+    let get_env_filter =
+        || EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new(&env_filter));
+
+    // Store the fields of the spans, only for the spans the records are made of.
+    let storage_layer = JsonStorageLayer.with_filter(get_env_filter());
 
     // Emit a Bunyan compatible formatted record.
-    let formatting_layer = BunyanFormattingLayer::new(name, sink);
+    let formatting_layer = BunyanFormattingLayer::new(name, sink).with_filter(get_env_filter());
+
+    // Export the spans, along with the statements executed by `sqlx` within them.
+    //
+    // `sqlx` 0.6 reports a statement once it has been executed, as an event rather than
+    // as a span of its own, so a statement shows up as an event of the span running it.
+    // The statements are only logged at the trace level, to keep them out of the records.
+    let opentelemetry_layer = tracer.map(|tracer| {
+        tracing_opentelemetry::layer()
+            .with_tracer(tracer)
+            .with_filter(get_env_filter().add_directive("sqlx::query=trace".parse().unwrap()))
+    });
 
     Registry::default()
-        .with(env_filter)
-        .with(JsonStorageLayer)
+        .with(storage_layer)
         .with(formatting_layer)
+        .with(opentelemetry_layer)
+}
+
+/// Returns a tracer exporting the spans to an OTLP collector, if one is configured.
+///
+/// # Implementation Notes
+///
+/// Must be called within a Tokio runtime, which exports the spans in the background.
+pub fn get_tracer(settings: &TracingSettings) -> Result<Option<Tracer>, TraceError> {
+    let endpoint = match &settings.otlp_endpoint {
+        Some(endpoint) => endpoint,
+        None => return Ok(None),
+    };
+
+    // Continue the traces started by the callers, from their `traceparent` header.
+    global::set_text_map_propagator(TraceContextPropagator::new());
+
+    let sampler = Sampler::ParentBased(Box::new(Sampler::TraceIdRatioBased(
+        settings.sampling_ratio,
+    )));
+    let resource = Resource::new([KeyValue::new("service.name", settings.service_name.clone())]);
+    let tracer = opentelemetry_otlp::new_pipeline()
+        .tracing()
+        .with_exporter(
+            opentelemetry_otlp::new_exporter()
+                .http()
+                .with_endpoint(endpoint),
+        )
+        .with_trace_config(
+            trace::config()
+                .with_sampler(sampler)
+                .with_resource(resource),
+        )
+        .install_batch(Tokio)?;
+
+    Ok(Some(tracer))
+}
+
+/// Exports the remaining spans and then stop the tracer.
+pub fn shutdown_tracer() {
+    global::shutdown_tracer_provider();
 }
 
 /// Initializes a `Subscriber` as global default to process span data.
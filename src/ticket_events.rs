@@ -0,0 +1,104 @@
+use crate::{domain::TicketEvent, shutdown::Shutdown};
+use anyhow::Context;
+use sqlx::{postgres::PgListener, PgPool};
+use tokio::sync::broadcast;
+
+/// Name of the Postgres channel carrying the ticket events.
+const TICKET_EVENTS_CHANNEL: &str = "ticket_events";
+
+/// Representation of the broadcaster of ticket events.
+///
+/// # Implementation Notes
+///
+/// Events are published through Postgres `NOTIFY` and received back through `LISTEN`,
+/// so that every instance of the application sees the events of every other instance.
+#[derive(Clone)]
+pub struct TicketEventBroadcaster(broadcast::Sender<TicketEvent>);
+
+impl TicketEventBroadcaster {
+    /// Creates a broadcaster buffering up to `capacity` events per subscriber.
+    pub fn new(capacity: usize) -> Self {
+        let (sender, _) = broadcast::channel(capacity);
+
+        Self(sender)
+    }
+
+    /// Returns a receiver of the events published from now on.
+    pub fn subscribe(&self) -> broadcast::Receiver<TicketEvent> {
+        self.0.subscribe()
+    }
+
+    /// Forwards the ticket events received from Postgres to the subscribers until stopped.
+    pub async fn run_until_stopped(self, pool: PgPool, shutdown: Shutdown) {
+        loop {
+            let outcome = tokio::select! {
+                outcome = self.forward_events(&pool) => outcome,
+                _ = shutdown.wait() => return,
+            };
+            if let Err(e) = outcome {
+                tracing::error!(
+                    error.cause_chain = ?e,
+                    error.message = %e,
+                    "Failed to listen to the ticket events"
+                );
+            }
+
+            // Avoid a busy loop while the database is unavailable.
+            tokio::select! {
+                _ = tokio::time::sleep(std::time::Duration::from_secs(1)) => {}
+                _ = shutdown.wait() => return,
+            }
+        }
+    }
+
+    /// Listens to the ticket events channel and forwards every notification.
+    async fn forward_events(&self, pool: &PgPool) -> Result<(), anyhow::Error> {
+        let mut listener = PgListener::connect_with(pool)
+            .await
+            .context("Failed to connect the listener")?;
+        listener
+            .listen(TICKET_EVENTS_CHANNEL)
+            .await
+            .context("Failed to listen to the ticket events channel")?;
+
+        loop {
+            let notification = listener
+                .recv()
+                .await
+                .context("Failed to receive a notification")?;
+
+            match serde_json::from_str::<TicketEvent>(notification.payload()) {
+                // Sending only fails when there is no subscriber.
+                Ok(event) => {
+                    let _ = self.0.send(event);
+                }
+                Err(e) => {
+                    tracing::warn!(
+                        error.message = %e,
+                        payload = notification.payload(),
+                        "Skipped a malformed ticket event"
+                    );
+                }
+            }
+        }
+    }
+}
+
+/// Publishes a ticket event to every instance of the application.
+#[tracing::instrument(name = "Publishing a ticket event", skip(pool))]
+pub async fn publish_ticket_event(pool: &PgPool, event: &TicketEvent) -> Result<(), anyhow::Error> {
+    let payload = serde_json::to_string(event).context("Failed to serialize the ticket event")?;
+
+    sqlx::query!(
+        r#"
+        SELECT pg_notify($1, $2)
+        "#,
+        TICKET_EVENTS_CHANNEL,
+        payload
+    )
+    .execute(pool)
+    .await
+    .context("Failed to notify the ticket events channel")?;
+
+    Ok(())
+}
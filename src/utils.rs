@@ -1,4 +1,21 @@
-use actix_web::{http::header::LOCATION, HttpResponse};
+use crate::correlation_id::CorrelationId;
+use actix_web::{
+    error::InternalError,
+    guard::GuardContext,
+    http::{
+        header::{ContentType, CONTENT_TYPE, LOCATION},
+        StatusCode,
+    },
+    HttpResponse,
+};
+use askama::Template;
+
+/// Representation of the error template.
+#[derive(Template)]
+#[template(path = "error.html")]
+struct ErrorTemplate {
+    correlation_id: Option<CorrelationId>,
+}
 
 /// Returns a `303 See Other` and redirect to the specified location.
 pub fn see_other(location: &str) -> HttpResponse {
@@ -12,5 +29,28 @@ pub fn e500<T>(e: T) -> actix_web::Error
 where
     T: std::fmt::Debug + std::fmt::Display + 'static,
 {
-    actix_web::error::ErrorInternalServerError(e)
+    InternalError::from_response(e, error_page(StatusCode::INTERNAL_SERVER_ERROR)).into()
+}
+
+/// Returns an error page showing the correlation id of the request,
+/// which lets the support find the logs of the error.
+pub fn error_page(status_code: StatusCode) -> HttpResponse {
+    let body = ErrorTemplate {
+        correlation_id: CorrelationId::current(),
+    }
+    .render()
+    .unwrap();
+
+    HttpResponse::build(status_code)
+        .content_type(ContentType::html())
+        .body(body)
+}
+
+/// Returns `true` if the request body is a multipart form.
+pub fn is_multipart_form(ctx: &GuardContext) -> bool {
+    ctx.head()
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.starts_with("multipart/form-data"))
 }
@@ -0,0 +1,50 @@
+// Server Sent Events extension of HTMX, enabled by `hx-ext="sse"`.
+//
+// An element with `sse-connect="<url>"` opens an event source, its descendants either
+// swap the data of an event with `sse-swap="<event>"` or send their request with `hx-trigger="sse:<event>"`.
+(function () {
+    var api;
+
+    htmx.defineExtension('sse', {
+        init: function (apiRef) {
+            api = apiRef;
+        },
+        onEvent: function (name, evt) {
+            if (name !== 'htmx:afterProcessNode') {
+                return;
+            }
+            var elt = evt.target;
+
+            // HTMX closes the event source when the element is removed.
+            var url = api.getAttributeValue(elt, 'sse-connect');
+            if (url && !api.getInternalData(elt).sseEventSource) {
+                api.getInternalData(elt).sseEventSource = htmx.createEventSource(url);
+            }
+
+            var swapNames = api.getAttributeValue(elt, 'sse-swap');
+            if (swapNames) {
+                var sourceElt = api.getClosestMatch(elt, function (e) {
+                    return api.getInternalData(e).sseEventSource != null;
+                });
+                if (!sourceElt) {
+                    api.triggerErrorEvent(elt, 'htmx:noSSESourceError');
+                    return;
+                }
+                var source = api.getInternalData(sourceElt).sseEventSource;
+                swapNames.split(',').forEach(function (swapName) {
+                    var listener = function (event) {
+                        if (!api.bodyContains(elt)) {
+                            source.removeEventListener(swapName.trim(), listener);
+                            return;
+                        }
+                        var settleInfo = api.makeSettleInfo(elt);
+                        var swapSpec = api.getSwapSpecification(elt);
+                        api.selectAndSwap(swapSpec.swapStyle, api.getTarget(elt), elt, event.data, settleInfo);
+                        api.settleImmediately(settleInfo.tasks);
+                    };
+                    source.addEventListener(swapName.trim(), listener);
+                });
+            }
+        }
+    });
+})();
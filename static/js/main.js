@@ -1,8 +1,60 @@
-// Tell HTMX to swap the content even if the response is a `400 Bad Request`.
+// Tell HTMX to swap the content even if the response is a `400 Bad Request`, a `409 Conflict`
+// or a server error, whose page shows the request id to give to the support.
 window.onload = function () {
     document.body.addEventListener('htmx:beforeSwap', function (evt) {
-        if (evt.detail.xhr.status === 400) {
+        var status = evt.detail.xhr.status;
+        if (status === 400 || status === 409 || status >= 500) {
             evt.detail.shouldSwap = true;
         }
     });
+
+    // Move the cards of the board by dragging them between the columns.
+    document.body.addEventListener('dragstart', function (evt) {
+        if (evt.target.classList && evt.target.classList.contains('board-card')) {
+            evt.target.classList.add('board-card-dragging');
+            evt.dataTransfer.effectAllowed = 'move';
+            evt.dataTransfer.setData('text/plain', evt.target.dataset.ticketId);
+        }
+    });
+    document.body.addEventListener('dragend', function (evt) {
+        if (evt.target.classList && evt.target.classList.contains('board-card')) {
+            evt.target.classList.remove('board-card-dragging');
+        }
+    });
+    document.body.addEventListener('dragover', function (evt) {
+        if (evt.target.closest && evt.target.closest('.board-column')) {
+            evt.preventDefault();
+        }
+    });
+    document.body.addEventListener('drop', function (evt) {
+        var column = evt.target.closest && evt.target.closest('.board-column');
+        var card = document.querySelector('.board-card-dragging');
+        if (!column || !card) {
+            return;
+        }
+        evt.preventDefault();
+
+        // The neighbours are the cards right above and right below the drop point.
+        var values = { column: column.dataset.column, version: card.dataset.version };
+        var cards = Array.from(column.querySelectorAll('.board-card')).filter(function (c) {
+            return c !== card;
+        });
+        var after = cards.find(function (c) {
+            var box = c.getBoundingClientRect();
+            return evt.clientY < box.top + box.height / 2;
+        });
+        var before = after ? cards[cards.indexOf(after) - 1] : cards[cards.length - 1];
+        if (before) {
+            values.before_id = before.dataset.ticketId;
+        }
+        if (after) {
+            values.after_id = after.dataset.ticketId;
+        }
+
+        htmx.ajax('POST', '/dashboard/board/tickets/' + card.dataset.ticketId + '/move', {
+            target: '.content',
+            swap: 'innerHTML',
+            values: values
+        });
+    });
 };
\ No newline at end of file
@@ -0,0 +1,127 @@
+use crate::helpers::{assert_is_redirect_to, create_and_run_test_app};
+use secrecy::Secret;
+use tessera::{
+    admin::{check_config, create_user, deactivate_user, reset_password},
+    configuration::get_configuration,
+    domain::UserRole,
+};
+
+// Must create a user with the given role, who can then log in.
+#[tokio::test]
+async fn create_user_stores_a_user_with_its_role() {
+    let test_app = create_and_run_test_app().await;
+
+    let user_id = create_user(
+        &test_app.db_pool,
+        "ops".to_string(),
+        Secret::new("correct-horse".to_string()),
+        UserRole::Admin,
+    )
+    .await
+    .unwrap();
+    let role = sqlx::query!("SELECT role FROM users WHERE user_id = $1", user_id)
+        .fetch_one(&test_app.db_pool)
+        .await
+        .unwrap()
+        .role;
+    assert_eq!(role, "admin");
+    let projects: Vec<String> = sqlx::query_scalar(
+        "SELECT projects.key FROM projects
+        JOIN project_members ON project_members.project_id = projects.id
+        WHERE project_members.user_id = $1",
+    )
+    .bind(user_id)
+    .fetch_all(&test_app.db_pool)
+    .await
+    .unwrap();
+    assert_eq!(projects, ["MAIN"]);
+
+    let response = test_app
+        .post_login(&serde_json::json!({ "username": "ops", "password": "correct-horse" }))
+        .await;
+    assert_is_redirect_to(&response, "/dashboard/");
+
+    // Must refuse a taken username or an invalid password.
+    let test_cases = [
+        ("ops", "correct-horse"),
+        (test_app.test_user.username.as_str(), "correct-horse"),
+        ("other-ops", "short"),
+    ];
+    for (username, password) in test_cases {
+        assert!(create_user(
+            &test_app.db_pool,
+            username.to_string(),
+            Secret::new(password.to_string()),
+            UserRole::User,
+        )
+        .await
+        .is_err());
+    }
+}
+
+// Must replace the password, the previous one being rejected.
+#[tokio::test]
+async fn reset_password_replaces_the_password() {
+    let test_app = create_and_run_test_app().await;
+    let username = &test_app.test_user.username;
+
+    reset_password(
+        &test_app.db_pool,
+        username,
+        Secret::new("correct-horse".to_string()),
+    )
+    .await
+    .unwrap();
+
+    let response = test_app
+        .post_login(&serde_json::json!({
+            "username": username,
+            "password": &test_app.test_user.password
+        }))
+        .await;
+    assert_is_redirect_to(&response, "/login");
+    let response = test_app
+        .post_login(&serde_json::json!({ "username": username, "password": "correct-horse" }))
+        .await;
+    assert_is_redirect_to(&response, "/dashboard/");
+
+    assert!(reset_password(
+        &test_app.db_pool,
+        "unknown",
+        Secret::new("correct-horse".to_string())
+    )
+    .await
+    .is_err());
+}
+
+// Must end the sessions of the deactivated user and refuse their next logins.
+#[tokio::test]
+async fn deactivate_user_logs_the_user_out() {
+    let test_app = create_and_run_test_app().await;
+    test_app.test_user.login(&test_app).await;
+
+    deactivate_user(&test_app.db_pool, &test_app.test_user.username)
+        .await
+        .unwrap();
+
+    let response = test_app.get_dashboard().await;
+    assert_is_redirect_to(&response, "/login");
+    let response = test_app
+        .post_login(&serde_json::json!({
+            "username": &test_app.test_user.username,
+            "password": &test_app.test_user.password
+        }))
+        .await;
+    assert_is_redirect_to(&response, "/login");
+}
+
+// Must connect to the database and to Redis, and report the one that cannot be reached.
+#[tokio::test]
+async fn check_config_tests_the_connections() {
+    let mut configuration = get_configuration().expect("Failed to get the configuration values");
+    check_config(&configuration).await.unwrap();
+
+    configuration.redis_uri = Secret::new("redis://127.0.0.1:1".to_string());
+    let error = check_config(&configuration).await.unwrap_err();
+    assert_eq!(error.to_string(), "Failed to connect to Redis");
+}
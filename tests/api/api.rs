@@ -0,0 +1,115 @@
+use crate::helpers::create_and_run_test_app;
+
+// Must return the ticket as JSON with its version as `ETag`,
+// when a `GET` request with a valid ticket id is received at `/api/tickets/{id}`.
+#[tokio::test]
+async fn api_see_ticket_returns_the_ticket_with_an_etag() {
+    let test_app = create_and_run_test_app().await;
+    test_app.test_user.login(&test_app).await;
+
+    let body = "title=Issue with x&description=After doing x&priority=medium";
+    test_app.post_tickets(body.into()).await;
+
+    let response = test_app.get_api_ticket(1).await;
+    assert_eq!(response.status().as_u16(), 200);
+    assert_eq!(response.headers().get("ETag").unwrap(), "\"1\"");
+
+    let ticket: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(ticket["id"], 1);
+    assert_eq!(ticket["title"], "Issue with x");
+    assert_eq!(ticket["is_open"], true);
+    assert_eq!(ticket["version"], 1);
+}
+
+// Must return a `404 Not Found` response,
+// when a `GET` request with an invalid ticket id is received at `/api/tickets/{id}`.
+#[tokio::test]
+async fn api_see_ticket_returns_a_404_when_invalid_ticket_id() {
+    let test_app = create_and_run_test_app().await;
+    test_app.test_user.login(&test_app).await;
+
+    let response = test_app.get_api_ticket(1).await;
+    assert_eq!(response.status().as_u16(), 404);
+}
+
+// Must close the ticket and return the new `ETag`,
+// when a `POST` request with a matching `If-Match` is received at `/api/tickets/{id}/close`.
+#[tokio::test]
+async fn api_close_ticket_closes_the_ticket_when_matching_version() {
+    let test_app = create_and_run_test_app().await;
+    test_app.test_user.login(&test_app).await;
+
+    let body = "title=Issue with x&description=After doing x&priority=medium";
+    test_app.post_tickets(body.into()).await;
+
+    let response = test_app.post_api_close_ticket(1, Some("\"1\"")).await;
+    assert_eq!(response.status().as_u16(), 200);
+    assert_eq!(response.headers().get("ETag").unwrap(), "\"2\"");
+
+    let ticket: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(ticket["is_open"], false);
+}
+
+// Must still report the ticket as closed,
+// when notifying its creator fails once the change is committed.
+#[tokio::test]
+async fn api_close_ticket_returns_a_200_when_the_notification_fails() {
+    let test_app = create_and_run_test_app().await;
+    test_app.test_user.login(&test_app).await;
+
+    let body = "title=Issue with x&description=After doing x&priority=medium";
+    test_app.post_tickets(body.into()).await;
+    sqlx::query("ALTER TABLE notifications RENAME TO unavailable_notifications")
+        .execute(&test_app.db_pool)
+        .await
+        .unwrap();
+
+    let response = test_app.post_api_close_ticket(1, Some("\"1\"")).await;
+    assert_eq!(response.status().as_u16(), 200);
+    let ticket: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(ticket["is_open"], false);
+}
+
+// Must return a `409 Conflict` response with the changes made in the meantime,
+// when a `POST` request with a stale `If-Match` is received at `/api/tickets/{id}/close`.
+#[tokio::test]
+async fn api_close_ticket_returns_a_409_when_stale_version() {
+    let test_app = create_and_run_test_app().await;
+    test_app.test_user.login(&test_app).await;
+
+    let body = "title=Issue with x&description=After doing x&priority=medium";
+    test_app.post_tickets(body.into()).await;
+    test_app.post_api_close_ticket(1, Some("\"1\"")).await;
+
+    let response = test_app.post_api_close_ticket(1, Some("\"1\"")).await;
+    assert_eq!(response.status().as_u16(), 409);
+
+    let error: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(error["changes"][0]["version"], 2);
+    assert_eq!(error["changes"][0]["field"], "status");
+    assert_eq!(error["changes"][0]["new_value"], "closed");
+}
+
+// Must return a `428 Precondition Required` response,
+// when a `POST` request without `If-Match` is received at `/api/tickets/{id}/close`.
+#[tokio::test]
+async fn api_close_ticket_returns_a_428_when_missing_if_match() {
+    let test_app = create_and_run_test_app().await;
+    test_app.test_user.login(&test_app).await;
+
+    let body = "title=Issue with x&description=After doing x&priority=medium";
+    test_app.post_tickets(body.into()).await;
+
+    let response = test_app.post_api_close_ticket(1, None).await;
+    assert_eq!(response.status().as_u16(), 428);
+}
+
+// Must redirect an unknown user trying to use the API.
+#[tokio::test]
+async fn api_see_ticket_redirects_if_not_logged_in() {
+    let test_app = create_and_run_test_app().await;
+
+    let response = test_app.get_api_ticket(1).await;
+    assert_eq!(response.status().as_u16(), 303);
+    assert_eq!(response.headers().get("Location").unwrap(), "/login");
+}
@@ -0,0 +1,193 @@
+use crate::helpers::{
+    configure_database, create_and_run_test_app, temp_storage, TestApp, TestUser,
+};
+use reqwest::multipart::{Form, Part};
+use sqlx::PgPool;
+use std::{path::PathBuf, sync::Arc};
+use tessera::{
+    archive::{export_archive, import_archive, ARCHIVE_TABLES},
+    configuration::get_configuration,
+    storage::AttachmentStorage,
+};
+use uuid::Uuid;
+
+/// Returns a random path in the temporary directory.
+fn temp_path() -> PathBuf {
+    std::env::temp_dir().join(Uuid::new_v4().to_string())
+}
+
+/// Returns a pool to a new empty database and a storage in a new directory.
+async fn empty_instance() -> (PgPool, Arc<dyn AttachmentStorage>) {
+    let mut configuration = get_configuration().expect("Failed to get the configuration values");
+    configuration.database.database_name = Uuid::new_v4().to_string();
+    let pool = configure_database(&configuration.database).await;
+    let storage = temp_storage().get_storage().unwrap();
+
+    (pool, storage)
+}
+
+/// Creates a closed ticket with an attachment and logged time.
+async fn create_ticket(test_app: &TestApp) {
+    let form = Form::new()
+        .text("title", "Issue with x")
+        .text("description", "After doing x")
+        .text("priority", "high")
+        .part(
+            "attachments",
+            Part::bytes(b"panicked at ...".to_vec())
+                .file_name("logs.txt")
+                .mime_str("text/plain")
+                .unwrap(),
+        );
+    test_app.post_tickets_with_attachments(form).await;
+    let body = serde_json::json!({ "duration": "1h", "spent_on": "2026-10-01" });
+    test_app.post_time_entries(1, &body).await;
+    test_app.post_close_ticket(1, 1).await;
+}
+
+/// Returns the password hash of the user.
+async fn password_hash_of(pool: &PgPool, username: &str) -> String {
+    sqlx::query!(
+        "SELECT password_hash FROM users WHERE username = $1",
+        username
+    )
+    .fetch_one(pool)
+    .await
+    .unwrap()
+    .password_hash
+}
+
+// Must restore the tickets, their related data and their attachments into an empty instance,
+// but not the password hashes.
+#[tokio::test]
+async fn archive_round_trip_restores_every_table_and_attachment() {
+    let test_app = create_and_run_test_app().await;
+    test_app.test_user.login(&test_app).await;
+    create_ticket(&test_app).await;
+
+    let path = temp_path();
+    let manifest = export_archive(
+        &test_app.db_pool,
+        &*test_app.attachment_storage,
+        &path,
+        false,
+    )
+    .await
+    .unwrap();
+    assert!(!manifest.includes_password_hashes);
+    assert_eq!(manifest.tables["tickets"], 1);
+    assert_eq!(manifest.tables["time_entries"], 1);
+    assert_eq!(manifest.attachments, 1);
+
+    let (pool, storage) = empty_instance().await;
+    import_archive(&pool, &*storage, &path).await.unwrap();
+
+    let ticket = sqlx::query!("SELECT key, title, priority, is_open, version FROM tickets")
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(ticket.key, "MAIN-1");
+    assert_eq!(ticket.title, "Issue with x");
+    assert_eq!(ticket.priority, "high");
+    assert!(!ticket.is_open);
+    assert_eq!(ticket.version, 2);
+    let attachment = sqlx::query!("SELECT storage_key FROM attachments")
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    let content = storage.get(&attachment.storage_key).await.unwrap();
+    assert_eq!(&content[..], b"panicked at ...");
+
+    // The users must reset their password, and the new rows must not reuse the restored ids.
+    let username = &test_app.test_user.username;
+    assert_ne!(
+        password_hash_of(&pool, username).await,
+        password_hash_of(&test_app.db_pool, username).await
+    );
+    let next_id = sqlx::query!(r#"SELECT nextval('tickets_id_seq') AS "id!""#)
+        .fetch_one(&pool)
+        .await
+        .unwrap()
+        .id;
+    assert_eq!(next_id, 2);
+
+    // Must refuse to restore into a database holding data.
+    assert!(import_archive(&pool, &*storage, &path).await.is_err());
+}
+
+// Must refuse to restore into a database holding users but no tickets yet.
+#[tokio::test]
+async fn import_archive_fails_when_the_database_holds_users() {
+    let test_app = create_and_run_test_app().await;
+    let path = temp_path();
+    export_archive(
+        &test_app.db_pool,
+        &*test_app.attachment_storage,
+        &path,
+        false,
+    )
+    .await
+    .unwrap();
+
+    let (pool, storage) = empty_instance().await;
+    let user = TestUser::new();
+    user.store(&pool).await;
+
+    let error = import_archive(&pool, &*storage, &path).await.unwrap_err();
+    assert!(error.to_string().contains("users"));
+    let usernames: Vec<String> = sqlx::query_scalar("SELECT username FROM users")
+        .fetch_all(&pool)
+        .await
+        .unwrap();
+    assert_eq!(usernames, vec![user.username]);
+}
+
+// Must keep the password hashes when explicitly requested.
+#[tokio::test]
+async fn archive_keeps_password_hashes_when_requested() {
+    let test_app = create_and_run_test_app().await;
+
+    let path = temp_path();
+    let manifest = export_archive(
+        &test_app.db_pool,
+        &*test_app.attachment_storage,
+        &path,
+        true,
+    )
+    .await
+    .unwrap();
+    assert!(manifest.includes_password_hashes);
+
+    let (pool, storage) = empty_instance().await;
+    import_archive(&pool, &*storage, &path).await.unwrap();
+    let username = &test_app.test_user.username;
+    assert_eq!(
+        password_hash_of(&pool, username).await,
+        password_hash_of(&test_app.db_pool, username).await
+    );
+}
+
+// Must store every table of the database.
+#[tokio::test]
+async fn archive_tables_cover_the_whole_schema() {
+    let test_app = create_and_run_test_app().await;
+
+    let mut tables: Vec<String> = sqlx::query!(
+        r#"
+        SELECT table_name AS "table_name!"
+        FROM information_schema.tables
+        WHERE table_schema = 'public' AND table_name <> '_sqlx_migrations'
+        "#
+    )
+    .fetch_all(&test_app.db_pool)
+    .await
+    .unwrap()
+    .into_iter()
+    .map(|row| row.table_name)
+    .collect();
+    tables.sort();
+
+    let mut archive_tables = ARCHIVE_TABLES.to_vec();
+    archive_tables.sort();
+    assert_eq!(tables, archive_tables);
+}
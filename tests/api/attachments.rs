@@ -0,0 +1,380 @@
+use crate::helpers::{
+    assert_is_redirect_to, create_and_run_test_app, create_and_run_test_app_with, TestApp,
+};
+use actix_web::{web, App, HttpRequest, HttpResponse, HttpServer};
+use image::{ImageBuffer, ImageOutputFormat, Rgb};
+use reqwest::multipart::{Form, Part};
+use secrecy::Secret;
+use std::{collections::HashMap, io::Cursor, net::TcpListener, sync::Mutex};
+use tessera::{
+    configuration::StorageSettings,
+    domain::ValidAttachment,
+    routes::get_attachments,
+    storage::{AttachmentStorage, S3Storage},
+};
+use uuid::Uuid;
+
+/// Returns a form part holding a file.
+fn file_part(file_name: &str, content_type: &str, content: Vec<u8>) -> Part {
+    Part::bytes(content)
+        .file_name(file_name.to_string())
+        .mime_str(content_type)
+        .unwrap()
+}
+
+/// Returns a 300x300 PNG image.
+fn png_image() -> Vec<u8> {
+    let mut content = Vec::new();
+    ImageBuffer::from_pixel(300, 300, Rgb([255u8, 165, 0]))
+        .write_to(&mut Cursor::new(&mut content), ImageOutputFormat::Png)
+        .unwrap();
+
+    content
+}
+
+/// Creates a ticket without attachments.
+async fn create_ticket(test_app: &TestApp) {
+    let body = "title=Issue with x&description=After doing x&priority=medium";
+    test_app.post_tickets(body.into()).await;
+}
+
+/// Returns the attachments of the ticket.
+async fn attachments_of(test_app: &TestApp, ticket_id: i32) -> Vec<ValidAttachment> {
+    get_attachments(&test_app.db_pool, ticket_id).await.unwrap()
+}
+
+// Must persist the ticket and its attachments,
+// when a multipart `POST` request is received at `/dashboard/tickets/new`.
+#[tokio::test]
+async fn create_ticket_with_attachments_persists_the_attachments() {
+    let test_app = create_and_run_test_app().await;
+    test_app.test_user.login(&test_app).await;
+
+    let form = Form::new()
+        .text("title", "Issue with x")
+        .text("description", "After doing x")
+        .text("priority", "medium")
+        .part(
+            "attachments",
+            file_part("logs.txt", "text/plain", b"panicked at ...".to_vec()),
+        );
+    let response = test_app.post_tickets_with_attachments(form).await;
+    assert_is_redirect_to(&response, "/dashboard/tickets/new");
+
+    let attachments = attachments_of(&test_app, 1).await;
+    assert_eq!(attachments.len(), 1);
+    assert_eq!(attachments[0].file_name, "logs.txt");
+    assert_eq!(attachments[0].size_bytes, 15);
+    assert!(attachments[0].thumbnail_key.is_none());
+
+    let response = test_app.get_attachment(1, attachments[0].id).await;
+    assert_eq!(response.status().as_u16(), 200);
+    assert_eq!(
+        response.headers().get("Content-Type").unwrap(),
+        "text/plain"
+    );
+    assert_eq!(
+        response.headers().get("Content-Disposition").unwrap(),
+        r#"attachment; filename="logs.txt""#
+    );
+    assert_eq!(response.text().await.unwrap(), "panicked at ...");
+}
+
+// Must return a `400 Bad Request` response,
+// when a multipart `POST` request with invalid data is received at `/dashboard/tickets/new`.
+#[tokio::test]
+async fn create_ticket_with_attachments_returns_a_400_when_invalid_data() {
+    let test_app = create_and_run_test_app().await;
+    test_app.test_user.login(&test_app).await;
+
+    let form = Form::new()
+        .text("title", "")
+        .text("description", "After doing x")
+        .text("priority", "medium");
+    let response = test_app.post_tickets_with_attachments(form).await;
+    assert_eq!(response.status().as_u16(), 400);
+
+    let form = Form::new()
+        .text("title", "Issue with x")
+        .text("description", "After doing x")
+        .text("priority", "medium")
+        .part(
+            "attachments",
+            file_part("page.html", "text/html", b"<p></p>".to_vec()),
+        );
+    let response = test_app.post_tickets_with_attachments(form).await;
+    assert_eq!(response.status().as_u16(), 400);
+}
+
+// Must return a `400 Bad Request` response,
+// when a multipart `POST` request with too many parts or too many bytes is received at `/dashboard/tickets/new`.
+#[tokio::test]
+async fn create_ticket_with_attachments_returns_a_400_when_the_form_is_too_large() {
+    let test_app = create_and_run_test_app_with(|configuration| {
+        configuration.attachments.max_parts = 4;
+        configuration.attachments.max_form_size_bytes = 100;
+    })
+    .await;
+    test_app.test_user.login(&test_app).await;
+
+    let form = Form::new()
+        .text("title", "Issue with x")
+        .text("description", "After doing x")
+        .text("priority", "medium")
+        .part(
+            "attachments",
+            file_part("a.txt", "text/plain", b"a".to_vec()),
+        )
+        .part(
+            "attachments",
+            file_part("b.txt", "text/plain", b"b".to_vec()),
+        );
+    let response = test_app.post_tickets_with_attachments(form).await;
+    assert_eq!(response.status().as_u16(), 400);
+    assert!(response
+        .text()
+        .await
+        .unwrap()
+        .contains("the form cannot have more than 4 parts."));
+
+    let form = Form::new()
+        .text("title", "Issue with x")
+        .text("description", "x".repeat(90))
+        .text("priority", "medium");
+    let response = test_app.post_tickets_with_attachments(form).await;
+    assert_eq!(response.status().as_u16(), 400);
+    assert!(response
+        .text()
+        .await
+        .unwrap()
+        .contains("the form cannot be larger than 100 bytes."));
+
+    let tickets: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM tickets")
+        .fetch_one(&test_app.db_pool)
+        .await
+        .unwrap();
+    assert_eq!(tickets, 0);
+}
+
+// Must not save the ticket when its files cannot be stored.
+#[tokio::test]
+async fn create_ticket_with_attachments_saves_nothing_when_the_files_cannot_be_stored() {
+    // A file in place of the storage directory makes every write fail.
+    let path = std::env::temp_dir().join(Uuid::new_v4().to_string());
+    std::fs::write(&path, "").unwrap();
+    let test_app = create_and_run_test_app_with(|configuration| {
+        configuration.attachments.storage = StorageSettings::Local {
+            path: path.to_string_lossy().into(),
+        };
+    })
+    .await;
+    test_app.test_user.login(&test_app).await;
+
+    let form = Form::new()
+        .text("title", "Issue with x")
+        .text("description", "After doing x")
+        .text("priority", "medium")
+        .part(
+            "attachments",
+            file_part("logs.txt", "text/plain", b"panicked at ...".to_vec()),
+        );
+    let response = test_app.post_tickets_with_attachments(form).await;
+    assert_eq!(response.status().as_u16(), 500);
+
+    let tickets: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM tickets")
+        .fetch_one(&test_app.db_pool)
+        .await
+        .unwrap();
+    assert_eq!(tickets, 0);
+}
+
+// Must create a thumbnail of the images,
+// when a `POST` request is received at `/dashboard/tickets/{id}/attachments`.
+#[tokio::test]
+async fn upload_attachments_creates_a_thumbnail_of_images() {
+    let test_app = create_and_run_test_app().await;
+    test_app.test_user.login(&test_app).await;
+    create_ticket(&test_app).await;
+
+    let form = Form::new().part(
+        "attachments",
+        file_part("screenshot.png", "image/png", png_image()),
+    );
+    let response = test_app.post_attachments(1, form).await;
+    assert_is_redirect_to(&response, "/dashboard/tickets/1");
+
+    let html_page = test_app.get_see_ticket_html(1).await;
+    assert!(html_page.contains("You have successfully attached the files."));
+    assert!(html_page.contains("screenshot.png"));
+
+    let attachments = attachments_of(&test_app, 1).await;
+    assert_eq!(attachments.len(), 1);
+
+    let response = test_app
+        .get_attachment_thumbnail(1, attachments[0].id)
+        .await;
+    assert_eq!(response.status().as_u16(), 200);
+    assert_eq!(response.headers().get("Content-Type").unwrap(), "image/png");
+
+    let thumbnail = image::load_from_memory(&response.bytes().await.unwrap()).unwrap();
+    assert_eq!((thumbnail.width(), thumbnail.height()), (200, 200));
+}
+
+// Must reject the files of a type not allowed,
+// when a `POST` request is received at `/dashboard/tickets/{id}/attachments`.
+#[tokio::test]
+async fn upload_attachments_rejects_content_types_not_allowed() {
+    let test_app = create_and_run_test_app().await;
+    test_app.test_user.login(&test_app).await;
+    create_ticket(&test_app).await;
+
+    let form = Form::new().part(
+        "attachments",
+        file_part("page.html", "text/html", b"<script></script>".to_vec()),
+    );
+    let response = test_app.post_attachments(1, form).await;
+    assert_is_redirect_to(&response, "/dashboard/tickets/1");
+
+    let html_page = test_app.get_see_ticket_html(1).await;
+    assert!(html_page.contains("cannot be of type `text/html`."));
+    assert!(attachments_of(&test_app, 1).await.is_empty());
+}
+
+// Must reject the files larger than the limit,
+// when a `POST` request is received at `/dashboard/tickets/{id}/attachments`.
+#[tokio::test]
+async fn upload_attachments_rejects_files_too_large() {
+    let test_app = create_and_run_test_app().await;
+    test_app.test_user.login(&test_app).await;
+    create_ticket(&test_app).await;
+
+    let form = Form::new().part(
+        "attachments",
+        file_part("logs.txt", "text/plain", vec![b'a'; 10 * 1024 * 1024 + 1]),
+    );
+    let response = test_app.post_attachments(1, form).await;
+    assert_is_redirect_to(&response, "/dashboard/tickets/1");
+
+    let html_page = test_app.get_see_ticket_html(1).await;
+    assert!(html_page.contains("cannot be larger than 10485760 bytes."));
+    assert!(attachments_of(&test_app, 1).await.is_empty());
+}
+
+// Must return a `404 Not Found` response,
+// when the attachment belongs to another ticket.
+#[tokio::test]
+async fn download_attachment_returns_a_404_when_another_ticket() {
+    let test_app = create_and_run_test_app().await;
+    test_app.test_user.login(&test_app).await;
+    create_ticket(&test_app).await;
+    create_ticket(&test_app).await;
+
+    let form = Form::new().part(
+        "attachments",
+        file_part("logs.txt", "text/plain", b"panicked at ...".to_vec()),
+    );
+    test_app.post_attachments(1, form).await;
+    let attachments = attachments_of(&test_app, 1).await;
+
+    let response = test_app.get_attachment(2, attachments[0].id).await;
+    assert_eq!(response.status().as_u16(), 404);
+
+    let response = test_app
+        .get_attachment_thumbnail(1, attachments[0].id)
+        .await;
+    assert_eq!(response.status().as_u16(), 404);
+}
+
+// Must redirect an unknown user trying to download an attachment.
+#[tokio::test]
+async fn download_attachment_redirects_if_not_logged_in() {
+    let test_app = create_and_run_test_app().await;
+    test_app.test_user.login(&test_app).await;
+    create_ticket(&test_app).await;
+
+    let form = Form::new().part(
+        "attachments",
+        file_part("logs.txt", "text/plain", b"panicked at ...".to_vec()),
+    );
+    test_app.post_attachments(1, form).await;
+    let attachments = attachments_of(&test_app, 1).await;
+    test_app.test_user.logout(&test_app).await;
+
+    let response = test_app.get_attachment(1, attachments[0].id).await;
+    assert_is_redirect_to(&response, "/login");
+}
+
+/// Handles the object requests of the S3 stand-in, storing objects in memory.
+async fn s3_object(
+    request: HttpRequest,
+    body: web::Bytes,
+    objects: web::Data<Mutex<HashMap<String, web::Bytes>>>,
+) -> HttpResponse {
+    let is_signed = request
+        .headers()
+        .get("Authorization")
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.starts_with("AWS4-HMAC-SHA256 Credential=minio/"));
+    if !is_signed || request.headers().get("x-amz-date").is_none() {
+        return HttpResponse::Forbidden().finish();
+    }
+
+    let mut objects = objects.lock().unwrap();
+    let path = request.path().to_string();
+    match request.method().as_str() {
+        "PUT" => {
+            objects.insert(path, body);
+            HttpResponse::Ok().finish()
+        }
+        "GET" => match objects.get(&path) {
+            Some(content) => HttpResponse::Ok().body(content.clone()),
+            None => HttpResponse::NotFound().finish(),
+        },
+        "DELETE" => {
+            objects.remove(&path);
+            HttpResponse::NoContent().finish()
+        }
+        _ => HttpResponse::MethodNotAllowed().finish(),
+    }
+}
+
+/// Runs a stand-in of an S3-compatible object storage and then return its address.
+fn spawn_s3_stand_in() -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let objects = web::Data::new(Mutex::new(HashMap::<String, web::Bytes>::new()));
+
+    let server = HttpServer::new(move || {
+        App::new()
+            .app_data(objects.clone())
+            .default_service(web::to(s3_object))
+    })
+    .listen(listener)
+    .unwrap()
+    .run();
+    tokio::spawn(server);
+
+    format!("http://127.0.0.1:{}", port)
+}
+
+// Must store, return and delete objects with signed requests.
+#[tokio::test]
+async fn s3_storage_stores_returns_and_deletes_objects() {
+    let storage = S3Storage::new(
+        spawn_s3_stand_in().parse().unwrap(),
+        "attachments".to_string(),
+        "us-east-1".to_string(),
+        "minio".to_string(),
+        Secret::new("minio-secret".to_string()),
+    );
+
+    storage
+        .put("tickets/1/logs", "text/plain", "panicked at ...".into())
+        .await
+        .unwrap();
+    let content = storage.get("tickets/1/logs").await.unwrap();
+    assert_eq!(content, "panicked at ...");
+
+    storage.delete("tickets/1/logs").await.unwrap();
+    assert!(storage.get("tickets/1/logs").await.is_err());
+}
@@ -0,0 +1,153 @@
+use crate::helpers::{assert_is_redirect_to, create_and_run_test_app, TestApp, TestUser};
+use tessera::routes::get_ticket;
+
+/// Creates a ticket with the given title.
+async fn create_ticket(test_app: &TestApp, title: &str) {
+    let body = format!("title={}&description=After doing x&priority=medium", title);
+    test_app.post_tickets(body).await;
+}
+
+/// Returns the positions of the titles in the page, in the order they are displayed.
+fn order_of(html_page: &str, titles: &[&str]) -> Vec<usize> {
+    titles
+        .iter()
+        .map(|title| html_page.find(title).unwrap())
+        .collect()
+}
+
+/// Makes the test user an administrator.
+async fn promote_to_admin(test_app: &TestApp) {
+    sqlx::query!(
+        "UPDATE users SET role = 'admin' WHERE user_id = $1",
+        test_app.test_user.user_id
+    )
+    .execute(&test_app.db_pool)
+    .await
+    .unwrap();
+}
+
+// Must close the ticket and increment its version,
+// when a ticket is moved to the `closed` column.
+#[tokio::test]
+async fn move_ticket_to_another_column_performs_the_transition() {
+    let test_app = create_and_run_test_app().await;
+    test_app.test_user.login(&test_app).await;
+    create_ticket(&test_app, "Issue with x").await;
+
+    let body = serde_json::json!({ "column": "closed", "version": 1 });
+    let response = test_app.post_move_ticket(1, &body).await;
+    assert_is_redirect_to(&response, "/dashboard/board?group=status");
+
+    let ticket = get_ticket(&test_app.db_pool, 1).await.unwrap();
+    assert!(!ticket.is_open);
+    assert_eq!(ticket.version, 2);
+
+    let body = serde_json::json!({ "column": "high", "version": 2 });
+    test_app.post_move_ticket(1, &body).await;
+    let ticket = get_ticket(&test_app.db_pool, 1).await.unwrap();
+    assert_eq!(ticket.priority, "high");
+    assert_eq!(ticket.version, 3);
+}
+
+// Must keep the new order of the column without changing the version,
+// when a ticket is moved within its column.
+#[tokio::test]
+async fn move_ticket_within_its_column_reorders_the_column() {
+    let test_app = create_and_run_test_app().await;
+    test_app.test_user.login(&test_app).await;
+    for title in ["Issue with x", "Issue with y", "Issue with z"] {
+        create_ticket(&test_app, title).await;
+    }
+
+    let html_page = test_app.get_board_html("status").await;
+    let positions = order_of(
+        &html_page,
+        &["Issue with x", "Issue with y", "Issue with z"],
+    );
+    assert!(positions.windows(2).all(|w| w[0] < w[1]));
+
+    // Move `z` to the top, then `x` between `z` and `y`.
+    let body = serde_json::json!({ "column": "open", "version": 1, "after_id": 1 });
+    test_app.post_move_ticket(3, &body).await;
+    let body = serde_json::json!({ "column": "open", "version": 1, "before_id": 3, "after_id": 2 });
+    test_app.post_move_ticket(1, &body).await;
+
+    let html_page = test_app.get_board_html("status").await;
+    let positions = order_of(
+        &html_page,
+        &["Issue with z", "Issue with x", "Issue with y"],
+    );
+    assert!(positions.windows(2).all(|w| w[0] < w[1]));
+    assert_eq!(get_ticket(&test_app.db_pool, 1).await.unwrap().version, 1);
+}
+
+// Must return a `409 Conflict` response,
+// when the ticket has been changed since the version of the card.
+#[tokio::test]
+async fn move_ticket_returns_a_409_when_stale_version() {
+    let test_app = create_and_run_test_app().await;
+    test_app.test_user.login(&test_app).await;
+    create_ticket(&test_app, "Issue with x").await;
+    test_app.post_close_ticket(1, 1).await;
+
+    let body = serde_json::json!({ "column": "open", "version": 1 });
+    let response = test_app.post_move_ticket(1, &body).await;
+    assert_eq!(response.status().as_u16(), 409);
+
+    assert!(!get_ticket(&test_app.db_pool, 1).await.unwrap().is_open);
+}
+
+// Must warn when a column exceeds the WIP limit set by an administrator.
+#[tokio::test]
+async fn wip_limits_warn_when_exceeded() {
+    let test_app = create_and_run_test_app().await;
+    test_app.test_user.login(&test_app).await;
+    create_ticket(&test_app, "Issue with x").await;
+    create_ticket(&test_app, "Issue with y").await;
+
+    let response = test_app.post_wip_limit("high", "1").await;
+    assert_is_redirect_to(&response, "/dashboard/board?group=priority");
+    let html_page = test_app.get_board_html("priority").await;
+    assert!(html_page.contains("Only administrators can set WIP limits."));
+
+    promote_to_admin(&test_app).await;
+    test_app.post_wip_limit("high", "1").await;
+    let html_page = test_app.get_board_html("priority").await;
+    assert!(html_page.contains("You have successfully set the WIP limit of the column high to 1."));
+    assert!(!html_page.contains("WIP limit exceeded"));
+
+    for id in [1, 2] {
+        let body = serde_json::json!({ "column": "high", "version": 1 });
+        test_app.post_move_ticket(id, &body).await;
+    }
+    let html_page = test_app.get_board_html("priority").await;
+    assert!(html_page.contains("The column high exceeds its WIP limit of 1."));
+    assert!(html_page.contains("WIP limit exceeded"));
+
+    test_app.post_wip_limit("high", "").await;
+    let html_page = test_app.get_board_html("priority").await;
+    assert!(html_page.contains("You have successfully removed the WIP limit of the column high."));
+    assert!(!html_page.contains("WIP limit exceeded"));
+}
+
+// Must not move the tickets of projects the user is not a member of.
+#[tokio::test]
+async fn move_ticket_rejects_tickets_of_other_projects() {
+    let test_app = create_and_run_test_app().await;
+    test_app.test_user.login(&test_app).await;
+    test_app.post_projects("WEB", "Website").await;
+    create_ticket(&test_app, "Issue with secret").await;
+    test_app.test_user.logout(&test_app).await;
+
+    let other_user = TestUser::new();
+    other_user.store(&test_app.db_pool).await;
+    other_user.login(&test_app).await;
+
+    let body = serde_json::json!({ "column": "closed", "version": 1 });
+    test_app.post_move_ticket(1, &body).await;
+
+    let html_page = test_app.get_board_html("status").await;
+    assert!(html_page.contains("This ticket does not exist."));
+    assert!(!html_page.contains("Issue with secret"));
+    assert!(get_ticket(&test_app.db_pool, 1).await.unwrap().is_open);
+}
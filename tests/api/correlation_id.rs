@@ -0,0 +1,147 @@
+use crate::helpers::{collector, create_and_run_test_app, TestApp};
+use opentelemetry_proto::tonic::common::v1::any_value::Value;
+use uuid::Uuid;
+
+/// Creates a ticket and then drop a column of the tickets table, so that seeing it fails.
+async fn break_ticket(test_app: &TestApp) {
+    test_app
+        .post_tickets("title=Issue with x&description=After doing x&priority=medium".into())
+        .await;
+
+    // Because of the dropped column this will trigger a fatal database error.
+    sqlx::query("ALTER TABLE tickets DROP COLUMN title")
+        .execute(&test_app.db_pool)
+        .await
+        .unwrap();
+}
+
+// Must generate a request id, return it in the `X-Request-Id` header
+// and then show it on the page of a `500 Internal Server Error`.
+#[tokio::test]
+async fn error_pages_show_the_generated_request_id() {
+    let test_app = create_and_run_test_app().await;
+    test_app.test_user.login(&test_app).await;
+    break_ticket(&test_app).await;
+
+    let response = test_app.get_see_ticket(1).await;
+    assert_eq!(response.status().as_u16(), 500);
+    let request_id = response
+        .headers()
+        .get("X-Request-Id")
+        .unwrap()
+        .to_str()
+        .unwrap()
+        .to_string();
+    assert!(Uuid::parse_str(&request_id).is_ok());
+
+    let html_page = response.text().await.unwrap();
+    assert!(html_page.contains("Something went wrong"));
+    assert!(html_page.contains(&request_id));
+}
+
+// Must return the page of a `500 Internal Server Error` as a fragment showing the request id,
+// when the request is sent by HTMX.
+#[tokio::test]
+async fn error_pages_are_fragments_htmx_can_swap() {
+    let test_app = create_and_run_test_app().await;
+    test_app.test_user.login(&test_app).await;
+    break_ticket(&test_app).await;
+
+    let response = test_app
+        .get_see_ticket_with_headers(1, &[("HX-Request", "true"), ("X-Request-Id", "support-7")])
+        .await;
+    assert_eq!(response.status().as_u16(), 500);
+    assert_eq!(
+        response.headers().get("Content-Type").unwrap(),
+        "text/html; charset=utf-8"
+    );
+
+    // HTMX swaps the body into the target, so it must not be a whole document.
+    let html_page = response.text().await.unwrap();
+    assert!(!html_page.contains("<html"));
+    assert!(!html_page.contains("<body"));
+    assert!(html_page.contains("the request id <code>support-7</code>"));
+}
+
+// Must reuse the request id sent by the caller when valid, otherwise generate one.
+#[tokio::test]
+async fn responses_reuse_the_request_id_sent_when_valid() {
+    let test_app = create_and_run_test_app().await;
+    test_app.test_user.login(&test_app).await;
+    break_ticket(&test_app).await;
+
+    let response = test_app
+        .get_see_ticket_with_headers(1, &[("X-Request-Id", "lb-4f2a.1_b")])
+        .await;
+    assert_eq!(
+        response.headers().get("X-Request-Id").unwrap(),
+        "lb-4f2a.1_b"
+    );
+    assert!(response.text().await.unwrap().contains("lb-4f2a.1_b"));
+
+    let too_long = "a".repeat(129);
+    for invalid_id in ["<script>", "with space", too_long.as_str()] {
+        let response = test_app
+            .get_see_ticket_with_headers(1, &[("X-Request-Id", invalid_id)])
+            .await;
+        let request_id = response.headers().get("X-Request-Id").unwrap();
+        assert!(Uuid::parse_str(request_id.to_str().unwrap()).is_ok());
+    }
+}
+
+// Must return the request id in the JSON errors of the API.
+#[tokio::test]
+async fn api_errors_carry_the_request_id() {
+    let test_app = create_and_run_test_app().await;
+    test_app.test_user.login(&test_app).await;
+
+    let response = test_app.get_api_ticket(1).await;
+    assert_eq!(response.status().as_u16(), 404);
+    let request_id = response
+        .headers()
+        .get("X-Request-Id")
+        .unwrap()
+        .to_str()
+        .unwrap()
+        .to_string();
+
+    let error: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(error["request_id"], request_id);
+}
+
+// Must record the request id in the root span of the request.
+#[tokio::test]
+async fn root_spans_record_the_request_id() {
+    let test_app = create_and_run_test_app().await;
+    test_app.test_user.login(&test_app).await;
+
+    let trace_id = Uuid::new_v4().as_bytes().to_vec();
+    let parent_span_id = Uuid::new_v4().as_bytes()[..8].to_vec();
+    let traceparent = format!(
+        "00-{}-{}-01",
+        hex::encode(&trace_id),
+        hex::encode(&parent_span_id)
+    );
+    test_app
+        .get_see_ticket_with_headers(
+            1,
+            &[
+                ("traceparent", &traceparent),
+                ("X-Request-Id", "support-42"),
+            ],
+        )
+        .await;
+
+    let spans = collector().get_trace(&trace_id, &parent_span_id).await;
+    let root_span = spans
+        .iter()
+        .find(|span| span.parent_span_id == parent_span_id)
+        .unwrap();
+    let correlation_id = root_span
+        .attributes
+        .iter()
+        .find(|attribute| attribute.key == "correlation_id")
+        .and_then(|attribute| attribute.value.clone()?.value)
+        .unwrap();
+    assert_eq!(correlation_id, Value::StringValue("support-42".to_string()));
+}
@@ -1,4 +1,6 @@
 use crate::helpers::create_and_run_test_app;
+use chrono::{Duration, Utc};
+use tessera::routes::get_dashboard_metrics;
 
 // Must redirect an unknown user trying to access the dashboard.
 #[tokio::test]
@@ -9,3 +11,77 @@ async fn dashboard_redirects_when_not_logged_in() {
     assert_eq!(response.status().as_u16(), 303);
     assert_eq!(response.headers().get("Location").unwrap(), "/login");
 }
+
+// Must show the counts by priority, the tickets assigned to the user and the recent ones.
+#[tokio::test]
+async fn dashboard_shows_the_metrics_of_the_current_project() {
+    let test_app = create_and_run_test_app().await;
+    test_app.test_user.login(&test_app).await;
+    for (title, priority) in [("Issue with x", "high"), ("Issue with y", "low")] {
+        let body = format!("title={title}&description=After doing x&priority={priority}");
+        test_app.post_tickets(body).await;
+    }
+    test_app
+        .post_ticket_assignee(1, 1, &test_app.test_user.username)
+        .await;
+    test_app.post_close_ticket(2, 1).await;
+
+    let html_page = test_app.get_dashboard_html().await;
+    assert!(html_page.contains(&format!("Welcome {}", test_app.test_user.username)));
+    assert!(html_page.contains(r#"hx-get="/dashboard/projects/switcher""#));
+    assert!(html_page.contains(
+        "<td>high</td>\n                        <td>1</td>\n                        <td>0</td>"
+    ));
+    assert!(html_page.contains(
+        "<td>low</td>\n                        <td>0</td>\n                        <td>1</td>"
+    ));
+    assert!(html_page.contains("2 created, 1 closed"));
+    assert!(html_page.contains("MAIN-1 - Issue with x"));
+    assert!(html_page.contains("MAIN-2 - Issue with y (closed)"));
+}
+
+// Must compute the median time to close over the last 30 days.
+#[tokio::test]
+async fn dashboard_metrics_compute_the_median_time_to_close() {
+    let test_app = create_and_run_test_app().await;
+    test_app.test_user.login(&test_app).await;
+    for title in ["Issue with x", "Issue with y", "Issue with z"] {
+        let body = format!("title={title}&description=After doing x&priority=medium");
+        test_app.post_tickets(body).await;
+    }
+    let now = Utc::now();
+    for (id, hours) in [(1, 2), (2, 10), (3, 100)] {
+        sqlx::query!(
+            "UPDATE tickets SET is_open = FALSE, created_at = $1, closed_at = $2 WHERE id = $3",
+            now - Duration::hours(hours),
+            now,
+            id
+        )
+        .execute(&test_app.db_pool)
+        .await
+        .unwrap();
+    }
+
+    let metrics = get_dashboard_metrics(&test_app.db_pool, 1, now.date_naive())
+        .await
+        .unwrap();
+    assert_eq!(metrics.median_time_to_close, Some(Duration::hours(10)));
+    assert_eq!(metrics.closed_total(), 3);
+    assert_eq!(metrics.daily_activity.len(), 30);
+    assert_eq!(metrics.daily_activity.last().unwrap().closed, 3);
+}
+
+// Must reuse the metrics of the project for a short while.
+#[tokio::test]
+async fn dashboard_metrics_are_cached_briefly() {
+    let test_app = create_and_run_test_app().await;
+    test_app.test_user.login(&test_app).await;
+
+    let html_page = test_app.get_dashboard_html().await;
+    assert!(html_page.contains("0 created, 0 closed"));
+
+    let body = "title=Issue with x&description=After doing x&priority=high";
+    test_app.post_tickets(body.into()).await;
+    let html_page = test_app.get_dashboard_html().await;
+    assert!(html_page.contains("0 created, 0 closed"));
+}
@@ -0,0 +1,96 @@
+use crate::helpers::create_and_run_test_app_with;
+use std::net::TcpListener;
+use tessera::configuration::{ReadReplicaSettings, READ_REPLICA_APPLICATION_NAME};
+
+// Must cancel the statements running longer than the statement timeout.
+#[tokio::test]
+async fn statement_timeout_cancels_slow_statements() {
+    let test_app = create_and_run_test_app_with(|configuration| {
+        configuration.database.statement_timeout_milliseconds = 100;
+    })
+    .await;
+
+    let outcome = sqlx::query("SELECT pg_sleep(1)")
+        .execute(&test_app.db_pool)
+        .await;
+    let error = outcome.expect_err("The statement was not cancelled");
+    let code = error
+        .as_database_error()
+        .and_then(|e| e.code())
+        .map(|code| code.into_owned());
+    // Postgres code of `query_canceled`.
+    assert_eq!(code.as_deref(), Some("57014"));
+}
+
+// Must serve the lists and the reports from the read replica,
+// when a read replica is configured.
+#[tokio::test]
+async fn list_and_report_pages_are_served_by_the_read_replica() {
+    let test_app = create_and_run_test_app_with(|configuration| {
+        configuration.database.read_replica = Some(ReadReplicaSettings {
+            host: configuration.database.host.clone(),
+            port: configuration.database.port,
+        });
+    })
+    .await;
+    test_app.test_user.login(&test_app).await;
+    test_app
+        .post_tickets("title=Issue with x&description=After doing x&priority=medium".into())
+        .await;
+
+    let html_page = test_app.get_see_tickets_html().await;
+    assert!(html_page.contains("Issue with x"));
+    let html_page = test_app.get_board_html("status").await;
+    assert!(html_page.contains("Issue with x"));
+    let response = test_app.get_report("reports").await;
+    assert_eq!(response.status().as_u16(), 200);
+
+    // The replica is the primary database itself,
+    // its connections are told apart by their application name.
+    let replica_queries: Vec<String> = sqlx::query_scalar(
+        "SELECT query FROM pg_stat_activity WHERE datname = current_database() AND application_name = $1",
+    )
+    .bind(READ_REPLICA_APPLICATION_NAME)
+    .fetch_all(&test_app.db_pool)
+    .await
+    .unwrap();
+    assert!(replica_queries
+        .iter()
+        .any(|query| query.contains("tickets")));
+}
+
+// Must only fail the lists and the reports,
+// when the read replica is unreachable.
+#[tokio::test]
+async fn list_and_report_pages_fail_when_the_read_replica_is_unreachable() {
+    // Find a port nothing listens to.
+    let unused_port = TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap()
+        .port();
+    let test_app = create_and_run_test_app_with(|configuration| {
+        configuration.database.acquire_timeout_milliseconds = 500;
+        configuration.database.read_replica = Some(ReadReplicaSettings {
+            host: "127.0.0.1".into(),
+            port: unused_port,
+        });
+    })
+    .await;
+    test_app.test_user.login(&test_app).await;
+    test_app
+        .post_tickets("title=Issue with x&description=After doing x&priority=medium".into())
+        .await;
+
+    let response = test_app.get_see_tickets().await;
+    assert_eq!(response.status().as_u16(), 500);
+    let response = test_app.get_report("reports.json").await;
+    assert_eq!(response.status().as_u16(), 500);
+    for path in ["milestones", "sprints", "backlog", "timesheet"] {
+        let response = test_app.get_report(path).await;
+        assert_eq!(response.status().as_u16(), 500, "{path}");
+    }
+
+    let response = test_app.get_see_ticket(1).await;
+    assert_eq!(response.status().as_u16(), 200);
+}
@@ -1,4 +1,6 @@
 use crate::helpers::create_and_run_test_app;
+use sqlx::{Connection, Executor, PgConnection};
+use tessera::configuration::get_configuration;
 
 // Must return a `200 OK` response with an empty body,
 // when a `GET` request is received at `/health_check`.
@@ -10,3 +12,58 @@ async fn health_check_returns_a_200_with_an_empty_body() {
     assert_eq!(response.status().as_u16(), 200);
     assert_eq!(response.content_length(), Some(0));
 }
+
+// Must return a `200 OK` response with the latency of every dependency,
+// when a `GET` request is received at `/ready` and the dependencies are reachable.
+#[tokio::test]
+async fn ready_returns_a_200_when_dependencies_are_healthy() {
+    let test_app = create_and_run_test_app().await;
+
+    let response = test_app.get_ready().await;
+    assert_eq!(response.status().as_u16(), 200);
+    let readiness: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(readiness["is_ready"], true);
+    assert_eq!(readiness["is_shutting_down"], false);
+    for dependency in ["postgres", "redis"] {
+        assert_eq!(readiness[dependency]["is_healthy"], true);
+        assert!(readiness[dependency]["latency_ms"].as_f64().unwrap() >= 0.0);
+        assert!(readiness[dependency].get("error").is_none());
+    }
+}
+
+// Must return a `503 Service Unavailable` response naming the unhealthy dependency,
+// when the database cannot be reached.
+#[tokio::test]
+async fn ready_returns_a_503_when_the_database_is_unreachable() {
+    let test_app = create_and_run_test_app().await;
+
+    // Refuse the new connections to the database and close the existing ones.
+    let database_name: String = sqlx::query_scalar("SELECT current_database()::TEXT")
+        .fetch_one(&test_app.db_pool)
+        .await
+        .unwrap();
+    let configuration = get_configuration().expect("Failed to get the configuration values");
+    let mut connection = PgConnection::connect_with(&configuration.database.without_db())
+        .await
+        .expect("Failed to connect to the Postgres instance");
+    connection
+        .execute(&*format!(
+            r#"ALTER DATABASE "{}" ALLOW_CONNECTIONS false"#,
+            database_name
+        ))
+        .await
+        .unwrap();
+    sqlx::query("SELECT pg_terminate_backend(pid) FROM pg_stat_activity WHERE datname = $1")
+        .bind(&database_name)
+        .execute(&mut connection)
+        .await
+        .unwrap();
+
+    let response = test_app.get_ready().await;
+    assert_eq!(response.status().as_u16(), 503);
+    let readiness: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(readiness["is_ready"], false);
+    assert_eq!(readiness["postgres"]["is_healthy"], false);
+    assert!(readiness["postgres"]["error"].is_string());
+    assert_eq!(readiness["redis"]["is_healthy"], true);
+}
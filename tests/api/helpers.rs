@@ -1,20 +1,38 @@
+use actix_web::{web, App, HttpResponse, HttpServer};
 use argon2::{
     password_hash::SaltString,
     {Algorithm, Argon2, Params, PasswordHasher, Version},
 };
-use reqwest::{Client, Response};
+use opentelemetry_proto::tonic::{
+    collector::trace::v1::ExportTraceServiceRequest, trace::v1::Span,
+};
+use prost::Message;
+use reqwest::{multipart::Form, Client, Response};
+use secrecy::ExposeSecret;
 use sqlx::{migrate, Connection, Executor, PgConnection, PgPool};
-use std::sync::Once;
+use std::{
+    net::TcpListener,
+    sync::{Arc, Mutex, Once, OnceLock},
+    time::{Duration, Instant},
+};
 use tessera::{
-    configuration::{get_configuration, DatabaseSettings},
+    configuration::{
+        get_configuration, DatabaseSettings, Settings, StorageSettings, TracingSettings,
+    },
+    shutdown::Shutdown,
     startup::Application,
-    telemetry::{get_subscriber, init_subscriber},
+    storage::AttachmentStorage,
+    telemetry::{get_subscriber, get_tracer, init_subscriber},
 };
+use tokio::{runtime::Runtime, task::JoinHandle};
 use uuid::Uuid;
 
 // Ensures that the `TRACING` stack is only initialized once.
 static TRACING: Once = Once::new();
 
+// Collector stub receiving the spans exported by every test application.
+static COLLECTOR: OnceLock<CollectorStub> = OnceLock::new();
+
 /// Initializes telemetry.
 ///
 /// Only execute the following code once.
@@ -23,25 +41,128 @@ fn initialize_telemetry() {
         let subscriber_name = "test".to_string();
         let filter_name = "info".to_string();
 
+        // Only export the traces started by a sampled `traceparent` header.
+        //
+        // The spans are exported by the runtime of the collector, which outlives the runtime of every test.
+        let _guard = collector().runtime.enter();
+        let tracer = get_tracer(&TracingSettings {
+            service_name: "test".to_string(),
+            otlp_endpoint: Some(collector().endpoint.clone()),
+            sampling_ratio: 0.0,
+        })
+        .expect("Failed to create the tracer");
+
         // Check if the `TEST_LOG` environment variable is set.
         //
         // Print logs if set, otherwise discard logs.
         if std::env::var("TEST_LOG").is_ok() {
-            let subscriber = get_subscriber(subscriber_name, filter_name, std::io::stdout);
+            let subscriber = get_subscriber(subscriber_name, filter_name, std::io::stdout, tracer);
             init_subscriber(subscriber);
         } else {
-            let subscriber = get_subscriber(subscriber_name, filter_name, std::io::sink);
+            let subscriber = get_subscriber(subscriber_name, filter_name, std::io::sink, tracer);
             init_subscriber(subscriber);
         };
     })
 }
 
+/// Returns the collector stub, after starting it if needed.
+pub fn collector() -> &'static CollectorStub {
+    COLLECTOR.get_or_init(CollectorStub::start)
+}
+
+/// Representation of a stub of an OTLP collector, storing the spans it receives.
+pub struct CollectorStub {
+    pub endpoint: String,
+    spans: web::Data<Mutex<Vec<Span>>>,
+    runtime: Runtime,
+}
+
+impl CollectorStub {
+    /// Starts the collector on its own runtime, so that it outlives the runtime of every test.
+    fn start() -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("Failed to bind random port");
+        let endpoint = format!(
+            "http://127.0.0.1:{}/v1/traces",
+            listener.local_addr().unwrap().port()
+        );
+        let spans = web::Data::new(Mutex::new(Vec::new()));
+
+        let runtime = Runtime::new().expect("Failed to create the runtime of the collector");
+        let server_spans = spans.clone();
+        let server = {
+            let _guard = runtime.enter();
+            HttpServer::new(move || {
+                App::new()
+                    .route("/v1/traces", web::post().to(receive_traces))
+                    .app_data(server_spans.clone())
+            })
+            .workers(1)
+            .listen(listener)
+            .expect("Failed to listen")
+            .run()
+        };
+        runtime.spawn(server);
+
+        Self {
+            endpoint,
+            spans,
+            runtime,
+        }
+    }
+
+    /// Waits for the root span of the given trace to be exported and then return every span of the trace.
+    pub async fn get_trace(&self, trace_id: &[u8], parent_span_id: &[u8]) -> Vec<Span> {
+        let deadline = Instant::now() + Duration::from_secs(15);
+        loop {
+            let spans: Vec<Span> = self
+                .spans
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|span| span.trace_id == trace_id)
+                .cloned()
+                .collect();
+            if spans
+                .iter()
+                .any(|span| span.parent_span_id == parent_span_id)
+            {
+                return spans;
+            }
+            assert!(
+                Instant::now() < deadline,
+                "The trace was not exported in time"
+            );
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+    }
+}
+
+/// Stores the spans of an OTLP export request.
+async fn receive_traces(body: web::Bytes, spans: web::Data<Mutex<Vec<Span>>>) -> HttpResponse {
+    let request =
+        ExportTraceServiceRequest::decode(body).expect("Failed to decode the export request");
+    spans.lock().unwrap().extend(
+        request
+            .resource_spans
+            .into_iter()
+            .flat_map(|resource_spans| resource_spans.scope_spans)
+            .flat_map(|scope_spans| scope_spans.spans),
+    );
+
+    HttpResponse::Ok().finish()
+}
+
 /// Representation of a test application.
 pub struct TestApp {
     pub db_pool: PgPool,
     pub address: String,
     pub api_client: Client,
     pub test_user: TestUser,
+    pub attachment_storage: Arc<dyn AttachmentStorage>,
+    pub setup_token: Option<String>,
+    pub metrics_address: Option<String>,
+    pub shutdown: Shutdown,
+    pub application_task: JoinHandle<Result<(), std::io::Error>>,
 }
 
 /// Representation of a test user.
@@ -62,7 +183,7 @@ impl TestUser {
     }
 
     /// Stores the test user.
-    async fn store(&self, pool: &PgPool) {
+    pub async fn store(&self, pool: &PgPool) {
         // Create random salt.
         let salt = SaltString::generate(&mut rand::thread_rng());
 
@@ -85,6 +206,16 @@ impl TestUser {
         .execute(pool)
         .await
         .expect("Failed to save the test user details in the database");
+
+        // Join the default project, like the users predating projects.
+        sqlx::query!(
+            "INSERT INTO project_members (project_id, user_id, joined_at)
+            SELECT id, $1, now() FROM projects WHERE key = 'MAIN'",
+            self.user_id,
+        )
+        .execute(pool)
+        .await
+        .expect("Failed to save the test user membership in the database");
     }
 
     /// Logs in the test user.
@@ -104,6 +235,7 @@ impl TestUser {
 
 impl TestApp {
     /// Creates a `GET` request, send it at `/health_check` and then return the response.
+    #[allow(clippy::needless_borrows_for_generic_args)]
     pub async fn get_health_check(&self) -> Response {
         self.api_client
             .get(&format!("{}/health_check", &self.address))
@@ -112,7 +244,26 @@ impl TestApp {
             .expect("Failed to execute request")
     }
 
+    /// Creates a `GET` request, send it at `/ready` and then return the response.
+    pub async fn get_ready(&self) -> Response {
+        self.api_client
+            .get(format!("{}/ready", &self.address))
+            .send()
+            .await
+            .expect("Failed to execute request")
+    }
+
+    /// Creates a `GET` request, send it at `/metrics` of the given address and then return the response.
+    pub async fn get_metrics(&self, address: &str) -> Response {
+        self.api_client
+            .get(format!("{}/metrics", address))
+            .send()
+            .await
+            .expect("Failed to execute request")
+    }
+
     /// Creates a `GET` request, send it at `/register` and then return the response.
+    #[allow(clippy::needless_borrows_for_generic_args)]
     pub async fn get_register(&self) -> Response {
         self.api_client
             .get(&format!("{}/register", &self.address))
@@ -127,6 +278,7 @@ impl TestApp {
     }
 
     /// Creates a `POST` request, send it at `/register` and then return the response.
+    #[allow(clippy::needless_borrows_for_generic_args)]
     pub async fn post_register<Body>(&self, body: &Body) -> Response
     where
         Body: serde::Serialize,
@@ -140,6 +292,7 @@ impl TestApp {
     }
 
     /// Creates a `GET` request, send it at `/login` and then return the response.
+    #[allow(clippy::needless_borrows_for_generic_args)]
     pub async fn get_login(&self) -> Response {
         self.api_client
             .get(&format!("{}/login", &self.address))
@@ -154,6 +307,7 @@ impl TestApp {
     }
 
     /// Creates a `POST` request, send it at `/login` and then return the response.
+    #[allow(clippy::needless_borrows_for_generic_args)]
     pub async fn post_login<Body>(&self, body: &Body) -> Response
     where
         Body: serde::Serialize,
@@ -166,7 +320,30 @@ impl TestApp {
             .expect("Failed to execute request")
     }
 
+    /// Creates a `GET` request, send it at `/setup` and then return the response.
+    pub async fn get_setup(&self, token: &str) -> Response {
+        self.api_client
+            .get(format!("{}/setup?token={}", &self.address, token))
+            .send()
+            .await
+            .expect("Failed to execute request")
+    }
+
+    /// Creates a `POST` request, send it at `/setup` and then return the response.
+    pub async fn post_setup<Body>(&self, body: &Body) -> Response
+    where
+        Body: serde::Serialize,
+    {
+        self.api_client
+            .post(format!("{}/setup", &self.address))
+            .form(body)
+            .send()
+            .await
+            .expect("Failed to execute request")
+    }
+
     /// Creates a `GET` request, send it at `/dashboard/` and then return the response.
+    #[allow(clippy::needless_borrows_for_generic_args)]
     pub async fn get_dashboard(&self) -> Response {
         self.api_client
             .get(&format!("{}/dashboard/", &self.address))
@@ -181,6 +358,7 @@ impl TestApp {
     }
 
     /// Creates a `GET` request, send it at `/dashboard/tickets/new` and then return the response.
+    #[allow(clippy::needless_borrows_for_generic_args)]
     pub async fn get_create_tickets(&self) -> Response {
         self.api_client
             .get(&format!("{}/dashboard/tickets/new", &self.address))
@@ -195,6 +373,7 @@ impl TestApp {
     }
 
     /// Creates a `POST` request, send it at `/dashboard/tickets/new` and then return the response.
+    #[allow(clippy::needless_borrows_for_generic_args)]
     pub async fn post_tickets(&self, body: String) -> Response {
         self.api_client
             .post(&format!("{}/dashboard/tickets/new", &self.address))
@@ -205,7 +384,18 @@ impl TestApp {
             .expect("Failed to execute request")
     }
 
+    /// Creates a `POST` request, send it at `/dashboard/tickets/preview` and then return the response.
+    pub async fn post_preview_ticket_description(&self, description: &str) -> Response {
+        self.api_client
+            .post(format!("{}/dashboard/tickets/preview", &self.address))
+            .form(&serde_json::json!({ "description": description }))
+            .send()
+            .await
+            .expect("Failed to execute request")
+    }
+
     /// Creates a `GET` request, send it at `/dashboard/password` and then return the response.
+    #[allow(clippy::needless_borrows_for_generic_args)]
     pub async fn get_change_password(&self) -> Response {
         self.api_client
             .get(&format!("{}/dashboard/password", &self.address))
@@ -220,6 +410,7 @@ impl TestApp {
     }
 
     /// Creates a `POST` request, send it at `/dashboard/password` and then return the response.
+    #[allow(clippy::needless_borrows_for_generic_args)]
     pub async fn post_change_password<Body>(&self, body: &Body) -> Response
     where
         Body: serde::Serialize,
@@ -233,6 +424,7 @@ impl TestApp {
     }
 
     /// Creates a `GET` request, send it at `/dashboard/tickets` and then return the response.
+    #[allow(clippy::needless_borrows_for_generic_args)]
     pub async fn get_see_tickets(&self) -> Response {
         self.api_client
             .get(&format!("{}/dashboard/tickets", &self.address))
@@ -241,7 +433,13 @@ impl TestApp {
             .expect("Failed to execute request")
     }
 
+    /// Returns the see tickets full response text.
+    pub async fn get_see_tickets_html(&self) -> String {
+        self.get_see_tickets().await.text().await.unwrap()
+    }
+
     /// Creates a `GET` request, send it at `/dashboard/tickets/{id}` and then return the response.
+    #[allow(clippy::needless_borrows_for_generic_args)]
     pub async fn get_see_ticket(&self, ticket_id: i32) -> Response {
         self.api_client
             .get(&format!(
@@ -253,24 +451,507 @@ impl TestApp {
             .expect("Failed to execute request")
     }
 
+    /// Creates a `GET` request with the given headers, send it at `/dashboard/tickets/{id}` and then return the response.
+    pub async fn get_see_ticket_with_headers(
+        &self,
+        ticket_id: i32,
+        headers: &[(&str, &str)],
+    ) -> Response {
+        let mut request = self
+            .api_client
+            .get(format!("{}/dashboard/tickets/{}", &self.address, ticket_id));
+        for (name, value) in headers {
+            request = request.header(*name, *value);
+        }
+
+        request.send().await.expect("Failed to execute request")
+    }
+
     /// Returns the see ticket form full response text.
     pub async fn get_see_ticket_html(&self, ticket_id: i32) -> String {
         self.get_see_ticket(ticket_id).await.text().await.unwrap()
     }
 
+    /// Creates a `POST` request, send it at `/dashboard/projects` and then return the response.
+    pub async fn post_projects(&self, key: &str, name: &str) -> Response {
+        self.api_client
+            .post(format!("{}/dashboard/projects", &self.address))
+            .form(&serde_json::json!({ "key": key, "name": name }))
+            .send()
+            .await
+            .expect("Failed to execute request")
+    }
+
+    /// Creates a `GET` request, send it at `/dashboard/projects` and then return the response text.
+    pub async fn get_projects_html(&self) -> String {
+        self.api_client
+            .get(format!("{}/dashboard/projects", &self.address))
+            .send()
+            .await
+            .expect("Failed to execute request")
+            .text()
+            .await
+            .unwrap()
+    }
+
+    /// Creates a `POST` request, send it at `/dashboard/projects/{key}/members` and then return the response.
+    pub async fn post_project_members(&self, key: &str, username: &str) -> Response {
+        self.api_client
+            .post(format!(
+                "{}/dashboard/projects/{}/members",
+                &self.address, key
+            ))
+            .form(&serde_json::json!({ "username": username }))
+            .send()
+            .await
+            .expect("Failed to execute request")
+    }
+
+    /// Creates a `POST` request, send it at `/dashboard/projects/current` and then return the response.
+    pub async fn post_current_project(&self, project_id: i32) -> Response {
+        self.api_client
+            .post(format!("{}/dashboard/projects/current", &self.address))
+            .form(&serde_json::json!({ "project_id": project_id }))
+            .send()
+            .await
+            .expect("Failed to execute request")
+    }
+
+    /// Creates a `GET` request, send it at `/dashboard/tickets/{reference}` and then return the response.
+    pub async fn get_see_ticket_by_reference(&self, reference: &str) -> Response {
+        self.api_client
+            .get(format!("{}/dashboard/tickets/{}", &self.address, reference))
+            .send()
+            .await
+            .expect("Failed to execute request")
+    }
+
+    /// Creates a multipart `POST` request, send it at `/dashboard/tickets/new` and then return the response.
+    pub async fn post_tickets_with_attachments(&self, form: Form) -> Response {
+        self.api_client
+            .post(format!("{}/dashboard/tickets/new", &self.address))
+            .multipart(form)
+            .send()
+            .await
+            .expect("Failed to execute request")
+    }
+
+    /// Creates a multipart `POST` request, send it at `/dashboard/import` and then return the response.
+    pub async fn post_import(&self, form: Form) -> Response {
+        self.api_client
+            .post(format!("{}/dashboard/import", &self.address))
+            .multipart(form)
+            .send()
+            .await
+            .expect("Failed to execute request")
+    }
+
+    /// Creates a multipart `POST` request, send it at `/dashboard/tickets/{id}/attachments` and then return the response.
+    pub async fn post_attachments(&self, ticket_id: i32, form: Form) -> Response {
+        self.api_client
+            .post(format!(
+                "{}/dashboard/tickets/{}/attachments",
+                &self.address, ticket_id
+            ))
+            .multipart(form)
+            .send()
+            .await
+            .expect("Failed to execute request")
+    }
+
+    /// Creates a `GET` request, send it at `/dashboard/tickets/{id}/attachments/{attachment_id}` and then return the response.
+    pub async fn get_attachment(&self, ticket_id: i32, attachment_id: Uuid) -> Response {
+        self.api_client
+            .get(format!(
+                "{}/dashboard/tickets/{}/attachments/{}",
+                &self.address, ticket_id, attachment_id
+            ))
+            .send()
+            .await
+            .expect("Failed to execute request")
+    }
+
+    /// Creates a `GET` request, send it at `/dashboard/tickets/{id}/attachments/{attachment_id}/thumbnail` and then return the response.
+    pub async fn get_attachment_thumbnail(&self, ticket_id: i32, attachment_id: Uuid) -> Response {
+        self.api_client
+            .get(format!(
+                "{}/dashboard/tickets/{}/attachments/{}/thumbnail",
+                &self.address, ticket_id, attachment_id
+            ))
+            .send()
+            .await
+            .expect("Failed to execute request")
+    }
+
     /// Creates a `POST` request, send it at `/dashboard/tickets/{id}/close` and then return the response.
-    pub async fn post_close_ticket(&self, ticket_id: i32) -> Response {
+    #[allow(clippy::needless_borrows_for_generic_args)]
+    pub async fn post_close_ticket(&self, ticket_id: i32, version: i32) -> Response {
         self.api_client
             .post(&format!(
                 "{}/dashboard/tickets/{}/close",
                 &self.address, ticket_id
             ))
+            .form(&serde_json::json!({ "version": version }))
+            .send()
+            .await
+            .expect("Failed to execute request")
+    }
+
+    /// Creates a `GET` request, send it at `/dashboard/board` and then return the response text.
+    pub async fn get_board_html(&self, group: &str) -> String {
+        self.api_client
+            .get(format!("{}/dashboard/board?group={}", &self.address, group))
+            .send()
+            .await
+            .expect("Failed to execute request")
+            .text()
+            .await
+            .unwrap()
+    }
+
+    /// Creates a `POST` request, send it at `/dashboard/board/tickets/{id}/move` and then return the response.
+    pub async fn post_move_ticket<Body>(&self, ticket_id: i32, body: &Body) -> Response
+    where
+        Body: serde::Serialize,
+    {
+        self.api_client
+            .post(format!(
+                "{}/dashboard/board/tickets/{}/move",
+                &self.address, ticket_id
+            ))
+            .form(body)
+            .send()
+            .await
+            .expect("Failed to execute request")
+    }
+
+    /// Creates a `POST` request, send it at `/dashboard/board/limits` and then return the response.
+    pub async fn post_wip_limit(&self, column: &str, wip_limit: &str) -> Response {
+        self.api_client
+            .post(format!("{}/dashboard/board/limits", &self.address))
+            .form(&serde_json::json!({ "column": column, "wip_limit": wip_limit }))
+            .send()
+            .await
+            .expect("Failed to execute request")
+    }
+
+    /// Creates a `POST` request, send it at `/dashboard/tickets/{id}/close` with a canonical ticket and then return the response.
+    pub async fn post_close_ticket_as_duplicate(
+        &self,
+        ticket_id: i32,
+        version: i32,
+        duplicate_of: &str,
+    ) -> Response {
+        self.api_client
+            .post(format!(
+                "{}/dashboard/tickets/{}/close",
+                &self.address, ticket_id
+            ))
+            .form(&serde_json::json!({ "version": version, "duplicate_of": duplicate_of }))
+            .send()
+            .await
+            .expect("Failed to execute request")
+    }
+
+    /// Creates a `POST` request, send it at `/dashboard/tickets/{id}/links` and then return the response.
+    pub async fn post_ticket_links(
+        &self,
+        ticket_id: i32,
+        relation: &str,
+        reference: &str,
+    ) -> Response {
+        self.api_client
+            .post(format!(
+                "{}/dashboard/tickets/{}/links",
+                &self.address, ticket_id
+            ))
+            .form(&serde_json::json!({ "relation": relation, "reference": reference }))
+            .send()
+            .await
+            .expect("Failed to execute request")
+    }
+
+    /// Creates a `POST` request, send it at `/dashboard/tickets/{id}/links/{link_id}/delete` and then return the response.
+    pub async fn post_delete_ticket_link(&self, ticket_id: i32, link_id: i32) -> Response {
+        self.api_client
+            .post(format!(
+                "{}/dashboard/tickets/{}/links/{}/delete",
+                &self.address, ticket_id, link_id
+            ))
+            .send()
+            .await
+            .expect("Failed to execute request")
+    }
+
+    /// Creates a `POST` request, send it at `/dashboard/tickets/{id}/assignee` and then return the response.
+    pub async fn post_ticket_assignee(
+        &self,
+        ticket_id: i32,
+        version: i32,
+        username: &str,
+    ) -> Response {
+        self.api_client
+            .post(format!(
+                "{}/dashboard/tickets/{}/assignee",
+                &self.address, ticket_id
+            ))
+            .form(&serde_json::json!({ "version": version, "username": username }))
+            .send()
+            .await
+            .expect("Failed to execute request")
+    }
+
+    /// Creates a `POST` request, send it at `/dashboard/tickets/{id}/due-date` and then return the response.
+    pub async fn post_ticket_due_date(
+        &self,
+        ticket_id: i32,
+        version: i32,
+        due_date: &str,
+    ) -> Response {
+        self.api_client
+            .post(format!(
+                "{}/dashboard/tickets/{}/due-date",
+                &self.address, ticket_id
+            ))
+            .form(&serde_json::json!({ "version": version, "due_date": due_date }))
+            .send()
+            .await
+            .expect("Failed to execute request")
+    }
+
+    /// Creates a `POST` request, send it at `/dashboard/tickets/{id}/estimate` and then return the response.
+    pub async fn post_ticket_estimate(
+        &self,
+        ticket_id: i32,
+        version: i32,
+        estimate: &str,
+    ) -> Response {
+        self.api_client
+            .post(format!(
+                "{}/dashboard/tickets/{}/estimate",
+                &self.address, ticket_id
+            ))
+            .form(&serde_json::json!({ "version": version, "estimate": estimate }))
+            .send()
+            .await
+            .expect("Failed to execute request")
+    }
+
+    /// Creates a `POST` request, send it at `/dashboard/tickets/{id}/time-entries` and then return the response.
+    pub async fn post_time_entries<Body>(&self, ticket_id: i32, body: &Body) -> Response
+    where
+        Body: serde::Serialize,
+    {
+        self.api_client
+            .post(format!(
+                "{}/dashboard/tickets/{}/time-entries",
+                &self.address, ticket_id
+            ))
+            .form(body)
+            .send()
+            .await
+            .expect("Failed to execute request")
+    }
+
+    /// Creates a `GET` request, send it at the given timesheet path and then return the response.
+    pub async fn get_timesheet(&self, path: &str) -> Response {
+        self.api_client
+            .get(format!("{}/dashboard/{}", &self.address, path))
+            .send()
+            .await
+            .expect("Failed to execute request")
+    }
+
+    /// Creates a `POST` request, send it at `/dashboard/milestones` and then return the response.
+    pub async fn post_milestones(&self, name: &str, target_date: &str) -> Response {
+        self.api_client
+            .post(format!("{}/dashboard/milestones", &self.address))
+            .form(&serde_json::json!({ "name": name, "target_date": target_date }))
+            .send()
+            .await
+            .expect("Failed to execute request")
+    }
+
+    /// Creates a `GET` request, send it at `/dashboard/milestones` and then return the html page.
+    pub async fn get_milestones_html(&self) -> String {
+        self.api_client
+            .get(format!("{}/dashboard/milestones", &self.address))
+            .send()
+            .await
+            .expect("Failed to execute request")
+            .text()
+            .await
+            .unwrap()
+    }
+
+    /// Creates a `GET` request, send it at `/dashboard/milestones/{path}` and then return the response.
+    pub async fn get_milestone(&self, path: &str) -> Response {
+        self.api_client
+            .get(format!("{}/dashboard/milestones/{}", &self.address, path))
+            .send()
+            .await
+            .expect("Failed to execute request")
+    }
+
+    /// Creates a `POST` request, send it at `/dashboard/milestones/{id}/{action}` and then return the response.
+    pub async fn post_milestone_action(&self, milestone_id: i32, action: &str) -> Response {
+        self.api_client
+            .post(format!(
+                "{}/dashboard/milestones/{}/{}",
+                &self.address, milestone_id, action
+            ))
+            .send()
+            .await
+            .expect("Failed to execute request")
+    }
+
+    /// Creates a `POST` request, send it at `/dashboard/tickets/{id}/milestone` and then return the response.
+    pub async fn post_ticket_milestone(
+        &self,
+        ticket_id: i32,
+        version: i32,
+        milestone_id: &str,
+    ) -> Response {
+        self.api_client
+            .post(format!(
+                "{}/dashboard/tickets/{}/milestone",
+                &self.address, ticket_id
+            ))
+            .form(&serde_json::json!({ "version": version, "milestone_id": milestone_id }))
+            .send()
+            .await
+            .expect("Failed to execute request")
+    }
+
+    /// Creates a `POST` request, send it at `/dashboard/sprints` and then return the response.
+    pub async fn post_sprints(&self, name: &str, starts_on: &str, ends_on: &str) -> Response {
+        self.api_client
+            .post(format!("{}/dashboard/sprints", &self.address))
+            .form(&serde_json::json!({ "name": name, "starts_on": starts_on, "ends_on": ends_on }))
+            .send()
+            .await
+            .expect("Failed to execute request")
+    }
+
+    /// Creates a `GET` request, send it at the given report path and then return the response.
+    pub async fn get_report(&self, path: &str) -> Response {
+        self.api_client
+            .get(format!("{}/dashboard/{}", &self.address, path))
+            .send()
+            .await
+            .expect("Failed to execute request")
+    }
+
+    /// Creates a `GET` request, send it at `/dashboard/{path}` and then return the html page.
+    pub async fn get_planning_html(&self, path: &str) -> String {
+        self.api_client
+            .get(format!("{}/dashboard/{}", &self.address, path))
+            .send()
+            .await
+            .expect("Failed to execute request")
+            .text()
+            .await
+            .unwrap()
+    }
+
+    /// Creates a `POST` request, send it at `/dashboard/{path}` with a sprint and then return the response.
+    pub async fn post_ticket_sprint(&self, path: &str, version: i32, sprint_id: &str) -> Response {
+        self.api_client
+            .post(format!("{}/dashboard/{}", &self.address, path))
+            .form(&serde_json::json!({ "version": version, "sprint_id": sprint_id }))
+            .send()
+            .await
+            .expect("Failed to execute request")
+    }
+
+    /// Creates a `POST` request, send it at `/dashboard/tickets/{id}/story-points` and then return the response.
+    pub async fn post_ticket_story_points(
+        &self,
+        ticket_id: i32,
+        version: i32,
+        story_points: &str,
+    ) -> Response {
+        self.api_client
+            .post(format!(
+                "{}/dashboard/tickets/{}/story-points",
+                &self.address, ticket_id
+            ))
+            .form(&serde_json::json!({ "version": version, "story_points": story_points }))
+            .send()
+            .await
+            .expect("Failed to execute request")
+    }
+
+    /// Creates a `GET` request, send it at `/api/tickets/{id}` and then return the response.
+    pub async fn get_api_ticket(&self, ticket_id: i32) -> Response {
+        self.api_client
+            .get(format!("{}/api/tickets/{}", &self.address, ticket_id))
+            .send()
+            .await
+            .expect("Failed to execute request")
+    }
+
+    /// Creates a `POST` request, send it at `/api/tickets/{id}/close` and then return the response.
+    pub async fn post_api_close_ticket(&self, ticket_id: i32, if_match: Option<&str>) -> Response {
+        let mut request = self
+            .api_client
+            .post(format!("{}/api/tickets/{}/close", &self.address, ticket_id));
+        if let Some(if_match) = if_match {
+            request = request.header("If-Match", if_match);
+        }
+
+        request.send().await.expect("Failed to execute request")
+    }
+
+    /// Creates a `GET` request, send it at `/dashboard/notifications` and then return the response.
+    pub async fn get_notifications(&self) -> Response {
+        self.api_client
+            .get(format!("{}/dashboard/notifications", &self.address))
+            .send()
+            .await
+            .expect("Failed to execute request")
+    }
+
+    /// Returns the notifications full response text.
+    pub async fn get_notifications_html(&self) -> String {
+        self.get_notifications().await.text().await.unwrap()
+    }
+
+    /// Creates a `GET` request, send it at `/dashboard/notifications/unread` and then return the response text.
+    pub async fn get_unread_notifications_text(&self) -> String {
+        self.api_client
+            .get(format!("{}/dashboard/notifications/unread", &self.address))
+            .send()
+            .await
+            .expect("Failed to execute request")
+            .text()
+            .await
+            .unwrap()
+    }
+
+    /// Creates a `POST` request, send it at `/dashboard/notifications/{id}/read` and then return the response.
+    pub async fn post_read_notification(&self, notification_id: i32) -> Response {
+        self.api_client
+            .post(format!(
+                "{}/dashboard/notifications/{}/read",
+                &self.address, notification_id
+            ))
+            .send()
+            .await
+            .expect("Failed to execute request")
+    }
+
+    /// Creates a `POST` request, send it at `/dashboard/notifications/read` and then return the response.
+    pub async fn post_read_all_notifications(&self) -> Response {
+        self.api_client
+            .post(format!("{}/dashboard/notifications/read", &self.address))
             .send()
             .await
             .expect("Failed to execute request")
     }
 
     /// Creates a `POST` request, send it at `/dashboard/logout` and then return the response.
+    #[allow(clippy::needless_borrows_for_generic_args)]
     pub async fn post_logout(&self) -> Response {
         self.api_client
             .post(&format!("{}/dashboard/logout", &self.address))
@@ -280,8 +961,23 @@ impl TestApp {
     }
 }
 
+/// Returns the settings of a storage writing to a new temporary directory, outside the repository.
+pub fn temp_storage() -> StorageSettings {
+    StorageSettings::Local {
+        path: std::env::temp_dir()
+            .join(Uuid::new_v4().to_string())
+            .to_string_lossy()
+            .into(),
+    }
+}
+
 /// Creates and then run the test application.
 pub async fn create_and_run_test_app() -> TestApp {
+    create_and_run_test_app_with(|_| {}).await
+}
+
+/// Creates and then run the test application, after customizing its configuration.
+pub async fn create_and_run_test_app_with(customize: impl FnOnce(&mut Settings)) -> TestApp {
     initialize_telemetry();
 
     let configuration = {
@@ -291,6 +987,9 @@ pub async fn create_and_run_test_app() -> TestApp {
         configuration.database.database_name = Uuid::new_v4().to_string();
         // Find a random available port by triggering an OS scan using the port 0.
         configuration.application.port = 0;
+        // Store the attachments in a random directory.
+        configuration.attachments.storage = temp_storage();
+        customize(&mut configuration);
 
         configuration
     };
@@ -301,22 +1000,30 @@ pub async fn create_and_run_test_app() -> TestApp {
         .await
         .expect("Failed to create the application");
     let application_port = application.port();
-    tokio::spawn(application.run_until_stopped());
-
-    // Build the HTTP client.
-    let client = reqwest::Client::builder()
-        // Forbid to follow redirects.
-        .redirect(reqwest::redirect::Policy::none())
-        .cookie_store(true)
-        .build()
-        .unwrap();
+    let metrics_address = application
+        .metrics_port()
+        .map(|port| format!("http://127.0.0.1:{}", port));
+    let setup_token = application
+        .setup_token()
+        .map(|token| token.expose_secret().clone());
+    let shutdown = application.shutdown();
+    let application_task = tokio::spawn(application.run_until_stopped());
 
     // Create test application.
     let test_app = TestApp {
         db_pool: configuration.database.get_connection_pool(),
         address: format!("http://127.0.0.1:{}", application_port),
-        api_client: client,
+        api_client: build_api_client(),
         test_user: TestUser::new(),
+        attachment_storage: configuration
+            .attachments
+            .storage
+            .get_storage()
+            .expect("Failed to get the attachment storage"),
+        setup_token,
+        metrics_address,
+        shutdown,
+        application_task,
     };
 
     // Store the test user.
@@ -325,6 +1032,22 @@ pub async fn create_and_run_test_app() -> TestApp {
     test_app
 }
 
+/// Asserts that the response is a `303 See Other` to the given location.
+pub fn assert_is_redirect_to(response: &Response, location: &str) {
+    assert_eq!(response.status().as_u16(), 303);
+    assert_eq!(response.headers().get("Location").unwrap(), location);
+}
+
+/// Returns an HTTP client keeping its own cookies.
+pub fn build_api_client() -> Client {
+    reqwest::Client::builder()
+        // Forbid to follow redirects.
+        .redirect(reqwest::redirect::Policy::none())
+        .cookie_store(true)
+        .build()
+        .unwrap()
+}
+
 /// Returns a pool to a newly created and migrated database.
 ///
 /// # Implementation Notes
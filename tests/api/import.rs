@@ -0,0 +1,199 @@
+use crate::helpers::{assert_is_redirect_to, create_and_run_test_app, TestApp};
+use reqwest::multipart::{Form, Part};
+use uuid::Uuid;
+
+/// Makes the test user an administrator.
+async fn promote_to_admin(test_app: &TestApp) {
+    sqlx::query!(
+        "UPDATE users SET role = 'admin' WHERE user_id = $1",
+        test_app.test_user.user_id
+    )
+    .execute(&test_app.db_pool)
+    .await
+    .unwrap();
+}
+
+/// Returns an import form of a CSV file exported from a spreadsheet.
+fn csv_form(content: String, mode: &str) -> Form {
+    Form::new()
+        .text("format", "csv")
+        .text("mode", mode.to_string())
+        .text("title_column", "Summary")
+        .text("description_column", "Details")
+        .text("priority_column", "Priority")
+        .text("author_column", "Reporter")
+        .text("status_column", "")
+        .part("file", Part::text(content).file_name("tickets.csv"))
+}
+
+/// Returns the title, the author and the status of the tickets, by key.
+async fn imported_tickets(test_app: &TestApp) -> Vec<(String, String, String, bool)> {
+    sqlx::query!("SELECT key, title, created_by, is_open FROM tickets ORDER BY id")
+        .fetch_all(&test_app.db_pool)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|row| (row.key, row.title, row.created_by, row.is_open))
+        .collect()
+}
+
+// Must report the invalid rows of a dry run and of an import without importing anything.
+#[tokio::test]
+async fn import_tickets_reports_invalid_rows_without_importing() {
+    let test_app = create_and_run_test_app().await;
+    test_app.test_user.login(&test_app).await;
+
+    let content = "Summary,Details,Priority,Reporter\n\
+                   Issue with x,After doing x,high,alice\n\
+                   ,After doing y,low,bob\n"
+        .to_string();
+    let response = test_app
+        .post_import(csv_form(content.clone(), "dry-run"))
+        .await;
+    assert_is_redirect_to(&response, "/dashboard/import");
+    let html_page = test_app.get_planning_html("import").await;
+    assert!(html_page.contains("Only administrators can import tickets."));
+
+    promote_to_admin(&test_app).await;
+    let html_page = test_app
+        .post_import(csv_form(content.clone(), "dry-run"))
+        .await
+        .text()
+        .await
+        .unwrap();
+    assert!(html_page
+        .contains("Dry run: 1 tickets can be imported into the project MAIN, 1 rows have errors."));
+    assert!(html_page.contains("<td>2</td>"));
+    assert!(html_page.contains("ticket title cannot be empty."));
+    assert!(html_page.contains("Unknown authors replaced by imported-user: alice"));
+
+    let html_page = test_app
+        .post_import(csv_form(content, "import"))
+        .await
+        .text()
+        .await
+        .unwrap();
+    assert!(html_page.contains("Nothing has been imported, fix the 1 rows with errors first."));
+    assert!(imported_tickets(&test_app).await.is_empty());
+}
+
+// Must import every row, mapping the authors to the existing users or to the placeholder user.
+#[tokio::test]
+async fn import_tickets_maps_authors_to_users_or_placeholder() {
+    let test_app = create_and_run_test_app().await;
+    test_app.test_user.login(&test_app).await;
+    promote_to_admin(&test_app).await;
+
+    let username = &test_app.test_user.username;
+    let content = format!(
+        "Summary,Details,Priority,Reporter\n\
+         Issue with x,After doing x,high,{}\n\
+         Issue with y,\"After doing y, z\",,alice\n",
+        username
+    );
+    let html_page = test_app
+        .post_import(csv_form(content, "import"))
+        .await
+        .text()
+        .await
+        .unwrap();
+    assert!(html_page.contains("You have successfully imported 2 tickets into the project MAIN."));
+
+    assert_eq!(
+        imported_tickets(&test_app).await,
+        [
+            (
+                "MAIN-1".to_string(),
+                "Issue with x".to_string(),
+                username.clone(),
+                true
+            ),
+            (
+                "MAIN-2".to_string(),
+                "Issue with y".to_string(),
+                "imported-user".to_string(),
+                true
+            ),
+        ]
+    );
+}
+
+// Must import nothing when a user registered the username of the placeholder user.
+#[tokio::test]
+async fn import_tickets_fails_when_the_placeholder_username_is_taken() {
+    let test_app = create_and_run_test_app().await;
+    test_app.test_user.login(&test_app).await;
+    promote_to_admin(&test_app).await;
+    sqlx::query("INSERT INTO users (user_id, username, password_hash) VALUES ($1, $2, $3)")
+        .bind(Uuid::new_v4())
+        .bind("imported-user")
+        .bind("hash")
+        .execute(&test_app.db_pool)
+        .await
+        .unwrap();
+
+    let content = "Summary,Details,Priority,Reporter
+                   Issue with x,After doing x,high,alice
+"
+    .to_string();
+    let html_page = test_app
+        .post_import(csv_form(content, "import"))
+        .await
+        .text()
+        .await
+        .unwrap();
+    assert!(html_page.contains(
+        "Nothing has been imported, the placeholder username imported-user belongs to another user."
+    ));
+    assert!(imported_tickets(&test_app).await.is_empty());
+}
+
+// Must reject a form with more fields than the import form has.
+#[tokio::test]
+async fn import_tickets_rejects_too_many_fields() {
+    let test_app = create_and_run_test_app().await;
+    test_app.test_user.login(&test_app).await;
+    promote_to_admin(&test_app).await;
+
+    let form = (0..20).fold(Form::new(), |form, i| {
+        form.text(format!("field_{}", i), "x")
+    });
+    let response = test_app.post_import(form).await;
+    assert_is_redirect_to(&response, "/dashboard/import");
+    let html_page = test_app.get_planning_html("import").await;
+    assert!(html_page.contains("Validation error: the form cannot have more than 16 fields."));
+}
+
+// Must import the issues of a GitHub export with their state, but not the pull requests.
+#[tokio::test]
+async fn import_tickets_reads_github_issues() {
+    let test_app = create_and_run_test_app().await;
+    test_app.test_user.login(&test_app).await;
+    promote_to_admin(&test_app).await;
+
+    let content = r#"[
+        {
+            "number": 7, "title": "Issue with x", "body": "After doing x", "state": "closed",
+            "user": { "login": "octocat" }, "labels": [{ "name": "priority: low" }],
+            "created_at": "2026-10-01T08:00:00Z", "closed_at": "2026-10-02T08:00:00Z"
+        },
+        { "number": 8, "title": "Fix x", "state": "open", "pull_request": {} }
+    ]"#;
+    let form = Form::new()
+        .text("format", "github-json")
+        .text("mode", "import")
+        .text("title_column", "title")
+        .text("description_column", "description")
+        .part("file", Part::text(content).file_name("issues.json"));
+    let html_page = test_app.post_import(form).await.text().await.unwrap();
+    assert!(html_page.contains("You have successfully imported 1 tickets into the project MAIN."));
+
+    let ticket = sqlx::query!("SELECT priority, is_open, created_at, closed_at FROM tickets")
+        .fetch_one(&test_app.db_pool)
+        .await
+        .unwrap();
+    assert_eq!(ticket.priority, "low");
+    assert!(!ticket.is_open);
+    assert_eq!(ticket.created_at.to_rfc3339(), "2026-10-01T08:00:00+00:00");
+    assert!(ticket.closed_at.is_some());
+}
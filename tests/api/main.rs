@@ -1,8 +1,30 @@
+mod admin;
+mod api;
+mod archive;
+mod attachments;
+mod board;
+mod correlation_id;
 mod dashboard;
+mod database;
 mod health_check;
 mod helpers;
+mod import;
 mod login;
 mod logout;
+mod metrics;
+mod migration;
+mod milestones;
+mod notifications;
 mod password;
+mod projects;
 mod register;
+mod reports;
+mod setup;
+mod shutdown;
+mod sla;
+mod sprints;
+mod telemetry;
+mod ticket_events;
+mod ticket_links;
 mod tickets;
+mod time_entries;
@@ -0,0 +1,90 @@
+use crate::helpers::{create_and_run_test_app, create_and_run_test_app_with};
+
+/// Returns the value of the sample of the metric with the given labels, if any.
+fn sample(metrics: &str, name_and_labels: &str) -> Option<f64> {
+    metrics
+        .lines()
+        .find_map(|line| line.strip_prefix(name_and_labels)?.trim().parse().ok())
+}
+
+// Must count the requests, the logins and the open tickets.
+#[tokio::test]
+async fn metrics_exposes_requests_logins_and_open_tickets() {
+    let test_app = create_and_run_test_app_with(|configuration| {
+        configuration.application.expose_metrics_on_main_port = true;
+    })
+    .await;
+    test_app
+        .post_login(&serde_json::json!({ "username": "unknown", "password": "wrong-password" }))
+        .await;
+    test_app.test_user.login(&test_app).await;
+    for priority in ["high", "high", "low"] {
+        let body = format!(
+            "title=Issue&description=After doing x&priority={}",
+            priority
+        );
+        test_app.post_tickets(body).await;
+    }
+    test_app.get_health_check().await;
+
+    let response = test_app.get_metrics(&test_app.address).await;
+    assert_eq!(response.status().as_u16(), 200);
+    let metrics = response.text().await.unwrap();
+
+    // The counters are shared by the test applications running in the same process.
+    let requests =
+        r#"tessera_http_requests_total{method="GET",route="/health_check",status="200"}"#;
+    assert!(sample(&metrics, requests).unwrap() >= 1.0);
+    let login_requests =
+        r#"tessera_http_requests_total{method="POST",route="/login",status="303"}"#;
+    assert!(sample(&metrics, login_requests).unwrap() >= 2.0);
+    assert!(metrics.contains(r#"tessera_http_request_duration_seconds_bucket{method="GET",route="/health_check",status="200",le="#));
+    assert!(sample(&metrics, r#"tessera_logins_total{outcome="success"}"#).unwrap() >= 1.0);
+    assert!(sample(&metrics, r#"tessera_logins_total{outcome="failure"}"#).unwrap() >= 1.0);
+    assert!(metrics.contains(r#"tessera_password_hash_duration_seconds_count{operation="verify"}"#));
+    assert!(metrics.contains(r#"tessera_db_pool_connections{state="idle"}"#));
+
+    // The gauges are read from the database of the test application.
+    assert_eq!(
+        sample(&metrics, r#"tessera_open_tickets{priority="high"}"#),
+        Some(2.0)
+    );
+    assert_eq!(
+        sample(&metrics, r#"tessera_open_tickets{priority="low"}"#),
+        Some(1.0)
+    );
+    assert_eq!(
+        sample(&metrics, r#"tessera_open_tickets{priority="medium"}"#),
+        None
+    );
+}
+
+// Must not expose the metrics on the main port by default.
+#[tokio::test]
+async fn metrics_are_private_by_default() {
+    let test_app = create_and_run_test_app().await;
+
+    let response = test_app.get_metrics(&test_app.address).await;
+    assert_eq!(response.status().as_u16(), 404);
+}
+
+// Must only expose the metrics on their own port when configured.
+#[tokio::test]
+async fn metrics_are_served_on_their_own_port_when_configured() {
+    let test_app = create_and_run_test_app_with(|configuration| {
+        configuration.application.metrics_port = Some(0);
+        configuration.application.expose_metrics_on_main_port = true;
+    })
+    .await;
+    let metrics_address = test_app.metrics_address.clone().unwrap();
+
+    let response = test_app.get_metrics(&test_app.address).await;
+    assert_eq!(response.status().as_u16(), 404);
+    let response = test_app.get_metrics(&metrics_address).await;
+    assert_eq!(response.status().as_u16(), 200);
+    assert!(response
+        .text()
+        .await
+        .unwrap()
+        .contains("tessera_http_requests_total"));
+}
@@ -0,0 +1,112 @@
+use crate::helpers::temp_storage;
+use sqlx::{Connection, Executor, PgConnection, PgPool};
+use tessera::{
+    configuration::{get_configuration, Settings},
+    migration::{run_migrations, MIGRATOR},
+    startup::Application,
+};
+use uuid::Uuid;
+
+/// Returns the configuration of an application using a new database which is not migrated.
+async fn configuration_with_empty_database() -> (Settings, PgPool) {
+    let mut configuration = get_configuration().expect("Failed to get the configuration values");
+    configuration.database.database_name = Uuid::new_v4().to_string();
+    configuration.application.port = 0;
+    configuration.attachments.storage = temp_storage();
+
+    let mut connection = PgConnection::connect_with(&configuration.database.without_db())
+        .await
+        .expect("Failed to connect to the Postgres instance");
+    connection
+        .execute(&*format!(
+            r#"CREATE DATABASE "{}";"#,
+            configuration.database.database_name
+        ))
+        .await
+        .expect("Failed to create the database");
+    let pool = configuration.database.get_connection_pool();
+
+    (configuration, pool)
+}
+
+/// Returns the number of migrations applied to the database.
+async fn applied_migrations(pool: &PgPool) -> i64 {
+    sqlx::query_scalar("SELECT COUNT(*) FROM _sqlx_migrations")
+        .fetch_one(pool)
+        .await
+        .unwrap()
+}
+
+// Must migrate the database when the application starts, once even with concurrent instances.
+#[tokio::test]
+async fn application_migrates_the_database_on_startup() {
+    let (configuration, pool) = configuration_with_empty_database().await;
+
+    let (first, second) = tokio::join!(
+        Application::new(configuration.clone()),
+        Application::new(configuration.clone())
+    );
+    assert!(first.is_ok());
+    assert!(second.is_ok());
+    assert_eq!(
+        applied_migrations(&pool).await,
+        MIGRATOR.iter().count() as i64
+    );
+}
+
+// Must refuse to start, leaving the database untouched, when the migrations on startup are disabled.
+#[tokio::test]
+async fn application_requires_a_migrated_database_when_disabled() {
+    let (mut configuration, pool) = configuration_with_empty_database().await;
+    configuration.database.migrate_on_startup = false;
+
+    let error = Application::new(configuration).await.err().unwrap();
+    assert!(error.to_string().contains("has not been migrated"));
+    let is_migrated: bool =
+        sqlx::query_scalar("SELECT to_regclass('_sqlx_migrations') IS NOT NULL")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+    assert!(!is_migrated);
+}
+
+// Must refuse to start when the database has a migration unknown to the binary.
+#[tokio::test]
+async fn application_refuses_a_newer_schema() {
+    let (configuration, pool) = configuration_with_empty_database().await;
+    run_migrations(&pool).await.unwrap();
+    sqlx::query(
+        r#"
+        INSERT INTO _sqlx_migrations (version, description, success, checksum, execution_time)
+        VALUES (99991231000000, 'from the future', TRUE, '\x00', 0)
+        "#,
+    )
+    .execute(&pool)
+    .await
+    .unwrap();
+
+    for migrate_on_startup in [true, false] {
+        let mut configuration = configuration.clone();
+        configuration.database.migrate_on_startup = migrate_on_startup;
+        let error = Application::new(configuration).await.err().unwrap();
+        assert!(error.to_string().contains("newer than the version"));
+    }
+}
+
+// Must run the migrations without the statement timeout of the requests,
+// and then restore it for the following statements.
+#[tokio::test]
+async fn migrations_are_not_cancelled_by_the_statement_timeout() {
+    let (mut configuration, _) = configuration_with_empty_database().await;
+    configuration.database.statement_timeout_milliseconds = 1;
+    let pool = configuration.database.get_connection_pool();
+
+    run_migrations(&pool).await.unwrap();
+    assert_eq!(
+        applied_migrations(&pool).await,
+        MIGRATOR.iter().count() as i64
+    );
+
+    let outcome = sqlx::query("SELECT pg_sleep(0.1)").execute(&pool).await;
+    assert!(outcome.is_err());
+}
@@ -0,0 +1,127 @@
+use crate::helpers::{assert_is_redirect_to, create_and_run_test_app, TestApp, TestUser};
+use tessera::routes::get_ticket;
+
+/// Creates a ticket with the given title.
+async fn create_ticket(test_app: &TestApp, title: &str) {
+    let body = format!("title={}&description=After doing x&priority=medium", title);
+    test_app.post_tickets(body).await;
+}
+
+// Must list the new milestone and reject a duplicated name,
+// when a `POST` request is received at `/dashboard/milestones`.
+#[tokio::test]
+async fn create_milestone_lists_it_and_rejects_duplicated_names() {
+    let test_app = create_and_run_test_app().await;
+    test_app.test_user.login(&test_app).await;
+
+    let response = test_app.post_milestones("v1.0", "2026-10-31").await;
+    assert_is_redirect_to(&response, "/dashboard/milestones");
+    let html_page = test_app.get_milestones_html().await;
+    assert!(html_page.contains("You have successfully created the milestone v1.0."));
+    assert!(html_page.contains("<strong>v1.0</strong>"));
+    assert!(html_page.contains("Target date: 2026-10-31"));
+
+    test_app.post_milestones("v1.0", "").await;
+    let html_page = test_app.get_milestones_html().await;
+    assert!(html_page.contains("This milestone name is already taken."));
+}
+
+// Must track the progress of the tickets of a milestone.
+#[tokio::test]
+async fn set_milestone_tracks_the_progress_of_the_milestone() {
+    let test_app = create_and_run_test_app().await;
+    test_app.test_user.login(&test_app).await;
+    create_ticket(&test_app, "Issue with x").await;
+    create_ticket(&test_app, "Issue with y").await;
+    test_app.post_milestones("v1.0", "").await;
+
+    for ticket_id in [1, 2] {
+        let response = test_app.post_ticket_milestone(ticket_id, 1, "1").await;
+        assert_is_redirect_to(&response, &format!("/dashboard/tickets/{}", ticket_id));
+    }
+    let ticket = get_ticket(&test_app.db_pool, 1).await.unwrap();
+    assert_eq!(ticket.milestone_id, Some(1));
+    assert_eq!(ticket.version, 2);
+    let html_page = test_app.get_see_ticket_html(1).await;
+    assert!(html_page.contains("You have successfully changed the milestone of this ticket."));
+
+    test_app.post_close_ticket(1, 2).await;
+    let html_page = test_app.get_milestone("1").await.text().await.unwrap();
+    assert!(html_page.contains("1 closed, 1 open"));
+
+    test_app.post_milestones("v2.0", "").await;
+    let html_page = test_app.get_milestones_html().await;
+    assert!(html_page.contains("1 closed, 1 open"));
+    assert!(html_page.contains("0 closed, 0 open"));
+}
+
+// Must not add tickets to a closed milestone.
+#[tokio::test]
+async fn set_milestone_rejects_closed_milestones() {
+    let test_app = create_and_run_test_app().await;
+    test_app.test_user.login(&test_app).await;
+    create_ticket(&test_app, "Issue with x").await;
+    test_app.post_milestones("v1.0", "").await;
+
+    let response = test_app.post_milestone_action(1, "close").await;
+    assert_is_redirect_to(&response, "/dashboard/milestones/1");
+    let html_page = test_app.get_milestone("1").await.text().await.unwrap();
+    assert!(html_page.contains("You have successfully closed the milestone v1.0."));
+
+    test_app.post_ticket_milestone(1, 1, "1").await;
+    let html_page = test_app.get_see_ticket_html(1).await;
+    assert!(html_page.contains("Validation error: the milestone v1.0 is closed."));
+    let ticket = get_ticket(&test_app.db_pool, 1).await.unwrap();
+    assert_eq!(ticket.milestone_id, None);
+}
+
+// Must generate the Markdown changelog of the closed tickets and download it.
+#[tokio::test]
+async fn changelog_lists_the_closed_tickets_of_the_milestone() {
+    let test_app = create_and_run_test_app().await;
+    test_app.test_user.login(&test_app).await;
+    create_ticket(&test_app, "Issue with x").await;
+    create_ticket(&test_app, "Issue with y").await;
+    test_app.post_milestones("v1.0", "").await;
+    test_app.post_ticket_milestone(1, 1, "1").await;
+    test_app.post_ticket_milestone(2, 1, "1").await;
+    test_app.post_close_ticket(1, 2).await;
+
+    let html_page = test_app
+        .get_milestone("1/changelog")
+        .await
+        .text()
+        .await
+        .unwrap();
+    assert!(html_page.contains("## Medium priority"));
+    assert!(html_page.contains("- Issue with x (MAIN-1)"));
+    assert!(!html_page.contains("Issue with y"));
+
+    let response = test_app.get_milestone("1/changelog.md").await;
+    assert_eq!(
+        response.headers().get("Content-Type").unwrap(),
+        "text/markdown; charset=utf-8"
+    );
+    let markdown = response.text().await.unwrap();
+    assert!(markdown.starts_with("# v1.0\n"));
+    assert!(markdown.contains("- Issue with x (MAIN-1)"));
+}
+
+// Must treat the milestones of other projects as if they did not exist.
+#[tokio::test]
+async fn see_milestone_rejects_milestones_of_other_projects() {
+    let test_app = create_and_run_test_app().await;
+    test_app.test_user.login(&test_app).await;
+    test_app.post_projects("WEB", "Website").await;
+    test_app.post_milestones("v1.0", "").await;
+    test_app.test_user.logout(&test_app).await;
+
+    let other_user = TestUser::new();
+    other_user.store(&test_app.db_pool).await;
+    other_user.login(&test_app).await;
+
+    let response = test_app.get_milestone("1").await;
+    assert_is_redirect_to(&response, "/dashboard/milestones");
+    let html_page = test_app.get_milestones_html().await;
+    assert!(html_page.contains("This milestone does not exist."));
+}
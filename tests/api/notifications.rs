@@ -0,0 +1,200 @@
+use crate::helpers::{create_and_run_test_app, TestApp, TestUser};
+use chrono::{Duration, Utc};
+use tessera::routes::{delete_expired_notifications, get_notifications};
+
+/// Creates a ticket as the test user and then close it as another user.
+async fn close_ticket_as_another_user(test_app: &TestApp) -> TestUser {
+    let other_user = TestUser::new();
+    other_user.store(&test_app.db_pool).await;
+
+    test_app.test_user.login(test_app).await;
+    let body = "title=Issue with ...&description=After doing ...&priority=medium";
+    test_app.post_tickets(body.into()).await;
+    test_app.test_user.logout(test_app).await;
+
+    other_user.login(test_app).await;
+    test_app.post_close_ticket(1, 1).await;
+    other_user.logout(test_app).await;
+
+    other_user
+}
+
+// Must notify the creator of a ticket,
+// when the ticket is closed by another user.
+#[tokio::test]
+async fn close_ticket_notifies_the_ticket_creator() {
+    let test_app = create_and_run_test_app().await;
+    let other_user = close_ticket_as_another_user(&test_app).await;
+
+    test_app.test_user.login(&test_app).await;
+
+    let count = test_app.get_unread_notifications_text().await;
+    assert_eq!(count, "1");
+
+    let html_page = test_app.get_notifications_html().await;
+    assert!(html_page.contains(&format!(
+        "{} closed the ticket MAIN-1.",
+        other_user.username
+    )));
+}
+
+// Must not notify the creator of a ticket,
+// when the ticket is closed by its creator.
+#[tokio::test]
+async fn close_ticket_does_not_notify_when_closed_by_its_creator() {
+    let test_app = create_and_run_test_app().await;
+    test_app.test_user.login(&test_app).await;
+
+    let body = "title=Issue with ...&description=After doing ...&priority=medium";
+    test_app.post_tickets(body.into()).await;
+    test_app.post_close_ticket(1, 1).await;
+
+    let count = test_app.get_unread_notifications_text().await;
+    assert_eq!(count, "0");
+}
+
+// Must mark a notification as read,
+// when a `POST` request is received at `/dashboard/notifications/{id}/read`.
+#[tokio::test]
+async fn read_notification_marks_the_notification_as_read() {
+    let test_app = create_and_run_test_app().await;
+    close_ticket_as_another_user(&test_app).await;
+
+    test_app.test_user.login(&test_app).await;
+
+    let notifications = get_notifications(&test_app.db_pool, test_app.test_user.user_id)
+        .await
+        .unwrap();
+    let notification = notifications.first().unwrap();
+
+    let response = test_app.post_read_notification(notification.id).await;
+    assert_eq!(response.status().as_u16(), 303);
+    assert_eq!(
+        response.headers().get("Location").unwrap(),
+        "/dashboard/notifications"
+    );
+
+    let count = test_app.get_unread_notifications_text().await;
+    assert_eq!(count, "0");
+}
+
+// Must not mark the notification of another user as read.
+#[tokio::test]
+async fn read_notification_ignores_notifications_of_other_users() {
+    let test_app = create_and_run_test_app().await;
+    let other_user = close_ticket_as_another_user(&test_app).await;
+
+    other_user.login(&test_app).await;
+
+    let notifications = get_notifications(&test_app.db_pool, test_app.test_user.user_id)
+        .await
+        .unwrap();
+    let notification = notifications.first().unwrap();
+
+    test_app.post_read_notification(notification.id).await;
+
+    let notifications = get_notifications(&test_app.db_pool, test_app.test_user.user_id)
+        .await
+        .unwrap();
+    assert!(notifications.first().unwrap().is_unread());
+}
+
+// Must mark every notification as read,
+// when a `POST` request is received at `/dashboard/notifications/read`.
+#[tokio::test]
+async fn read_all_notifications_marks_every_notification_as_read() {
+    let test_app = create_and_run_test_app().await;
+    close_ticket_as_another_user(&test_app).await;
+
+    test_app.test_user.login(&test_app).await;
+
+    let response = test_app.post_read_all_notifications().await;
+    assert_eq!(response.status().as_u16(), 303);
+
+    let html_page = test_app.get_notifications_html().await;
+    assert!(html_page.contains("You have marked every notification as read."));
+
+    let count = test_app.get_unread_notifications_text().await;
+    assert_eq!(count, "0");
+}
+
+// Must redirect an unknown user trying to see notifications.
+#[tokio::test]
+async fn see_notifications_redirects_if_not_logged_in() {
+    let test_app = create_and_run_test_app().await;
+
+    let response = test_app.get_notifications().await;
+    assert_eq!(response.status().as_u16(), 303);
+    assert_eq!(response.headers().get("Location").unwrap(), "/login");
+}
+
+// Must only delete the notifications read before the retention period.
+#[tokio::test]
+async fn delete_expired_notifications_only_deletes_old_read_notifications() {
+    let test_app = create_and_run_test_app().await;
+    close_ticket_as_another_user(&test_app).await;
+
+    // Duplicate the notification as an old read one and a recent read one.
+    for read_at in [Utc::now() - Duration::days(31), Utc::now()] {
+        sqlx::query!(
+            "INSERT INTO notifications (user_id, ticket_id, kind, message, created_at, read_at)
+            SELECT user_id, ticket_id, kind, message, created_at, $1
+            FROM notifications
+            WHERE read_at IS NULL",
+            read_at
+        )
+        .execute(&test_app.db_pool)
+        .await
+        .expect("Failed to duplicate the notification");
+    }
+
+    let deleted = delete_expired_notifications(&test_app.db_pool, Duration::days(30))
+        .await
+        .unwrap();
+    assert_eq!(deleted, 1);
+
+    let notifications = get_notifications(&test_app.db_pool, test_app.test_user.user_id)
+        .await
+        .unwrap();
+    assert_eq!(notifications.len(), 2);
+}
+
+// Must notify the members of the project mentioned in the description of a new ticket,
+// but neither its creator nor the users outside of the project.
+#[tokio::test]
+async fn create_ticket_notifies_the_mentioned_members() {
+    let test_app = create_and_run_test_app().await;
+    let member = TestUser::new();
+    member.store(&test_app.db_pool).await;
+    let stranger = TestUser::new();
+    stranger.store(&test_app.db_pool).await;
+    sqlx::query("DELETE FROM project_members WHERE user_id = $1")
+        .bind(stranger.user_id)
+        .execute(&test_app.db_pool)
+        .await
+        .unwrap();
+
+    test_app.test_user.login(&test_app).await;
+    let body = format!(
+        "title=Issue with x&description=Ask @{}, @{} and @{}.&priority=medium",
+        member.username, stranger.username, test_app.test_user.username
+    );
+    test_app.post_tickets(body).await;
+
+    let notifications = get_notifications(&test_app.db_pool, member.user_id)
+        .await
+        .unwrap();
+    assert_eq!(notifications.len(), 1);
+    assert_eq!(notifications[0].kind, "mention");
+    assert_eq!(
+        notifications[0].message,
+        format!(
+            "{} mentioned you in the ticket MAIN-1.",
+            test_app.test_user.username
+        )
+    );
+    for user_id in [stranger.user_id, test_app.test_user.user_id] {
+        let notifications = get_notifications(&test_app.db_pool, user_id).await.unwrap();
+        assert!(notifications.is_empty());
+    }
+}
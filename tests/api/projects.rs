@@ -0,0 +1,172 @@
+use crate::helpers::{assert_is_redirect_to, create_and_run_test_app, TestUser};
+use tessera::routes::{get_project_by_key, get_ticket};
+
+// Must create a project and switch to it,
+// when a `POST` request with valid form data is received at `/dashboard/projects`.
+#[tokio::test]
+async fn create_project_switches_to_the_new_project() {
+    let test_app = create_and_run_test_app().await;
+    test_app.test_user.login(&test_app).await;
+
+    let response = test_app.post_projects("web", "Website").await;
+    assert_is_redirect_to(&response, "/dashboard/projects");
+
+    let html_page = test_app.get_projects_html().await;
+    assert!(html_page.contains("You have successfully created a new project."));
+    assert!(html_page.contains("<strong>WEB</strong> - Website"));
+
+    let body = "title=Issue with x&description=After doing x&priority=medium";
+    test_app.post_tickets(body.into()).await;
+
+    let ticket = get_ticket(&test_app.db_pool, 1).await.unwrap();
+    assert_eq!(ticket.key, "WEB-1");
+}
+
+// Must reject a project key already taken or invalid.
+#[tokio::test]
+async fn create_project_rejects_invalid_or_taken_keys() {
+    let test_app = create_and_run_test_app().await;
+    test_app.test_user.login(&test_app).await;
+
+    test_app.post_projects("main", "Another main").await;
+    let html_page = test_app.get_projects_html().await;
+    assert!(html_page.contains("This project key is already taken."));
+
+    test_app.post_projects("web-app", "Website").await;
+    let html_page = test_app.get_projects_html().await;
+    assert!(html_page.contains("Validation error: project key must be"));
+}
+
+// Must number the tickets of every project independently.
+#[tokio::test]
+async fn tickets_are_numbered_per_project() {
+    let test_app = create_and_run_test_app().await;
+    test_app.test_user.login(&test_app).await;
+
+    let body = "title=Issue with x&description=After doing x&priority=medium";
+    test_app.post_tickets(body.into()).await;
+    test_app.post_projects("WEB", "Website").await;
+    test_app.post_tickets(body.into()).await;
+    test_app.post_tickets(body.into()).await;
+    let main = get_project_by_key(&test_app.db_pool, "MAIN")
+        .await
+        .unwrap()
+        .unwrap();
+    let response = test_app.post_current_project(main.id).await;
+    assert_is_redirect_to(&response, "/dashboard/tickets");
+    test_app.post_tickets(body.into()).await;
+
+    let mut keys = Vec::new();
+    for id in 1..=4 {
+        keys.push(get_ticket(&test_app.db_pool, id).await.unwrap().key);
+    }
+    assert_eq!(keys, ["MAIN-1", "WEB-1", "WEB-2", "MAIN-2"]);
+}
+
+// Must resolve a ticket by key as well as by its id,
+// when a `GET` request is received at `/dashboard/tickets/{reference}`.
+#[tokio::test]
+async fn see_ticket_resolves_keys_and_ids() {
+    let test_app = create_and_run_test_app().await;
+    test_app.test_user.login(&test_app).await;
+
+    test_app.post_projects("WEB", "Website").await;
+    let body = "title=Issue with x&description=After doing x&priority=medium";
+    test_app.post_tickets(body.into()).await;
+
+    for reference in ["1", "WEB-1", "web-1"] {
+        let response = test_app.get_see_ticket_by_reference(reference).await;
+        assert_eq!(response.status().as_u16(), 200);
+        assert!(response
+            .text()
+            .await
+            .unwrap()
+            .contains("WEB-1 - Created the"));
+    }
+}
+
+// Must hide the tickets of a project from the users who are not members.
+#[tokio::test]
+async fn tickets_are_hidden_from_non_members() {
+    let test_app = create_and_run_test_app().await;
+    test_app.test_user.login(&test_app).await;
+
+    test_app.post_projects("WEB", "Website").await;
+    let body = "title=Issue with secret&description=After doing x&priority=medium";
+    test_app.post_tickets(body.into()).await;
+    test_app.test_user.logout(&test_app).await;
+
+    let other_user = TestUser::new();
+    other_user.store(&test_app.db_pool).await;
+    other_user.login(&test_app).await;
+
+    let html_page = test_app.get_see_tickets_html().await;
+    assert!(!html_page.contains("Issue with secret"));
+
+    let response = test_app.get_see_ticket_by_reference("WEB-1").await;
+    assert_is_redirect_to(&response, "/dashboard/tickets");
+    let html_page = test_app.get_see_tickets_html().await;
+    assert!(html_page.contains("This ticket does not exist."));
+
+    let response = test_app.get_api_ticket(1).await;
+    assert_eq!(response.status().as_u16(), 404);
+}
+
+// Must give the new members access to the tickets of the project,
+// when a `POST` request is received at `/dashboard/projects/{key}/members`.
+#[tokio::test]
+async fn add_project_member_gives_access_to_the_tickets() {
+    let test_app = create_and_run_test_app().await;
+    let other_user = TestUser::new();
+    other_user.store(&test_app.db_pool).await;
+    test_app.test_user.login(&test_app).await;
+
+    test_app.post_projects("WEB", "Website").await;
+    let body = "title=Issue with x&description=After doing x&priority=medium";
+    test_app.post_tickets(body.into()).await;
+
+    let response = test_app
+        .post_project_members("WEB", &other_user.username)
+        .await;
+    assert_is_redirect_to(&response, "/dashboard/projects");
+    let html_page = test_app.get_projects_html().await;
+    assert!(html_page.contains(&format!(
+        "You have successfully added {} to Website.",
+        other_user.username
+    )));
+
+    test_app.post_project_members("WEB", "nobody").await;
+    let html_page = test_app.get_projects_html().await;
+    assert!(html_page.contains("This user does not exist."));
+
+    test_app.test_user.logout(&test_app).await;
+    other_user.login(&test_app).await;
+
+    let response = test_app.get_see_ticket_by_reference("WEB-1").await;
+    assert_eq!(response.status().as_u16(), 200);
+}
+
+// Must not switch to a project the user is not a member of,
+// when a `POST` request is received at `/dashboard/projects/current`.
+#[tokio::test]
+async fn switch_project_rejects_projects_of_other_users() {
+    let test_app = create_and_run_test_app().await;
+    test_app.test_user.login(&test_app).await;
+    test_app.post_projects("WEB", "Website").await;
+    let web = get_project_by_key(&test_app.db_pool, "WEB")
+        .await
+        .unwrap()
+        .unwrap();
+    test_app.test_user.logout(&test_app).await;
+
+    let other_user = TestUser::new();
+    other_user.store(&test_app.db_pool).await;
+    other_user.login(&test_app).await;
+
+    let response = test_app.post_current_project(web.id).await;
+    assert_is_redirect_to(&response, "/dashboard/tickets");
+
+    let html_page = test_app.get_see_tickets_html().await;
+    assert!(html_page.contains("This project does not exist."));
+    assert!(html_page.contains("Tickets of Main (MAIN)"));
+}
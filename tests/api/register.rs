@@ -54,6 +54,19 @@ async fn register_persists_the_new_user() {
     .await
     .expect("Failed to fetch the saved user");
     assert_eq!(saved.username, "fake-username");
+
+    // The new user is a member of the default project.
+    let projects: Vec<String> = sqlx::query_scalar(
+        "SELECT projects.key FROM projects
+        JOIN project_members ON project_members.project_id = projects.id
+        JOIN users ON users.user_id = project_members.user_id
+        WHERE users.username = $1",
+    )
+    .bind(body_username)
+    .fetch_all(&test_app.db_pool)
+    .await
+    .unwrap();
+    assert_eq!(projects, ["MAIN"]);
 }
 
 // Must return a flash error message,
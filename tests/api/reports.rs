@@ -0,0 +1,131 @@
+use crate::helpers::{assert_is_redirect_to, create_and_run_test_app, TestApp};
+use chrono::Utc;
+
+/// Creates a ticket with the given title and priority.
+async fn create_ticket(test_app: &TestApp, title: &str, priority: &str) {
+    let body = format!(
+        "title={}&description=After doing x&priority={}",
+        title, priority
+    );
+    test_app.post_tickets(body).await;
+}
+
+// Must draw the chart of the report and list its rows.
+#[tokio::test]
+async fn see_reports_draws_the_chart_of_the_report() {
+    let test_app = create_and_run_test_app().await;
+    test_app.test_user.login(&test_app).await;
+    create_ticket(&test_app, "Issue with x", "high").await;
+    create_ticket(&test_app, "Issue with y", "low").await;
+    test_app.post_close_ticket(1, 1).await;
+
+    let html_page = test_app.get_report("reports").await.text().await.unwrap();
+    assert!(html_page.contains("<strong>Created and closed tickets</strong>"));
+    assert!(html_page.contains("<svg"));
+    assert!(html_page.contains("report-series"));
+
+    let html_page = test_app
+        .get_report("reports?report=open-ticket-age")
+        .await
+        .text()
+        .await
+        .unwrap();
+    assert!(html_page.contains("<strong>Age of open tickets by priority</strong>"));
+    assert_eq!(html_page.matches("class=\"report-bar\"").count(), 12);
+}
+
+// Must count the tickets still open, even when they were created before the range.
+#[tokio::test]
+async fn open_ticket_age_counts_the_tickets_created_before_the_range() {
+    let test_app = create_and_run_test_app().await;
+    test_app.test_user.login(&test_app).await;
+    create_ticket(&test_app, "Issue with x", "high").await;
+    create_ticket(&test_app, "Issue with y", "high").await;
+    sqlx::query("UPDATE tickets SET created_at = now() - interval '90 days' WHERE id = 1")
+        .execute(&test_app.db_pool)
+        .await
+        .unwrap();
+    let today = Utc::now().date_naive();
+
+    let response = test_app
+        .get_report(&format!(
+            "reports.csv?report=open-ticket-age&from={}&to={}",
+            today, today
+        ))
+        .await;
+    let csv = response.text().await.unwrap();
+    assert!(csv.contains("\nhigh,1,0,0,1\n"));
+}
+
+// Must export the rows of the report in the CSV and the JSON formats.
+#[tokio::test]
+async fn export_report_downloads_csv_and_json() {
+    let test_app = create_and_run_test_app().await;
+    test_app.test_user.login(&test_app).await;
+    create_ticket(&test_app, "Issue with x", "high").await;
+    create_ticket(&test_app, "Issue with y", "low").await;
+    test_app.post_close_ticket(1, 1).await;
+    let today = Utc::now().date_naive();
+
+    let response = test_app
+        .get_report(&format!(
+            "reports.csv?report=cumulative-flow&from={}&to={}",
+            today, today
+        ))
+        .await;
+    assert_eq!(
+        response.headers().get("Content-Type").unwrap(),
+        "text/csv; charset=utf-8"
+    );
+    assert_eq!(
+        response.headers().get("Content-Disposition").unwrap(),
+        &format!(
+            "attachment; filename=\"cumulative-flow-{}-{}.csv\"",
+            today, today
+        )
+    );
+    let csv = response.text().await.unwrap();
+    assert_eq!(csv, format!("date,open,closed\n{},1,1\n", today));
+
+    let response = test_app
+        .get_report(&format!(
+            "reports.json?report=throughput&from={}&to={}",
+            today, today
+        ))
+        .await;
+    let json: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(
+        json,
+        serde_json::json!({
+            "report": "throughput",
+            "from": today.to_string(),
+            "to": today.to_string(),
+            "rows": [{ "user": test_app.test_user.username, "closed": 1 }],
+        })
+    );
+}
+
+// Must reject an unknown report or an invalid range.
+#[tokio::test]
+async fn see_reports_rejects_invalid_queries() {
+    let test_app = create_and_run_test_app().await;
+    test_app.test_user.login(&test_app).await;
+
+    let test_cases = [
+        ("reports?report=velocity", "`velocity` is not a report."),
+        (
+            "reports.csv?from=2026-10-02&to=2026-10-01",
+            "the start of the range cannot be after its end.",
+        ),
+        (
+            "reports.json?from=yesterday",
+            "`yesterday` is not a date such as 2026-10-31.",
+        ),
+    ];
+    for (path, error_message) in test_cases {
+        let response = test_app.get_report(path).await;
+        assert_is_redirect_to(&response, "/dashboard/reports");
+        let html_page = test_app.get_report("reports").await.text().await.unwrap();
+        assert!(html_page.contains(&format!("Validation error: {}", error_message)));
+    }
+}
@@ -0,0 +1,118 @@
+use crate::helpers::{assert_is_redirect_to, create_and_run_test_app, TestApp};
+use secrecy::Secret;
+use tessera::{bootstrap::bootstrap_admin, configuration::BootstrapSettings};
+
+/// Returns the role of the user with the given username, if any.
+async fn role_of(test_app: &TestApp, username: &str) -> Option<String> {
+    sqlx::query!("SELECT role FROM users WHERE username = $1", username)
+        .fetch_optional(&test_app.db_pool)
+        .await
+        .unwrap()
+        .map(|row| row.role)
+}
+
+// Must create the first administrator with the token printed to the logs, only once.
+#[tokio::test]
+async fn setup_creates_the_first_administrator_once() {
+    let test_app = create_and_run_test_app().await;
+    let token = test_app.setup_token.clone().unwrap();
+
+    let html_page = test_app.get_setup(&token).await.text().await.unwrap();
+    assert!(html_page.contains(&format!("value=\"{}\"", token)));
+
+    let body = serde_json::json!({
+        "token": &token,
+        "username": "ops",
+        "password": "correct-horse"
+    });
+    let response = test_app.post_setup(&body).await;
+    assert_is_redirect_to(&response, "/");
+    assert_eq!(role_of(&test_app, "ops").await.as_deref(), Some("admin"));
+    let response = test_app
+        .post_login(&serde_json::json!({ "username": "ops", "password": "correct-horse" }))
+        .await;
+    assert_is_redirect_to(&response, "/dashboard/");
+
+    // Must be closed once an administrator exists.
+    assert_is_redirect_to(&test_app.get_setup(&token).await, "/");
+    let body = serde_json::json!({
+        "token": &token,
+        "username": "other-ops",
+        "password": "correct-horse"
+    });
+    assert_is_redirect_to(&test_app.post_setup(&body).await, "/");
+    assert_eq!(role_of(&test_app, "other-ops").await, None);
+}
+
+// Must refuse an invalid token, and keep the token usable after an invalid form.
+#[tokio::test]
+async fn setup_rejects_invalid_token_and_form() {
+    let test_app = create_and_run_test_app().await;
+    let token = test_app.setup_token.clone().unwrap();
+
+    let body = serde_json::json!({
+        "token": "guessed",
+        "username": "ops",
+        "password": "correct-horse"
+    });
+    assert_is_redirect_to(&test_app.post_setup(&body).await, "/setup");
+    let html_page = test_app.get_setup("").await.text().await.unwrap();
+    assert!(html_page.contains("Validation error: the setup token is invalid."));
+
+    let body = serde_json::json!({
+        "token": &token,
+        "username": "ops",
+        "password": "short"
+    });
+    assert_is_redirect_to(
+        &test_app.post_setup(&body).await,
+        &format!("/setup?token={}", token),
+    );
+    let html_page = test_app.get_setup(&token).await.text().await.unwrap();
+    assert!(html_page.contains("Validation error: password must be at least 8 characters long."));
+    assert_eq!(role_of(&test_app, "ops").await, None);
+
+    let body = serde_json::json!({
+        "token": &token,
+        "username": "ops",
+        "password": "correct-horse"
+    });
+    assert_is_redirect_to(&test_app.post_setup(&body).await, "/");
+    assert_eq!(role_of(&test_app, "ops").await.as_deref(), Some("admin"));
+}
+
+// Must create the first administrator from the configured password, without setup page.
+#[tokio::test]
+async fn bootstrap_creates_the_administrator_from_the_configuration() {
+    let test_app = create_and_run_test_app().await;
+    let settings = BootstrapSettings {
+        admin_username: "admin".to_string(),
+        admin_password: Some(Secret::new("correct-horse".to_string())),
+    };
+
+    let setup_token = bootstrap_admin(&test_app.db_pool, &settings).await.unwrap();
+    assert!(setup_token.get().is_none());
+    assert_eq!(role_of(&test_app, "admin").await.as_deref(), Some("admin"));
+    let is_member: bool = sqlx::query_scalar(
+        r#"
+        SELECT EXISTS (
+            SELECT 1
+            FROM project_members
+            JOIN users USING (user_id)
+            WHERE username = 'admin'
+        )
+        "#,
+    )
+    .fetch_one(&test_app.db_pool)
+    .await
+    .unwrap();
+    assert!(is_member);
+
+    // Must leave the existing administrator untouched.
+    let settings = BootstrapSettings {
+        admin_username: "other-admin".to_string(),
+        ..settings
+    };
+    bootstrap_admin(&test_app.db_pool, &settings).await.unwrap();
+    assert_eq!(role_of(&test_app, "other-admin").await, None);
+}
@@ -0,0 +1,82 @@
+use crate::helpers::{create_and_run_test_app_with, TestApp};
+use std::time::Duration;
+
+/// Waits for the application to stop and then returns how it exited.
+async fn wait_for_application(test_app: TestApp, timeout: Duration) -> Result<(), std::io::Error> {
+    tokio::time::timeout(timeout, test_app.application_task)
+        .await
+        .expect("The application did not stop in time")
+        .expect("The application task failed to complete")
+}
+
+// Must return a `503 Service Unavailable` response,
+// when a `GET` request is received at `/ready` once the application is shutting down.
+#[tokio::test]
+async fn ready_returns_a_503_once_shutting_down() {
+    let test_app = create_and_run_test_app_with(|configuration| {
+        configuration.application.shutdown_delay_seconds = 2;
+    })
+    .await;
+
+    test_app.shutdown.trigger();
+
+    let response = test_app.get_ready().await;
+    assert_eq!(response.status().as_u16(), 503);
+    let readiness: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(readiness["is_ready"], false);
+    assert_eq!(readiness["is_shutting_down"], true);
+    assert_eq!(readiness["postgres"]["is_healthy"], true);
+}
+
+// Must stop accepting connections and then exit,
+// once the shutdown delay is over.
+#[tokio::test]
+async fn shutdown_stops_the_application() {
+    let test_app = create_and_run_test_app_with(|configuration| {
+        configuration.application.shutdown_delay_seconds = 0;
+    })
+    .await;
+    let address = test_app.address.clone();
+    let api_client = test_app.api_client.clone();
+
+    test_app.shutdown.trigger();
+    wait_for_application(test_app, Duration::from_secs(10))
+        .await
+        .expect("The application failed");
+
+    let outcome = api_client
+        .get(format!("{}/health_check", address))
+        .send()
+        .await;
+    assert!(outcome.is_err());
+}
+
+// Must end the ticket events streams rather than waiting for the grace period,
+// once the application is shutting down.
+#[tokio::test]
+async fn shutdown_ends_the_ticket_events_streams() {
+    let test_app = create_and_run_test_app_with(|configuration| {
+        configuration.application.shutdown_delay_seconds = 0;
+        configuration.application.shutdown_grace_period_seconds = 60;
+    })
+    .await;
+    test_app.test_user.login(&test_app).await;
+    let mut response = test_app
+        .api_client
+        .get(format!("{}/dashboard/tickets/events", &test_app.address))
+        .send()
+        .await
+        .expect("Failed to execute request");
+    assert_eq!(response.status().as_u16(), 200);
+
+    test_app.shutdown.trigger();
+
+    let ended = tokio::time::timeout(Duration::from_secs(10), async {
+        while let Ok(Some(_)) = response.chunk().await {}
+    })
+    .await;
+    assert!(ended.is_ok(), "The ticket events stream did not end");
+    wait_for_application(test_app, Duration::from_secs(10))
+        .await
+        .expect("The application failed");
+}
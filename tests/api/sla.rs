@@ -0,0 +1,214 @@
+use crate::helpers::{assert_is_redirect_to, create_and_run_test_app, TestApp, TestUser};
+use chrono::{Duration, Utc};
+use tessera::{
+    configuration::get_configuration, routes::get_ticket, sla_worker::flag_sla_breaches,
+};
+
+/// Creates a high priority ticket as the test user.
+async fn create_ticket(test_app: &TestApp) {
+    let body = "title=Issue with x&description=After doing x&priority=high";
+    test_app.post_tickets(body.into()).await;
+}
+
+/// Stores another user, member of the default project like every test user.
+async fn store_project_member(test_app: &TestApp) -> TestUser {
+    let other_user = TestUser::new();
+    other_user.store(&test_app.db_pool).await;
+
+    other_user
+}
+
+// Must show how long is left before the first deadline of a new ticket.
+#[tokio::test]
+async fn see_tickets_shows_the_time_left_before_the_deadline() {
+    let test_app = create_and_run_test_app().await;
+    test_app.test_user.login(&test_app).await;
+
+    let body = "title=Issue with x&description=After doing x&priority=high&due_date=2020-01-31";
+    test_app.post_tickets(body.into()).await;
+
+    let html_page = test_app.get_see_tickets_html().await;
+    assert!(html_page.contains("left for first response"));
+    assert!(html_page.contains("Overdue since the 2020-01-31"));
+
+    let ticket = get_ticket(&test_app.db_pool, 1).await.unwrap();
+    assert_eq!(ticket.due_on.unwrap().to_string(), "2020-01-31");
+}
+
+// Must flag a breach once, notify the assignee and show it in the list.
+#[tokio::test]
+async fn flag_sla_breaches_notifies_the_assignee_once() {
+    let test_app = create_and_run_test_app().await;
+    let assignee = store_project_member(&test_app).await;
+    test_app.test_user.login(&test_app).await;
+    create_ticket(&test_app).await;
+
+    let response = test_app
+        .post_ticket_assignee(1, 1, &assignee.username)
+        .await;
+    assert_is_redirect_to(&response, "/dashboard/tickets/1");
+    sqlx::query!(
+        "UPDATE tickets SET created_at = $1 WHERE id = 1",
+        Utc::now() - Duration::days(30)
+    )
+    .execute(&test_app.db_pool)
+    .await
+    .unwrap();
+
+    let policies = get_configuration().unwrap().sla.get_policies().unwrap();
+    let flagged = flag_sla_breaches(&test_app.db_pool, &policies, Utc::now())
+        .await
+        .unwrap();
+    assert_eq!(flagged, 2);
+    let flagged = flag_sla_breaches(&test_app.db_pool, &policies, Utc::now())
+        .await
+        .unwrap();
+    assert_eq!(flagged, 0);
+
+    let html_page = test_app.get_see_tickets_html().await;
+    assert!(html_page.contains("SLA breached: first response"));
+    test_app.test_user.logout(&test_app).await;
+
+    assignee.login(&test_app).await;
+    let html_page = test_app.get_notifications_html().await;
+    assert!(html_page.contains("The ticket MAIN-1 breached its first response target."));
+    assert!(html_page.contains("The ticket MAIN-1 breached its resolution target."));
+}
+
+// Must neither flag a breach without notifying the assignee nor stop at a failing ticket.
+#[tokio::test]
+async fn flag_sla_breaches_flags_a_breach_with_its_notification() {
+    let test_app = create_and_run_test_app().await;
+    let assignee = store_project_member(&test_app).await;
+    test_app.test_user.login(&test_app).await;
+    create_ticket(&test_app).await;
+    create_ticket(&test_app).await;
+
+    // Only the first ticket has an assignee to notify.
+    test_app
+        .post_ticket_assignee(1, 1, &assignee.username)
+        .await;
+    sqlx::query("UPDATE tickets SET created_at = $1")
+        .bind(Utc::now() - Duration::days(30))
+        .execute(&test_app.db_pool)
+        .await
+        .unwrap();
+
+    // Because of the constraint the notifications of the breaches cannot be saved.
+    sqlx::query(
+        "ALTER TABLE notifications ADD CONSTRAINT no_sla_breach CHECK (kind <> 'sla_breach')",
+    )
+    .execute(&test_app.db_pool)
+    .await
+    .unwrap();
+    let policies = get_configuration().unwrap().sla.get_policies().unwrap();
+    let flagged = flag_sla_breaches(&test_app.db_pool, &policies, Utc::now())
+        .await
+        .unwrap();
+    assert_eq!(flagged, 2);
+    let flagged_tickets: Vec<i32> =
+        sqlx::query_scalar("SELECT DISTINCT ticket_id FROM sla_breaches")
+            .fetch_all(&test_app.db_pool)
+            .await
+            .unwrap();
+    assert_eq!(flagged_tickets, [2]);
+
+    sqlx::query("ALTER TABLE notifications DROP CONSTRAINT no_sla_breach")
+        .execute(&test_app.db_pool)
+        .await
+        .unwrap();
+    let flagged = flag_sla_breaches(&test_app.db_pool, &policies, Utc::now())
+        .await
+        .unwrap();
+    assert_eq!(flagged, 2);
+    let notifications: i64 =
+        sqlx::query_scalar("SELECT COUNT(*) FROM notifications WHERE kind = 'sla_breach'")
+            .fetch_one(&test_app.db_pool)
+            .await
+            .unwrap();
+    assert_eq!(notifications, 2);
+}
+
+// Must assign the ticket to a member of its project and notify the assignee.
+#[tokio::test]
+async fn assign_ticket_notifies_the_assignee() {
+    let test_app = create_and_run_test_app().await;
+    let assignee = store_project_member(&test_app).await;
+    test_app.test_user.login(&test_app).await;
+    create_ticket(&test_app).await;
+
+    test_app.post_ticket_assignee(1, 1, "nobody").await;
+    let html_page = test_app.get_see_ticket_html(1).await;
+    assert!(html_page.contains("nobody is not a member of the project of this ticket."));
+
+    test_app
+        .post_ticket_assignee(1, 1, &assignee.username)
+        .await;
+    let html_page = test_app.get_see_ticket_html(1).await;
+    assert!(html_page.contains(&format!(
+        "You have successfully assigned this ticket to {}.",
+        assignee.username
+    )));
+
+    let ticket = get_ticket(&test_app.db_pool, 1).await.unwrap();
+    assert_eq!(ticket.assigned_to, Some(assignee.username.clone()));
+    assert_eq!(ticket.version, 2);
+
+    // The changes of the other fields are not assignments.
+    test_app.post_ticket_due_date(1, 2, "2026-10-31").await;
+    let kinds: Vec<String> =
+        sqlx::query_scalar("SELECT kind FROM notifications WHERE user_id = $1 ORDER BY id")
+            .bind(assignee.user_id)
+            .fetch_all(&test_app.db_pool)
+            .await
+            .unwrap();
+    assert_eq!(kinds, vec!["assignment", "field_change"]);
+    test_app.test_user.logout(&test_app).await;
+
+    assignee.login(&test_app).await;
+    let html_page = test_app.get_notifications_html().await;
+    assert!(html_page.contains(&format!(
+        "{} assigned you the ticket MAIN-1.",
+        test_app.test_user.username
+    )));
+}
+
+// Must change the due date, and reject the dates that cannot be parsed.
+#[tokio::test]
+async fn set_due_date_changes_the_due_date() {
+    let test_app = create_and_run_test_app().await;
+    test_app.test_user.login(&test_app).await;
+    create_ticket(&test_app).await;
+
+    test_app.post_ticket_due_date(1, 1, "tomorrow").await;
+    let html_page = test_app.get_see_ticket_html(1).await;
+    assert!(html_page.contains("Validation error: `tomorrow` is not a due date"));
+
+    test_app.post_ticket_due_date(1, 1, "2026-10-31").await;
+    let html_page = test_app.get_see_ticket_html(1).await;
+    assert!(html_page.contains("You have successfully changed the due date of this ticket."));
+    assert!(html_page.contains("Due the 2026-10-31"));
+
+    let response = test_app.post_ticket_due_date(1, 1, "").await;
+    assert_eq!(response.status().as_u16(), 409);
+}
+
+// Must record the first response when someone else than the creator changes the ticket.
+#[tokio::test]
+async fn first_response_is_recorded_for_changes_of_other_users() {
+    let test_app = create_and_run_test_app().await;
+    let other_user = store_project_member(&test_app).await;
+    test_app.test_user.login(&test_app).await;
+    create_ticket(&test_app).await;
+
+    test_app.post_ticket_due_date(1, 1, "2026-10-31").await;
+    let ticket = get_ticket(&test_app.db_pool, 1).await.unwrap();
+    assert!(ticket.first_responded_at.is_none());
+    test_app.test_user.logout(&test_app).await;
+
+    other_user.login(&test_app).await;
+    test_app.post_close_ticket(1, 2).await;
+    let ticket = get_ticket(&test_app.db_pool, 1).await.unwrap();
+    assert!(ticket.first_responded_at.is_some());
+    assert!(ticket.closed_at.is_some());
+}
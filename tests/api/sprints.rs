@@ -0,0 +1,173 @@
+use crate::helpers::{assert_is_redirect_to, create_and_run_test_app, TestApp};
+use chrono::{Duration, Utc};
+use config::{Config, File, FileFormat};
+use tessera::{
+    configuration::SprintSettings, routes::get_ticket, sprint_worker::record_sprint_snapshots,
+};
+
+/// Creates a ticket with the given title.
+async fn create_ticket(test_app: &TestApp, title: &str) {
+    let body = format!("title={}&description=After doing x&priority=medium", title);
+    test_app.post_tickets(body).await;
+}
+
+/// Creates a sprint of two weeks running today.
+async fn create_running_sprint(test_app: &TestApp) {
+    let today = Utc::now().date_naive();
+    let starts_on = (today - Duration::days(2)).to_string();
+    let ends_on = (today + Duration::days(11)).to_string();
+    test_app
+        .post_sprints("Sprint 1", &starts_on, &ends_on)
+        .await;
+}
+
+// Must list the new sprint and reject invalid dates or a duplicated name,
+// when a `POST` request is received at `/dashboard/sprints`.
+#[tokio::test]
+async fn create_sprint_lists_it_and_rejects_invalid_sprints() {
+    let test_app = create_and_run_test_app().await;
+    test_app.test_user.login(&test_app).await;
+
+    let response = test_app
+        .post_sprints("Sprint 1", "2026-10-19", "2026-10-30")
+        .await;
+    assert_is_redirect_to(&response, "/dashboard/sprints");
+    let html_page = test_app.get_planning_html("sprints").await;
+    assert!(html_page.contains("You have successfully created the sprint Sprint 1."));
+    assert!(html_page.contains("From 2026-10-19 to 2026-10-30"));
+
+    let test_cases = [
+        (
+            "Sprint 1",
+            "2026-11-02",
+            "2026-11-13",
+            "This sprint name is already taken.",
+        ),
+        (
+            "Sprint 2",
+            "2026-11-13",
+            "2026-11-02",
+            "Validation error: a sprint cannot end before it starts.",
+        ),
+    ];
+    for (name, starts_on, ends_on, error_message) in test_cases {
+        test_app.post_sprints(name, starts_on, ends_on).await;
+        let html_page = test_app.get_planning_html("sprints").await;
+        assert!(html_page.contains(error_message));
+    }
+}
+
+// Must list the backlog by rank and move its tickets into a sprint.
+#[tokio::test]
+async fn plan_ticket_moves_the_ticket_from_the_backlog_into_the_sprint() {
+    let test_app = create_and_run_test_app().await;
+    test_app.test_user.login(&test_app).await;
+    create_ticket(&test_app, "Issue with x").await;
+    create_ticket(&test_app, "Issue with y").await;
+    create_running_sprint(&test_app).await;
+
+    let html_page = test_app.get_planning_html("backlog").await;
+    let x = html_page.find("Issue with x").unwrap();
+    let y = html_page.find("Issue with y").unwrap();
+    assert!(x < y);
+
+    let response = test_app
+        .post_ticket_sprint("backlog/tickets/1/sprint", 1, "1")
+        .await;
+    assert_is_redirect_to(&response, "/dashboard/backlog");
+    let html_page = test_app.get_planning_html("backlog").await;
+    assert!(html_page
+        .contains("You have successfully moved the ticket MAIN-1 into the sprint Sprint 1."));
+    assert!(!html_page.contains("Issue with x"));
+    assert!(html_page.contains("Issue with y"));
+
+    let ticket = get_ticket(&test_app.db_pool, 1).await.unwrap();
+    assert_eq!(ticket.sprint_id, Some(1));
+    assert_eq!(ticket.version, 2);
+}
+
+// Must draw the burndown of the remaining story points of the sprint.
+#[tokio::test]
+async fn see_sprint_draws_the_burndown_of_the_remaining_points() {
+    let test_app = create_and_run_test_app().await;
+    test_app.test_user.login(&test_app).await;
+    create_ticket(&test_app, "Issue with x").await;
+    create_ticket(&test_app, "Issue with y").await;
+    create_running_sprint(&test_app).await;
+
+    for (ticket_id, points) in [(1, "5"), (2, "3")] {
+        let response = test_app
+            .post_ticket_story_points(ticket_id, 1, points)
+            .await;
+        assert_is_redirect_to(&response, &format!("/dashboard/tickets/{}", ticket_id));
+        test_app
+            .post_ticket_sprint(&format!("tickets/{}/sprint", ticket_id), 2, "1")
+            .await;
+    }
+    test_app.post_close_ticket(1, 3).await;
+
+    let today = Utc::now().date_naive();
+    let recorded = record_sprint_snapshots(&test_app.db_pool, today)
+        .await
+        .unwrap();
+    assert_eq!(recorded, 1);
+    let snapshot = sqlx::query!("SELECT remaining_points, total_points FROM sprint_snapshots")
+        .fetch_one(&test_app.db_pool)
+        .await
+        .unwrap();
+    assert_eq!(snapshot.remaining_points, 3);
+    assert_eq!(snapshot.total_points, 8);
+
+    let html_page = test_app.get_planning_html("sprints/1").await;
+    assert!(html_page.contains("3 of 8 points left"));
+    assert!(html_page.contains("<svg"));
+    assert!(html_page.contains("burndown-remaining"));
+    let html_page = test_app.get_planning_html("sprints").await;
+    assert!(html_page.contains("3 of 8 points left"));
+}
+
+// Must not move tickets into a sprint that has ended, nor accept invalid story points.
+#[tokio::test]
+async fn set_sprint_rejects_ended_sprints_and_invalid_story_points() {
+    let test_app = create_and_run_test_app().await;
+    test_app.test_user.login(&test_app).await;
+    create_ticket(&test_app, "Issue with x").await;
+    test_app
+        .post_sprints("Sprint 0", "2020-01-06", "2020-01-17")
+        .await;
+
+    test_app
+        .post_ticket_sprint("tickets/1/sprint", 1, "1")
+        .await;
+    let html_page = test_app.get_see_ticket_html(1).await;
+    assert!(html_page.contains("Validation error: the sprint Sprint 0 has ended."));
+
+    test_app.post_ticket_story_points(1, 1, "101").await;
+    let html_page = test_app.get_see_ticket_html(1).await;
+    assert!(html_page.contains("`101` is not a number of story points between 0 and 100."));
+
+    let ticket = get_ticket(&test_app.db_pool, 1).await.unwrap();
+    assert_eq!(ticket.sprint_id, None);
+    assert_eq!(ticket.story_points, None);
+}
+
+// Must reject a snapshot interval of 0 seconds, the worker would never wait.
+#[test]
+fn sprint_settings_reject_a_snapshot_interval_of_zero() {
+    let settings = |interval: &str| {
+        Config::builder()
+            .add_source(File::from_str(
+                &format!("snapshot_interval_seconds: {}", interval),
+                FileFormat::Yaml,
+            ))
+            .build()
+            .unwrap()
+            .try_deserialize::<SprintSettings>()
+    };
+
+    assert!(settings("0").is_err());
+    assert_eq!(
+        settings("60").unwrap().snapshot_interval(),
+        std::time::Duration::from_secs(60)
+    );
+}
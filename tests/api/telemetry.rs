@@ -0,0 +1,41 @@
+use crate::helpers::{collector, create_and_run_test_app};
+use uuid::Uuid;
+
+// Must export the trace continued from the `traceparent` header, down to the database queries.
+#[tokio::test]
+async fn requests_continue_their_trace_down_to_the_database_queries() {
+    let test_app = create_and_run_test_app().await;
+    test_app.test_user.login(&test_app).await;
+    test_app
+        .post_tickets("title=Issue&description=After doing x&priority=high".into())
+        .await;
+
+    let trace_id = Uuid::new_v4().as_bytes().to_vec();
+    let parent_span_id = Uuid::new_v4().as_bytes()[..8].to_vec();
+    let traceparent = format!(
+        "00-{}-{}-01",
+        hex::encode(&trace_id),
+        hex::encode(&parent_span_id)
+    );
+    let response = test_app
+        .get_see_ticket_with_headers(1, &[("traceparent", &traceparent)])
+        .await;
+    assert_eq!(response.status().as_u16(), 200);
+
+    let spans = collector().get_trace(&trace_id, &parent_span_id).await;
+    let root_span = spans
+        .iter()
+        .find(|span| span.parent_span_id == parent_span_id)
+        .unwrap();
+    assert_eq!(root_span.name, "HTTP GET /dashboard/tickets/{id}");
+
+    // The statements are recorded as events of the spans of the database queries.
+    let query_span = spans
+        .iter()
+        .find(|span| span.name == "Getting ticket details from the tickets table")
+        .unwrap();
+    assert!(query_span
+        .events
+        .iter()
+        .any(|event| event.name.starts_with("SELECT * FROM tickets")));
+}
@@ -0,0 +1,87 @@
+use crate::helpers::{build_api_client, create_and_run_test_app, TestApp, TestUser};
+use reqwest::{Client, Response};
+use std::time::Duration;
+
+/// Logs in another user on its own HTTP client, then open the ticket events stream.
+async fn open_ticket_events_as_another_user(test_app: &TestApp) -> (Client, Response) {
+    let other_user = TestUser::new();
+    other_user.store(&test_app.db_pool).await;
+
+    let client = build_api_client();
+    client
+        .post(format!("{}/login", &test_app.address))
+        .form(&serde_json::json!({
+            "username": &other_user.username,
+            "password": &other_user.password
+        }))
+        .send()
+        .await
+        .expect("Failed to execute request");
+
+    let response = client
+        .get(format!("{}/dashboard/tickets/events", &test_app.address))
+        .send()
+        .await
+        .expect("Failed to execute request");
+
+    (client, response)
+}
+
+/// Reads the stream until the expected text is received.
+async fn read_until(response: &mut Response, expected: &str) -> String {
+    let mut received = String::new();
+
+    tokio::time::timeout(Duration::from_secs(5), async {
+        while !received.contains(expected) {
+            let chunk = response
+                .chunk()
+                .await
+                .expect("Failed to read the stream")
+                .expect("The stream has ended");
+            received.push_str(&String::from_utf8_lossy(&chunk));
+        }
+    })
+    .await
+    .unwrap_or_else(|_| panic!("Did not receive `{}`, got `{}`", expected, received));
+
+    received
+}
+
+// Must stream the creation and the closing of a ticket to the other users,
+// when a `GET` request is received at `/dashboard/tickets/events`.
+#[tokio::test]
+async fn ticket_events_streams_ticket_changes_to_other_users() {
+    let test_app = create_and_run_test_app().await;
+    let (_client, mut response) = open_ticket_events_as_another_user(&test_app).await;
+    assert_eq!(response.status().as_u16(), 200);
+    assert_eq!(
+        response.headers().get("Content-Type").unwrap(),
+        "text/event-stream"
+    );
+
+    test_app.test_user.login(&test_app).await;
+
+    let body = "title=Issue with ...&description=After doing ...&priority=medium";
+    test_app.post_tickets(body.into()).await;
+    let received = read_until(&mut response, "event: ticket-1").await;
+    assert!(received.contains(r#""kind":"created""#));
+
+    test_app.post_close_ticket(1, 1).await;
+    let received = read_until(&mut response, r#""kind":"closed""#).await;
+    assert!(received.contains("event: ticket\n"));
+}
+
+// Must redirect an unknown user trying to stream ticket events.
+#[tokio::test]
+async fn ticket_events_redirects_if_not_logged_in() {
+    let test_app = create_and_run_test_app().await;
+
+    let response = test_app
+        .api_client
+        .get(format!("{}/dashboard/tickets/events", &test_app.address))
+        .send()
+        .await
+        .expect("Failed to execute request");
+    assert_eq!(response.status().as_u16(), 303);
+    assert_eq!(response.headers().get("Location").unwrap(), "/login");
+}
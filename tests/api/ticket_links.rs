@@ -0,0 +1,143 @@
+use crate::helpers::{assert_is_redirect_to, create_and_run_test_app, TestApp};
+use tessera::routes::get_ticket;
+
+/// Creates the given number of tickets.
+async fn create_tickets(test_app: &TestApp, count: usize) {
+    for _ in 0..count {
+        let body = "title=Issue with x&description=After doing x&priority=medium";
+        test_app.post_tickets(body.into()).await;
+    }
+}
+
+// Must show the link on both tickets, inverse on the other one,
+// when a `POST` request is received at `/dashboard/tickets/{id}/links`.
+#[tokio::test]
+async fn create_ticket_link_shows_the_inverse_link() {
+    let test_app = create_and_run_test_app().await;
+    test_app.test_user.login(&test_app).await;
+    create_tickets(&test_app, 2).await;
+
+    let response = test_app.post_ticket_links(1, "blocks", "MAIN-2").await;
+    assert_is_redirect_to(&response, "/dashboard/tickets/1");
+
+    let html_page = test_app.get_see_ticket_html(1).await;
+    assert!(html_page.contains("You have successfully linked the tickets."));
+    assert!(html_page.contains("blocks\n            <a hx-get=\"/dashboard/tickets/MAIN-2\""));
+
+    let html_page = test_app.get_see_ticket_html(2).await;
+    assert!(
+        html_page.contains("is blocked by\n            <a hx-get=\"/dashboard/tickets/MAIN-1\"")
+    );
+}
+
+// Must reject the links closing a cycle.
+#[tokio::test]
+async fn create_ticket_link_rejects_cycles() {
+    let test_app = create_and_run_test_app().await;
+    test_app.test_user.login(&test_app).await;
+    create_tickets(&test_app, 3).await;
+
+    test_app.post_ticket_links(1, "blocks", "MAIN-2").await;
+    test_app.post_ticket_links(2, "blocks", "MAIN-3").await;
+
+    for (ticket_id, relation, reference) in [
+        (2, "blocks", "MAIN-1"),
+        (1, "blocked_by", "MAIN-3"),
+        (1, "blocks", "MAIN-1"),
+    ] {
+        test_app
+            .post_ticket_links(ticket_id, relation, reference)
+            .await;
+        let html_page = test_app.get_see_ticket_html(ticket_id).await;
+        assert!(html_page.contains("Validation error:"));
+        assert!(!html_page.contains("You have successfully linked the tickets."));
+    }
+
+    let html_page = test_app.get_see_ticket_html(1).await;
+    assert!(!html_page.contains("is blocked by\n            <a"));
+}
+
+// Must show the progress of the sub-tasks on the parent,
+// and give every sub-task a single parent.
+#[tokio::test]
+async fn parent_ticket_shows_the_progress_of_its_sub_tasks() {
+    let test_app = create_and_run_test_app().await;
+    test_app.test_user.login(&test_app).await;
+    create_tickets(&test_app, 4).await;
+
+    test_app.post_ticket_links(1, "parent_of", "MAIN-2").await;
+    test_app.post_ticket_links(3, "child_of", "MAIN-1").await;
+    test_app.post_close_ticket(2, 1).await;
+
+    let html_page = test_app.get_see_ticket_html(1).await;
+    assert!(html_page.contains(r#"<progress value="1" max="2"></progress>"#));
+    assert!(html_page.contains("1 / 2 sub-tasks closed"));
+
+    test_app.post_ticket_links(4, "parent_of", "MAIN-2").await;
+    let html_page = test_app.get_see_ticket_html(4).await;
+    assert!(html_page.contains("Validation error: this ticket already has a parent."));
+}
+
+// Must close the ticket and point to the canonical one,
+// when a `POST` request with a duplicate is received at `/dashboard/tickets/{id}/close`.
+#[tokio::test]
+async fn close_ticket_as_duplicate_points_to_the_canonical_ticket() {
+    let test_app = create_and_run_test_app().await;
+    test_app.test_user.login(&test_app).await;
+    create_tickets(&test_app, 2).await;
+
+    let response = test_app
+        .post_close_ticket_as_duplicate(2, 1, "MAIN-1")
+        .await;
+    assert_is_redirect_to(&response, "/dashboard/tickets/2");
+
+    let html_page = test_app.get_see_ticket_html(2).await;
+    assert!(html_page.contains("You have successfully closed this ticket as a duplicate."));
+    assert!(html_page.contains("Duplicate of"));
+    assert!(!get_ticket(&test_app.db_pool, 2).await.unwrap().is_open);
+
+    let html_page = test_app.get_see_ticket_html(1).await;
+    assert!(html_page.contains("is duplicated by"));
+}
+
+// Must leave the ticket open when the canonical ticket does not exist.
+#[tokio::test]
+async fn close_ticket_as_duplicate_rejects_unknown_canonical_tickets() {
+    let test_app = create_and_run_test_app().await;
+    test_app.test_user.login(&test_app).await;
+    create_tickets(&test_app, 1).await;
+
+    test_app
+        .post_close_ticket_as_duplicate(1, 1, "MAIN-42")
+        .await;
+    let html_page = test_app.get_see_ticket_html(1).await;
+    assert!(html_page.contains("The canonical ticket does not exist."));
+
+    test_app
+        .post_close_ticket_as_duplicate(1, 1, "MAIN-1")
+        .await;
+    let html_page = test_app.get_see_ticket_html(1).await;
+    assert!(html_page.contains("Validation error: a ticket cannot be linked to itself."));
+    assert!(get_ticket(&test_app.db_pool, 1).await.unwrap().is_open);
+}
+
+// Must remove the link from both tickets,
+// when a `POST` request is received at `/dashboard/tickets/{id}/links/{link_id}/delete`.
+#[tokio::test]
+async fn delete_ticket_link_removes_the_link() {
+    let test_app = create_and_run_test_app().await;
+    test_app.test_user.login(&test_app).await;
+    create_tickets(&test_app, 2).await;
+    test_app.post_ticket_links(1, "relates_to", "MAIN-2").await;
+
+    test_app.post_ticket_links(2, "relates_to", "MAIN-1").await;
+    let html_page = test_app.get_see_ticket_html(2).await;
+    assert!(html_page.contains("Validation error: these tickets are already linked."));
+
+    let response = test_app.post_delete_ticket_link(2, 1).await;
+    assert_is_redirect_to(&response, "/dashboard/tickets/2");
+
+    let html_page = test_app.get_see_ticket_html(2).await;
+    assert!(html_page.contains("You have successfully removed the link."));
+    assert!(!html_page.contains("relates to\n            <a"));
+}
@@ -1,5 +1,5 @@
-use crate::helpers::create_and_run_test_app;
-use tessera::routes::{get_ticket, get_tickets};
+use crate::helpers::{assert_is_redirect_to, create_and_run_test_app};
+use tessera::routes::{get_project_by_key, get_ticket, get_tickets};
 
 // Must return a `303 See Other` response,
 // when a `POST` request with valid form data is received at `/dashboard/tickets/new`.
@@ -168,6 +168,7 @@ async fn see_tickets_returns_a_200() {
 // Must return every ticket,
 // when a `GET` request is received at `/dashboard/tickets`.
 #[tokio::test]
+#[allow(clippy::get_first)]
 async fn see_tickets_returns_tickets() {
     let test_app = create_and_run_test_app().await;
     test_app.test_user.login(&test_app).await;
@@ -180,7 +181,11 @@ async fn see_tickets_returns_tickets() {
     test_app.post_tickets(body2.into()).await;
     test_app.post_tickets(body3.into()).await;
 
-    let saved = get_tickets(&test_app.db_pool).await.unwrap();
+    let main = get_project_by_key(&test_app.db_pool, "MAIN")
+        .await
+        .unwrap()
+        .unwrap();
+    let saved = get_tickets(&test_app.db_pool, main.id).await.unwrap();
 
     let saved_ticket_x = saved.get(0).unwrap();
     assert_eq!(saved_ticket_x.id, 1);
@@ -266,6 +271,22 @@ async fn see_ticket_returns_ticket() {
     assert!(saved_ticket_z.is_open);
 }
 
+// Must render the description as sanitized Markdown,
+// when a `GET` request with a valid ticket id is received at `/dashboard/tickets/{id}`.
+#[tokio::test]
+async fn see_ticket_renders_the_description_as_sanitized_markdown() {
+    let test_app = create_and_run_test_app().await;
+    test_app.test_user.login(&test_app).await;
+
+    let body =
+        "title=Issue with x&description=After doing *x*<script>alert(1)</script>&priority=medium";
+    test_app.post_tickets(body.into()).await;
+
+    let html_page = test_app.get_see_ticket_html(1).await;
+    assert!(html_page.contains("<em>x</em>"));
+    assert!(!html_page.contains("alert(1)"));
+}
+
 // Must redirect an unknown user trying to see a ticket.
 #[tokio::test]
 async fn see_ticket_redirects_if_not_logged_in() {
@@ -276,15 +297,20 @@ async fn see_ticket_redirects_if_not_logged_in() {
     assert_eq!(response.headers().get("Location").unwrap(), "/login");
 }
 
-// Must return a `500 Internal Server Error` response,
-// when a `GET` request with an invalid ticket id is received at `/dashboard/tickets/{id}`.
+// Must redirect to the tickets with an error flash message,
+// when a `GET` request with an unknown ticket id or key is received at `/dashboard/tickets/{reference}`.
 #[tokio::test]
-async fn see_ticket_returns_a_500_when_invalid_ticket_id() {
+async fn see_ticket_returns_a_303_when_invalid_ticket_id() {
     let test_app = create_and_run_test_app().await;
     test_app.test_user.login(&test_app).await;
 
-    let response = test_app.get_see_ticket(1).await;
-    assert_eq!(response.status().as_u16(), 500);
+    for reference in ["1", "MAIN-1"] {
+        let response = test_app.get_see_ticket_by_reference(reference).await;
+        assert_is_redirect_to(&response, "/dashboard/tickets");
+
+        let html_page = test_app.get_see_tickets_html().await;
+        assert!(html_page.contains("This ticket does not exist."));
+    }
 }
 
 // Must return a `303 See Other` response,
@@ -298,7 +324,7 @@ async fn close_ticket_returns_a_303_when_valid_ticket_id() {
 
     test_app.post_tickets(body.into()).await;
 
-    let response = test_app.post_close_ticket(1).await;
+    let response = test_app.post_close_ticket(1, 1).await;
     assert_eq!(response.status().as_u16(), 303);
 
     let html_page = test_app.get_see_ticket_html(1).await;
@@ -316,7 +342,7 @@ async fn close_ticket_closes_ticket() {
 
     test_app.post_tickets(body.into()).await;
 
-    test_app.post_close_ticket(1).await;
+    test_app.post_close_ticket(1, 1).await;
 
     let saved_ticket_x = get_ticket(&test_app.db_pool, 1).await.unwrap();
     assert!(!saved_ticket_x.is_open);
@@ -330,7 +356,7 @@ async fn close_ticket_closes_ticket() {
 async fn close_ticket_redirects_if_not_logged_in() {
     let test_app = create_and_run_test_app().await;
 
-    let response = test_app.post_close_ticket(1).await;
+    let response = test_app.post_close_ticket(1, 1).await;
     assert_eq!(response.status().as_u16(), 303);
     assert_eq!(response.headers().get("Location").unwrap(), "/login");
 }
@@ -342,7 +368,32 @@ async fn close_ticket_returns_a_303_when_invalid_ticket_id() {
     let test_app = create_and_run_test_app().await;
     test_app.test_user.login(&test_app).await;
 
-    let response = test_app.post_close_ticket(1).await;
+    let response = test_app.post_close_ticket(1, 1).await;
+    assert_eq!(response.status().as_u16(), 303);
+    assert_eq!(
+        response.headers().get("Location").unwrap(),
+        "/dashboard/tickets/1"
+    );
+
+    let response = test_app.get_see_ticket(1).await;
+    assert_is_redirect_to(&response, "/dashboard/tickets");
+    let html_page = test_app.get_see_tickets_html().await;
+    assert!(html_page.contains("This ticket does not exist."));
+}
+
+// Must return an error flash message,
+// when a `POST` request for an already closed ticket is received at `/dashboard/tickets/{id}/close`.
+#[tokio::test]
+async fn close_ticket_returns_an_error_flash_message_when_already_closed() {
+    let test_app = create_and_run_test_app().await;
+    test_app.test_user.login(&test_app).await;
+
+    let body = "title=Issue with ...&description=After doing ...&priority=medium";
+
+    test_app.post_tickets(body.into()).await;
+    test_app.post_close_ticket(1, 1).await;
+
+    let response = test_app.post_close_ticket(1, 2).await;
     assert_eq!(response.status().as_u16(), 303);
     assert_eq!(
         response.headers().get("Location").unwrap(),
@@ -350,5 +401,72 @@ async fn close_ticket_returns_a_303_when_invalid_ticket_id() {
     );
 
     let html_page = test_app.get_see_ticket_html(1).await;
-    assert!(html_page.contains("Failed to get the ticket details from the tickets table"));
+    assert!(html_page.contains("This ticket has already been closed."));
+}
+
+// Must increment the version of a ticket,
+// when a `POST` request with the current version is received at `/dashboard/tickets/{id}/close`.
+#[tokio::test]
+async fn close_ticket_increments_the_version() {
+    let test_app = create_and_run_test_app().await;
+    test_app.test_user.login(&test_app).await;
+
+    let body = "title=Issue with x&description=After doing x&priority=medium";
+
+    test_app.post_tickets(body.into()).await;
+    assert_eq!(get_ticket(&test_app.db_pool, 1).await.unwrap().version, 1);
+
+    test_app.post_close_ticket(1, 1).await;
+    assert_eq!(get_ticket(&test_app.db_pool, 1).await.unwrap().version, 2);
+}
+
+// Must return a `409 Conflict` response showing what changed,
+// when a `POST` request with a stale version is received at `/dashboard/tickets/{id}/close`.
+#[tokio::test]
+async fn close_ticket_returns_a_409_when_stale_version() {
+    let test_app = create_and_run_test_app().await;
+    test_app.test_user.login(&test_app).await;
+
+    let body = "title=Issue with x&description=After doing x&priority=medium";
+
+    test_app.post_tickets(body.into()).await;
+    test_app.post_close_ticket(1, 1).await;
+
+    // The ticket was loaded before being closed.
+    let response = test_app.post_close_ticket(1, 1).await;
+    assert_eq!(response.status().as_u16(), 409);
+
+    let html_page = response.text().await.unwrap();
+    assert!(html_page.contains("has been changed by someone else"));
+    assert!(html_page.contains("Version 2: the status changed from"));
+    assert!(html_page.contains(&format!("by {}", test_app.test_user.username)));
+}
+
+// Must return the description rendered as sanitized Markdown,
+// when a `POST` request is received at `/dashboard/tickets/preview`.
+#[tokio::test]
+async fn preview_ticket_description_returns_sanitized_markdown() {
+    let test_app = create_and_run_test_app().await;
+    test_app.test_user.login(&test_app).await;
+
+    let response = test_app
+        .post_preview_ticket_description("After doing **x**<script>alert(1)</script>")
+        .await;
+    assert_eq!(response.status().as_u16(), 200);
+
+    let html = response.text().await.unwrap();
+    assert!(html.contains("<strong>x</strong>"));
+    assert!(!html.contains("alert(1)"));
+}
+
+// Must redirect an unknown user trying to preview a description.
+#[tokio::test]
+async fn preview_ticket_description_redirects_if_not_logged_in() {
+    let test_app = create_and_run_test_app().await;
+
+    let response = test_app
+        .post_preview_ticket_description("After doing x")
+        .await;
+    assert_eq!(response.status().as_u16(), 303);
+    assert_eq!(response.headers().get("Location").unwrap(), "/login");
 }
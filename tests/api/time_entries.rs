@@ -0,0 +1,141 @@
+use crate::helpers::{assert_is_redirect_to, create_and_run_test_app, TestApp, TestUser};
+use chrono::{Duration, Utc};
+use tessera::domain::Timesheet;
+
+/// Creates a ticket with the given title.
+async fn create_ticket(test_app: &TestApp, title: &str) {
+    let body = format!("title={}&description=After doing x&priority=medium", title);
+    test_app.post_tickets(body).await;
+}
+
+// Must show the time logged against the original estimate.
+#[tokio::test]
+async fn log_time_shows_the_logged_time_against_the_estimate() {
+    let test_app = create_and_run_test_app().await;
+    test_app.test_user.login(&test_app).await;
+    create_ticket(&test_app, "Issue with x").await;
+
+    let response = test_app.post_ticket_estimate(1, 1, "4h").await;
+    assert_is_redirect_to(&response, "/dashboard/tickets/1");
+    let today = Utc::now().date_naive().to_string();
+    let body =
+        serde_json::json!({ "duration": "1h 30m", "spent_on": today, "note": "Reproduced x" });
+    let response = test_app.post_time_entries(1, &body).await;
+    assert_is_redirect_to(&response, "/dashboard/tickets/1");
+
+    let html_page = test_app.get_see_ticket_html(1).await;
+    assert!(html_page.contains("You have successfully logged 1h 30m on this ticket."));
+    assert!(html_page.contains("1h 30m logged of 4h 00m estimated"));
+    assert!(html_page.contains("Reproduced x"));
+}
+
+// Must reject the entries that are too long, in the future or not a duration.
+#[tokio::test]
+async fn log_time_rejects_invalid_entries() {
+    let test_app = create_and_run_test_app().await;
+    test_app.test_user.login(&test_app).await;
+    create_ticket(&test_app, "Issue with x").await;
+
+    let today = Utc::now().date_naive();
+    let tomorrow = today + Duration::days(1);
+    let test_cases = [
+        ("25h", today, "a time entry cannot be longer than 24 hours."),
+        (
+            "1h",
+            tomorrow,
+            "a time entry cannot be logged in the future.",
+        ),
+        ("1.5h", today, "`1.5h` is not a duration such as 1h 30m."),
+    ];
+    for (duration, spent_on, error_message) in test_cases {
+        let body = serde_json::json!({ "duration": duration, "spent_on": spent_on.to_string() });
+        test_app.post_time_entries(1, &body).await;
+
+        let html_page = test_app.get_see_ticket_html(1).await;
+        assert!(html_page.contains(error_message));
+    }
+
+    let html_page = test_app.get_see_ticket_html(1).await;
+    assert!(html_page.contains("0h 00m logged, not estimated"));
+}
+
+// Must sum the time logged during the week by ticket and export the entries.
+#[tokio::test]
+async fn timesheet_sums_the_week_and_exports_csv() {
+    let test_app = create_and_run_test_app().await;
+    test_app.test_user.login(&test_app).await;
+    create_ticket(&test_app, "Issue with x").await;
+    create_ticket(&test_app, "Issue with y").await;
+
+    // Log time during the previous week, which is entirely in the past.
+    let monday = Timesheet::week_start(Utc::now().date_naive()) - Duration::days(7);
+    for (ticket_id, offset, duration) in [(1, 0, "1h"), (1, 1, "2h"), (2, 1, "30m")] {
+        let spent_on = (monday + Duration::days(offset)).to_string();
+        let body = serde_json::json!({ "duration": duration, "spent_on": spent_on });
+        test_app.post_time_entries(ticket_id, &body).await;
+    }
+
+    let response = test_app
+        .get_timesheet(&format!("timesheet?week={}", monday + Duration::days(3)))
+        .await;
+    let html_page = response.text().await.unwrap();
+    assert!(html_page.contains(&format!("during the week of the {}", monday)));
+    assert!(html_page.contains("3h 00m"));
+    assert!(html_page.contains("2h 30m"));
+    assert!(html_page.contains("3h 30m"));
+
+    let response = test_app
+        .get_timesheet(&format!("timesheet.csv?week={}", monday))
+        .await;
+    assert_eq!(
+        response.headers().get("Content-Type").unwrap(),
+        "text/csv; charset=utf-8"
+    );
+    let csv = response.text().await.unwrap();
+    let lines: Vec<&str> = csv.lines().collect();
+    assert_eq!(lines.len(), 4);
+    assert_eq!(lines[0], "user,date,ticket,title,hours,note");
+    assert_eq!(
+        lines[1],
+        format!(
+            "{},{},MAIN-1,Issue with x,1.00,",
+            test_app.test_user.username, monday
+        )
+    );
+}
+
+// Must only let the administrators see the timesheet of other users.
+#[tokio::test]
+async fn timesheet_of_other_users_is_reserved_to_administrators() {
+    let test_app = create_and_run_test_app().await;
+    let other_user = TestUser::new();
+    other_user.store(&test_app.db_pool).await;
+    test_app.test_user.login(&test_app).await;
+
+    let response = test_app
+        .get_timesheet(&format!("timesheet?user={}", other_user.username))
+        .await;
+    assert_is_redirect_to(&response, "/dashboard/timesheet");
+    let html_page = test_app
+        .get_timesheet("timesheet")
+        .await
+        .text()
+        .await
+        .unwrap();
+    assert!(html_page.contains("Only administrators can see the timesheet of other users."));
+
+    sqlx::query!(
+        "UPDATE users SET role = 'admin' WHERE user_id = $1",
+        test_app.test_user.user_id
+    )
+    .execute(&test_app.db_pool)
+    .await
+    .unwrap();
+    let html_page = test_app
+        .get_timesheet(&format!("timesheet?user={}", other_user.username))
+        .await
+        .text()
+        .await
+        .unwrap();
+    assert!(html_page.contains(&format!("Time logged by {}", other_user.username)));
+}